@@ -0,0 +1,32 @@
+//! Content analysis benchmarks. Compare byte scanning implementations with
+//! `cargo bench --bench analysis` and `cargo bench --bench analysis --features simd`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use yamllint_rs::analysis::ContentAnalysis;
+use yamllint_rs::linter::Linter;
+
+fn large_document(entries: usize) -> String {
+    let mut content = String::from("---\n");
+    for i in 0..entries {
+        content.push_str(&format!(
+            "item_{i}:\n  name: \"entry {i}\"\n  tags: [a, b, c]\n  meta: {{key: value}}\n  script: |\n    echo {i}   \n",
+        ));
+    }
+    content
+}
+
+fn bench_analysis(c: &mut Criterion) {
+    let content = large_document(5_000);
+
+    c.bench_function("analyze_lines", |b| {
+        b.iter(|| ContentAnalysis::analyze_with_tokens(black_box(&content), false))
+    });
+
+    let linter = Linter::new();
+    c.bench_function("lint_large_document", |b| {
+        b.iter(|| linter.check(black_box(&content), "bench.yaml"))
+    });
+}
+
+criterion_group!(benches, bench_analysis);
+criterion_main!(benches);
@@ -1,7 +1,8 @@
 //! Single-pass content analysis system.
 
+use crate::scan;
 use std::collections::HashMap;
-use yaml_rust::scanner::{Scanner, Token, TokenType};
+use yaml_rust::scanner::{Scanner, TScalarStyle, Token, TokenType};
 
 /// Information about a single line
 #[derive(Debug, Clone)]
@@ -12,8 +13,10 @@ pub struct LineInfo {
     pub length: usize,
     /// Whether the line is empty
     pub is_empty: bool,
-    /// Whether the line is a comment
+    /// Whether the line is a comment; never true inside a block scalar
     pub is_comment: bool,
+    /// Whether the line is content of a literal or folded block scalar
+    pub in_block_scalar: bool,
     /// Whether the line has trailing whitespace
     pub has_trailing_whitespace: bool,
     /// Number of trailing whitespace characters
@@ -108,8 +111,9 @@ pub struct ContentAnalysis {
     pub ends_with_document_marker: bool,
     pub truthy_values: HashMap<usize, Vec<String>>,
     pub duplicate_keys: HashMap<usize, Vec<String>>,
-    pub empty_values: HashMap<usize, Vec<String>>,
     pub tokens: Option<TokenAnalysis>,
+    /// Literal and folded block scalars, in order; only found with tokens
+    pub block_scalars: Vec<BlockScalarSpan>,
 }
 
 impl ContentAnalysis {
@@ -121,7 +125,6 @@ impl ContentAnalysis {
         let mut lines = Vec::new();
         let mut truthy_values = HashMap::new();
         let mut duplicate_keys = HashMap::new();
-        let mut empty_values = HashMap::new();
 
         let mut structure = YamlStructure::new();
         let mut current_contexts: Vec<usize> = Vec::new();
@@ -134,13 +137,22 @@ impl ContentAnalysis {
             None
         };
 
-        for line in content.lines() {
+        let block_scalars = tokens
+            .as_ref()
+            .map(|tokens| block_scalar_spans(&tokens.tokens, content))
+            .unwrap_or_default();
+        let mut spans = block_scalars.iter().peekable();
+
+        for line in scan::lines(content) {
             let trimmed = line.trim();
             let indentation = line.len() - line.trim_start().len();
 
-            let line_info = Self::analyze_line(line_number, line);
+            while spans.next_if(|span| span.last_line < line_number).is_some() {}
+            let in_block_scalar = spans.peek().is_some_and(|span| span.contains(line_number));
+            let line_info = Self::analyze_line(line_number, line, in_block_scalar);
 
-            if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            // Scalar text is neither a comment nor a key, whatever it looks like
+            if !trimmed.is_empty() && !line_info.is_comment && !in_block_scalar {
                 current_contexts.retain(|&context_idx| {
                     if context_idx < structure.contexts.len() {
                         let context = &structure.contexts[context_idx];
@@ -200,17 +212,6 @@ impl ContentAnalysis {
                 truthy_values.insert(line_number, line_truthy_values);
             }
 
-            if line_info.has_colon {
-                if let Some(value) = Self::extract_value(line) {
-                    if value.trim().is_empty() {
-                        empty_values
-                            .entry(line_number)
-                            .or_insert_with(Vec::new)
-                            .push(value);
-                    }
-                }
-            }
-
             lines.push(line_info);
             line_number += 1;
         }
@@ -234,8 +235,8 @@ impl ContentAnalysis {
             ends_with_document_marker,
             truthy_values,
             duplicate_keys,
-            empty_values,
             tokens,
+            block_scalars,
         }
     }
 
@@ -243,11 +244,11 @@ impl ContentAnalysis {
         self.tokens.as_ref()
     }
 
-    fn analyze_line(line_number: usize, line: &str) -> LineInfo {
+    fn analyze_line(line_number: usize, line: &str, in_block_scalar: bool) -> LineInfo {
         let length = line.len();
         let trimmed = line.trim();
         let is_empty = trimmed.is_empty();
-        let is_comment = trimmed.starts_with('#');
+        let is_comment = !in_block_scalar && trimmed.starts_with('#');
         let has_trailing_whitespace = line.ends_with(' ') || line.ends_with('\t');
         let trailing_whitespace_count = if has_trailing_whitespace {
             line.len() - line.trim_end().len()
@@ -260,16 +261,17 @@ impl ContentAnalysis {
 
         // Check for various patterns
         let is_list_item = line.trim_start().starts_with('-');
-        let has_colon = line.contains(':');
-        let has_quotes = line.contains('"') || line.contains('\'');
-        let has_braces = line.contains('{') || line.contains('}');
-        let has_brackets = line.contains('[') || line.contains(']');
+        let has_colon = scan::contains(line, b':');
+        let has_quotes = scan::contains_either(line, b'"', b'\'');
+        let has_braces = scan::contains_either(line, b'{', b'}');
+        let has_brackets = scan::contains_either(line, b'[', b']');
 
         LineInfo {
             line_number,
             length,
             is_empty,
             is_comment,
+            in_block_scalar,
             has_trailing_whitespace,
             trailing_whitespace_count,
             indentation,
@@ -311,15 +313,6 @@ impl ContentAnalysis {
         }
     }
 
-    /// Extract value from a key-value line
-    fn extract_value(line: &str) -> Option<String> {
-        if let Some(colon_pos) = line.find(':') {
-            Some(line[colon_pos + 1..].trim().to_string())
-        } else {
-            None
-        }
-    }
-
     /// Get line information by line number
     pub fn get_line(&self, line_number: usize) -> Option<&LineInfo> {
         if line_number > 0 && line_number <= self.lines.len() {
@@ -350,6 +343,12 @@ impl ContentAnalysis {
         self.lines.iter().filter(|line| line.is_empty).collect()
     }
 
+    /// Whether `line_number` is content of a literal or folded block scalar
+    pub fn in_block_scalar(&self, line_number: usize) -> bool {
+        self.get_line(line_number)
+            .is_some_and(|line| line.in_block_scalar)
+    }
+
     /// Get all comment lines
     pub fn get_comment_lines(&self) -> Vec<&LineInfo> {
         self.lines.iter().filter(|line| line.is_comment).collect()
@@ -393,10 +392,118 @@ impl ContentAnalysis {
     }
 }
 
+/// Physical lines occupied by the content of a literal (`|`) or folded (`>`)
+/// block scalar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockScalarSpan {
+    /// Line of the `|` or `>` indicator, where the scalar logically starts (1-based)
+    pub start_line: usize,
+    /// First content line (1-based)
+    pub first_line: usize,
+    /// Last non-blank content line (1-based)
+    pub last_line: usize,
+}
+
+impl BlockScalarSpan {
+    pub fn contains(&self, line: usize) -> bool {
+        (self.first_line..=self.last_line).contains(&line)
+    }
+}
+
+/// Locate block scalars from scanner tokens. The scanner marks a block scalar
+/// at its content, so the indicator is taken from the preceding token's line
+/// and content runs until the next token, minus trailing blank lines and
+/// comments dedented below the scalar's content.
+pub fn block_scalar_spans(tokens: &[Token], content: &str) -> Vec<BlockScalarSpan> {
+    let lines: Vec<&str> = content.lines().collect();
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let mut spans = Vec::new();
+
+    for (idx, Token(marker, token_type)) in tokens.iter().enumerate() {
+        if !matches!(
+            token_type,
+            TokenType::Scalar(TScalarStyle::Literal, _) | TokenType::Scalar(TScalarStyle::Foled, _)
+        ) {
+            continue;
+        }
+
+        let start_line = idx
+            .checked_sub(1)
+            .and_then(|prev| tokens.get(prev))
+            .map(|Token(prev, _)| prev.line())
+            .unwrap_or(marker.line());
+        let end_line = tokens
+            .get(idx + 1)
+            .map(|Token(next, _)| next.line().saturating_sub(1))
+            .unwrap_or(lines.len())
+            .min(lines.len());
+
+        let body = (start_line + 1..=end_line).filter(|&line| !lines[line - 1].trim().is_empty());
+        let Some(first_line) = body.clone().next() else {
+            continue;
+        };
+        let content_indent = indent(lines[first_line - 1]);
+        let last_line = (first_line..=end_line)
+            .rev()
+            .find(|&line| {
+                let text = lines[line - 1];
+                !text.trim().is_empty() && indent(text) >= content_indent
+            })
+            .unwrap_or(first_line);
+
+        spans.push(BlockScalarSpan {
+            start_line,
+            first_line,
+            last_line,
+        });
+    }
+
+    spans
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_block_scalar_spans() {
+        let content =
+            "a: |\n  line one\n\n  line two\n# trailing comment\nb: >-\n  folded\nc: plain\n";
+        let tokens = TokenAnalysis::analyze(content).tokens;
+        let spans = block_scalar_spans(&tokens, content);
+
+        assert_eq!(
+            spans,
+            vec![
+                BlockScalarSpan {
+                    start_line: 1,
+                    first_line: 2,
+                    last_line: 4,
+                },
+                BlockScalarSpan {
+                    start_line: 6,
+                    first_line: 7,
+                    last_line: 7,
+                },
+            ]
+        );
+        assert!(spans[0].contains(3));
+        assert!(!spans[0].contains(5));
+    }
+
+    #[test]
+    fn test_block_scalar_lines_are_not_comments() {
+        let content = "script: |\n   # not a comment\n   key: text\n# comment\nkey: value\n";
+        let analysis = ContentAnalysis::analyze(content);
+
+        assert!(analysis.in_block_scalar(2));
+        assert!(!analysis.get_line(2).unwrap().is_comment);
+        assert!(analysis.get_line(4).unwrap().is_comment);
+        assert!(!analysis.in_block_scalar(4));
+        // `key:` inside the scalar is text, not a duplicate of line 5
+        assert!(analysis.duplicate_keys.is_empty());
+    }
+
     #[test]
     fn test_content_analysis_basic() {
         let content = "key1: value1\nkey2: value2\nkey3: value3";
@@ -406,7 +513,6 @@ mod tests {
         assert_eq!(analysis.lines.len(), 3);
         assert!(analysis.duplicate_keys.is_empty());
         assert!(analysis.truthy_values.is_empty());
-        assert!(analysis.empty_values.is_empty());
     }
 
     #[test]
@@ -524,16 +630,6 @@ contact:
         assert!(analysis.truthy_values.contains_key(&4)); // '1'
     }
 
-    #[test]
-    fn test_content_analysis_empty_values() {
-        let content = "key1: \nkey2: value\nkey3:   \nkey4: another";
-        let analysis = ContentAnalysis::analyze(content);
-
-        assert_eq!(analysis.empty_values.len(), 2);
-        assert!(analysis.empty_values.contains_key(&1)); // Empty value
-        assert!(analysis.empty_values.contains_key(&3)); // Whitespace-only value
-    }
-
     #[test]
     fn test_content_analysis_line_info() {
         let content = "  - key: value  \n# comment\n\nkey2: value2";
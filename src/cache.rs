@@ -0,0 +1,184 @@
+//! Incremental linting cache for `--cache`.
+//!
+//! Lint results are stored per file under `.yamllint-cache`, keyed by a hash
+//! of the file content and by the fingerprint of the effective config it was
+//! linted with (see [`crate::config_hash`]), so editing a config file or
+//! passing different overrides invalidates the stored results. A file whose
+//! content and config are unchanged reuses its stored issues instead of being
+//! linted again. Results are stored before output options (`--no-warnings`,
+//! `--redact-values`, `--diff`) are applied, so those can change freely.
+
+use crate::config_hash::{fingerprint, hash_hex};
+use crate::{config::Config, LintIssue, LintResult};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Directory the cache is stored in, relative to the working directory
+pub const CACHE_DIR: &str = ".yamllint-cache";
+
+const CACHE_FILE: &str = "results.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    content_hash: String,
+    config_hash: String,
+    issues: Vec<(LintIssue, String)>,
+    scalar_start_lines: HashMap<usize, usize>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// Tool version that wrote the cache; other versions start empty
+    version: String,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Per-file lint results shared by all worker threads of a run
+#[derive(Debug)]
+pub struct LintCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    /// Fingerprints of the configs seen this run. Holding the `Arc` keeps
+    /// its address from being reused by another config.
+    fingerprints: Mutex<Vec<(Arc<Config>, String)>>,
+}
+
+impl LintCache {
+    /// Load the cache stored in `dir`. A missing, unreadable or outdated
+    /// cache starts empty rather than failing the run.
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(CACHE_FILE);
+        let entries = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CacheFile>(&bytes).ok())
+            .filter(|file| file.version == env!("CARGO_PKG_VERSION"))
+            .map(|file| file.entries)
+            .unwrap_or_default();
+        Self {
+            path,
+            entries: Mutex::new(entries),
+            fingerprints: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Stored result for `file`, if it was linted with the same content and config
+    pub fn get(
+        &self,
+        file: &str,
+        content: &str,
+        config: &Option<Arc<Config>>,
+    ) -> Option<LintResult> {
+        let content_hash = hash_hex(content.as_bytes());
+        let config_hash = self.config_hash(config);
+        let entries = self.entries.lock().unwrap();
+        let entry = entries.get(file)?;
+        if entry.content_hash != content_hash || entry.config_hash != config_hash {
+            return None;
+        }
+        Some(LintResult {
+            file: file.to_string(),
+            issues: entry.issues.clone(),
+            scalar_start_lines: entry.scalar_start_lines.clone(),
+        })
+    }
+
+    /// Store the result of linting `content` with `config`
+    pub fn insert(&self, content: &str, config: &Option<Arc<Config>>, result: &LintResult) {
+        let entry = CacheEntry {
+            content_hash: hash_hex(content.as_bytes()),
+            config_hash: self.config_hash(config),
+            issues: result.issues.clone(),
+            scalar_start_lines: result.scalar_start_lines.clone(),
+        };
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(result.file.clone(), entry);
+    }
+
+    /// Fingerprint of `config`, computed once per config per run
+    fn config_hash(&self, config: &Option<Arc<Config>>) -> String {
+        let Some(config) = config else {
+            return String::new();
+        };
+        let mut fingerprints = self.fingerprints.lock().unwrap();
+        if let Some((_, hash)) = fingerprints
+            .iter()
+            .find(|(seen, _)| Arc::ptr_eq(seen, config))
+        {
+            return hash.clone();
+        }
+        let hash = fingerprint(config);
+        fingerprints.push((Arc::clone(config), hash.clone()));
+        hash
+    }
+
+    /// Write the cache back to disk
+    pub fn save(&self) -> Result<()> {
+        let file = CacheFile {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            entries: self.entries.lock().unwrap().clone(),
+        };
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Cannot create cache directory {}", dir.display()))?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec(&file)?)
+            .with_context(|| format!("Cannot write cache {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+
+    fn result(file: &str) -> LintResult {
+        LintResult {
+            file: file.to_string(),
+            issues: vec![(
+                LintIssue {
+                    line: 2,
+                    column: 11,
+                    message: "trailing spaces".to_string(),
+                    code: "trailing-spaces/trailing-spaces".to_string(),
+                    severity: Severity::Error,
+                },
+                "trailing-spaces".to_string(),
+            )],
+            scalar_start_lines: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_cache_round_trip_and_invalidation() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = &Some(Arc::new(Config::default()));
+        let content = "---\nkey: value   \n";
+
+        let cache = LintCache::load(dir.path());
+        assert!(cache.get("a.yaml", content, config).is_none());
+        cache.insert(content, config, &result("a.yaml"));
+        cache.save().unwrap();
+
+        let cache = LintCache::load(dir.path());
+        let cached = cache.get("a.yaml", content, config).unwrap();
+        assert_eq!(cached.issues[0].0.line, 2);
+        assert_eq!(cached.issues[0].1, "trailing-spaces");
+
+        assert!(cache.get("a.yaml", "---\nkey: value\n", config).is_none());
+        let mut changed = Config::default();
+        changed.set_rule_enabled("trailing-spaces", false);
+        assert!(cache
+            .get("a.yaml", content, &Some(Arc::new(changed)))
+            .is_none());
+        // An equal config loaded separately still hits
+        assert!(cache
+            .get("a.yaml", content, &Some(Arc::new(Config::default())))
+            .is_some());
+        assert!(cache.get("b.yaml", content, config).is_none());
+    }
+}
@@ -0,0 +1,113 @@
+//! Owners of paths from a GitHub-style `CODEOWNERS` file, used by
+//! `--group-by owner`.
+
+use anyhow::{Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+/// Where a `CODEOWNERS` file is looked for, in the order GitHub uses
+pub const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Group of files no `CODEOWNERS` entry matches
+pub const UNOWNED: &str = "(unowned)";
+
+/// Parsed `CODEOWNERS` entries; the last matching entry wins
+pub struct CodeOwners {
+    entries: Vec<(Gitignore, Vec<String>)>,
+}
+
+impl CodeOwners {
+    /// The first `CODEOWNERS` file under `root`, if any
+    pub fn discover(root: &Path) -> Option<PathBuf> {
+        CODEOWNERS_LOCATIONS
+            .iter()
+            .map(|location| root.join(location))
+            .find(|path| path.is_file())
+    }
+
+    pub fn load(path: &Path, root: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content, root).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    /// Parse entries of the form `pattern owner...`, with patterns relative to `root`
+    pub fn parse(content: &str, root: &Path) -> Result<Self> {
+        let mut entries = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let Some(pattern) = fields.next() else {
+                continue;
+            };
+            let owners = fields
+                .take_while(|field| !field.starts_with('#'))
+                .map(str::to_string)
+                .collect();
+
+            let mut builder = GitignoreBuilder::new(root);
+            builder.add_line(None, pattern)?;
+            entries.push((builder.build()?, owners));
+        }
+        Ok(Self { entries })
+    }
+
+    /// Owners of `path` (relative to the root), empty when no entry matches
+    /// or the matching entry lists no owners
+    pub fn owners(&self, path: &Path) -> &[String] {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        if path.has_root() {
+            return &[];
+        }
+        self.entries
+            .iter()
+            .rev()
+            .find(|(matcher, _)| matcher.matched_path_or_any_parents(path, false).is_ignore())
+            .map(|(_, owners)| owners.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Group name of `path`: its owners separated by spaces, or [`UNOWNED`]
+    pub fn group(&self, path: &Path) -> String {
+        match self.owners(path) {
+            [] => UNOWNED.to_string(),
+            owners => owners.join(" "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codeowners_last_matching_entry_wins() {
+        let owners = CodeOwners::parse(
+            "# Default owners\n\
+             *       @org/platform\n\
+             /services/billing/ @org/billing @alice  # payments\n\
+             *.lock\n\
+             docs/   @org/docs\n",
+            Path::new(""),
+        )
+        .unwrap();
+
+        assert_eq!(owners.group(Path::new("values.yaml")), "@org/platform");
+        assert_eq!(
+            owners.group(Path::new("services/billing/deploy/app.yaml")),
+            "@org/billing @alice"
+        );
+        assert_eq!(
+            owners.group(Path::new("nested/docs/site.yaml")),
+            "@org/docs"
+        );
+        assert_eq!(owners.group(Path::new("Cargo.lock")), UNOWNED);
+        assert_eq!(
+            owners.group(Path::new("other/services/billing/x.yaml")),
+            "@org/platform"
+        );
+    }
+}
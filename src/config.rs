@@ -3,7 +3,7 @@
 use crate::Severity;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +16,103 @@ pub struct Config {
     pub ignore: Option<String>,
     #[serde(rename = "ignore-from-file", skip_serializing_if = "Option::is_none")]
     pub ignore_from_file: Option<String>,
+    /// Severity overrides applied to issues after rule evaluation; the first
+    /// matching entry wins
+    #[serde(
+        rename = "severity-by-path",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub severity_by_path: Vec<SeverityOverride>,
+    /// Rule adjustments for files matching globs, applied in order over
+    /// `rules` for each linted file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<PathOverride>,
+    /// Named sets of yamllint-format rule overrides, merged over `rules`
+    /// when selected with `--profile`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profiles: HashMap<String, serde_json::Value>,
+    /// Honor `# yamllint-rs: rule.option=value` modelines on a file's first
+    /// line, overriding rule options for that file only
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub modelines: bool,
+    /// Globs selecting the files linted when walking a directory, like
+    /// yamllint's `yaml-files`; [`DEFAULT_YAML_FILES`] when unset
+    #[serde(
+        rename = "yaml-files",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub yaml_files: Option<Vec<String>>,
+    /// Rules whose issues are reported but never fixed, even with `--fix`
+    #[serde(
+        rename = "exclude-from-fix",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub exclude_from_fix: Vec<String>,
+    /// yamllint's `locale`, the collation `key-ordering` compares keys with;
+    /// byte order when unset, `C` or not a supported locale
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Directory of the config file, which `overrides` and `severity-by-path`
+    /// globs are relative to; the working directory when unset
+    #[serde(skip)]
+    pub base_dir: Option<PathBuf>,
+}
+
+/// Keys every rule accepts besides its own options
+const COMMON_RULE_KEYS: &[&str] = &["level", "enable", "disable", "ignore", "ignore-from-file"];
+
+/// The name in `names` closest to a misspelled `name`, if one is close enough
+/// to be a likely typo
+fn closest_name<'a>(name: &str, names: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    names
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2.max(name.len() / 4))
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between `a` and `b`, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substituted = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substituted.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Files linted when walking a directory, unless the config sets `yaml-files`
+pub const DEFAULT_YAML_FILES: &[&str] = &["*.yaml", "*.yml"];
+
+/// Severity applied to issues in files matching a glob
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeverityOverride {
+    /// Glob relative to the working directory. `*` matches within a path
+    /// segment and `**` across segments; patterns without `/` match file
+    /// names at any depth.
+    pub files: String,
+    pub level: Severity,
+    /// Rules whose issues get `level`; every rule's when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<String>,
+}
+
+/// yamllint-format rule options applied to files matching any of `files`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathOverride {
+    /// Globs matched like `severity-by-path` patterns
+    pub files: Vec<String>,
+    /// A `rules:` mapping, merged over the options already set for each rule
+    pub rules: serde_json::Value,
 }
 
 /// Global configuration settings
@@ -36,10 +133,19 @@ pub struct RuleConfig {
     pub enabled: Option<bool>,
     /// Severity override for this rule
     pub severity: Option<Severity>,
+    /// Files this rule skips, one pattern per line, matched like the
+    /// top-level `ignore`. A list of patterns is accepted too.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_patterns",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ignore: Option<String>,
     /// Rule-specific settings
     #[serde(flatten)]
     pub other: serde_json::Map<String, serde_json::Value>,
-    /// Rule-specific settings (deprecated, use other)
+    /// Options under the native format's snake_case names (deprecated);
+    /// moved into `other` when the config is loaded
     pub settings: Option<serde_json::Value>,
 }
 
@@ -48,76 +154,117 @@ impl Default for RuleConfig {
         Self {
             enabled: None,
             severity: None,
+            ignore: None,
             other: serde_json::Map::new(),
             settings: None,
         }
     }
 }
 
-/// Rule-specific configuration structures
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LineLengthConfig {
-    pub max_length: usize,
-    /// Allow non-breakable words (without spaces) to overflow the limit
-    pub allow_non_breakable_words: bool,
-    /// Allow non-breakable inline mappings (key: value where value has no spaces)
-    #[serde(default)]
-    pub allow_non_breakable_inline_mappings: bool,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct IndentationConfig {
-    pub spaces: Option<usize>,
-    pub indent_sequences: Option<bool>,
-    pub check_multi_line_strings: Option<bool>,
-    pub ignore: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CommentsConfig {
-    pub min_spaces_from_content: Option<usize>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TruthyConfig {
-    pub allowed_values: Vec<String>,
-}
+impl RuleConfig {
+    /// Append patterns, one per line, to the rule's `ignore`
+    pub fn add_ignore_patterns(&mut self, patterns: &str) {
+        self.ignore = Some(match self.ignore.take() {
+            Some(ignore) if !ignore.trim().is_empty() => {
+                format!("{}\n{}", ignore.trim_end(), patterns)
+            }
+            _ => patterns.to_string(),
+        });
+    }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TrailingSpacesConfig {
-    pub allow: bool,
-}
+    /// Move options given under the native format's `settings` into the
+    /// yamllint-named options, without replacing options set both ways
+    pub fn resolve_native_settings(&mut self, rule_id: &str) {
+        let Some(serde_json::Value::Object(settings)) = self.settings.take() else {
+            return;
+        };
+        for (name, value) in settings {
+            if value.is_null() {
+                continue;
+            }
+            let option = NATIVE_OPTION_NAMES
+                .iter()
+                .find(|(rule, native, _)| *rule == rule_id && *native == name)
+                .map_or_else(
+                    || name.replace('_', "-"),
+                    |(_, _, option)| option.to_string(),
+                );
+            if option == "ignore" {
+                if let Some(patterns) = ignore_patterns_from_json(&value) {
+                    self.add_ignore_patterns(&patterns);
+                }
+                continue;
+            }
+            self.other.entry(option).or_insert(value);
+        }
+    }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DocumentStartConfig {
-    pub present: Option<bool>,
+    /// Read the files named by the rule's `ignore-from-file` setting into
+    /// its `ignore` patterns, so checking a file needs no further reads
+    pub fn resolve_ignore_from_file(&mut self) {
+        if let Some(patterns) = self
+            .other
+            .remove("ignore-from-file")
+            .and_then(|files| ignore_patterns_from_files(&files))
+        {
+            self.add_ignore_patterns(&patterns);
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DocumentEndConfig {
-    pub present: Option<bool>,
-}
+/// Native `settings` names that are not yamllint's option name with `_`
+/// for `-`: rule, native name, option name
+const NATIVE_OPTION_NAMES: &[(&str, &str, &str)] = &[
+    ("line-length", "max_length", "max"),
+    ("new-lines", "type_", "type"),
+];
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EmptyLinesConfig {
-    pub max: Option<usize>,
-    pub max_start: Option<usize>,
-    pub max_end: Option<usize>,
+/// Patterns in the files a rule's `ignore-from-file` names: one path or a
+/// list, relative to the working directory as in yamllint. Unreadable files
+/// add nothing, as with the top-level key.
+pub fn ignore_patterns_from_files(value: &serde_json::Value) -> Option<String> {
+    let paths = ignore_patterns_from_json(value)?;
+    Some(
+        paths
+            .lines()
+            .filter_map(|path| std::fs::read_to_string(path.trim()).ok())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct KeyOrderingConfig {
-    pub order: Option<Vec<String>>,
-}
+/// Patterns given as a block string or a list, like yamllint's `ignore`
+fn deserialize_patterns<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Patterns {
+        Block(String),
+        List(Vec<String>),
+    }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AnchorsConfig {
-    pub max_length: Option<usize>,
+    Ok(
+        Option::<Patterns>::deserialize(deserializer)?.map(|patterns| match patterns {
+            Patterns::Block(block) => block,
+            Patterns::List(list) => list.join("\n"),
+        }),
+    )
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NewLinesConfig {
-    pub type_: Option<String>, // "unix" or "dos"
+/// `ignore` patterns from a yamllint-format value: a block string or a list
+pub fn ignore_patterns_from_json(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(block) => Some(block.clone()),
+        serde_json::Value::Array(list) => Some(
+            list.iter()
+                .filter_map(|pattern| pattern.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+        _ => None,
+    }
 }
 
 impl Default for Config {
@@ -138,6 +285,14 @@ impl Config {
             },
             ignore: None,
             ignore_from_file: None,
+            severity_by_path: Vec::new(),
+            overrides: Vec::new(),
+            profiles: HashMap::new(),
+            modelines: false,
+            yaml_files: None,
+            exclude_from_fix: Vec::new(),
+            locale: None,
+            base_dir: None,
         };
 
         // Set up default rule configurations
@@ -153,14 +308,6 @@ impl Config {
             RuleConfig {
                 enabled: Some(true),
                 severity: Some(Severity::Error),
-                settings: Some(
-                    serde_json::to_value(LineLengthConfig {
-                        max_length: 80,
-                        allow_non_breakable_words: true,
-                        allow_non_breakable_inline_mappings: false,
-                    })
-                    .unwrap(),
-                ),
                 ..Default::default()
             },
         );
@@ -171,9 +318,6 @@ impl Config {
             RuleConfig {
                 enabled: Some(true),
                 severity: Some(Severity::Error),
-                settings: Some(
-                    serde_json::to_value(TrailingSpacesConfig { allow: false }).unwrap(),
-                ),
                 ..Default::default()
             },
         );
@@ -184,12 +328,6 @@ impl Config {
             RuleConfig {
                 enabled: Some(true),
                 severity: Some(Severity::Warning),
-                settings: Some(
-                    serde_json::to_value(CommentsConfig {
-                        min_spaces_from_content: Some(2),
-                    })
-                    .unwrap(),
-                ),
                 ..Default::default()
             },
         );
@@ -200,12 +338,6 @@ impl Config {
             RuleConfig {
                 enabled: Some(true),
                 severity: Some(Severity::Warning), // Changed from Error to Warning to match yamllint default
-                settings: Some(
-                    serde_json::to_value(TruthyConfig {
-                        allowed_values: vec!["false".to_string(), "true".to_string()],
-                    })
-                    .unwrap(),
-                ),
                 ..Default::default()
             },
         );
@@ -216,7 +348,6 @@ impl Config {
             RuleConfig {
                 enabled: Some(true),
                 severity: Some(Severity::Warning),
-                settings: None,
                 ..Default::default()
             },
         );
@@ -227,7 +358,6 @@ impl Config {
             RuleConfig {
                 enabled: Some(true),
                 severity: Some(Severity::Error),
-                settings: None,
                 ..Default::default()
             },
         );
@@ -238,12 +368,6 @@ impl Config {
             RuleConfig {
                 enabled: Some(true),
                 severity: Some(Severity::Warning),
-                settings: Some(
-                    serde_json::to_value(DocumentStartConfig {
-                        present: Some(true),
-                    })
-                    .unwrap(),
-                ),
                 ..Default::default()
             },
         );
@@ -254,12 +378,6 @@ impl Config {
             RuleConfig {
                 enabled: Some(false), // Changed from true to false to match yamllint default
                 severity: Some(Severity::Warning),
-                settings: Some(
-                    serde_json::to_value(DocumentEndConfig {
-                        present: Some(true),
-                    })
-                    .unwrap(),
-                ),
                 ..Default::default()
             },
         );
@@ -270,15 +388,6 @@ impl Config {
             RuleConfig {
                 enabled: Some(true),
                 severity: Some(Severity::Error),
-                settings: Some(
-                    serde_json::to_value(IndentationConfig {
-                        spaces: Some(2),
-                        indent_sequences: Some(true),
-                        check_multi_line_strings: Some(false),
-                        ignore: None,
-                    })
-                    .unwrap(),
-                ),
                 ..Default::default()
             },
         );
@@ -301,7 +410,6 @@ impl Config {
                 RuleConfig {
                     enabled: Some(true),
                     severity: Some(Severity::Error),
-                    settings: None,
                     ..Default::default()
                 },
             );
@@ -322,11 +430,130 @@ impl Config {
                 RuleConfig {
                     enabled: Some(false), // Disabled to match yamllint default
                     severity: Some(Severity::Error),
-                    settings: None,
                     ..Default::default()
                 },
             );
         }
+
+        // Secrets rule (opt-in, not part of yamllint)
+        self.rules.insert(
+            "secrets".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Warning),
+                ..Default::default()
+            },
+        );
+
+        // Forbidden values rule (not part of yamllint)
+        self.rules.insert(
+            "forbidden-values".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Error),
+                ..Default::default()
+            },
+        );
+
+        // Key naming rule (not part of yamllint)
+        self.rules.insert(
+            "key-naming".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Error),
+                ..Default::default()
+            },
+        );
+
+        // File size rule (not part of yamllint)
+        self.rules.insert(
+            "file-size".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Warning),
+                ..Default::default()
+            },
+        );
+
+        // Norway problem rule (not part of yamllint)
+        self.rules.insert(
+            "norway-problem".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Warning),
+                ..Default::default()
+            },
+        );
+
+        // Timestamp values rule (not part of yamllint)
+        self.rules.insert(
+            "timestamp-values".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Warning),
+                ..Default::default()
+            },
+        );
+
+        // Non-ASCII rule (not part of yamllint)
+        self.rules.insert(
+            "non-ascii".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Warning),
+                ..Default::default()
+            },
+        );
+
+        // Sequence ordering rule (not part of yamllint)
+        self.rules.insert(
+            "sequence-ordering".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Warning),
+                ..Default::default()
+            },
+        );
+
+        // Custom tags rule (not part of yamllint)
+        self.rules.insert(
+            "custom-tags".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Error),
+                ..Default::default()
+            },
+        );
+
+        // No aliases rule (not part of yamllint)
+        self.rules.insert(
+            "no-aliases".to_string(),
+            RuleConfig {
+                enabled: Some(false),
+                severity: Some(Severity::Error),
+                ..Default::default()
+            },
+        );
+
+        // Directive syntax rule (not part of yamllint)
+        self.rules.insert(
+            "directive-syntax".to_string(),
+            RuleConfig {
+                enabled: Some(true),
+                severity: Some(Severity::Warning),
+                ..Default::default()
+            },
+        );
+
+        // YAML syntax rule (yamllint reports these as syntax errors)
+        self.rules.insert(
+            "yaml-syntax".to_string(),
+            RuleConfig {
+                enabled: Some(true),
+                severity: Some(Severity::Error),
+                ..Default::default()
+            },
+        );
     }
 
     /// Get configuration for a specific rule
@@ -350,15 +577,16 @@ impl Config {
             .unwrap_or(self.global.default_severity.unwrap_or(Severity::Error))
     }
 
-    /// Get rule-specific settings
-    pub fn get_rule_settings<T>(&self, rule_id: &str) -> Option<T>
+    /// Options of `rule_id` as the rule's typed options struct, with its
+    /// defaults for options the config leaves unset
+    pub fn rule_options<T>(&self, rule_id: &str) -> Result<T, serde_json::Error>
     where
-        T: for<'de> Deserialize<'de>,
+        T: serde::de::DeserializeOwned + Default,
     {
-        self.rules
-            .get(rule_id)
-            .and_then(|config| config.settings.as_ref())
-            .and_then(|settings| serde_json::from_value(settings.clone()).ok())
+        match self.rules.get(rule_id) {
+            Some(rule) => serde_json::from_value(serde_json::Value::Object(rule.other.clone())),
+            None => Ok(T::default()),
+        }
     }
 
     /// Enable or disable a rule
@@ -374,6 +602,116 @@ impl Config {
             .enabled = Some(enabled);
     }
 
+    /// Apply command-line rule selection: when `enable_only` is non-empty every
+    /// other rule is disabled, then each rule in `disable` is turned off
+    pub fn select_rules(&mut self, enable_only: &[String], disable: &[String]) {
+        if !enable_only.is_empty() {
+            for rule_id in crate::rules::registry::RuleRegistry::new().get_rule_ids() {
+                let enabled = enable_only.contains(&rule_id);
+                self.set_rule_enabled(&rule_id, enabled);
+            }
+        }
+        for rule_id in disable {
+            self.set_rule_enabled(rule_id, false);
+        }
+    }
+
+    /// Rule names no rule is registered under, rule options no rule reads and
+    /// option values of the wrong type, which would otherwise be ignored
+    /// without notice, one message each
+    pub fn invalid_settings(&self) -> Vec<String> {
+        let registry = crate::rules::registry::RuleRegistry::new();
+        let factory = crate::rules::factory::RuleFactory::new();
+        let mut rule_ids: Vec<&String> = self.rules.keys().collect();
+        rule_ids.sort();
+
+        let mut problems = Vec::new();
+        for rule_id in rule_ids {
+            let Some(defaults) = factory.default_config_json(rule_id) else {
+                let known = registry.get_rule_ids();
+                problems.push(
+                    match closest_name(rule_id, known.iter().map(String::as_str)) {
+                        Some(known) => {
+                            format!("unknown rule '{}' (did you mean '{}'?)", rule_id, known)
+                        }
+                        None => format!("unknown rule '{}'", rule_id),
+                    },
+                );
+                continue;
+            };
+            let options: Vec<&str> = defaults
+                .as_object()
+                .map(|defaults| defaults.keys().map(String::as_str).collect())
+                .unwrap_or_default();
+            let (mut known, mut unknown): (Vec<_>, Vec<_>) = self.rules[rule_id]
+                .other
+                .iter()
+                .filter(|(key, _)| !COMMON_RULE_KEYS.contains(&key.as_str()))
+                .partition(|(key, _)| options.contains(&key.as_str()));
+            known.sort_by_key(|(key, _)| *key);
+            unknown.sort_by_key(|(key, _)| *key);
+
+            // Each option is read on its own, so errors name the option
+            for (option, value) in known {
+                let mut single = Config::new();
+                single.rules.insert(
+                    rule_id.clone(),
+                    RuleConfig {
+                        other: serde_json::Map::from_iter([(option.clone(), value.clone())]),
+                        ..Default::default()
+                    },
+                );
+                if let Some(Err(error)) = factory.try_create_rule_with_config(rule_id, &single) {
+                    problems.push(format!(
+                        "invalid option '{}' for rule '{}': {}",
+                        option, rule_id, error
+                    ));
+                }
+            }
+            for (option, _) in unknown {
+                problems.push(match closest_name(option, options.iter().copied()) {
+                    Some(known) => format!(
+                        "unknown option '{}' for rule '{}' (did you mean '{}'?)",
+                        option, rule_id, known
+                    ),
+                    None => format!("unknown option '{}' for rule '{}'", option, rule_id),
+                });
+            }
+        }
+
+        if let Some(locale) = self
+            .locale
+            .as_deref()
+            .filter(|locale| !crate::rules::key_ordering::is_supported_locale(locale))
+        {
+            problems.push(format!(
+                "unsupported locale '{}', key-ordering compares keys by code point",
+                locale
+            ));
+        }
+
+        let known = registry.get_rule_ids();
+        for entry in &self.severity_by_path {
+            for rule_id in entry.rules.iter().filter(|id| !known.contains(id)) {
+                problems.push(
+                    match closest_name(rule_id, known.iter().map(String::as_str)) {
+                        Some(known) => format!(
+                            "unknown rule '{}' in severity-by-path (did you mean '{}'?)",
+                            rule_id, known
+                        ),
+                        None => format!("unknown rule '{}' in severity-by-path", rule_id),
+                    },
+                );
+            }
+        }
+        problems
+    }
+
+    /// Whether `exclude-from-fix` keeps `--fix` away from `rule_id`
+    pub fn is_fix_excluded(&self, rule_id: &str) -> bool {
+        self.exclude_from_fix.iter().any(|id| id == rule_id)
+    }
+
     /// Set severity for a rule
     pub fn set_rule_severity(&mut self, rule_id: &str, severity: Severity) {
         self.rules
@@ -405,6 +743,87 @@ impl Config {
             .collect()
     }
 
+    /// Severity that `severity-by-path` assigns to `rule_id`'s issues in
+    /// `file_path`, if any
+    pub fn severity_override_for(&self, file_path: &str, rule_id: &str) -> Option<Severity> {
+        let path = self.path_from_base_dir(file_path);
+        self.severity_by_path
+            .iter()
+            .filter(|entry| entry.rules.is_empty() || entry.rules.iter().any(|id| id == rule_id))
+            .find(|entry| glob_to_regex(&entry.files).is_some_and(|regex| regex.is_match(&path)))
+            .map(|entry| entry.level)
+    }
+
+    /// `overrides` entries whose globs match `file_path`, in config order
+    pub fn overrides_for<'a>(&'a self, file_path: &str) -> Vec<&'a PathOverride> {
+        let path = self.path_from_base_dir(file_path);
+        self.overrides
+            .iter()
+            .filter(|entry| {
+                entry
+                    .files
+                    .iter()
+                    .any(|files| glob_to_regex(files).is_some_and(|regex| regex.is_match(&path)))
+            })
+            .collect()
+    }
+
+    /// `file_path`, relative to the working directory, made relative to
+    /// [`Config::base_dir`] with `/` separators and no leading `./`. Paths
+    /// outside the config's directory are kept as given.
+    fn path_from_base_dir(&self, file_path: &str) -> String {
+        let path = file_path.replace('\\', "/");
+        let path = path.trim_start_matches("./");
+        let Some(base_dir) = &self.base_dir else {
+            return path.to_string();
+        };
+        let absolute = std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| PathBuf::from(path));
+        let absolute = absolute.canonicalize().unwrap_or(absolute);
+        match absolute.strip_prefix(base_dir) {
+            Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+            Err(_) => path.to_string(),
+        }
+    }
+
+    /// Replace `yaml-files` with one `*.ext` pattern per extension, e.g. from
+    /// `--extensions`
+    pub fn set_yaml_file_extensions(&mut self, extensions: &[String]) {
+        if extensions.is_empty() {
+            return;
+        }
+        self.yaml_files = Some(
+            extensions
+                .iter()
+                .map(|extension| format!("*.{}", extension.trim_start_matches('.')))
+                .collect(),
+        );
+    }
+
+    /// Whether walking `base_dir` should lint `path`, see [`is_yaml_file`]
+    pub fn is_yaml_file(&self, path: &Path, base_dir: &Path, include_hidden: bool) -> bool {
+        match &self.yaml_files {
+            Some(patterns) => is_yaml_file(patterns, path, base_dir, include_hidden),
+            None => is_yaml_file(DEFAULT_YAML_FILES, path, base_dir, include_hidden),
+        }
+    }
+
+    /// Append patterns to `ignore`, e.g. from `--ignore-pattern`
+    pub fn add_ignore_patterns(&mut self, patterns: &[String]) {
+        if patterns.is_empty() {
+            return;
+        }
+        let mut ignore = self.ignore.take().unwrap_or_default();
+        for pattern in patterns {
+            if !ignore.is_empty() && !ignore.ends_with('\n') {
+                ignore.push('\n');
+            }
+            ignore.push_str(pattern);
+        }
+        self.ignore = Some(ignore);
+    }
+
     fn collect_ignore_patterns(&self, config_dir: Option<&Path>) -> Vec<String> {
         let mut patterns = Vec::new();
 
@@ -447,64 +866,189 @@ impl Config {
             return false;
         }
 
-        let file_path_normalized = if let Some(base_dir) = config_dir {
-            if let Ok(relative) = file_path.strip_prefix(base_dir) {
-                let rel_str = relative.to_string_lossy().replace('\\', "/");
-                if rel_str.starts_with('/') {
-                    rel_str[1..].to_string()
-                } else {
-                    rel_str
-                }
-            } else {
-                file_path.to_string_lossy().replace('\\', "/")
-            }
-        } else {
-            if let Ok(cwd) = std::env::current_dir() {
-                if let Ok(relative) = file_path.strip_prefix(&cwd) {
-                    let rel_str = relative.to_string_lossy().replace('\\', "/");
-                    if rel_str.starts_with('/') {
-                        rel_str[1..].to_string()
-                    } else {
-                        rel_str
-                    }
-                } else {
-                    file_path.to_string_lossy().replace('\\', "/")
-                }
-            } else {
-                file_path.to_string_lossy().replace('\\', "/")
-            }
+        let file_path_normalized = normalize_ignore_path(file_path, config_dir);
+        patterns
+            .iter()
+            .any(|pattern| matches_ignore_pattern(pattern, &file_path_normalized, file_path))
+    }
+
+    /// Whether the rule's own `ignore` patterns exclude `file_path`. They are
+    /// matched like the top-level `ignore`, relative to the working directory.
+    pub fn is_ignored_by_rule(&self, rule_id: &str, file_path: &Path) -> bool {
+        let Some(ignore) = self
+            .rules
+            .get(rule_id)
+            .and_then(|rule| rule.ignore.as_deref())
+        else {
+            return false;
         };
 
-        for pattern in patterns {
-            let pattern_normalized = pattern.trim().replace('\\', "/");
-
-            if pattern_normalized.ends_with('/') {
-                let dir_pattern = pattern_normalized.trim_end_matches('/');
-                if !dir_pattern.is_empty() {
-                    if file_path_normalized == dir_pattern
-                        || file_path_normalized.starts_with(&format!("{}/", dir_pattern))
-                    {
-                        return true;
-                    }
-                }
-            } else {
-                let pattern_trimmed = pattern_normalized.trim();
-                if !pattern_trimmed.is_empty() {
-                    if file_path_normalized == pattern_trimmed {
-                        return true;
-                    }
-                    if file_path_normalized.ends_with(&format!("/{}", pattern_trimmed)) {
-                        return true;
-                    }
-                    if let Some(file_name) = file_path.file_name() {
-                        if file_name.to_string_lossy() == pattern_trimmed {
-                            return true;
-                        }
-                    }
+        let file_path_normalized = normalize_ignore_path(file_path, None);
+        ignore
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .any(|pattern| matches_ignore_pattern(pattern, &file_path_normalized, file_path))
+    }
+}
+
+/// Environment variable naming the profile to select, below `--profile`
+pub const PROFILE_ENV: &str = "YAMLLINT_RS_PROFILE";
+
+/// Settings layered over a loaded config. Precedence runs from the built-in
+/// defaults through the configs a file `extends` and the file itself (see
+/// [`crate::load_config`]) to the environment and then the command line, each
+/// layer applied over the ones before it:
+///
+/// 1. the profile named by [`PROFILE_ENV`], unless `profile` names one;
+/// 2. the profile named on the command line;
+/// 3. `set_options`, in order;
+/// 4. rule selection, `enable_only` then `disable`, which wins over any
+///    option set before it;
+/// 5. `ignore_patterns` and `extensions`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    /// Profile from the environment
+    pub env_profile: Option<String>,
+    /// Profile from `--profile`, replacing `env_profile`
+    pub profile: Option<String>,
+    /// `rule.option=value` overrides, see [`crate::apply_rule_option_overrides`]
+    pub set_options: Vec<String>,
+    pub enable_only: Vec<String>,
+    pub disable: Vec<String>,
+    /// Patterns appended to `ignore`
+    pub ignore_patterns: Vec<String>,
+    /// Extensions replacing `yaml-files`
+    pub extensions: Vec<String>,
+}
+
+impl ConfigLayers {
+    /// Layers with the environment's profile, if [`PROFILE_ENV`] is set
+    pub fn from_env() -> Self {
+        Self {
+            env_profile: std::env::var(PROFILE_ENV).ok().filter(|p| !p.is_empty()),
+            ..Default::default()
+        }
+    }
+
+    /// Apply every layer over `config`, in precedence order
+    pub fn apply(&self, config: &mut Config) -> anyhow::Result<()> {
+        if let Some(profile) = self.profile.as_ref().or(self.env_profile.as_ref()) {
+            crate::apply_profile(config, profile)?;
+        }
+        crate::apply_rule_option_overrides(config, &self.set_options)?;
+        config.select_rules(&self.enable_only, &self.disable);
+        config.add_ignore_patterns(&self.ignore_patterns);
+        config.set_yaml_file_extensions(&self.extensions);
+        Ok(())
+    }
+}
+
+/// `file_path` relative to `config_dir` (or the working directory), with `/`
+/// separators and no leading `./`
+fn normalize_ignore_path(file_path: &Path, config_dir: Option<&Path>) -> String {
+    let cwd = std::env::current_dir().ok();
+    let base_dir = config_dir.or(cwd.as_deref());
+    let relative = base_dir
+        .and_then(|base_dir| file_path.strip_prefix(base_dir).ok())
+        .unwrap_or(file_path);
+    let normalized = relative.to_string_lossy().replace('\\', "/");
+    normalized
+        .trim_start_matches("./")
+        .trim_start_matches('/')
+        .to_string()
+}
+
+/// Whether one `ignore` pattern matches a path normalized by
+/// [`normalize_ignore_path`]
+fn matches_ignore_pattern(pattern: &str, file_path_normalized: &str, file_path: &Path) -> bool {
+    let pattern_normalized = pattern.trim().replace('\\', "/");
+
+    if pattern_normalized.contains(['*', '?']) {
+        glob_ignores(&pattern_normalized, file_path_normalized)
+    } else if let Some(dir_pattern) = pattern_normalized.strip_suffix('/') {
+        !dir_pattern.is_empty()
+            && (file_path_normalized == dir_pattern
+                || file_path_normalized.starts_with(&format!("{}/", dir_pattern)))
+    } else {
+        !pattern_normalized.is_empty()
+            && (file_path_normalized == pattern_normalized
+                || file_path_normalized.ends_with(&format!("/{}", pattern_normalized))
+                || file_path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy() == pattern_normalized))
+    }
+}
+
+/// Whether any `yaml-files` glob matches `path`, relative to `base_dir`.
+/// Matching ignores case. Hidden files (`.clang-format`) only match patterns
+/// whose file name part starts with a dot, so `*.yaml` does not pull in
+/// hidden files a directory walk would otherwise skip, unless
+/// `include_hidden` is set (`--hidden`).
+pub fn is_yaml_file<S: AsRef<str>>(
+    patterns: &[S],
+    path: &Path,
+    base_dir: &Path,
+    include_hidden: bool,
+) -> bool {
+    let relative = path.strip_prefix(base_dir).unwrap_or(path);
+    let relative = relative.to_string_lossy().replace('\\', "/").to_lowercase();
+    let hidden = !include_hidden
+        && path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.as_ref().trim().to_lowercase();
+        let dotted = pattern
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| name.starts_with('.'));
+        (!hidden || dotted)
+            && glob_to_regex(&pattern).is_some_and(|regex| regex.is_match(&relative))
+    })
+}
+
+/// Whether a glob ignore pattern matches `path`. Patterns ending in `/`
+/// match files below any directory the glob matches.
+fn glob_ignores(pattern: &str, path: &str) -> bool {
+    let path = path.trim_start_matches("./");
+    let Some(dir_pattern) = pattern.strip_suffix('/') else {
+        return glob_to_regex(pattern).is_some_and(|regex| regex.is_match(path));
+    };
+    let Some(regex) = glob_to_regex(dir_pattern) else {
+        return false;
+    };
+    path.match_indices('/')
+        .any(|(index, _)| regex.is_match(&path[..index]))
+}
+
+/// Translate a path glob into an anchored regex
+fn glob_to_regex(pattern: &str) -> Option<regex::Regex> {
+    let pattern = pattern.trim().trim_start_matches("./");
+    let mut regex = String::from("^");
+    if !pattern.contains('/') {
+        regex.push_str("(?:.*/)?");
+    }
+
+    let mut chars = pattern.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
                 }
             }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&ch.to_string())),
         }
-
-        false
     }
+    regex.push('$');
+
+    crate::patterns::regex(&regex).ok()
 }
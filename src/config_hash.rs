@@ -0,0 +1,100 @@
+//! Canonical config fingerprints.
+//!
+//! Two configs get the same fingerprint exactly when they lint alike: every
+//! registered rule is resolved to the enabled state and severity it runs
+//! with, unset options are dropped and map keys are sorted, so the way a
+//! config was written (yamllint or native format, `extends`, nested config
+//! files, profiles or command-line overrides) does not matter, only the
+//! result. The tool version is part of the fingerprint because rule behavior
+//! can change between releases.
+
+use crate::config::Config;
+use crate::rules::registry::RuleRegistry;
+
+/// `config` with every registered rule resolved, as a JSON value with
+/// sorted keys and without unset options
+pub fn canonical_config(config: &Config) -> serde_json::Value {
+    let enabled_rules = config.get_enabled_rules();
+    let mut resolved = config.clone();
+    for rule_id in RuleRegistry::new().get_rule_ids() {
+        let enabled = enabled_rules.contains(&rule_id);
+        let severity = config.get_rule_severity(&rule_id);
+        let rule_config = resolved.rules.entry(rule_id).or_default();
+        rule_config.enabled = Some(enabled);
+        rule_config.severity = Some(severity);
+    }
+
+    let mut value = serde_json::to_value(&resolved).unwrap_or_default();
+    remove_nulls(&mut value);
+    value
+}
+
+/// Fingerprint of the effective configuration, stable across runs and
+/// platforms
+pub fn fingerprint(config: &Config) -> String {
+    let canonical = format!(
+        "{}\n{}",
+        env!("CARGO_PKG_VERSION"),
+        canonical_config(config)
+    );
+    hash_hex(canonical.as_bytes())
+}
+
+/// 64-bit FNV-1a hash as hex. Unlike `DefaultHasher`, the result is
+/// guaranteed not to change between Rust releases.
+pub fn hash_hex(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+fn remove_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, value| !value.is_null());
+            map.values_mut().for_each(remove_nulls);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{apply_rule_option_overrides, load_config_from_str};
+
+    #[test]
+    fn test_fingerprint_depends_only_on_effective_config() {
+        let default = fingerprint(&Config::default());
+        assert_eq!(default, fingerprint(&Config::default()));
+
+        // Explicitly restating a default changes nothing
+        let mut restated = Config::default();
+        restated.set_rule_enabled("line-length", true);
+        assert_eq!(fingerprint(&restated), default);
+
+        let mut disabled = Config::default();
+        disabled.set_rule_enabled("line-length", false);
+        assert_ne!(fingerprint(&disabled), default);
+
+        // The same options given in the file or on the command line match
+        let from_file =
+            load_config_from_str("extends: default\nrules:\n  line-length:\n    max: 120\n")
+                .unwrap();
+        let mut from_cli = load_config_from_str("extends: default\n").unwrap();
+        apply_rule_option_overrides(&mut from_cli, &["line-length.max=120".to_string()]).unwrap();
+        assert_eq!(fingerprint(&from_file), fingerprint(&from_cli));
+        assert_ne!(
+            fingerprint(&from_file),
+            fingerprint(&load_config_from_str("extends: default\n").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_hash_is_fnv1a() {
+        assert_eq!(hash_hex(b""), "cbf29ce484222325");
+        assert_eq!(hash_hex(b"a"), "af63dc4c8601ec8c");
+    }
+}
@@ -0,0 +1,350 @@
+//! Real-world YAML corpus for parity and performance checks (feature `corpus`).
+//!
+//! `corpus.yaml` pins public repositories to a tag or commit, optionally
+//! limited to a few directories through a sparse checkout. `fetch` clones or
+//! updates each one under a corpus directory with a shallow, blob-less fetch,
+//! and `run` lints every YAML file in them, reporting counts and timings per
+//! repository. With a reference `yamllint` on the PATH, `run` also compares
+//! findings by file, line and rule.
+
+use crate::git::git;
+use crate::linter::Linter;
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// Default manifest, relative to the working directory
+pub const MANIFEST: &str = "corpus.yaml";
+
+/// Default directory repositories are fetched into
+pub const CORPUS_DIR: &str = "corpus";
+
+/// Config both linters run with unless `-c` is given
+const DEFAULT_CONFIG: &str = "extends: default\n";
+
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    pub repos: Vec<CorpusRepo>,
+}
+
+/// One pinned repository
+#[derive(Debug, Deserialize)]
+pub struct CorpusRepo {
+    pub name: String,
+    pub url: String,
+    /// Tag, branch or commit to check out
+    pub rev: String,
+    /// Directories to check out, besides top-level files; everything when empty
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read corpus manifest {}", path.display()))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Invalid corpus manifest {}", path.display()))
+    }
+}
+
+/// A finding reduced to what both linters agree on naming
+pub type Finding = (String, usize, String);
+
+/// Results for one repository
+#[derive(Debug)]
+pub struct RepoReport {
+    pub name: String,
+    pub files: usize,
+    pub issues: usize,
+    pub elapsed: Duration,
+    pub parity: Option<Parity>,
+}
+
+/// Findings compared with the reference `yamllint`
+#[derive(Debug, Default)]
+pub struct Parity {
+    pub matched: usize,
+    pub only_reference: Vec<Finding>,
+    pub only_ours: Vec<Finding>,
+}
+
+impl Parity {
+    fn compare(reference: BTreeSet<Finding>, ours: BTreeSet<Finding>) -> Self {
+        Self {
+            matched: reference.intersection(&ours).count(),
+            only_reference: reference.difference(&ours).cloned().collect(),
+            only_ours: ours.difference(&reference).cloned().collect(),
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.only_reference.is_empty() && self.only_ours.is_empty()
+    }
+
+    /// Mismatch counts by rule, most frequent first
+    pub fn mismatches_by_rule(&self) -> Vec<(&str, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for (_, _, rule) in self.only_reference.iter().chain(&self.only_ours) {
+            *counts.entry(rule).or_default() += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        counts
+    }
+}
+
+/// Clone or update every repository in `manifest` under `dir`
+pub fn fetch(manifest: &Manifest, dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Cannot create corpus directory {}", dir.display()))?;
+    for repo in &manifest.repos {
+        eprintln!("Fetching {} at {}", repo.name, repo.rev);
+        fetch_repo(repo, &dir.join(&repo.name))
+            .with_context(|| format!("Cannot fetch {}", repo.name))?;
+    }
+    Ok(())
+}
+
+fn fetch_repo(repo: &CorpusRepo, dir: &Path) -> Result<()> {
+    if dir.join(".git").exists() {
+        git(dir, &["remote", "set-url", "origin", &repo.url])?;
+    } else {
+        std::fs::create_dir_all(dir)?;
+        git(dir, &["init", "-q"])?;
+        git(dir, &["remote", "add", "origin", &repo.url])?;
+    }
+
+    if repo.paths.is_empty() {
+        git(dir, &["sparse-checkout", "disable"])?;
+    } else {
+        let mut args = vec!["sparse-checkout", "set"];
+        args.extend(repo.paths.iter().map(String::as_str));
+        git(dir, &args)?;
+    }
+
+    git(
+        dir,
+        &[
+            "fetch",
+            "-q",
+            "--depth",
+            "1",
+            "--filter=blob:none",
+            "origin",
+            &repo.rev,
+        ],
+    )?;
+    git(dir, &["checkout", "-q", "--detach", "FETCH_HEAD"])?;
+    Ok(())
+}
+
+/// Lint every fetched repository in `manifest`. `config` is a yamllint-format
+/// config file shared with the reference linter when `parity` is set.
+pub fn run(
+    manifest: &Manifest,
+    dir: &Path,
+    config: Option<&Path>,
+    parity: bool,
+) -> Result<Vec<RepoReport>> {
+    let config_content = match config {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read config {}", path.display()))?,
+        None => DEFAULT_CONFIG.to_string(),
+    };
+    let linter = Linter::with_config(crate::load_config_from_str(&config_content)?);
+
+    let mut reports = Vec::new();
+    for repo in &manifest.repos {
+        let repo_dir = dir.join(&repo.name);
+        if !repo_dir.is_dir() {
+            anyhow::bail!(
+                "{} has not been fetched; run `corpus fetch` first",
+                repo.name
+            );
+        }
+
+        let files = yaml_files(&repo_dir);
+        let start = Instant::now();
+        let ours: Vec<Finding> = files
+            .par_iter()
+            .flat_map_iter(|file| {
+                let relative = relative_name(file, &repo_dir);
+                let content = std::fs::read(file)
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .unwrap_or_default();
+                linter
+                    .check(&content, &relative)
+                    .into_iter()
+                    .map(move |(issue, rule)| (relative.clone(), issue.line, rule))
+            })
+            .collect();
+        let elapsed = start.elapsed();
+
+        let parity = if parity {
+            let reference = reference_findings(&repo_dir, &config_content)?;
+            Some(Parity::compare(reference, ours.iter().cloned().collect()))
+        } else {
+            None
+        };
+
+        reports.push(RepoReport {
+            name: repo.name.clone(),
+            files: files.len(),
+            issues: ours.len(),
+            elapsed,
+            parity,
+        });
+    }
+    Ok(reports)
+}
+
+/// `.yaml` and `.yml` files under `dir`, outside `.git`
+fn yaml_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn relative_name(path: &Path, base: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Findings of `yamllint -f parsable` run over `dir`
+fn reference_findings(dir: &Path, config: &str) -> Result<BTreeSet<Finding>> {
+    let output = Command::new("yamllint")
+        .args(["-f", "parsable", "-d", config, "."])
+        .current_dir(dir)
+        .output()
+        .context("--parity requires the reference `yamllint` on the PATH")?;
+    if !matches!(output.status.code(), Some(0..=2)) {
+        anyhow::bail!(
+            "yamllint failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_parsable_line)
+        .collect())
+}
+
+/// Parse one `path:line:column: [level] message (rule)` line
+pub fn parse_parsable_line(line: &str) -> Option<Finding> {
+    lazy_static! {
+        static ref PARSABLE: Regex =
+            Regex::new(r"^(.+):(\d+):\d+: \[\w+\] .* \(([\w-]+)\)$").unwrap();
+    }
+    let captures = PARSABLE.captures(line)?;
+    let file = captures[1].trim_start_matches("./").to_string();
+    Some((file, captures[2].parse().ok()?, captures[3].to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_parsable_line() {
+        assert_eq!(
+            parse_parsable_line(
+                "./a/b.yaml:3:81: [error] line too long (90 > 80 characters) (line-length)"
+            ),
+            Some(("a/b.yaml".to_string(), 3, "line-length".to_string()))
+        );
+        assert_eq!(
+            parse_parsable_line(
+                "c:d.yml:1:1: [warning] missing document start \"---\" (document-start)"
+            ),
+            Some(("c:d.yml".to_string(), 1, "document-start".to_string()))
+        );
+        assert_eq!(parse_parsable_line("not a finding"), None);
+    }
+
+    #[test]
+    fn test_parity_compare() {
+        let finding = |file: &str, line, rule: &str| (file.to_string(), line, rule.to_string());
+        let reference = BTreeSet::from([
+            finding("a.yaml", 1, "document-start"),
+            finding("a.yaml", 2, "trailing-spaces"),
+        ]);
+        let ours = BTreeSet::from([
+            finding("a.yaml", 2, "trailing-spaces"),
+            finding("a.yaml", 3, "trailing-spaces"),
+        ]);
+        let parity = Parity::compare(reference, ours);
+        assert_eq!(parity.matched, 1);
+        assert!(!parity.is_exact());
+        assert_eq!(
+            parity.mismatches_by_rule(),
+            vec![("document-start", 1), ("trailing-spaces", 1)]
+        );
+    }
+
+    #[test]
+    fn test_fetch_pinned_tag_from_local_repo() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let upstream = temp_dir.path().join("upstream");
+        std::fs::create_dir_all(upstream.join("manifests")).unwrap();
+        std::fs::create_dir_all(upstream.join("unrelated")).unwrap();
+        std::fs::write(upstream.join("manifests/a.yaml"), "---\nkey: value\n").unwrap();
+        std::fs::write(upstream.join("unrelated/c.yaml"), "---\nkey: value\n").unwrap();
+        let commit = |args: &[&str]| {
+            git(
+                &upstream,
+                &[&["-c", "user.name=t", "-c", "user.email=t@t"], args].concat(),
+            )
+            .unwrap()
+        };
+        git(&upstream, &["init", "-q"]).unwrap();
+        git(&upstream, &["add", "."]).unwrap();
+        commit(&["commit", "-qm", "initial"]);
+        commit(&["tag", "v1"]);
+        std::fs::write(upstream.join("manifests/b.yaml"), "---\nnew: file\n").unwrap();
+        git(&upstream, &["add", "."]).unwrap();
+        commit(&["commit", "-qm", "after tag"]);
+
+        let manifest = Manifest {
+            repos: vec![CorpusRepo {
+                name: "local".to_string(),
+                url: format!("file://{}", upstream.display()),
+                rev: "v1".to_string(),
+                paths: vec!["manifests".to_string()],
+            }],
+        };
+        let corpus = temp_dir.path().join("corpus");
+        fetch(&manifest, &corpus).unwrap();
+        // Refetching updates in place
+        fetch(&manifest, &corpus).unwrap();
+
+        let files: Vec<String> = yaml_files(&corpus.join("local"))
+            .iter()
+            .map(|file| relative_name(file, &corpus.join("local")))
+            .collect();
+        assert_eq!(files, vec!["manifests/a.yaml"]);
+
+        let reports = run(&manifest, &corpus, None, false).unwrap();
+        assert_eq!(reports[0].files, 1);
+        assert_eq!(reports[0].issues, 0);
+    }
+}
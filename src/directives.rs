@@ -12,6 +12,216 @@ lazy_static! {
         Regex::new(r"^# (yamllint|yamllint-rs) enable( rule:\S+)*\s*$").unwrap();
     static ref DISABLE_LINE_PATTERN: Regex =
         Regex::new(r"^# (yamllint|yamllint-rs) disable-line( rule:\S+)*\s*$").unwrap();
+    /// `# yamllint configure rule:ID option=value ...` or `... rule:ID reset`
+    static ref CONFIGURE_PATTERN: Regex = Regex::new(
+        r"^# (yamllint|yamllint-rs) configure rule:(\S+)( reset|( [a-z0-9-]+=\S+)+)\s*$"
+    )
+    .unwrap();
+    /// Comments that look like they were meant to be directives, including
+    /// misspelled actions, but not prose that merely mentions yamllint
+    static ref DIRECTIVE_LIKE_PATTERN: Regex =
+        Regex::new(r"(?i)^#\s*yamllint(-rs)?\s*:?\s*((dis|en)[a-z-]*|configure)\b").unwrap();
+    /// `# yamllint-rs: rule.option=value ...` on a file's first line
+    static ref MODELINE_PATTERN: Regex = Regex::new(r"^#\s*yamllint-rs:\s*(.*)$").unwrap();
+    static ref MODELINE_ENTRY_PATTERN: Regex =
+        Regex::new(r"^([a-z0-9-]+)\.[a-z0-9-]+=\S*$").unwrap();
+}
+
+/// Syntax reminder included in malformed modeline messages
+pub const MODELINE_SYNTAX: &str = "# yamllint-rs: rule.option=value ...";
+
+/// Rule option overrides from a `# yamllint-rs:` modeline on the first line
+/// of `content`, as `rule.option=value` entries. Validation happens when the
+/// entries are applied, so malformed ones are returned too.
+pub fn parse_modeline(content: &str) -> Option<Vec<String>> {
+    let first_line = content.lines().next()?.trim();
+    let captures = MODELINE_PATTERN.captures(first_line)?;
+    let entries: Vec<String> = captures[1].split_whitespace().map(String::from).collect();
+    (!entries.is_empty()).then_some(entries)
+}
+
+/// Explain what is wrong with a `# yamllint-rs:` modeline comment found on
+/// line `line_number`. Returns `None` for other comments and valid modelines.
+pub fn malformed_modeline(
+    comment: &str,
+    line_number: usize,
+    is_known_rule: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let captures = MODELINE_PATTERN.captures(comment.trim())?;
+    if line_number != 1 {
+        return Some("modeline is only read on the first line".to_string());
+    }
+
+    let entries: Vec<&str> = captures[1].split_whitespace().collect();
+    if entries.is_empty() {
+        return Some(format!("empty modeline, expected \"{}\"", MODELINE_SYNTAX));
+    }
+    for entry in entries {
+        let Some(entry_captures) = MODELINE_ENTRY_PATTERN.captures(entry) else {
+            return Some(format!(
+                "malformed modeline entry \"{}\", expected \"{}\"",
+                entry, MODELINE_SYNTAX
+            ));
+        };
+        let rule = &entry_captures[1];
+        if !is_known_rule(rule) {
+            return Some(format!("unknown rule \"{}\" in modeline", rule));
+        }
+    }
+    None
+}
+
+/// Syntax reminder included in malformed directive messages
+pub const DIRECTIVE_SYNTAX: &str = "# yamllint disable|enable|disable-line [rule:ID ...]";
+
+/// Syntax reminder for malformed `configure` directives
+pub const CONFIGURE_SYNTAX: &str = "# yamllint configure rule:ID option=value ...|reset";
+
+/// Options of one rule changed by `# yamllint configure` comments, from the
+/// comment's line until the rule is configured again, reset or the file ends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigureRegion {
+    pub rule: String,
+    pub start_line: usize,
+    /// First line after the region, `None` when it runs to the end of the file
+    pub end_line: Option<usize>,
+    /// `rule.option=value` overrides in effect, later entries winning
+    pub overrides: Vec<String>,
+}
+
+impl ConfigureRegion {
+    pub fn contains(&self, line: usize) -> bool {
+        line >= self.start_line && self.end_line.is_none_or(|end| line < end)
+    }
+}
+
+/// Number of leading lines of `content` before its first content line:
+/// blank lines, comments, `%` directives and `---` markers
+pub fn header_len(content: &str) -> usize {
+    content
+        .lines()
+        .take_while(|line| {
+            let line = line.trim();
+            line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with('%')
+                || line == "---"
+                || line.starts_with("--- #")
+        })
+        .count()
+}
+
+/// Rule option overrides set by `configure` directives in the header of
+/// `content` (see [`header_len`]), as `rule.option=value` entries. They
+/// apply to the whole file, and a `reset` in the header drops the earlier
+/// entries for its rule.
+pub fn parse_file_configure(content: &str) -> Vec<String> {
+    let mut overrides: Vec<String> = Vec::new();
+    if !content.contains(" configure rule:") {
+        return overrides;
+    }
+    for line in content.lines().take(header_len(content)) {
+        let Some(captures) = CONFIGURE_PATTERN.captures(line.trim()) else {
+            continue;
+        };
+        let rule = &captures[2];
+        if captures[3].trim() == "reset" {
+            let prefix = format!("{}.", rule);
+            overrides.retain(|entry| !entry.starts_with(&prefix));
+            continue;
+        }
+        overrides.extend(
+            captures[3]
+                .split_whitespace()
+                .map(|entry| format!("{}.{}", rule, entry)),
+        );
+    }
+    overrides
+}
+
+/// Regions of `content` where `configure` directives change rule options.
+/// Options accumulate until `# yamllint configure rule:ID reset`; malformed
+/// directives are skipped and reported by `directive-syntax`.
+pub fn parse_configure_regions(content: &str) -> Vec<ConfigureRegion> {
+    let mut regions: Vec<ConfigureRegion> = Vec::new();
+    if !content.contains(" configure rule:") {
+        return regions;
+    }
+    for (line_num, line) in content.lines().enumerate() {
+        let line_num = line_num + 1;
+        let comment = match line.trim_start() {
+            trimmed if trimmed.starts_with('#') => trimmed,
+            _ => match DirectiveState::extract_inline_comment(line) {
+                Some(comment) => comment,
+                None => continue,
+            },
+        };
+        let Some(captures) = CONFIGURE_PATTERN.captures(comment.trim()) else {
+            continue;
+        };
+
+        let rule = &captures[2];
+        let mut overrides = Vec::new();
+        if let Some(open) = regions
+            .iter_mut()
+            .find(|region| region.rule == rule && region.end_line.is_none())
+        {
+            open.end_line = Some(line_num);
+            overrides = open.overrides.clone();
+        }
+        if captures[3].trim() == "reset" {
+            continue;
+        }
+        overrides.extend(
+            captures[3]
+                .split_whitespace()
+                .map(|entry| format!("{}.{}", rule, entry)),
+        );
+        regions.push(ConfigureRegion {
+            rule: rule.to_string(),
+            start_line: line_num,
+            end_line: None,
+            overrides,
+        });
+    }
+    regions
+}
+
+/// Explain what is wrong with a comment that looks like a directive but would
+/// be ignored. Returns `None` for ordinary comments and valid directives.
+pub fn malformed_directive(comment: &str, is_known_rule: impl Fn(&str) -> bool) -> Option<String> {
+    let comment = comment.trim();
+    if !DIRECTIVE_LIKE_PATTERN.is_match(comment) {
+        return None;
+    }
+
+    let is_configure = comment
+        .split_whitespace()
+        .nth(2)
+        .is_some_and(|action| action == "configure");
+    if is_configure && !CONFIGURE_PATTERN.is_match(comment) {
+        return Some(format!(
+            "malformed directive, expected \"{}\"",
+            CONFIGURE_SYNTAX
+        ));
+    }
+
+    let well_formed = DISABLE_PATTERN.is_match(comment)
+        || ENABLE_PATTERN.is_match(comment)
+        || DISABLE_LINE_PATTERN.is_match(comment)
+        || is_configure;
+    if !well_formed {
+        return Some(format!(
+            "malformed directive, expected \"{}\"",
+            DIRECTIVE_SYNTAX
+        ));
+    }
+
+    comment
+        .split_whitespace()
+        .filter_map(|item| item.strip_prefix("rule:"))
+        .find(|rule| !is_known_rule(rule))
+        .map(|rule| format!("unknown rule \"{}\" in directive", rule))
 }
 
 pub struct DirectiveState {
@@ -40,6 +250,14 @@ impl DirectiveState {
         }
     }
 
+    /// Directives in `content` as they apply to `rule_id` alone, for fixers
+    /// that must leave disabled lines untouched
+    pub fn for_rule(content: &str, rule_id: &str) -> Self {
+        let mut state = Self::new(HashSet::from([rule_id.to_string()]));
+        state.parse_from_content(content);
+        state
+    }
+
     /// Parse all directives from content and build state
     /// In yamllint, directives are processed line-by-line:
     /// - Block comment on line N → affects line N+1 and onwards (disabled_for_next_line)
@@ -75,7 +293,7 @@ impl DirectiveState {
     }
 
     /// Extract inline comment from a line (everything after #)
-    fn extract_inline_comment(line: &str) -> Option<&str> {
+    pub(crate) fn extract_inline_comment(line: &str) -> Option<&str> {
         // Simple approach: find first # that's not in quotes
         let mut in_single_quotes = false;
         let mut in_double_quotes = false;
@@ -0,0 +1,128 @@
+//! Detection of files that cannot be linted as UTF-8 text.
+//!
+//! YAML files are read as bytes and decoded here. Content with NUL bytes or
+//! invalid UTF-8 becomes a single `invalid-encoding` finding carrying a guess
+//! of the actual encoding, rather than an I/O error aborting the run.
+
+use crate::{LintIssue, LintResult, Severity};
+use std::collections::HashMap;
+
+/// Rule name and diagnostic code of the finding for undecodable files
+pub const INVALID_ENCODING: &str = "invalid-encoding";
+
+/// Why content could not be decoded, with the most likely actual encoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingError {
+    pub guess: &'static str,
+    pub message: String,
+}
+
+/// Decode file content as UTF-8 text, rejecting binary data
+pub fn decode(bytes: Vec<u8>) -> Result<String, EncodingError> {
+    if let Some(guess) = utf16_guess(&bytes) {
+        return Err(EncodingError {
+            guess,
+            message: format!("file is encoded as {}, expected UTF-8", guess),
+        });
+    }
+
+    if let Some(offset) = bytes.iter().position(|&byte| byte == 0) {
+        return Err(EncodingError {
+            guess: "binary",
+            message: format!(
+                "file contains a NUL byte at offset {} and looks binary",
+                offset
+            ),
+        });
+    }
+
+    String::from_utf8(bytes).map_err(|error| {
+        let offset = error.utf8_error().valid_up_to();
+        EncodingError {
+            guess: "latin-1",
+            message: format!(
+                "invalid UTF-8 at byte offset {} (possibly latin-1 or another 8-bit encoding)",
+                offset
+            ),
+        }
+    })
+}
+
+/// UTF-16 detected from a byte order mark or from NUL bytes in every other
+/// position, as ASCII text encoded as UTF-16 has
+fn utf16_guess(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0xFF, 0xFE, ..] => return Some("UTF-16LE"),
+        [0xFE, 0xFF, ..] => return Some("UTF-16BE"),
+        _ => {}
+    }
+
+    let sample = &bytes[..bytes.len().min(512) & !1];
+    if sample.is_empty() {
+        return None;
+    }
+    let pairs = sample.len() / 2;
+    let even_nuls = sample.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd_nuls = sample
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .filter(|&&b| b == 0)
+        .count();
+    if odd_nuls == pairs && even_nuls == 0 {
+        Some("UTF-16LE")
+    } else if even_nuls == pairs && odd_nuls == 0 {
+        Some("UTF-16BE")
+    } else {
+        None
+    }
+}
+
+/// Lint result holding the single `invalid-encoding` finding for a file
+pub fn invalid_encoding_result(file: String, error: &EncodingError) -> LintResult {
+    LintResult {
+        file,
+        issues: vec![(
+            LintIssue {
+                line: 1,
+                column: 1,
+                message: error.message.clone(),
+                code: INVALID_ENCODING.to_string(),
+                severity: Severity::Error,
+            },
+            INVALID_ENCODING.to_string(),
+        )],
+        scalar_start_lines: HashMap::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_valid_utf8() {
+        assert_eq!(
+            decode(b"key: caf\xc3\xa9\n".to_vec()).unwrap(),
+            "key: café\n"
+        );
+        assert_eq!(decode(Vec::new()).unwrap(), "");
+    }
+
+    #[test]
+    fn test_decode_guesses_encoding() {
+        let utf16le: Vec<u8> = "key: value\n".bytes().flat_map(|b| [b, 0]).collect();
+        assert_eq!(decode(utf16le).unwrap_err().guess, "UTF-16LE");
+        assert_eq!(
+            decode(vec![0xFE, 0xFF, 0, b'k']).unwrap_err().guess,
+            "UTF-16BE"
+        );
+        assert_eq!(
+            decode(b"\x89PNG\r\n\x1a\n\0\0".to_vec()).unwrap_err().guess,
+            "binary"
+        );
+        let latin1 = decode(b"key: caf\xe9\n".to_vec()).unwrap_err();
+        assert_eq!(latin1.guess, "latin-1");
+        assert!(latin1.message.contains("offset 8"));
+    }
+}
@@ -1,6 +1,6 @@
 //! Output formatting for lint issues.
 
-use crate::{LintIssue, OutputFormat, Severity};
+use crate::{FixOutcome, LintIssue, OutputFormat, Severity};
 
 /// Formatter trait for output formatting
 pub trait Formatter: Send + Sync {
@@ -9,6 +9,26 @@ pub trait Formatter: Send + Sync {
 
     /// Format a filename
     fn format_filename(&self, filename: &str) -> String;
+
+    /// Format one line of the `--fix` summary, or nothing for files that
+    /// needed no changes and have no remaining issues
+    fn format_fix_outcome(&self, outcome: &FixOutcome) -> String;
+}
+
+fn fix_outcome_message(outcome: &FixOutcome) -> Option<String> {
+    if outcome.fixed > 0 {
+        Some(format!(
+            "Fixed {} issues in {} ({} fixable, {} remaining)",
+            outcome.fixed, outcome.file, outcome.fixable, outcome.remaining
+        ))
+    } else if outcome.remaining > 0 {
+        Some(format!(
+            "Found {} non-fixable issues in {}",
+            outcome.remaining, outcome.file
+        ))
+    } else {
+        None
+    }
 }
 
 /// Standard (non-colored) formatter
@@ -16,11 +36,7 @@ pub struct StandardFormatter;
 
 impl Formatter for StandardFormatter {
     fn format_issue(&self, issue: &LintIssue, rule_name: &str) -> String {
-        let level = match issue.severity {
-            Severity::Error => "error",
-            Severity::Warning => "warning",
-            Severity::Info => "info",
-        };
+        let level = issue.severity.as_str();
 
         let location = format!("  {}:{}", issue.line, issue.column);
         let padding1 = " ".repeat((12 - location.len()).max(0));
@@ -36,6 +52,12 @@ impl Formatter for StandardFormatter {
     fn format_filename(&self, filename: &str) -> String {
         filename.to_string()
     }
+
+    fn format_fix_outcome(&self, outcome: &FixOutcome) -> String {
+        fix_outcome_message(outcome)
+            .map(|message| format!("{}\n", message))
+            .unwrap_or_default()
+    }
 }
 
 /// Colored formatter
@@ -43,11 +65,7 @@ pub struct ColoredFormatter;
 
 impl Formatter for ColoredFormatter {
     fn format_issue(&self, issue: &LintIssue, rule_name: &str) -> String {
-        let level = match issue.severity {
-            Severity::Error => "error",
-            Severity::Warning => "warning",
-            Severity::Info => "info",
-        };
+        let level = issue.severity.as_str();
 
         let location_str = format!("{}:{}", issue.line, issue.column);
         let location = format!("\x1B[2m{}\x1B[0m", location_str);
@@ -71,6 +89,13 @@ impl Formatter for ColoredFormatter {
     fn format_filename(&self, filename: &str) -> String {
         format!("\x1B[4m{}\x1B[0m", filename)
     }
+
+    fn format_fix_outcome(&self, outcome: &FixOutcome) -> String {
+        let color = if outcome.fixed > 0 { 32 } else { 33 };
+        fix_outcome_message(outcome)
+            .map(|message| format!("\x1B[{}m{}\x1B[0m\n", color, message))
+            .unwrap_or_default()
+    }
 }
 
 /// Create a formatter based on the output format
@@ -92,6 +117,7 @@ mod tests {
             line: 5,
             column: 10,
             message: "test message".to_string(),
+            code: "test-rule/test-code".to_string(),
             severity: Severity::Error,
         };
 
@@ -112,6 +138,7 @@ mod tests {
             line: 5,
             column: 10,
             message: "test message".to_string(),
+            code: "test-rule/test-code".to_string(),
             severity: Severity::Error,
         };
 
@@ -0,0 +1,172 @@
+//! Changed files and line ranges from git, for `--diff` and `--changed-since`.
+//!
+//! Lines count as changed when `git diff` adds or modifies them between the
+//! base (HEAD, or the merge base with a ref) and the working tree. Untracked
+//! files count as changed throughout. Deleted lines leave nothing to lint and
+//! are not tracked.
+
+use crate::LintIssue;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Changed line ranges by absolute file path
+#[derive(Debug, Clone, Default)]
+pub struct ChangedLines {
+    files: HashMap<PathBuf, Vec<RangeInclusive<usize>>>,
+}
+
+impl ChangedLines {
+    /// Changes in the working tree relative to HEAD (`since` is `None`), or
+    /// relative to the merge base of HEAD and `since`
+    pub fn from_git(since: Option<&str>) -> Result<Self> {
+        let root = git(Path::new("."), &["rev-parse", "--show-toplevel"])
+            .context("--diff and --changed-since require a git repository")?;
+        let root = std::fs::canonicalize(root.trim())?;
+
+        let base = match since {
+            Some(reference) => git(&root, &["merge-base", reference, "HEAD"])
+                .with_context(|| format!("Cannot find a merge base with '{}'", reference))?
+                .trim()
+                .to_string(),
+            None => "HEAD".to_string(),
+        };
+        // Explicit prefixes, as `diff.noprefix` or `diff.mnemonicPrefix`
+        // would change the `b/` that `parse_unified_diff` expects
+        let diff = git(
+            &root,
+            &[
+                "diff",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--src-prefix=a/",
+                "--dst-prefix=b/",
+                &base,
+            ],
+        )?;
+        let untracked = git(&root, &["ls-files", "--others", "--exclude-standard"])?;
+
+        let mut changed = Self::default();
+        for (file, ranges) in parse_unified_diff(&diff) {
+            changed.files.insert(root.join(file), ranges);
+        }
+        for file in untracked.lines().filter(|line| !line.is_empty()) {
+            changed.files.insert(root.join(file), vec![1..=usize::MAX]);
+        }
+        Ok(changed)
+    }
+
+    /// Whether `path` has any changes
+    pub fn contains_file(&self, path: &Path) -> bool {
+        self.ranges(path).is_some()
+    }
+
+    /// Keep only issues on changed lines of `path`
+    pub fn retain_changed(&self, path: &Path, issues: &mut Vec<(LintIssue, String)>) {
+        let ranges = self.ranges(path).unwrap_or(&[]);
+        issues.retain(|(issue, _)| ranges.iter().any(|range| range.contains(&issue.line)));
+    }
+
+    fn ranges(&self, path: &Path) -> Option<&[RangeInclusive<usize>]> {
+        let path = std::fs::canonicalize(path)
+            .or_else(|_| std::env::current_dir().map(|cwd| cwd.join(path)))
+            .ok()?;
+        self.files.get(&path).map(Vec::as_slice)
+    }
+}
+
+/// Output of a git command run in `dir`, failing with git's message
+pub(crate) fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "core.quotePath=false"])
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Added line ranges by path from `git diff --unified=0` output. Files with
+/// only deletions map to no ranges; deleted files are left out.
+pub fn parse_unified_diff(diff: &str) -> HashMap<String, Vec<RangeInclusive<usize>>> {
+    let mut files: HashMap<String, Vec<RangeInclusive<usize>>> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            current = path.strip_prefix("b/").map(str::to_string);
+            if let Some(file) = &current {
+                files.entry(file.clone()).or_default();
+            }
+        } else if let (Some(hunk), Some(file)) = (line.strip_prefix("@@ "), &current) {
+            let Some(added) = hunk.split(' ').find_map(|part| part.strip_prefix('+')) else {
+                continue;
+            };
+            let (start, count) = match added.split_once(',') {
+                Some((start, count)) => (start.parse(), count.parse()),
+                None => (added.parse(), Ok(1)),
+            };
+            if let (Ok(start), Ok(count)) = (start, count) {
+                if count > 0 {
+                    files
+                        .entry(file.clone())
+                        .or_default()
+                        .push(start..=start + count - 1);
+                }
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let diff = "\
+diff --git a/config.yaml b/config.yaml
+index 1111111..2222222 100644
+--- a/config.yaml
++++ b/config.yaml
+@@ -3 +3 @@ key: value
+-old: 1
++new: 1
+@@ -10,0 +11,3 @@ other:
++a: 1
++b: 2
++c: 3
+@@ -20,2 +22,0 @@
+-gone: 1
+-gone: 2
+diff --git a/removed.yaml b/removed.yaml
+deleted file mode 100644
+--- a/removed.yaml
++++ /dev/null
+@@ -1 +0,0 @@
+-x: 1
+diff --git a/only_deletions.yaml b/only_deletions.yaml
+--- a/only_deletions.yaml
++++ b/only_deletions.yaml
+@@ -4 +3,0 @@
+-x: 1
+";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files["config.yaml"], vec![3..=3, 11..=13]);
+        assert!(files["only_deletions.yaml"].is_empty());
+        assert!(!files.contains_key("removed.yaml"));
+        assert_eq!(files.len(), 2);
+    }
+}
@@ -0,0 +1,296 @@
+//! Config inferred from the style of existing YAML files, for `config infer`.
+//!
+//! The survey reads lines rather than parsing, so it sees files the linter
+//! would reject too; it only has to find the dominant convention.
+
+use std::collections::BTreeMap;
+
+/// Share of observations a convention needs to be enforced; below it the
+/// files are too mixed and the check is left off
+const DOMINANT_SHARE: f64 = 0.9;
+
+/// Share of lines the inferred `line-length` maximum covers
+const LINE_LENGTH_PERCENTILE: f64 = 0.99;
+
+/// Conventions observed across surveyed files
+#[derive(Debug, Default)]
+pub struct StyleSurvey {
+    files: usize,
+    /// Indentation step of block mappings, by how often it was seen
+    indent_steps: BTreeMap<usize, usize>,
+    indented_sequences: usize,
+    flush_sequences: usize,
+    line_lengths: Vec<usize>,
+    document_starts: usize,
+    single_quoted: usize,
+    double_quoted: usize,
+}
+
+impl StyleSurvey {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Files surveyed so far
+    pub fn files(&self) -> usize {
+        self.files
+    }
+
+    /// Record the conventions of one file
+    pub fn add_file(&mut self, content: &str) {
+        self.files += 1;
+        let mut saw_content = false;
+        // Column children of the previous `key:` line are indented from
+        let mut open_key: Option<usize> = None;
+        // Indentation a block scalar's lines are deeper than
+        let mut block_scalar: Option<usize> = None;
+
+        for line in content.lines() {
+            self.line_lengths.push(line.chars().count());
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            if let Some(parent) = block_scalar {
+                if indent > parent {
+                    continue;
+                }
+                block_scalar = None;
+            }
+
+            if !saw_content {
+                saw_content = true;
+                if trimmed.starts_with("---") || trimmed.starts_with('%') {
+                    self.document_starts += 1;
+                }
+            }
+
+            if let Some(key_column) = open_key.take() {
+                if trimmed.starts_with("- ") || trimmed == "-" {
+                    match indent.cmp(&key_column) {
+                        std::cmp::Ordering::Equal => self.flush_sequences += 1,
+                        std::cmp::Ordering::Greater => {
+                            self.indented_sequences += 1;
+                            *self.indent_steps.entry(indent - key_column).or_default() += 1;
+                        }
+                        std::cmp::Ordering::Less => {}
+                    }
+                } else if indent > key_column {
+                    *self.indent_steps.entry(indent - key_column).or_default() += 1;
+                }
+            }
+
+            // Sequence entries holding a mapping put its keys after the `- `
+            let mut key_column = indent;
+            let mut rest = trimmed;
+            while let Some(item) = rest.strip_prefix("- ") {
+                let item_start = item.trim_start();
+                key_column += rest.len() - item_start.len();
+                rest = item_start;
+            }
+
+            let rest = strip_comment(rest).trim_end();
+            if let Some(value) = mapping_value(rest) {
+                self.add_value(value);
+                if value.is_empty() {
+                    open_key = Some(key_column);
+                } else if is_block_scalar_header(value) {
+                    block_scalar = Some(indent);
+                }
+            } else if let Some(item) = trimmed.strip_prefix("- ") {
+                self.add_value(strip_comment(item).trim());
+            }
+        }
+    }
+
+    fn add_value(&mut self, value: &str) {
+        if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+            self.single_quoted += 1;
+        } else if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+            self.double_quoted += 1;
+        }
+    }
+
+    /// Most common mapping indentation step, 2 when none was seen
+    fn indent_spaces(&self) -> usize {
+        self.indent_steps
+            .iter()
+            .max_by_key(|(step, count)| (**count, std::cmp::Reverse(**step)))
+            .map_or(2, |(step, _)| *step)
+    }
+
+    /// Shortest line length at least [`LINE_LENGTH_PERCENTILE`] of lines fit
+    /// in, rounded up to a multiple of 10 and never below the default of 80
+    fn line_length_max(&self) -> usize {
+        let mut lengths = self.line_lengths.clone();
+        lengths.sort_unstable();
+        let index = ((lengths.len() as f64 * LINE_LENGTH_PERCENTILE).ceil() as usize).max(1);
+        let percentile = lengths.get(index - 1).copied().unwrap_or(0);
+        percentile.div_ceil(10).max(8) * 10
+    }
+
+    /// A `.yamllint` matching the surveyed style, with the evidence for each
+    /// setting in comments
+    pub fn to_config(&self) -> String {
+        let mut config = format!(
+            "# yamllint-rs configuration inferred from {} file(s) by\n\
+             # `yamllint-rs config infer`. Review it before committing.\n\
+             ---\n\
+             extends: default\n\nrules:\n",
+            self.files
+        );
+
+        let sequences = self.indented_sequences + self.flush_sequences;
+        config.push_str(&format!(
+            "  # Most blocks indented by {}; {} of {} sequence(s) indented under a key\n",
+            self.indent_spaces(),
+            self.indented_sequences,
+            sequences
+        ));
+        config.push_str(&format!(
+            "  indentation:\n    spaces: {}\n    indent-sequences: {}\n",
+            self.indent_spaces(),
+            self.indented_sequences >= self.flush_sequences
+        ));
+
+        config.push_str(&format!(
+            "  # {}% of {} line(s) fit\n  line-length:\n    max: {}\n",
+            LINE_LENGTH_PERCENTILE * 100.0,
+            self.line_lengths.len(),
+            self.line_length_max()
+        ));
+
+        config.push_str(&format!(
+            "  # {} of {} file(s) start with `---`\n",
+            self.document_starts, self.files
+        ));
+        match dominant(self.document_starts, self.files) {
+            Some(present) => {
+                config.push_str(&format!("  document-start:\n    present: {}\n", present))
+            }
+            None => config.push_str("  document-start: disable\n"),
+        }
+
+        let quoted = self.single_quoted + self.double_quoted;
+        config.push_str(&format!(
+            "  # {} single- and {} double-quoted value(s)\n",
+            self.single_quoted, self.double_quoted
+        ));
+        // Quotes are only checked for their type, never required or removed
+        match dominant(self.single_quoted, quoted) {
+            Some(single) => config.push_str(&format!(
+                "  quoted-strings:\n    quote-type: {}\n    required: false\n",
+                if single { "single" } else { "double" }
+            )),
+            None => config.push_str("  quoted-strings: disable\n"),
+        }
+        config
+    }
+}
+
+/// `Some(true)` when `count` is the dominant share of `total`, `Some(false)`
+/// when the rest is, `None` when neither is or nothing was seen
+fn dominant(count: usize, total: usize) -> Option<bool> {
+    if total == 0 {
+        return None;
+    }
+    let share = count as f64 / total as f64;
+    if share >= DOMINANT_SHARE {
+        Some(true)
+    } else if share <= 1.0 - DOMINANT_SHARE {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Value after the first `key:` separator of a block mapping line, if it is one
+fn mapping_value(line: &str) -> Option<&str> {
+    if line.starts_with(['"', '\'', '{', '[', '&', '*', '!', '|', '>']) {
+        return None;
+    }
+    match line.find(": ") {
+        Some(colon) => Some(line[colon + 2..].trim()),
+        None => line.strip_suffix(':').map(|_| ""),
+    }
+}
+
+/// `line` without a trailing ` # comment` outside quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '#') if previous.is_whitespace() => return &line[..index],
+            _ => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+fn is_block_scalar_header(value: &str) -> bool {
+    value.starts_with(['|', '>'])
+        && value[1..]
+            .chars()
+            .all(|c| matches!(c, '-' | '+') || c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_config_from_str;
+
+    #[test]
+    fn test_survey_finds_dominant_style() {
+        let mut survey = StyleSurvey::new();
+        for _ in 0..9 {
+            survey.add_file(
+                "---\n\
+                 server:\n    \
+                     name: 'web'\n    \
+                     ports:\n    \
+                     - 80\n    \
+                     - 443  # TLS\n    \
+                     script: |\n        \
+                         echo 'not a value'\n\
+                 jobs:\n    \
+                     - name: 'build'\n      \
+                       steps:\n          \
+                             run: make\n",
+            );
+        }
+        survey.add_file("plain: \"value\"\n");
+
+        assert_eq!(survey.files(), 10);
+        assert_eq!(survey.indent_spaces(), 4);
+        assert_eq!((survey.indented_sequences, survey.flush_sequences), (9, 9));
+        assert_eq!(survey.document_starts, 9);
+        assert_eq!((survey.single_quoted, survey.double_quoted), (18, 1));
+        assert_eq!(survey.line_length_max(), 80);
+
+        let config = load_config_from_str(&survey.to_config()).unwrap();
+        assert!(config.is_rule_enabled("document-start"));
+        assert!(config.is_rule_enabled("quoted-strings"));
+        let indentation = config
+            .rule_options::<crate::rules::indentation::IndentationConfig>("indentation")
+            .unwrap();
+        assert_eq!(indentation.spaces, 4);
+    }
+
+    #[test]
+    fn test_mixed_conventions_leave_checks_off() {
+        let mut survey = StyleSurvey::new();
+        survey.add_file("---\na: 'x'\n");
+        survey.add_file("b: \"y\"\n");
+        survey.add_file(&format!("c: {}\n", "z".repeat(120)));
+
+        assert_eq!(survey.line_length_max(), 130);
+        let config = load_config_from_str(&survey.to_config()).unwrap();
+        assert!(!config.is_rule_enabled("document-start"));
+        assert!(!config.is_rule_enabled("quoted-strings"));
+    }
+}
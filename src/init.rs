@@ -0,0 +1,184 @@
+//! Starter `.yamllint` files written by `config init`.
+
+use crate::rules::{factory::RuleFactory, registry::RuleRegistry};
+use crate::Severity;
+use serde_json::{json, Value};
+
+/// Longest option line written as a flow list, leaving room for indentation
+/// under the default `line-length` maximum
+const MAX_FLOW_WIDTH: usize = 72;
+
+/// Rule selection a starter config begins from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// The rules and options used without a config file
+    Default,
+    /// Like yamllint's `relaxed`: warnings only, and no rules about comments,
+    /// document starts or truthy values
+    Relaxed,
+    /// Every default rule as an error, plus checks for risky float and octal
+    /// values and unused or duplicated anchors
+    Strict,
+}
+
+impl Preset {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Preset::Default => "default",
+            Preset::Relaxed => "relaxed",
+            Preset::Strict => "strict",
+        }
+    }
+
+    /// Whether `rule_id` is enabled, at which level, and options changed from
+    /// the rule's defaults
+    fn rule(&self, rule_id: &str, enabled: bool, level: Severity) -> (bool, Severity, Value) {
+        match self {
+            Preset::Default => (enabled, level, json!({})),
+            Preset::Relaxed => match rule_id {
+                "comments" | "comments-indentation" | "document-start" | "truthy" => {
+                    (false, Severity::Warning, json!({}))
+                }
+                "yaml-syntax" => (enabled, Severity::Error, json!({})),
+                "braces" | "brackets" => (
+                    enabled,
+                    Severity::Warning,
+                    json!({ "max-spaces-inside": 1 }),
+                ),
+                "line-length" => (
+                    enabled,
+                    Severity::Warning,
+                    json!({ "allow-non-breakable-inline-mappings": true }),
+                ),
+                _ => (enabled, Severity::Warning, json!({})),
+            },
+            Preset::Strict => match rule_id {
+                "float-values" | "octal-values" => (true, Severity::Error, json!({})),
+                "anchors" => (
+                    true,
+                    Severity::Error,
+                    json!({ "forbid-duplicated-anchors": true, "forbid-unused-anchors": true }),
+                ),
+                _ => (enabled, Severity::Error, json!({})),
+            },
+        }
+    }
+}
+
+/// A commented `.yamllint` listing every rule with its options for `preset`,
+/// in the yamllint format so yamllint can read it too
+pub fn starter_config(preset: Preset) -> String {
+    let registry = RuleRegistry::new();
+    let factory = RuleFactory::new();
+    let mut rule_ids = registry.get_rule_ids();
+    rule_ids.sort();
+
+    let mut config = format!(
+        "# yamllint-rs configuration ({} preset), from `yamllint-rs config init`.\n\
+         # Every rule is listed with its options. Set a rule to `disable` to turn\n\
+         # it off; `level` is error, warning or info. See Rules.md for the options.\n\
+         ---\n\
+         extends: default\n\nrules:\n",
+        preset.as_str()
+    );
+    for metadata in rule_ids
+        .iter()
+        .filter_map(|id| registry.get_rule_metadata(id))
+    {
+        let (enabled, level, changed) = preset.rule(
+            metadata.id,
+            metadata.enabled_by_default,
+            metadata.default_severity,
+        );
+
+        let mut options = factory
+            .default_config_json(metadata.id)
+            .and_then(|defaults| defaults.as_object().cloned())
+            .unwrap_or_default();
+        options.extend(changed.as_object().cloned().unwrap_or_default());
+        // `level` first, then the options by name; unset options have no value to write
+        let mut body = vec![format!("level: {}", level.as_str())];
+        for (option, value) in options.iter().filter(|(_, value)| !value.is_null()) {
+            let line = format!("{}: {}", option, flow_value(value));
+            match value.as_array() {
+                // Lists too long for one line go one item per line
+                Some(items) if line.len() > MAX_FLOW_WIDTH => {
+                    body.push(format!("{}:", option));
+                    body.extend(items.iter().map(|item| format!("  - {}", flow_value(item))));
+                }
+                _ => body.push(line),
+            }
+        }
+
+        config.push_str(&format!("  # {}\n", metadata.description));
+        if enabled {
+            config.push_str(&format!("  {}:\n", metadata.id));
+            for line in &body {
+                config.push_str(&format!("    {}\n", line));
+            }
+        } else {
+            config.push_str(&format!("  {}: disable\n", metadata.id));
+            config.push_str(&format!("  # {}:\n", metadata.id));
+            for line in &body {
+                config.push_str(&format!("  #   {}\n", line));
+            }
+        }
+    }
+    config
+}
+
+/// `value` on one line: scalars as YAML writes them, lists in flow style
+fn flow_value(value: &Value) -> String {
+    match value {
+        Value::Array(items) => format!(
+            "[{}]",
+            items.iter().map(flow_value).collect::<Vec<_>>().join(", ")
+        ),
+        _ => serde_yaml::to_string(value)
+            .unwrap_or_default()
+            .trim_end()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_config_from_str;
+
+    #[test]
+    fn test_default_starter_config_matches_defaults() {
+        let starter = starter_config(Preset::Default);
+        assert!(starter.contains("  line-length:\n    level: error\n"));
+        assert!(starter.contains("  document-end: disable\n  # document-end:\n"));
+
+        let loaded = load_config_from_str(&starter).unwrap();
+        let registry = RuleRegistry::new();
+        for id in registry.get_rule_ids() {
+            let metadata = registry.get_rule_metadata(&id).unwrap();
+            assert_eq!(
+                loaded.is_rule_enabled(&id),
+                metadata.enabled_by_default,
+                "{}",
+                id
+            );
+            assert_eq!(
+                loaded.get_rule_severity(&id),
+                metadata.default_severity,
+                "{}",
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn test_presets_change_levels_and_rules() {
+        let relaxed = load_config_from_str(&starter_config(Preset::Relaxed)).unwrap();
+        assert!(!relaxed.is_rule_enabled("truthy"));
+        assert_eq!(relaxed.get_rule_severity("colons"), Severity::Warning);
+
+        let strict = load_config_from_str(&starter_config(Preset::Strict)).unwrap();
+        assert!(strict.is_rule_enabled("octal-values"));
+        assert_eq!(strict.get_rule_severity("truthy"), Severity::Error);
+    }
+}
@@ -1,17 +1,39 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use ignore::WalkBuilder;
 use rayon::prelude::*;
+use std::collections::HashMap;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 pub mod analysis;
+pub mod cache;
+pub mod codeowners;
 pub mod config;
+pub mod config_hash;
+#[cfg(feature = "corpus")]
+pub mod corpus;
 pub mod directives;
+pub mod encoding;
 pub mod formatter;
+pub mod git;
+pub mod infer;
+pub mod init;
+pub mod limit;
+pub mod linter;
+pub mod patterns;
+#[cfg(feature = "pretty-diagnostics")]
+pub mod pretty;
+pub mod redact;
+pub mod remote_config;
+pub mod reporter;
 pub mod rule_pool;
 pub mod rules;
+pub mod scan;
+pub mod sniff;
+pub mod timing;
+pub mod toml_config;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputFormat {
@@ -25,6 +47,38 @@ pub struct ProcessingOptions {
     pub verbose: bool,
     pub output_format: OutputFormat,
     pub show_progress: bool,
+    /// Replace scalar values with a placeholder in all reported messages
+    pub redact_values: bool,
+    /// Drop warning and info issues, reporting errors only
+    pub no_warnings: bool,
+    /// Number of threads used to lint files in parallel; `None` uses one per CPU
+    pub jobs: Option<usize>,
+    /// Only lint changed files and report issues on changed lines
+    pub changed_lines: Option<Arc<git::ChangedLines>>,
+    /// Reuse results of files whose content and config are unchanged
+    pub cache: Option<Arc<cache::LintCache>>,
+    /// Lint every file, ignoring config `ignore`/`ignore-from-file` patterns
+    /// and `.gitignore` files
+    pub no_ignore: bool,
+    /// Walk into files excluded by `.gitignore`, `.ignore` and git's exclude
+    /// files, still honouring config `ignore` patterns
+    pub no_gitignore: bool,
+    /// Walk into hidden directories (except `.git`) and lint hidden files
+    /// matching `yaml-files`
+    pub hidden: bool,
+    /// Follow symbolic links when walking directories
+    pub follow_symlinks: bool,
+    /// Also lint files without an extension whose content looks like YAML,
+    /// see [`sniff`]
+    pub detect_yaml: bool,
+    /// Hold results until `finish_reports` and report them in this order,
+    /// instead of as files finish
+    pub sort_by: Option<reporter::SortOrder>,
+    /// Accumulate the time each rule spends checking files; files served
+    /// from the cache are not timed
+    pub rule_timings: Option<Arc<timing::RuleTimings>>,
+    /// Stop reporting issues past `--max-issues`/`--max-issues-per-file`
+    pub issue_limit: Option<Arc<limit::IssueLimit>>,
 }
 
 impl Default for ProcessingOptions {
@@ -34,6 +88,19 @@ impl Default for ProcessingOptions {
             verbose: false,
             output_format: OutputFormat::Colored,
             show_progress: true,
+            redact_values: false,
+            no_warnings: false,
+            jobs: None,
+            changed_lines: None,
+            cache: None,
+            no_ignore: false,
+            no_gitignore: false,
+            hidden: false,
+            follow_symlinks: false,
+            detect_yaml: false,
+            sort_by: None,
+            rule_timings: None,
+            issue_limit: None,
         }
     }
 }
@@ -58,10 +125,25 @@ pub fn detect_output_format(format_str: &str) -> OutputFormat {
 
 pub struct FileProcessor {
     options: ProcessingOptions,
-    rules: Arc<Vec<Box<dyn rules::Rule>>>,
+    rules: rule_pool::RuleSet,
     fix_mode: bool,
     config: Option<Arc<config::Config>>,
-    formatter: Box<dyn formatter::Formatter>,
+    reporter: Arc<Mutex<reporter::MultiReporter>>,
+    nested_configs: Option<NestedConfigs>,
+}
+
+/// Rules built for a single file, with the config they were built from
+type FileRules = (rule_pool::RuleSet, Option<Arc<config::Config>>);
+
+/// Builds the config of a `.yamllint` found below a linted directory
+pub type ConfigLoader = dyn Fn(&Path) -> Result<config::Config> + Send + Sync;
+
+/// How files under a walked directory with a `.yamllint` of their own get
+/// their config
+struct NestedConfigs {
+    /// Canonical path of the config the processor was built from
+    own_config: Option<PathBuf>,
+    load: Box<ConfigLoader>,
 }
 
 impl FileProcessor {
@@ -70,58 +152,37 @@ impl FileProcessor {
         file_path: &str,
         config: &Option<Arc<config::Config>>,
     ) -> bool {
-        if let Some(config) = config {
-            if let Some(rule_config) = config.get_rule_config(rule_id) {
-                if let Some(ignore_val) = rule_config.other.get("ignore") {
-                    if let Some(ignore_str) = ignore_val.as_str() {
-                        let patterns: Vec<&str> = ignore_str
-                            .lines()
-                            .map(|line| line.trim())
-                            .filter(|line| !line.is_empty())
-                            .collect();
-
-                        for pattern in patterns {
-                            if file_path.contains(pattern) {
-                                return false;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        true
+        !config
+            .as_ref()
+            .is_some_and(|config| config.is_ignored_by_rule(rule_id, Path::new(file_path)))
     }
 
     pub fn new(options: ProcessingOptions) -> Self {
-        let formatter = formatter::create_formatter(options.output_format);
+        let reporter =
+            reporter::MultiReporter::stdout(options.output_format).with_sort_order(options.sort_by);
         Self {
             options,
-            rules: Arc::new(Vec::new()),
+            rules: rule_pool::RuleSet::default(),
             fix_mode: false,
             config: None,
-            formatter,
+            reporter: Arc::new(Mutex::new(reporter)),
+            nested_configs: None,
         }
     }
 
     pub fn with_default_rules(options: ProcessingOptions) -> Self {
-        let factory = rules::factory::RuleFactory::new();
         let config = config::Config::default();
-        let enabled_rules = config.get_enabled_rules();
-        let mut rules = factory.create_rules_by_ids_with_config(&enabled_rules, &config);
-        let config_arc = Arc::new(config);
-
-        for rule in &mut rules {
-            let severity = config_arc.get_rule_severity(rule.rule_id());
-            rule.set_severity(severity);
-        }
+        let rules = rule_pool::RuleSet::from_config(&config);
 
-        let formatter = formatter::create_formatter(options.output_format);
+        let reporter =
+            reporter::MultiReporter::stdout(options.output_format).with_sort_order(options.sort_by);
         Self {
             options,
-            rules: Arc::new(rules),
+            rules,
             fix_mode: false,
-            config: Some(config_arc),
-            formatter,
+            config: Some(Arc::new(config)),
+            reporter: Arc::new(Mutex::new(reporter)),
+            nested_configs: None,
         }
     }
 
@@ -132,25 +193,52 @@ impl FileProcessor {
     }
 
     pub fn with_config(options: ProcessingOptions, config: config::Config) -> Self {
-        let factory = rules::factory::RuleFactory::new();
-        let enabled_rules = config.get_enabled_rules();
+        let rules = rule_pool::RuleSet::from_config(&config);
 
-        let config_arc = Arc::new(config);
-        let mut rules = factory.create_rules_by_ids_with_config(&enabled_rules, &config_arc);
-
-        for rule in &mut rules {
-            let severity = config_arc.get_rule_severity(rule.rule_id());
-            rule.set_severity(severity);
-        }
-
-        let formatter = formatter::create_formatter(options.output_format);
+        let reporter =
+            reporter::MultiReporter::stdout(options.output_format).with_sort_order(options.sort_by);
         Self {
             options,
-            rules: Arc::new(rules),
+            rules,
             fix_mode: false,
-            config: Some(config_arc),
-            formatter,
+            config: Some(Arc::new(config)),
+            reporter: Arc::new(Mutex::new(reporter)),
+            nested_configs: None,
+        }
+    }
+
+    /// Rules and config for the file at `relative_path` when they differ from
+    /// the processor's: matching `overrides` entries apply first, then a
+    /// modeline on the file's first line when the config enables modelines,
+    /// then `configure` directives in the file's header. Modelines and
+    /// directives that fail to apply are ignored; the `directive-syntax` rule
+    /// reports them.
+    fn file_rules(
+        content: &str,
+        relative_path: &str,
+        config: &Option<Arc<config::Config>>,
+    ) -> Option<FileRules> {
+        let config = config.as_ref()?;
+        let overridden = apply_path_overrides(config, relative_path);
+        let modeline = directives::parse_modeline(content).filter(|_| config.modelines);
+        let configured = directives::parse_file_configure(content);
+        if overridden.is_none() && modeline.is_none() && configured.is_empty() {
+            return None;
+        }
+
+        let mut file_config = overridden.unwrap_or_else(|| config::Config::clone(config));
+        if let Some(overrides) = modeline {
+            let mut with_modeline = file_config.clone();
+            if apply_rule_option_overrides(&mut with_modeline, &overrides).is_ok() {
+                file_config = with_modeline;
+            }
         }
+        for entry in &configured {
+            // A single entry is validated before anything is changed
+            let _ = apply_rule_option_overrides(&mut file_config, std::slice::from_ref(entry));
+        }
+        let rules = rule_pool::RuleSet::from_config(&file_config);
+        Some((rules, Some(Arc::new(file_config))))
     }
 
     pub fn with_config_and_fix_mode(options: ProcessingOptions, config: config::Config) -> Self {
@@ -159,24 +247,73 @@ impl FileProcessor {
         processor
     }
 
+    /// Lint files under walked directories with the nearest `.yamllint` above
+    /// them, built by `load`, when that is not `own_config` (the config this
+    /// processor was built from). A nested config's `ignore` patterns are
+    /// relative to its directory.
+    pub fn with_nested_configs(
+        mut self,
+        own_config: Option<&Path>,
+        load: Box<ConfigLoader>,
+    ) -> Self {
+        self.nested_configs = Some(NestedConfigs {
+            own_config: own_config.and_then(|path| path.canonicalize().ok()),
+            load,
+        });
+        self
+    }
+
+    /// Replace the default stdout output with the given reporters
+    pub fn with_reporters(mut self, specs: &[reporter::ReporterSpec]) -> Result<Self> {
+        let reporter = reporter::MultiReporter::from_specs(
+            specs,
+            self.options.output_format,
+            self.options.redact_values,
+        )?
+        .with_sort_order(self.options.sort_by);
+        self.reporter = Arc::new(Mutex::new(reporter));
+        Ok(self)
+    }
+
+    /// Report through another processor's reporters, so several processors
+    /// (e.g. one per config root) produce a single merged report
+    pub fn with_shared_reporter(mut self, other: &FileProcessor) -> Self {
+        self.reporter = Arc::clone(&other.reporter);
+        self
+    }
+
+    /// Flush reporters that buffer their output until the end of the run
+    pub fn finish_reports(&self) -> Result<()> {
+        self.reporter.lock().unwrap().finish()
+    }
+
+    fn report(&self, result: &LintResult) -> Result<()> {
+        self.reporter.lock().unwrap().report(result)
+    }
+
+    fn report_fix(&self, result: &LintResult, outcome: &FixOutcome) -> Result<()> {
+        let mut reporter = self.reporter.lock().unwrap();
+        reporter.report(result)?;
+        reporter.report_fix(outcome)
+    }
+
     pub fn add_rule(&mut self, rule: Box<dyn rules::Rule>) {
-        Arc::get_mut(&mut self.rules)
-            .expect("Cannot add rule when rules are shared")
-            .push(rule);
+        self.rules.push(rule);
     }
 
     pub fn process_file<P: AsRef<Path>>(&self, file_path: P) -> Result<LintResult> {
         let path = file_path.as_ref();
 
-        if let Some(config) = &self.config {
-            let cwd = std::env::current_dir().ok();
-            let config_dir = cwd.as_deref();
-            if config.is_file_ignored(path, config_dir) {
-                return Ok(LintResult {
-                    file: self.get_relative_path(path),
-                    issues: vec![],
-                });
-            }
+        let cwd = std::env::current_dir().ok();
+        if self.is_ignored(path, cwd.as_deref())
+            || self.is_unchanged(path)
+            || Self::is_over_issue_limit(&self.options)
+        {
+            return Ok(LintResult {
+                file: self.get_relative_path(path),
+                issues: vec![],
+                scalar_start_lines: HashMap::new(),
+            });
         }
 
         let relative_path = self.get_relative_path(path);
@@ -185,7 +322,10 @@ impl FileProcessor {
             println!("Processing file: {}", relative_path);
         }
 
-        let content = std::fs::read_to_string(path)?;
+        let content = match Self::read_file(path, relative_path.clone()) {
+            Ok(content) => content,
+            Err(result) => return self.report_unlintable(result),
+        };
 
         if self.fix_mode {
             self.process_file_with_fixes(path, &content, &relative_path)
@@ -194,18 +334,95 @@ impl FileProcessor {
         }
     }
 
+    /// Lint raw bytes (e.g. read from stdin) as if they were the file at
+    /// `file_path`, reporting an `invalid-encoding` finding when they are not
+    /// UTF-8 text
+    pub fn process_bytes<P: AsRef<Path>>(
+        &self,
+        bytes: Vec<u8>,
+        file_path: P,
+    ) -> Result<LintResult> {
+        match encoding::decode(bytes) {
+            Ok(content) => self.process_content(&content, file_path),
+            Err(error) => {
+                let relative_path = self.get_relative_path(file_path.as_ref());
+                self.report_unlintable(encoding::invalid_encoding_result(relative_path, &error))
+            }
+        }
+    }
+
+    /// Content of the file at `path`, or the single `file-error` or
+    /// `invalid-encoding` finding reported instead when it cannot be read as
+    /// UTF-8 text
+    fn read_file(path: &Path, relative_path: String) -> std::result::Result<String, LintResult> {
+        let bytes = match std::fs::read(path).context("cannot read file") {
+            Ok(bytes) => bytes,
+            Err(error) => return Err(file_error_result(relative_path, &error)),
+        };
+        encoding::decode(bytes)
+            .map_err(|error| encoding::invalid_encoding_result(relative_path, &error))
+    }
+
+    fn report_unlintable(&self, mut result: LintResult) -> Result<LintResult> {
+        Self::apply_output_options(&self.options, &result.file, &mut result.issues);
+        self.report(&result)?;
+        Ok(result)
+    }
+
+    /// Lint in-memory content (e.g. read from stdin) as if it were the file at
+    /// `file_path`, which is used for ignore matching and in reports only.
+    /// Content is never fixed in place.
+    pub fn process_content<P: AsRef<Path>>(
+        &self,
+        content: &str,
+        file_path: P,
+    ) -> Result<LintResult> {
+        let path = file_path.as_ref();
+
+        let cwd = std::env::current_dir().ok();
+        if self.is_ignored(path, cwd.as_deref()) {
+            return Ok(LintResult {
+                file: self.get_relative_path(path),
+                issues: vec![],
+                scalar_start_lines: HashMap::new(),
+            });
+        }
+
+        let relative_path = self.get_relative_path(path);
+
+        if self.options.verbose {
+            println!("Processing file: {}", relative_path);
+        }
+
+        self.process_file_check_only(content, &relative_path)
+    }
+
     fn check_file_content(
         rules: &[Box<dyn rules::Rule>],
         content: &str,
         relative_path: &str,
         config: &Option<Arc<config::Config>>,
+        timings: Option<&timing::RuleTimings>,
     ) -> LintResult {
+        let file_rules = Self::file_rules(content, relative_path, config);
+        let (rules, config) = match &file_rules {
+            Some((rules, config)) => (&rules[..], config),
+            None => (rules, config),
+        };
+
         let all_rule_ids: std::collections::HashSet<String> =
             rules.iter().map(|r| r.rule_id().to_string()).collect();
         let mut directive_state = directives::DirectiveState::new(all_rule_ids);
         directive_state.parse_from_content(content);
 
+        let configure_regions = Self::configure_regions(content, config);
+
+        let mut times = Vec::new();
+        let start = std::time::Instant::now();
         let analysis = analysis::ContentAnalysis::analyze(content);
+        if timings.is_some() {
+            times.push((timing::ANALYSIS, start.elapsed()));
+        }
 
         let estimated_issues = rules.len() * 3;
         let mut all_issues = Vec::with_capacity(estimated_issues);
@@ -214,42 +431,201 @@ impl FileProcessor {
             if !Self::should_run_rule_for_file(rule_id, relative_path, config) {
                 continue;
             }
-            let issues = rule.check_with_analysis(content, relative_path, &analysis);
+            let start = std::time::Instant::now();
+            let issues = Self::check_rule_with_regions(
+                rule.as_ref(),
+                content,
+                relative_path,
+                &analysis,
+                config,
+                &configure_regions,
+            );
+            if timings.is_some() {
+                times.push((rule_id, start.elapsed()));
+            }
             for issue in issues {
                 all_issues.push((issue, rule_id.to_string()));
             }
         }
+        if let Some(timings) = timings {
+            timings.record_file(&times);
+        }
 
-        let filtered_issues = directive_state.filter_issues(all_issues);
+        let mut filtered_issues = directive_state.filter_issues(all_issues);
+        Self::apply_severity_overrides(relative_path, config, &mut filtered_issues);
         let mut sorted_issues = filtered_issues;
         sorted_issues.sort_by(|a, b| a.0.line.cmp(&b.0.line).then(a.0.column.cmp(&b.0.column)));
 
+        let scalar_start_lines =
+            Self::scalar_start_lines(content, analysis.tokens(), &sorted_issues);
         LintResult {
             file: relative_path.to_string(),
             issues: sorted_issues,
+            scalar_start_lines,
         }
     }
 
-    fn process_file_check_only(&self, content: &str, relative_path: &str) -> Result<LintResult> {
-        let result =
-            Self::check_file_content(self.rules.as_slice(), content, relative_path, &self.config);
+    /// `configure` directive regions of `content`. With a config, directives
+    /// in the file's header are already part of the file's config (see
+    /// `file_rules`), so only later ones form regions, and a `reset` returns
+    /// to the options the header set.
+    fn configure_regions(
+        content: &str,
+        config: &Option<Arc<config::Config>>,
+    ) -> Vec<directives::ConfigureRegion> {
+        let mut regions = directives::parse_configure_regions(content);
+        if config.is_some() {
+            let header_len = directives::header_len(content);
+            regions.retain(|region| region.start_line > header_len);
+        }
+        regions
+    }
 
-        if result.issues.is_empty() {
-            if self.options.verbose {
-                println!("✓ No issues found in {}", result.file);
+    /// Issues of `rule`, where those on lines of a `configure` directive
+    /// region come from the rule rebuilt with the region's options. Regions
+    /// whose options do not apply keep the rule's own issues.
+    fn check_rule_with_regions(
+        rule: &dyn rules::Rule,
+        content: &str,
+        relative_path: &str,
+        analysis: &analysis::ContentAnalysis,
+        config: &Option<Arc<config::Config>>,
+        regions: &[directives::ConfigureRegion],
+    ) -> Vec<LintIssue> {
+        let issues = rule.check_with_analysis(content, relative_path, analysis);
+        let rule_id = rule.rule_id();
+        let configured: Vec<_> = regions
+            .iter()
+            .filter(|region| region.rule == rule_id)
+            .filter_map(|region| {
+                let mut region_config = config.as_deref().cloned().unwrap_or_default();
+                apply_rule_option_overrides(&mut region_config, &region.overrides).ok()?;
+                let mut region_rule = rules::factory::RuleFactory::new()
+                    .create_rules_by_ids_with_config(&[rule_id.to_string()], &region_config)
+                    .pop()?;
+                region_rule.set_severity(region_config.get_rule_severity(rule_id));
+                Some((region, region_rule))
+            })
+            .collect();
+        if configured.is_empty() {
+            return issues;
+        }
+
+        let mut issues: Vec<LintIssue> = issues
+            .into_iter()
+            .filter(|issue| {
+                !configured
+                    .iter()
+                    .any(|(region, _)| region.contains(issue.line))
+            })
+            .collect();
+        for (region, region_rule) in &configured {
+            issues.extend(
+                region_rule
+                    .check_with_analysis(content, relative_path, analysis)
+                    .into_iter()
+                    .filter(|issue| region.contains(issue.line)),
+            );
+        }
+        issues
+    }
+
+    /// Map the lines of issues inside block scalars to the scalar's start
+    /// line. Content is only scanned when there are issues and no tokens are
+    /// at hand.
+    fn scalar_start_lines(
+        content: &str,
+        tokens: Option<&analysis::TokenAnalysis>,
+        issues: &[(LintIssue, String)],
+    ) -> HashMap<usize, usize> {
+        let mut lines = HashMap::new();
+        if issues.is_empty() {
+            return lines;
+        }
+
+        let scanned;
+        let tokens = match tokens {
+            Some(tokens) => tokens,
+            None => {
+                scanned = analysis::TokenAnalysis::analyze(content);
+                &scanned
             }
-        } else {
-            println!("{}", self.formatter.format_filename(&result.file));
+        };
+        let spans = analysis::block_scalar_spans(&tokens.tokens, content);
+        if spans.is_empty() {
+            return lines;
+        }
 
-            let mut output = String::with_capacity(result.issues.len() * 120);
+        for (issue, _) in issues {
+            if let Some(span) = spans.iter().find(|span| span.contains(issue.line)) {
+                lines.insert(issue.line, span.start_line);
+            }
+        }
+        lines
+    }
 
-            for (issue, rule_name) in &result.issues {
-                let formatted = self.formatter.format_issue(issue, rule_name);
-                output.push_str(&formatted);
+    /// Apply the config's `severity-by-path` level to every issue in the file
+    fn apply_severity_overrides(
+        relative_path: &str,
+        config: &Option<Arc<config::Config>>,
+        issues: &mut [(LintIssue, String)],
+    ) {
+        let Some(config) = config else {
+            return;
+        };
+        for (issue, rule_id) in issues.iter_mut() {
+            if let Some(severity) = config.severity_override_for(relative_path, rule_id) {
+                issue.severity = severity;
             }
+        }
+    }
+
+    /// Whether `--max-issues` was reached, recording the file as not linted
+    fn is_over_issue_limit(options: &ProcessingOptions) -> bool {
+        let Some(limit) = &options.issue_limit else {
+            return false;
+        };
+        let exhausted = limit.is_exhausted();
+        if exhausted {
+            limit.skip_file();
+        }
+        exhausted
+    }
 
-            print!("{}", output);
+    /// Apply options that only affect what is reported, after rules have run
+    fn apply_output_options(
+        options: &ProcessingOptions,
+        file: &str,
+        issues: &mut Vec<(LintIssue, String)>,
+    ) {
+        if let Some(changed_lines) = &options.changed_lines {
+            changed_lines.retain_changed(Path::new(file), issues);
+        }
+        if options.no_warnings {
+            issues.retain(|(issue, _)| issue.severity == Severity::Error);
+        }
+        if let Some(limit) = &options.issue_limit {
+            limit.truncate(issues);
+        }
+        if options.redact_values {
+            redact::redact_issues(issues);
+        }
+    }
+
+    fn process_file_check_only(&self, content: &str, relative_path: &str) -> Result<LintResult> {
+        let mut result = Self::process_file_check_only_static(
+            &self.rules,
+            content,
+            relative_path,
+            &self.config,
+            &self.options,
+        )?;
+        Self::apply_output_options(&self.options, relative_path, &mut result.issues);
+
+        if result.issues.is_empty() && self.options.verbose {
+            println!("✓ No issues found in {}", result.file);
         }
+        self.report(&result)?;
 
         Ok(result)
     }
@@ -260,6 +636,12 @@ impl FileProcessor {
         relative_path: &str,
         config: &Option<Arc<config::Config>>,
     ) -> (String, usize, usize, Vec<(LintIssue, String)>) {
+        let file_rules = Self::file_rules(content, relative_path, config);
+        let (rules, config) = match &file_rules {
+            Some((rules, config)) => (&rules[..], config),
+            None => (rules, config),
+        };
+
         let registry = rules::registry::RuleRegistry::new();
         let mut fixed_content = String::with_capacity(content.len());
         fixed_content.push_str(content);
@@ -274,7 +656,11 @@ impl FileProcessor {
                 if !Self::should_run_rule_for_file(rule_id, relative_path, config) {
                     return None;
                 }
-                if !rule.can_fix() {
+                if !rule.can_fix()
+                    || config
+                        .as_ref()
+                        .is_some_and(|config| config.is_fix_excluded(rule_id))
+                {
                     return None;
                 }
                 let metadata = registry.get_rule_metadata(rule_id)?;
@@ -296,6 +682,7 @@ impl FileProcessor {
         }
 
         let analysis = analysis::ContentAnalysis::analyze(&fixed_content);
+        let configure_regions = Self::configure_regions(&fixed_content, config);
         let estimated_issues = rules.len() * 3;
         let mut all_issues = Vec::with_capacity(estimated_issues);
         for rule in rules {
@@ -303,67 +690,49 @@ impl FileProcessor {
             if !Self::should_run_rule_for_file(rule_id, relative_path, config) {
                 continue;
             }
-            let issues = rule.check_with_analysis(&fixed_content, relative_path, &analysis);
+            let issues = Self::check_rule_with_regions(
+                rule.as_ref(),
+                &fixed_content,
+                relative_path,
+                &analysis,
+                config,
+                &configure_regions,
+            );
             for issue in issues {
                 all_issues.push((issue, rule_id.to_string()));
             }
         }
 
+        Self::apply_severity_overrides(relative_path, config, &mut all_issues);
         all_issues.sort_by(|a, b| a.0.line.cmp(&b.0.line).then(a.0.column.cmp(&b.0.column)));
 
         (fixed_content, total_fixes, fixable_issues, all_issues)
     }
 
-    fn process_file_with_fixes<P: AsRef<Path>>(
+    fn process_file_with_fixes(
         &self,
-        path: P,
+        path: &Path,
         content: &str,
         relative_path: &str,
     ) -> Result<LintResult> {
-        let (fixed_content, total_fixes, fixable_issues, all_issues) = Self::apply_fixes_and_check(
-            self.rules.as_slice(),
+        let (result, outcome) = Self::process_file_with_fixes_static(
+            &self.rules,
+            path,
             content,
             relative_path,
             &self.config,
-        );
-
-        let _non_fixable_issues = all_issues.len();
-
-        if fixed_content != content {
-            std::fs::write(path, &fixed_content)?;
-            if total_fixes > 0 {
-                println!(
-                    "Fixed {} issues in {} ({} fixable, {} remaining)",
-                    total_fixes, relative_path, fixable_issues, _non_fixable_issues
-                );
-            }
-        } else if _non_fixable_issues > 0 {
-            println!(
-                "Found {} non-fixable issues in {}:",
-                _non_fixable_issues, relative_path
-            );
-            for (issue, _rule_name) in &all_issues {
-                println!(
-                    "  {}:{}: {}: {}",
-                    issue.line,
-                    issue.column,
-                    format!("{:?}", issue.severity).to_lowercase(),
-                    issue.message
-                );
-            }
-        } else {
-            if self.options.verbose {
-                println!("✓ No issues found in {}", relative_path);
-            }
-        }
-
-        Ok(LintResult {
-            file: relative_path.to_string(),
-            issues: all_issues,
-        })
+            &self.options,
+        )?;
+        self.report_fix(&result, &outcome)?;
+        Ok(result)
     }
 
     pub fn process_directory<P: AsRef<Path>>(&self, dir_path: P) -> Result<usize> {
+        Ok(self.process_directory_counts(dir_path)?.total())
+    }
+
+    /// Like `process_directory`, but returns issue totals by severity
+    pub fn process_directory_counts<P: AsRef<Path>>(&self, dir_path: P) -> Result<IssueCounts> {
         let path = dir_path.as_ref();
 
         if !path.is_dir() {
@@ -377,29 +746,13 @@ impl FileProcessor {
             println!("Processing directory: {}", path.display());
         }
 
-        let mut yaml_files = Vec::with_capacity(100);
-
-        let walker = WalkBuilder::new(path).follow_links(false).build();
-
-        for result in walker {
-            let entry = result?;
-            let file_path = entry.path();
-            if file_path.is_file() && self.is_yaml_file(file_path) {
-                if let Some(config) = &self.config {
-                    let config_dir = Some(path);
-                    if config.is_file_ignored(file_path, config_dir) {
-                        continue;
-                    }
-                }
-                yaml_files.push(file_path.to_path_buf());
-            }
-        }
+        let yaml_files = self.collect_directory_entries(path)?;
 
         if yaml_files.is_empty() {
             if self.options.verbose {
                 println!("No YAML files found in directory");
             }
-            return Ok(0);
+            return Ok(IssueCounts::default());
         }
 
         if self.options.verbose {
@@ -409,73 +762,210 @@ impl FileProcessor {
             );
         }
 
-        let options = self.options.clone();
-        let fix_mode = self.fix_mode;
-        let shared_rules = self.rules.clone();
-
-        let results = if options.show_progress {
-            let total = yaml_files.len();
-            let counter = Arc::new(AtomicUsize::new(0));
-            Self::process_files_list(
-                &yaml_files,
-                shared_rules,
-                &options,
-                fix_mode,
-                &self.config,
-                Some(counter),
-                Some(total),
-            )?
-        } else {
-            Self::process_files_list(
-                &yaml_files,
-                shared_rules,
-                &options,
-                fix_mode,
-                &self.config,
-                None,
-                None,
-            )?
-        };
+        let counter = self.options.show_progress.then(|| AtomicUsize::new(0));
+        let results = Self::process_files_list(
+            &yaml_files,
+            &self.rules,
+            &self.options,
+            self.fix_mode,
+            &self.config,
+            counter.as_ref(),
+        )?;
+
+        let mut counts = IssueCounts::default();
+        for (result, outcome) in &results {
+            counts.add_result(result);
+            match outcome {
+                Some(outcome) => self.report_fix(result, outcome)?,
+                None => self.report(result)?,
+            }
+        }
 
-        let formatter = formatter::create_formatter(options.output_format);
         let mut stdout = std::io::stdout().lock();
-        let mut total_issues = 0;
-        for result in &results {
-            if !result.issues.is_empty() {
-                total_issues += result.issues.len();
-                writeln!(stdout, "{}", formatter.format_filename(&result.file))?;
+        if self.options.verbose {
+            writeln!(stdout, "Successfully processed {} files", results.len())?;
+        }
+
+        if self.options.verbose {
+            writeln!(stdout, "Completed processing {} files", yaml_files.len())?;
+        }
+
+        Ok(counts)
+    }
+
+    /// YAML files under a directory that would be linted, after extension
+    /// filtering, `.gitignore` handling and config `ignore` patterns
+    pub fn collect_directory_files<P: AsRef<Path>>(&self, dir_path: P) -> Result<Vec<PathBuf>> {
+        let entries = self.collect_directory_entries(dir_path)?;
+        Ok(entries.into_iter().map(|(file, _)| file).collect())
+    }
+
+    /// Files under a directory that would be linted, each with the rules of
+    /// the nested `.yamllint` governing it, if any
+    fn collect_directory_entries<P: AsRef<Path>>(
+        &self,
+        dir_path: P,
+    ) -> Result<Vec<(PathBuf, Option<Arc<FileRules>>)>> {
+        let files = self.walk_directory(dir_path.as_ref())?;
+        let Some(nested) = &self.nested_configs else {
+            return Ok(files.into_iter().map(|file| (file, None)).collect());
+        };
 
-                let mut output = String::with_capacity(result.issues.len() * 120);
+        // Configs are looked up once per directory and loaded once per file
+        let mut by_dir: HashMap<PathBuf, Option<(PathBuf, Arc<FileRules>)>> = HashMap::new();
+        let mut by_config: HashMap<PathBuf, Arc<FileRules>> = HashMap::new();
+        let mut entries = Vec::with_capacity(files.len());
+        for file in files {
+            let dir = file.parent().unwrap_or(Path::new("")).to_path_buf();
+            if !by_dir.contains_key(&dir) {
+                let rules = match discover_config_file_for_path(&file) {
+                    Some(config_path) if Some(&config_path) != nested.own_config.as_ref() => {
+                        let rules = match by_config.get(&config_path) {
+                            Some(rules) => Arc::clone(rules),
+                            None => {
+                                let config = (nested.load)(&config_path).with_context(|| {
+                                    format!("Invalid config {}", config_path.display())
+                                })?;
+                                let rules = rule_pool::RuleSet::from_config(&config);
+                                let rules = Arc::new((rules, Some(Arc::new(config))));
+                                by_config.insert(config_path.clone(), Arc::clone(&rules));
+                                rules
+                            }
+                        };
+                        let config_dir =
+                            config_path.parent().unwrap_or(Path::new("")).to_path_buf();
+                        Some((config_dir, rules))
+                    }
+                    _ => None,
+                };
+                by_dir.insert(dir.clone(), rules);
+            }
 
-                for (issue, rule_name) in &result.issues {
-                    let formatted = formatter.format_issue(issue, rule_name);
-                    output.push_str(&formatted);
+            match &by_dir[&dir] {
+                Some((config_dir, rules)) => {
+                    let ignored = !self.options.no_ignore
+                        && rules.1.as_ref().is_some_and(|config| {
+                            let file = file.canonicalize().unwrap_or_else(|_| file.clone());
+                            config.is_file_ignored(&file, Some(config_dir))
+                        });
+                    if !ignored {
+                        entries.push((file, Some(Arc::clone(rules))));
+                    }
                 }
+                None => entries.push((file, None)),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// YAML files under `path` selected by the processor's own config
+    fn walk_directory(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let mut yaml_files = Vec::with_capacity(100);
+
+        let use_ignore_files = !(self.options.no_ignore || self.options.no_gitignore);
+        let include_hidden = self.options.hidden;
+        let walker = WalkBuilder::new(path)
+            .follow_links(self.options.follow_symlinks)
+            .ignore(use_ignore_files)
+            .git_ignore(use_ignore_files)
+            .git_global(use_ignore_files)
+            .git_exclude(use_ignore_files)
+            .parents(use_ignore_files)
+            // Hidden directories are skipped unless `--hidden`, and `.git`
+            // always is; hidden files are left to `yaml-files`
+            .hidden(false)
+            .filter_entry(move |entry| {
+                let name = entry.file_name().to_string_lossy();
+                let skipped = if include_hidden {
+                    name == ".git"
+                } else {
+                    name.starts_with('.')
+                };
+                entry.depth() == 0 || !skipped || entry.file_type().is_some_and(|t| !t.is_dir())
+            })
+            .build();
 
-                write!(stdout, "{}", output)?;
+        for result in walker {
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(error) if self.options.follow_symlinks && is_symlink_error(&error) => {
+                    eprintln!("Warning: {}; skipped", error);
+                    continue;
+                }
+                Err(error) => return Err(error.into()),
+            };
+            let file_path = entry.path();
+            if file_path.is_file() && self.is_yaml_file(file_path, path) {
+                if self.is_ignored(file_path, Some(path)) || self.is_unchanged(file_path) {
+                    continue;
+                }
+                yaml_files.push(file_path.to_path_buf());
             }
         }
 
-        if self.options.verbose {
-            writeln!(stdout, "Successfully processed {} files", results.len())?;
+        // Walk order depends on the file system
+        yaml_files.sort();
+        Ok(yaml_files)
+    }
+
+    /// Files that linting `path` would process, without linting them. Files
+    /// given directly are listed unless the config ignores them, matching
+    /// `process_file`.
+    pub fn list_files<P: AsRef<Path>>(&self, path: P) -> Result<Vec<PathBuf>> {
+        let path = path.as_ref();
+
+        if path.is_dir() {
+            return self.collect_directory_files(path);
         }
 
-        if self.options.verbose {
-            writeln!(stdout, "Completed processing {} files", yaml_files.len())?;
+        let cwd = std::env::current_dir().ok();
+        if self.is_ignored(path, cwd.as_deref())
+            || self.is_unchanged(path)
+            || Self::is_over_issue_limit(&self.options)
+        {
+            return Ok(vec![]);
         }
 
-        Ok(total_issues)
+        Ok(vec![path.to_path_buf()])
     }
 
-    fn is_yaml_file(&self, path: &Path) -> bool {
-        if let Some(ext) = path.extension() {
-            matches!(
-                ext.to_string_lossy().to_lowercase().as_str(),
-                "yaml" | "yml"
-            )
-        } else {
-            false
-        }
+    /// Whether the config's `ignore` patterns exclude `path`, unless `--no-ignore` is in effect
+    fn is_ignored(&self, path: &Path, config_dir: Option<&Path>) -> bool {
+        !self.options.no_ignore
+            && self
+                .config
+                .as_ref()
+                .is_some_and(|config| config.is_file_ignored(path, config_dir))
+    }
+
+    /// Whether `--diff`/`--changed-since` is in effect and `path` has no changes
+    fn is_unchanged(&self, path: &Path) -> bool {
+        self.options
+            .changed_lines
+            .as_ref()
+            .is_some_and(|changed_lines| !changed_lines.contains_file(path))
+    }
+
+    /// Whether walking `base_dir` should lint `path`, per the config's
+    /// `yaml-files` or, with `--detect-yaml`, its content
+    fn is_yaml_file(&self, path: &Path, base_dir: &Path) -> bool {
+        let matched = match &self.config {
+            Some(config) => config.is_yaml_file(path, base_dir, self.options.hidden),
+            None => config::is_yaml_file(
+                config::DEFAULT_YAML_FILES,
+                path,
+                base_dir,
+                self.options.hidden,
+            ),
+        };
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        matched
+            || (self.options.detect_yaml
+                && path.extension().is_none()
+                && (self.options.hidden || !hidden)
+                && sniff::looks_like_yaml_file(path))
     }
 
     fn get_relative_path(&self, path: &Path) -> String {
@@ -491,78 +981,109 @@ impl FileProcessor {
         path.to_string_lossy().to_string()
     }
 
+    /// Run `op` in a thread pool sized by `ProcessingOptions::jobs`, so
+    /// parallel iterators inside it use at most that many threads
+    pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> Result<R> {
+        Self::install_static(&self.options, op)
+    }
+
+    fn install_static<R: Send>(
+        options: &ProcessingOptions,
+        op: impl FnOnce() -> R + Send,
+    ) -> Result<R> {
+        match options.jobs {
+            Some(jobs) => {
+                let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+                Ok(pool.install(op))
+            }
+            None => Ok(op()),
+        }
+    }
+
+    /// Lint `files` with rules and config borrowed by every worker, or those
+    /// of the file's nested config. `counter` drives progress output.
     fn process_files_list(
-        files: &[PathBuf],
-        rules: Arc<Vec<Box<dyn rules::Rule>>>,
+        files: &[(PathBuf, Option<Arc<FileRules>>)],
+        rules: &[Box<dyn rules::Rule>],
         options: &ProcessingOptions,
         fix_mode: bool,
         config: &Option<Arc<config::Config>>,
-        counter: Option<Arc<AtomicUsize>>,
-        total: Option<usize>,
-    ) -> Result<Vec<LintResult>> {
+        counter: Option<&AtomicUsize>,
+    ) -> Result<Vec<(LintResult, Option<FixOutcome>)>> {
+        let total = files.len();
+        let process = |(file, nested): &(PathBuf, Option<Arc<FileRules>>)| {
+            let (rules, config) = match nested {
+                Some(nested) => (&nested.0[..], &nested.1),
+                None => (rules, config),
+            };
+            Self::process_single_file(rules, file, options, fix_mode, config, counter, total)
+        };
         if files.len() > 3 {
-            files
-                .par_iter()
-                .map(|file| {
-                    Self::process_single_file(
-                        rules.clone(),
-                        file,
-                        options,
-                        fix_mode,
-                        config,
-                        counter.as_ref().map(Arc::clone),
-                        total,
-                    )
-                })
-                .collect()
+            Self::install_static(options, || files.par_iter().map(process).collect())?
         } else {
-            files
-                .iter()
-                .map(|file| {
-                    Self::process_single_file(
-                        rules.clone(),
-                        file,
-                        options,
-                        fix_mode,
-                        config,
-                        counter.as_ref().map(Arc::clone),
-                        total,
-                    )
-                })
-                .collect()
+            files.iter().map(process).collect()
         }
     }
 
     fn process_single_file(
-        rules: Arc<Vec<Box<dyn rules::Rule>>>,
+        rules: &[Box<dyn rules::Rule>],
         file_path: &Path,
         options: &ProcessingOptions,
         fix_mode: bool,
         config: &Option<Arc<config::Config>>,
-        counter: Option<Arc<AtomicUsize>>,
-        total: Option<usize>,
-    ) -> Result<LintResult> {
+        counter: Option<&AtomicUsize>,
+        total: usize,
+    ) -> Result<(LintResult, Option<FixOutcome>)> {
         let relative_path = Self::get_relative_path_static(file_path);
 
+        if Self::is_over_issue_limit(options) {
+            Self::count_progress(counter, total);
+            let result = LintResult {
+                file: relative_path,
+                issues: vec![],
+                scalar_start_lines: HashMap::new(),
+            };
+            return Ok((result, None));
+        }
+
         if options.verbose {
             eprintln!("Processing file: {}", relative_path);
         }
 
-        let content = std::fs::read_to_string(file_path)?;
-
-        let result = if fix_mode {
-            Self::process_file_with_fixes_static(
-                &rules,
+        // A file that cannot be read becomes a finding; the rest are still linted
+        let result = match Self::read_file(file_path, relative_path.clone()) {
+            Err(mut result) => {
+                Self::apply_output_options(options, &result.file, &mut result.issues);
+                Ok((result, None))
+            }
+            Ok(content) if fix_mode => Self::process_file_with_fixes_static(
+                rules,
                 file_path,
                 &content,
                 &relative_path,
                 config,
+                options,
             )
-        } else {
-            Self::process_file_check_only_static(&rules, &content, &relative_path, config)
+            .map(|(result, outcome)| (result, Some(outcome))),
+            Ok(content) => Self::process_file_check_only_static(
+                rules,
+                &content,
+                &relative_path,
+                config,
+                options,
+            )
+            .map(|mut result| {
+                Self::apply_output_options(options, &relative_path, &mut result.issues);
+                (result, None)
+            }),
         }?;
 
-        if let (Some(counter), Some(total)) = (counter, total) {
+        Self::count_progress(counter, total);
+        Ok(result)
+    }
+
+    fn count_progress(counter: Option<&AtomicUsize>, total: usize) {
+        if let Some(counter) = counter {
             let count = counter.fetch_add(1, Ordering::Relaxed) + 1;
             if count % 1000 == 0 || count == total {
                 let percent = (count * 100) / total;
@@ -572,8 +1093,6 @@ impl FileProcessor {
                 );
             }
         }
-
-        Ok(result)
     }
 
     fn process_file_check_only_static(
@@ -581,58 +1100,270 @@ impl FileProcessor {
         content: &str,
         relative_path: &str,
         config: &Option<Arc<config::Config>>,
+        options: &ProcessingOptions,
     ) -> Result<LintResult> {
-        let result = Self::check_file_content(rules, content, relative_path, config);
+        let timings = options.rule_timings.as_deref();
+        let Some(cache) = &options.cache else {
+            return Ok(Self::check_file_content(
+                rules,
+                content,
+                relative_path,
+                config,
+                timings,
+            ));
+        };
+
+        if let Some(result) = cache.get(relative_path, content, config) {
+            return Ok(result);
+        }
+        let result = Self::check_file_content(rules, content, relative_path, config, timings);
+        cache.insert(content, config, &result);
         Ok(result)
     }
 
+    /// Fix one file, writing the fixed content back. Output is left to the
+    /// caller so parallel workers never print.
     fn process_file_with_fixes_static(
         rules: &[Box<dyn rules::Rule>],
         path: &Path,
         content: &str,
         relative_path: &str,
         config: &Option<Arc<config::Config>>,
-    ) -> Result<LintResult> {
-        let (fixed_content, total_fixes, fixable_issues, all_issues) =
+        options: &ProcessingOptions,
+    ) -> Result<(LintResult, FixOutcome)> {
+        let (fixed_content, total_fixes, fixable_issues, mut all_issues) =
             Self::apply_fixes_and_check(rules, content, relative_path, config);
+        Self::apply_output_options(options, relative_path, &mut all_issues);
 
-        let _non_fixable_issues = all_issues.len();
-
-        if total_fixes > 0 {
+        if fixed_content != content {
             std::fs::write(path, &fixed_content)?;
-            println!(
-                "Fixed {} issues in {} ({} fixable, {} remaining)",
-                total_fixes, relative_path, fixable_issues, _non_fixable_issues
-            );
-        } else if !all_issues.is_empty() {
-            println!(
-                "Found {} non-fixable issues in {}:",
-                _non_fixable_issues, relative_path
-            );
-            for (issue, rule_name) in &all_issues {
-                let level = match issue.severity {
-                    crate::Severity::Error => "error",
-                    crate::Severity::Warning => "warning",
-                    crate::Severity::Info => "info",
-                };
-                println!(
-                    "  {}:{}:{}: {} {} ({})",
-                    relative_path, issue.line, issue.column, level, issue.message, rule_name
-                );
-            }
         }
 
-        Ok(LintResult {
+        let outcome = FixOutcome {
             file: relative_path.to_string(),
+            fixed: total_fixes,
+            fixable: fixable_issues,
+            remaining: all_issues.len(),
+        };
+        let result = LintResult {
+            file: relative_path.to_string(),
+            scalar_start_lines: Self::scalar_start_lines(&fixed_content, None, &all_issues),
             issues: all_issues,
-        })
+        };
+        Ok((result, outcome))
     }
 }
 
+/// Directory of config fragments merged over the config file next to it
+pub const CONFIG_FRAGMENTS_DIR: &str = ".yamllint.d";
+
+/// Load a config file, then merge the `.yamllint.d/` fragments next to it
 pub fn load_config<P: AsRef<Path>>(path: P) -> Result<config::Config> {
-    let content = std::fs::read_to_string(path)?;
+    let path = path.as_ref();
+    let value = read_config_value(path)?;
+    let mut config = parse_config_value(value, path.parent().unwrap_or(Path::new("")))?;
+    let fragments_dir = path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(CONFIG_FRAGMENTS_DIR);
+    apply_config_fragments(&mut config, &fragments_dir)?;
+    config.base_dir = config_base_dir(path);
+    Ok(config)
+}
+
+/// Absolute directory of the config file at `path`
+fn config_base_dir(path: &Path) -> Option<PathBuf> {
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+    dir.unwrap_or(Path::new(".")).canonicalize().ok()
+}
+
+/// File name of the config files discovered next to linted files
+pub const CONFIG_FILE_NAME: &str = ".yamllint";
+
+/// File name of the JSON config discovered next to linted files
+pub const JSON_CONFIG_FILE_NAME: &str = ".yamllint.json";
+
+/// Dedicated config file names in the order they are looked for in each
+/// directory
+const CONFIG_FILE_NAMES: &[&str] = &[
+    CONFIG_FILE_NAME,
+    toml_config::TOML_CONFIG_FILE_NAME,
+    JSON_CONFIG_FILE_NAME,
+];
+
+/// A config file's content as a YAML value, see [`parse_config_content`]
+fn read_config_value(path: &Path) -> Result<serde_yaml::Value> {
+    parse_config_content(&std::fs::read_to_string(path)?, path)
+}
+
+/// The content of the config at `path` as a YAML value. Files ending in
+/// `.toml` are read as TOML, see [`toml_config`], and files ending in
+/// `.json` as JSON.
+fn parse_config_content(content: &str, path: &Path) -> Result<serde_yaml::Value> {
+    if toml_config::is_toml(path) {
+        return toml_config::config_value(content, path);
+    }
+    if path.extension().is_some_and(|ext| ext == "json") {
+        let value: serde_json::Value = serde_json::from_str(content)?;
+        return Ok(serde_yaml::to_value(value)?);
+    }
+    Ok(serde_yaml::from_str(content)?)
+}
+
+/// Load a discovered config (a `.yamllint` or its TOML or JSON forms) as a
+/// refinement of the configs discovered in the directories above it, merged
+/// outermost first the way `extends` merges.
+/// The search stops at a config that sets `root: true`. Each file's own
+/// `extends` is resolved before merging, and the fragments of every level
+/// apply afterwards, outermost first. Other config files load as with
+/// [`load_config`].
+pub fn load_config_hierarchy<P: AsRef<Path>>(path: P) -> Result<config::Config> {
+    let path = path.as_ref();
+    let mut levels = Vec::new();
+    let mut current = Some(path.to_path_buf());
+    while let Some(file) = current {
+        let value = read_config_value(&file)
+            .with_context(|| format!("Invalid config {}", file.display()))?;
+        let is_root = value.get("root").and_then(|v| v.as_bool()) == Some(true);
+        current = (!is_root).then(|| parent_config_file(&file)).flatten();
+        levels.push((file, value));
+    }
+    if levels.len() == 1 {
+        return load_config(path);
+    }
+
+    let mut merged = serde_yaml::Value::Null;
+    for (file, value) in levels.iter().rev() {
+        let dir = file.parent().unwrap_or(Path::new(""));
+        let value = resolve_extends(value.clone(), dir, &mut Vec::new())?;
+        merged = match merged {
+            serde_yaml::Value::Null => value,
+            base => merge_extended_config(base, value),
+        };
+    }
+    let mut config = parse_config_value(merged, Path::new(""))?;
+    for (file, _) in levels.iter().rev() {
+        let dir = file.parent().unwrap_or(Path::new("."));
+        apply_config_fragments(&mut config, &dir.join(CONFIG_FRAGMENTS_DIR))?;
+    }
+    config.base_dir = config_base_dir(path);
+    Ok(config)
+}
+
+/// The config a discovered config at `path` refines: the nearest one above
+/// its directory
+fn parent_config_file(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?;
+    if !CONFIG_FILE_NAMES
+        .iter()
+        .chain(toml_config::SHARED_TOML_FILE_NAMES)
+        .any(|known| name == *known)
+    {
+        return None;
+    }
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+    let dir = dir.unwrap_or(Path::new(".")).canonicalize().ok()?;
+    discover_config_file_from_dir(dir.parent()?.to_path_buf())
+}
+
+/// Merge every `*.yaml` fragment in `dir` over `config`, in lexical order of
+/// file names, so later fragments win. A fragment may set `rules` (options
+/// are merged into the rule's existing ones, as with `--set`), `ignore`
+/// (appended), `yaml-files` (replaced), `severity-by-path` (checked
+/// before earlier entries) and `exclude-from-fix` (appended). A missing
+/// directory is not an error.
+pub fn apply_config_fragments(config: &mut config::Config, dir: &Path) -> Result<()> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(());
+    };
+    let mut fragments: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    fragments.sort();
+
+    for fragment in fragments {
+        let content = std::fs::read_to_string(&fragment)?;
+        apply_config_fragment(config, &content)
+            .with_context(|| format!("Invalid config fragment {}", fragment.display()))?;
+    }
+    Ok(())
+}
+
+/// Rule ids listed under `exclude-from-fix`, which must all exist
+fn parse_exclude_from_fix(value: &serde_yaml::Value) -> Result<Vec<String>> {
+    let rule_ids: Vec<String> = serde_yaml::from_value(value.clone())
+        .context("`exclude-from-fix` must be a list of rule ids")?;
+    let registry = rules::registry::RuleRegistry::new();
+    if let Some(unknown) = rule_ids
+        .iter()
+        .find(|id| registry.get_rule_metadata(id).is_none())
+    {
+        anyhow::bail!("Unknown rule '{}' in exclude-from-fix", unknown);
+    }
+    Ok(rule_ids)
+}
+
+fn apply_config_fragment(config: &mut config::Config, content: &str) -> Result<()> {
+    let fragment: serde_yaml::Value = serde_yaml::from_str(content)?;
+    let Some(fragment) = fragment.as_mapping() else {
+        // An empty fragment changes nothing
+        if fragment.is_null() {
+            return Ok(());
+        }
+        anyhow::bail!("a fragment must be a mapping");
+    };
 
-    match parse_original_yamllint_format(&content) {
+    for (key, value) in fragment {
+        match key.as_str().unwrap_or("") {
+            "rules" => {
+                let Some(rules) = value.as_mapping() else {
+                    anyhow::bail!("`rules` must be a mapping");
+                };
+                merge_original_rules(config, rules);
+            }
+            "ignore" => {
+                let patterns = config::ignore_patterns_from_json(&yaml_value_to_json(value))
+                    .unwrap_or_default();
+                let patterns: Vec<String> = patterns.lines().map(str::to_string).collect();
+                config.add_ignore_patterns(&patterns);
+            }
+            "yaml-files" => {
+                config.yaml_files = Some(serde_yaml::from_value(value.clone())?);
+            }
+            "exclude-from-fix" => {
+                config
+                    .exclude_from_fix
+                    .extend(parse_exclude_from_fix(value)?);
+            }
+            "severity-by-path" => {
+                let mut entries: Vec<config::SeverityOverride> =
+                    serde_yaml::from_value(value.clone())?;
+                entries.append(&mut config.severity_by_path);
+                config.severity_by_path = entries;
+            }
+            other => anyhow::bail!(
+                "unsupported key `{}` (fragments may set rules, ignore, yaml-files, severity-by-path and exclude-from-fix)",
+                other
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Parse a config document given inline, e.g. with `-d` on the command line.
+/// Paths in `extends` are relative to the working directory.
+pub fn load_config_from_str(content: &str) -> Result<config::Config> {
+    parse_config(content, Path::new(""))
+}
+
+/// Parse a config document whose `extends` paths are relative to `base_dir`
+fn parse_config(content: &str, base_dir: &Path) -> Result<config::Config> {
+    parse_config_value(serde_yaml::from_str(content)?, base_dir)
+}
+
+fn parse_config_value(value: serde_yaml::Value, base_dir: &Path) -> Result<config::Config> {
+    match parse_original_yamllint_format(value.clone(), base_dir) {
         Ok(original_config) => return Ok(original_config),
         Err(e) => {
             if !e.to_string().contains("Not original yamllint format") {
@@ -641,7 +1372,11 @@ pub fn load_config<P: AsRef<Path>>(path: P) -> Result<config::Config> {
         }
     }
 
-    let config: config::Config = serde_yaml::from_str(&content)?;
+    let mut config: config::Config = serde_yaml::from_value(value)?;
+    for (rule_id, rule) in config.rules.iter_mut() {
+        rule.resolve_native_settings(rule_id);
+        rule.resolve_ignore_from_file();
+    }
     Ok(config)
 }
 
@@ -678,10 +1413,145 @@ fn yaml_value_to_json(yaml_val: &serde_yaml::Value) -> serde_json::Value {
     }
 }
 
-fn parse_original_yamllint_format(content: &str) -> Result<config::Config> {
+/// Configs built into yamllint that `extends` may name; any other value is
+/// the path of a config file
+const BUILTIN_CONFIGS: [&str; 2] = ["default", "relaxed"];
+
+/// Replace an `extends` naming a file with that file's settings, merged
+/// under `config`'s own, following chains of `extends` until a built-in
+/// config. `chain` holds the files being extended, to catch cycles.
+fn resolve_extends(
+    config: serde_yaml::Value,
+    base_dir: &Path,
+    chain: &mut Vec<PathBuf>,
+) -> Result<serde_yaml::Value> {
+    let Some(extends) = config.get("extends").and_then(|v| v.as_str()) else {
+        return Ok(config);
+    };
+    if BUILTIN_CONFIGS.contains(&extends) {
+        return Ok(config);
+    }
+
+    // Remote configs are identified by their URL
+    let remote = remote_config::is_url(extends);
+    let (path, canonical) = if remote {
+        (PathBuf::from(extends), PathBuf::from(extends))
+    } else {
+        let path = base_dir.join(extends);
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        (path, canonical)
+    };
+    if chain.contains(&canonical) {
+        anyhow::bail!(
+            "Config extends itself: {} -> {}",
+            chain
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> "),
+            canonical.display()
+        );
+    }
+    let base = if remote {
+        let content = remote_config::fetch(extends)?;
+        let base = parse_config_content(&content, &path)
+            .with_context(|| format!("Invalid extended config {}", extends))?;
+        let nested = base.get("extends").and_then(|v| v.as_str());
+        if let Some(nested) = nested
+            .filter(|nested| !BUILTIN_CONFIGS.contains(nested) && !remote_config::is_url(nested))
+        {
+            anyhow::bail!(
+                "Remote config {} cannot extend the local config {}",
+                extends,
+                nested
+            );
+        }
+        base
+    } else {
+        if !path.is_file() {
+            anyhow::bail!("Cannot read extended config {}", path.display());
+        }
+        read_config_value(&path)
+            .with_context(|| format!("Invalid extended config {}", path.display()))?
+    };
+
+    chain.push(canonical);
+    let base = resolve_extends(base, path.parent().unwrap_or(Path::new("")), chain)?;
+    chain.pop();
+    Ok(merge_extended_config(base, config))
+}
+
+/// `config` over the config it extends: rule options are merged one by one
+/// (nested mappings too), `enable` keeps the base's options, and any other
+/// key replaces the base's value
+fn merge_extended_config(base: serde_yaml::Value, config: serde_yaml::Value) -> serde_yaml::Value {
+    use serde_yaml::Value;
+
+    // An empty base file extends nothing
+    let mut merged = match base {
+        Value::Mapping(base) => base,
+        _ => serde_yaml::Mapping::new(),
+    };
+    let Value::Mapping(config) = config else {
+        return Value::Mapping(merged);
+    };
+    if !merged.contains_key("extends") {
+        merged.insert("extends".into(), "default".into());
+    }
+    for (key, value) in config {
+        match (key.as_str(), value) {
+            (Some("extends"), _) => {}
+            (Some("rules"), Value::Mapping(rules)) => {
+                let mut merged_rules = match merged.remove("rules") {
+                    Some(Value::Mapping(base_rules)) => base_rules,
+                    _ => serde_yaml::Mapping::new(),
+                };
+                for (rule, options) in rules {
+                    let options = match (merged_rules.remove(&rule), options) {
+                        (Some(base @ Value::Mapping(_)), Value::String(state))
+                            if state == "enable" =>
+                        {
+                            base
+                        }
+                        (Some(base), options) => merge_yaml_mappings(base, options),
+                        (None, options) => options,
+                    };
+                    merged_rules.insert(rule, options);
+                }
+                merged.insert("rules".into(), Value::Mapping(merged_rules));
+            }
+            (_, value) => {
+                merged.insert(key, value);
+            }
+        }
+    }
+    Value::Mapping(merged)
+}
+
+/// `overlay` over `base`, merging mappings key by key
+fn merge_yaml_mappings(base: serde_yaml::Value, overlay: serde_yaml::Value) -> serde_yaml::Value {
     use serde_yaml::Value;
 
-    let yaml_value: Value = serde_yaml::from_str(content)?;
+    match (base, overlay) {
+        (Value::Mapping(mut base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                let value = match base.remove(&key) {
+                    Some(existing) => merge_yaml_mappings(existing, value),
+                    None => value,
+                };
+                base.insert(key, value);
+            }
+            Value::Mapping(base)
+        }
+        (_, overlay) => overlay,
+    }
+}
+
+fn parse_original_yamllint_format(
+    yaml_value: serde_yaml::Value,
+    base_dir: &Path,
+) -> Result<config::Config> {
+    let yaml_value = resolve_extends(yaml_value, base_dir, &mut Vec::new())?;
 
     let has_extends = yaml_value.get("extends").is_some();
     let has_rules_simple_format = yaml_value
@@ -740,280 +1610,394 @@ fn convert_original_yamllint_config(yaml_value: serde_yaml::Value) -> Result<con
         }
     }
 
+    if let Some(entries) = yaml_value
+        .get("severity-by-path")
+        .and_then(|v| v.as_sequence())
+    {
+        for entry in entries {
+            let files = entry.get("files").and_then(|v| v.as_str());
+            let level = entry.get("level").and_then(|v| v.as_str());
+            let rules = match entry.get("rules") {
+                None => Vec::new(),
+                Some(serde_yaml::Value::String(rule)) => vec![rule.clone()],
+                Some(rules) => serde_yaml::from_value(rules.clone())
+                    .context("severity-by-path `rules` must be a rule or a list of rules")?,
+            };
+            match (files, level) {
+                (Some(files), Some(level)) => {
+                    config.severity_by_path.push(config::SeverityOverride {
+                        files: files.to_string(),
+                        level: level.parse()?,
+                        rules,
+                    });
+                }
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "severity-by-path entries need `files` and `level`"
+                    ))
+                }
+            }
+        }
+    }
+
     if let Some(rules) = yaml_value.get("rules").and_then(|r| r.as_mapping()) {
-        for (rule_name, rule_config) in rules {
-            let rule_name = rule_name.as_str().unwrap_or("");
+        apply_original_rules(&mut config, rules);
+    }
 
-            if let Some(rule_str) = rule_config.as_str() {
-                match rule_str {
-                    "disable" => {
-                        config.set_rule_enabled(rule_name, false);
-                    }
-                    "enable" => {
-                        config.set_rule_enabled(rule_name, true);
-                    }
-                    _ => {
-                        config.set_rule_enabled(rule_name, true);
-                    }
-                }
-            } else if let Some(rule_map) = rule_config.as_mapping() {
-                let mut enabled = None;
-                let mut severity = None;
-                let mut settings: Option<serde_json::Value> = None;
+    if let Some(patterns) = yaml_value.get("yaml-files").and_then(|v| v.as_sequence()) {
+        config.yaml_files = Some(
+            patterns
+                .iter()
+                .filter_map(|pattern| pattern.as_str().map(str::to_string))
+                .collect(),
+        );
+    }
+
+    if let Some(rule_ids) = yaml_value.get("exclude-from-fix") {
+        config.exclude_from_fix = parse_exclude_from_fix(rule_ids)?;
+    }
 
-                if let Some(enable_val) = rule_map.get("enable") {
-                    enabled = enable_val.as_bool();
+    if let Some(locale) = yaml_value.get("locale").and_then(|v| v.as_str()) {
+        config.locale = Some(locale.to_string());
+    }
+
+    if let Some(modelines) = yaml_value.get("modelines").and_then(|v| v.as_bool()) {
+        config.modelines = modelines;
+    }
+
+    if let Some(entries) = yaml_value.get("overrides") {
+        config.overrides = parse_path_overrides(entries)?;
+    }
+
+    if let Some(profiles) = yaml_value.get("profiles").and_then(|v| v.as_mapping()) {
+        for (name, profile) in profiles {
+            let Some(name) = name.as_str() else {
+                continue;
+            };
+            if !profile.is_mapping() {
+                anyhow::bail!("Profile '{}' must be a mapping", name);
+            }
+            config
+                .profiles
+                .insert(name.to_string(), yaml_value_to_json(profile));
+        }
+    }
+
+    Ok(config)
+}
+
+/// Parse an `overrides:` list, whose entries take `files` (a glob or a list
+/// of globs) and a `rules:` mapping
+fn parse_path_overrides(value: &serde_yaml::Value) -> Result<Vec<config::PathOverride>> {
+    let Some(entries) = value.as_sequence() else {
+        anyhow::bail!("`overrides` must be a list");
+    };
+    let mut overrides = Vec::new();
+    for entry in entries {
+        let files = match entry.get("files") {
+            Some(serde_yaml::Value::String(files)) => vec![files.clone()],
+            Some(files @ serde_yaml::Value::Sequence(_)) => {
+                serde_yaml::from_value(files.clone())
+                    .context("overrides `files` must be a glob or a list of globs")?
+            }
+            _ => anyhow::bail!("overrides entries need `files` and `rules`"),
+        };
+        let Some(rules) = entry.get("rules").filter(|rules| rules.is_mapping()) else {
+            anyhow::bail!("overrides entries need `files` and `rules`");
+        };
+        overrides.push(config::PathOverride {
+            files,
+            rules: yaml_value_to_json(rules),
+        });
+    }
+    Ok(overrides)
+}
+
+/// Apply a yamllint-format `rules:` mapping to `config`
+fn apply_original_rules(config: &mut config::Config, rules: &serde_yaml::Mapping) {
+    for (rule_name, rule_config) in rules {
+        let rule_name = rule_name.as_str().unwrap_or("");
+
+        if let Some(rule_str) = rule_config.as_str() {
+            match rule_str {
+                "disable" => {
+                    config.set_rule_enabled(rule_name, false);
                 }
-                if let Some(disable_val) = rule_map.get("disable") {
-                    if let Some(disable_bool) = disable_val.as_bool() {
-                        enabled = Some(!disable_bool);
-                    }
+                "enable" => {
+                    config.set_rule_enabled(rule_name, true);
                 }
-
-                if let Some(level_val) = rule_map.get("level") {
-                    if let Some(level_str) = level_val.as_str() {
-                        match level_str {
-                            "error" => severity = Some(crate::Severity::Error),
-                            "warning" => severity = Some(crate::Severity::Warning),
-                            "info" => severity = Some(crate::Severity::Info),
-                            "disable" => enabled = Some(false),
-                            _ => {}
-                        }
-                    }
+                _ => {
+                    config.set_rule_enabled(rule_name, true);
                 }
+            }
+        } else if let Some(rule_map) = rule_config.as_mapping() {
+            apply_original_rule_mapping(config, rule_name, rule_map);
+        }
+    }
+}
 
-                match rule_name {
-                    "line-length" => {
-                        let mut max_length = 80;
-                        let mut allow_non_breakable_words = true;
+/// Apply a yamllint-format `rules:` mapping to `config`, keeping the options
+/// already set for a rule unless the mapping sets them again
+fn merge_original_rules(config: &mut config::Config, rules: &serde_yaml::Mapping) {
+    for (rule_name, rule_value) in rules {
+        let Some(rule_name) = rule_name.as_str() else {
+            continue;
+        };
+        let Some(rule_map) = rule_value.as_mapping() else {
+            let single = serde_yaml::Mapping::from_iter([(rule_name.into(), rule_value.clone())]);
+            apply_original_rules(config, &single);
+            continue;
+        };
+        let mut merged = match config
+            .rules
+            .get(rule_name)
+            .map(|rule| serde_yaml::to_value(&rule.other))
+        {
+            Some(Ok(serde_yaml::Value::Mapping(map))) => map,
+            _ => serde_yaml::Mapping::new(),
+        };
+        merged.extend(rule_map.clone());
+        apply_original_rule_mapping(config, rule_name, &merged);
+    }
+}
 
-                        if let Some(max_val) = rule_map.get("max").and_then(|v| v.as_u64()) {
-                            max_length = max_val as usize;
-                        }
-                        if let Some(allow_val) = rule_map.get("allow-non-breakable-words") {
-                            if let Some(allow_bool) = allow_val.as_bool() {
-                                allow_non_breakable_words = allow_bool;
-                            }
-                        }
+/// Config for the file at `file_path` with its matching `overrides` entries
+/// applied, or `None` when no entry matches
+fn apply_path_overrides(config: &config::Config, file_path: &str) -> Option<config::Config> {
+    let overrides = config.overrides_for(file_path);
+    if overrides.is_empty() {
+        return None;
+    }
+    let mut file_config = config.clone();
+    for entry in overrides {
+        if let Ok(serde_yaml::Value::Mapping(rules)) = serde_yaml::to_value(&entry.rules) {
+            merge_original_rules(&mut file_config, &rules);
+        }
+    }
+    Some(file_config)
+}
 
-                        let mut allow_non_breakable_inline_mappings = false;
-                        if let Some(allow_val) = rule_map.get("allow-non-breakable-inline-mappings")
-                        {
-                            if let Some(allow_bool) = allow_val.as_bool() {
-                                allow_non_breakable_inline_mappings = allow_bool;
-                            }
-                        }
+/// Merge the named profile's `rules` over the base rules. Configs without
+/// any `profiles` ignore the selection, so a profile chosen through the
+/// environment does not break repositories that never defined one.
+pub fn apply_profile(config: &mut config::Config, profile: &str) -> Result<()> {
+    if config.profiles.is_empty() {
+        return Ok(());
+    }
+    let Some(overrides) = config.profiles.get(profile) else {
+        let mut known: Vec<&String> = config.profiles.keys().collect();
+        known.sort();
+        anyhow::bail!(
+            "Unknown profile '{}' (available: {})",
+            profile,
+            known.into_iter().cloned().collect::<Vec<_>>().join(", ")
+        );
+    };
 
-                        let rule_settings = serde_json::to_value(config::LineLengthConfig {
-                            max_length,
-                            allow_non_breakable_words,
-                            allow_non_breakable_inline_mappings,
-                        })
-                        .unwrap();
-                        settings = Some(rule_settings);
-                    }
-                    "document-start" => {
-                        if let Some(present_val) = rule_map.get("present") {
-                            if let Some(present_bool) = present_val.as_bool() {
-                                let rule_settings =
-                                    serde_json::to_value(config::DocumentStartConfig {
-                                        present: Some(present_bool),
-                                    })
-                                    .unwrap();
-                                settings = Some(rule_settings);
-                            }
-                        }
-                    }
-                    "indentation" => {
-                        let mut spaces = Some(2);
-                        let mut indent_sequences = Some(true);
-                        let check_multi_line_strings = Some(false);
-                        let mut ignore = None;
-
-                        if let Some(spaces_val) = rule_map.get("spaces").and_then(|v| v.as_u64()) {
-                            spaces = Some(spaces_val as usize);
-                        }
-                        if let Some(indent_val) = rule_map.get("indent-sequences") {
-                            if let Some(indent_bool) = indent_val.as_bool() {
-                                indent_sequences = Some(indent_bool);
-                            } else {
-                                enabled = Some(false);
-                            }
-                        }
+    let overrides: serde_yaml::Value = serde_yaml::to_value(overrides)?;
+    if let Some(rules) = overrides.get("rules").and_then(|r| r.as_mapping()) {
+        apply_original_rules(config, rules);
+    }
+    Ok(())
+}
 
-                        if let Some(ignore_val) = rule_map.get("ignore") {
-                            if let Some(s) = ignore_val.as_str() {
-                                ignore = Some(s.to_string());
-                            } else {
-                                ignore = serde_yaml::to_string(ignore_val)
-                                    .ok()
-                                    .map(|s| s.trim_matches('"').to_string());
-                            }
-                        }
-                        let rule_settings = serde_json::to_value(config::IndentationConfig {
-                            spaces,
-                            indent_sequences,
-                            check_multi_line_strings,
-                            ignore,
-                        })
-                        .unwrap();
-                        settings = Some(rule_settings);
-                    }
-                    "comments" => {
-                        if let Some(min_spaces_val) = rule_map
-                            .get("min-spaces-from-content")
-                            .and_then(|v| v.as_u64())
-                        {
-                            let rule_settings = serde_json::to_value(config::CommentsConfig {
-                                min_spaces_from_content: Some(min_spaces_val as usize),
-                            })
-                            .unwrap();
-                            settings = Some(rule_settings);
-                        }
-                    }
-                    "truthy" => {
-                        let mut allowed_values = vec!["false".to_string(), "true".to_string()];
-                        if let Some(allowed_vals) =
-                            rule_map.get("allowed-values").and_then(|v| v.as_sequence())
-                        {
-                            allowed_values = allowed_vals
-                                .iter()
-                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                .collect();
-                        }
-                        let rule_settings =
-                            serde_json::to_value(config::TruthyConfig { allowed_values }).unwrap();
-                        settings = Some(rule_settings);
-                    }
-                    "empty-lines" => {
-                        let mut max = None;
-                        let mut max_start = None;
-                        let mut max_end = None;
+/// Apply command-line `rule.option=value` overrides, given with yamllint
+/// option names (e.g. `line-length.max=120`). Values are parsed as YAML, and
+/// a rule's enabled state is left unchanged.
+pub fn apply_rule_option_overrides(
+    config: &mut config::Config,
+    overrides: &[String],
+) -> Result<()> {
+    let known_rules = rules::registry::RuleRegistry::new().get_rule_ids();
+
+    for spec in overrides {
+        let parsed = spec
+            .split_once('=')
+            .and_then(|(target, value)| Some((target.split_once('.')?, value)));
+        let Some(((rule_name, option), value)) = parsed else {
+            anyhow::bail!("Invalid --set '{}': expected rule.option=value", spec);
+        };
+        if !known_rules.iter().any(|id| id == rule_name) {
+            anyhow::bail!("Unknown rule in --set '{}': {}", spec, rule_name);
+        }
+        if option.is_empty() {
+            anyhow::bail!("Invalid --set '{}': missing option name", spec);
+        }
 
-                        if let Some(max_val) = rule_map.get("max").and_then(|v| v.as_u64()) {
-                            max = Some(max_val as usize);
-                        }
-                        if let Some(start_val) = rule_map.get("max-start").and_then(|v| v.as_u64())
-                        {
-                            max_start = Some(start_val as usize);
-                        }
-                        if let Some(end_val) = rule_map.get("max-end").and_then(|v| v.as_u64()) {
-                            max_end = Some(end_val as usize);
-                        }
+        let value: serde_yaml::Value = serde_yaml::from_str(value)
+            .with_context(|| format!("Invalid value in --set '{}'", spec))?;
 
-                        let rule_settings = serde_json::to_value(config::EmptyLinesConfig {
-                            max,
-                            max_start,
-                            max_end,
-                        })
-                        .unwrap();
-                        settings = Some(rule_settings);
-                    }
-                    "trailing-spaces" => {
-                        let allow = rule_map
-                            .get("allow")
-                            .and_then(|v| v.as_bool())
-                            .unwrap_or(false);
-                        let rule_settings =
-                            serde_json::to_value(config::TrailingSpacesConfig { allow }).unwrap();
-                        settings = Some(rule_settings);
-                    }
-                    "document-end" => {
-                        if let Some(present_val) = rule_map.get("present") {
-                            if let Some(present_bool) = present_val.as_bool() {
-                                let rule_settings =
-                                    serde_json::to_value(config::DocumentEndConfig {
-                                        present: Some(present_bool),
-                                    })
-                                    .unwrap();
-                                settings = Some(rule_settings);
-                            }
-                        }
-                    }
-                    "key-ordering" => {
-                        if let Some(order_vals) =
-                            rule_map.get("order").and_then(|v| v.as_sequence())
-                        {
-                            let order: Vec<String> = order_vals
-                                .iter()
-                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                .collect();
-                            let rule_settings = serde_json::to_value(config::KeyOrderingConfig {
-                                order: Some(order),
-                            })
-                            .unwrap();
-                            settings = Some(rule_settings);
-                        }
-                    }
-                    "anchors" => {
-                        if let Some(max_len_val) =
-                            rule_map.get("max-length").and_then(|v| v.as_u64())
-                        {
-                            let rule_settings = serde_json::to_value(config::AnchorsConfig {
-                                max_length: Some(max_len_val as usize),
-                            })
-                            .unwrap();
-                            settings = Some(rule_settings);
-                        }
-                    }
-                    "new-lines" => {
-                        if let Some(type_val) = rule_map.get("type").and_then(|v| v.as_str()) {
-                            let type_str = type_val.to_string();
-                            let rule_settings = serde_json::to_value(config::NewLinesConfig {
-                                type_: Some(type_str),
-                            })
-                            .unwrap();
-                            settings = Some(rule_settings);
-                        }
-                    }
-                    _ => {}
-                }
+        let existing = config.rules.get(rule_name);
+        let enabled = existing.and_then(|c| c.enabled);
+        let mut rule_map = match existing.map(|c| serde_yaml::to_value(&c.other)) {
+            Some(Ok(serde_yaml::Value::Mapping(map))) => map,
+            _ => serde_yaml::Mapping::new(),
+        };
+        rule_map.insert(serde_yaml::Value::String(option.to_string()), value);
 
-                let existing = config.rules.get(rule_name).cloned();
-                let final_enabled = if let Some(ref existing_config) = existing {
-                    enabled.or(existing_config.enabled)
-                } else {
-                    enabled
-                };
+        apply_original_rule_mapping(config, rule_name, &rule_map);
+        if let (Some(enabled), Some(rule_config)) = (enabled, config.rules.get_mut(rule_name)) {
+            if option != "level" {
+                rule_config.enabled = Some(enabled);
+            }
+        }
+    }
+    Ok(())
+}
 
-                let final_severity =
-                    severity.or_else(|| existing.as_ref().and_then(|c| c.severity));
-                let final_settings = settings.or_else(|| existing.clone().and_then(|c| c.settings));
+/// Render `config` as YAML for `config dump`. Every registered rule is
+/// listed with the enabled state and severity it will actually run with, and
+/// unset options are omitted; keys are sorted so dumps can be diffed.
+pub fn dump_config(config: &config::Config) -> Result<String> {
+    Ok(serde_yaml::to_string(&config_hash::canonical_config(
+        config,
+    ))?)
+}
 
-                let mut final_other = existing.map(|c| c.other).unwrap_or_default();
+/// Apply one rule's yamllint-format option mapping (`max`, `level`, ...) to
+/// `config`, merging its raw options into those already set for the rule
+fn apply_original_rule_mapping(
+    config: &mut config::Config,
+    rule_name: &str,
+    rule_map: &serde_yaml::Mapping,
+) {
+    let mut enabled = None;
+    let mut severity = None;
+
+    if let Some(enable_val) = rule_map.get("enable") {
+        enabled = enable_val.as_bool();
+    }
+    if let Some(disable_val) = rule_map.get("disable") {
+        if let Some(disable_bool) = disable_val.as_bool() {
+            enabled = Some(!disable_bool);
+        }
+    }
 
-                for (key, value) in rule_map {
-                    if let Some(key_str) = key.as_str() {
-                        let json_val = yaml_value_to_json(value);
-                        final_other.insert(key_str.to_string(), json_val);
-                    }
-                }
+    if let Some(level_val) = rule_map.get("level") {
+        if let Some(level_str) = level_val.as_str() {
+            if level_str == "disable" {
+                enabled = Some(false);
+            } else if let Ok(level) = level_str.parse() {
+                severity = Some(level);
+            }
+        }
+    }
 
-                config.rules.insert(
-                    rule_name.to_string(),
-                    config::RuleConfig {
-                        enabled: final_enabled,
-                        severity: final_severity,
-                        settings: final_settings,
-                        other: final_other,
-                    },
-                );
+    let existing = config.rules.get(rule_name).cloned();
+    // Like yamllint, configuring a rule with a mapping enables it
+    let final_enabled = enabled.or(Some(true));
+
+    let final_severity = severity.or_else(|| existing.as_ref().and_then(|c| c.severity));
+
+    let mut final_ignore = existing.as_ref().and_then(|c| c.ignore.clone());
+    let mut final_other = existing.map(|c| c.other).unwrap_or_default();
+
+    for (key, value) in rule_map {
+        if let Some(key_str) = key.as_str() {
+            let json_val = yaml_value_to_json(value);
+            if key_str == "ignore" {
+                final_ignore = config::ignore_patterns_from_json(&json_val);
+                continue;
             }
+            final_other.insert(key_str.to_string(), json_val);
         }
     }
 
-    Ok(config)
+    let mut rule_config = config::RuleConfig {
+        enabled: final_enabled,
+        severity: final_severity,
+        ignore: final_ignore,
+        settings: None,
+        other: final_other,
+    };
+    rule_config.resolve_ignore_from_file();
+    config.rules.insert(rule_name.to_string(), rule_config);
+}
+
+/// Environment variable naming a config file to use when neither `-c` nor
+/// `-d` is given, instead of looking for `.yamllint` files
+pub const CONFIG_FILE_ENV: &str = "YAMLLINT_CONFIG_FILE";
+
+/// The config file named by [`CONFIG_FILE_ENV`], if it is set and not empty
+pub fn config_file_from_env() -> Option<PathBuf> {
+    std::env::var_os(CONFIG_FILE_ENV)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The user's personal config, used when no project config is found:
+/// `$XDG_CONFIG_HOME/yamllint/config`, or `~/.config/yamllint/config` when
+/// that variable is unset as with yamllint, then the platform's own config
+/// directory on macOS and Windows
+pub fn user_config_file() -> Option<PathBuf> {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    };
+    let home = env_dir("HOME");
+    let mut config_dirs = Vec::new();
+    match env_dir("XDG_CONFIG_HOME") {
+        Some(dir) => config_dirs.push(dir),
+        None => config_dirs.extend(home.as_ref().map(|home| home.join(".config"))),
+    }
+    if cfg!(target_os = "macos") {
+        config_dirs.extend(
+            home.as_ref()
+                .map(|home| home.join("Library/Application Support")),
+        );
+    }
+    if cfg!(windows) {
+        config_dirs.extend(env_dir("APPDATA"));
+    }
+
+    config_dirs
+        .into_iter()
+        .map(|dir| dir.join("yamllint").join("config"))
+        .find(|path| path.is_file())
 }
 
 pub fn discover_config_file() -> Option<PathBuf> {
     discover_config_file_from_dir(std::env::current_dir().ok()?)
 }
 
+/// Find the config (`.yamllint`, `.yamllint.toml`, `.yamllint.json` or a
+/// `pyproject.toml` with a `[tool.yamllint-rs]` table) governing a linted
+/// path, searching upwards from the path itself when it is a directory or
+/// from its parent otherwise
+pub fn discover_config_file_for_path<P: AsRef<Path>>(path: P) -> Option<PathBuf> {
+    let path = path.as_ref();
+    let start = if path.is_dir() {
+        path.to_path_buf()
+    } else {
+        match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        }
+    };
+    let start = std::fs::canonicalize(&start).unwrap_or(start);
+    discover_config_file_from_dir(start)
+}
+
+/// The config file directly in `dir`: the first of [`CONFIG_FILE_NAMES`]
+/// that exists, else a shared TOML file with a `[tool.yamllint-rs]` table
+fn config_file_in_dir(dir: &Path) -> Option<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+        .or_else(|| {
+            toml_config::SHARED_TOML_FILE_NAMES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| toml_config::has_tool_table(path))
+        })
+}
+
 pub fn discover_config_file_from_dir(start_dir: PathBuf) -> Option<PathBuf> {
     let mut dir = start_dir.as_path();
     loop {
-        let config_path = dir.join(".yamllint");
-        if config_path.exists() {
+        if let Some(config_path) = config_file_in_dir(dir) {
             return Some(config_path);
         }
 
@@ -1031,25 +2015,231 @@ pub fn discover_config_file_from_dir(start_dir: PathBuf) -> Option<PathBuf> {
 pub struct LintResult {
     pub file: String,
     pub issues: Vec<(LintIssue, String)>,
+    /// For issues inside literal or folded block scalars, the line where the
+    /// enclosing scalar starts, keyed by the issue's physical line
+    pub scalar_start_lines: HashMap<usize, usize>,
 }
 
-#[derive(Debug, Clone)]
+impl LintResult {
+    /// Line where the block scalar containing `issue` starts, if any
+    pub fn scalar_start_line(&self, issue: &LintIssue) -> Option<usize> {
+        self.scalar_start_lines.get(&issue.line).copied()
+    }
+
+    /// Issues as they appear in reports, each with its file
+    pub fn reported_issues(&self) -> impl Iterator<Item = ReportedIssue<'_>> {
+        self.issues.iter().map(|(issue, rule)| ReportedIssue {
+            file: Some(&self.file),
+            issue,
+            rule,
+            scalar_start_line: self.scalar_start_line(issue),
+        })
+    }
+}
+
+/// Serialized as `{"file": ..., "issues": [...]}`, each issue as in the JSON
+/// report but without the repeated `file`
+impl serde::Serialize for LintResult {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let issues: Vec<ReportedIssue> = self
+            .reported_issues()
+            .map(|issue| ReportedIssue {
+                file: None,
+                ..issue
+            })
+            .collect();
+        let mut state = serializer.serialize_struct("LintResult", 2)?;
+        state.serialize_field("file", &self.file)?;
+        state.serialize_field("issues", &issues)?;
+        state.end()
+    }
+}
+
+/// One issue per line in the parsable format, `file:line:column: [level] message (rule)`
+impl std::fmt::Display for LintResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (issue, rule) in &self.issues {
+            writeln!(f, "{}:{} ({})", self.file, issue, rule)?;
+        }
+        Ok(())
+    }
+}
+
+/// An issue as serialized in reports: the issue's fields plus its rule and,
+/// inside block scalars, the line where the scalar starts
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct ReportedIssue<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<&'a str>,
+    #[serde(flatten)]
+    pub issue: &'a LintIssue,
+    pub rule: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scalar_start_line: Option<usize>,
+}
+
+/// What `--fix` did to one file, reported in the summary at the end of a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixOutcome {
+    pub file: String,
+    /// Fixes applied and written back to the file
+    pub fixed: usize,
+    /// Issues found by rules that can fix them
+    pub fixable: usize,
+    /// Issues left after fixing
+    pub remaining: usize,
+}
+
+/// Whether a walk error comes from following a symlink: a loop back to an
+/// ancestor directory, or a link whose target does not exist
+fn is_symlink_error(error: &ignore::Error) -> bool {
+    match error {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithPath { err, .. } | ignore::Error::WithDepth { err, .. } => {
+            is_symlink_error(err)
+        }
+        ignore::Error::Io(error) => error.kind() == std::io::ErrorKind::NotFound,
+        _ => false,
+    }
+}
+
+/// Rule name and diagnostic code of the finding for files that could not be
+/// read, e.g. because they vanished or lack read permission
+pub const FILE_ERROR: &str = "file-error";
+
+/// Lint result holding the single `file-error` finding for a file
+fn file_error_result(file: String, error: &anyhow::Error) -> LintResult {
+    LintResult {
+        file,
+        issues: vec![(
+            LintIssue {
+                line: 1,
+                column: 1,
+                message: format!("{:#}", error),
+                code: FILE_ERROR.to_string(),
+                severity: Severity::Error,
+            },
+            FILE_ERROR.to_string(),
+        )],
+        scalar_start_lines: HashMap::new(),
+    }
+}
+
+/// Issue totals by severity, used to pick the process exit code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IssueCounts {
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    /// Files skipped because they are binary or not UTF-8, counted apart
+    /// from `errors`
+    pub invalid_files: usize,
+    /// Files that could not be read at all, counted apart from `errors`
+    pub unreadable_files: usize,
+}
+
+impl IssueCounts {
+    pub fn add_result(&mut self, result: &LintResult) {
+        for (issue, rule) in &result.issues {
+            self.add_issue(issue, rule);
+        }
+    }
+
+    pub fn add_issue(&mut self, issue: &LintIssue, rule: &str) {
+        if rule == encoding::INVALID_ENCODING {
+            self.invalid_files += 1;
+        } else if rule == FILE_ERROR {
+            self.unreadable_files += 1;
+        } else {
+            match issue.severity {
+                Severity::Error => self.errors += 1,
+                Severity::Warning => self.warnings += 1,
+                Severity::Info => self.infos += 1,
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: IssueCounts) {
+        self.errors += other.errors;
+        self.warnings += other.warnings;
+        self.infos += other.infos;
+        self.invalid_files += other.invalid_files;
+        self.unreadable_files += other.unreadable_files;
+    }
+
+    pub fn total(&self) -> usize {
+        self.errors + self.warnings + self.infos + self.invalid_files + self.unreadable_files
+    }
+}
+
+/// Serialized with the field names of the JSON report, `severity` as `level`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct LintIssue {
     pub line: usize,
     pub column: usize,
     pub message: String,
+    /// Stable diagnostic code, e.g. `colons/too-many-spaces-before`
+    pub code: String,
+    #[serde(rename = "level", alias = "severity")]
     pub severity: Severity,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+/// `line:column: [level] message`, the parsable format without file and rule
+impl std::fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: [{}] {}",
+            self.line, self.column, self.severity, self.message
+        )
+    }
+}
+
+/// Issue severity, ordered from least to most severe
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
-    Error,
-    Warning,
+    // The capitalized names are what native configs have always used
+    #[serde(alias = "Info")]
     Info,
+    #[serde(alias = "Warning")]
+    Warning,
+    #[serde(alias = "Error")]
+    Error,
 }
 
 impl Severity {
-    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+    /// Whether this severity meets `threshold`, e.g. `Error.is_at_least(Warning)`
+    pub fn is_at_least(self, threshold: Severity) -> bool {
+        self >= threshold
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
         match s.to_lowercase().as_str() {
             "error" => Ok(Severity::Error),
             "warning" => Ok(Severity::Warning),
@@ -1057,14 +2247,6 @@ impl Severity {
             _ => Err(anyhow::anyhow!("Invalid severity: {}", s)),
         }
     }
-
-    pub fn to_string(&self) -> String {
-        match self {
-            Severity::Error => "error".to_string(),
-            Severity::Warning => "warning".to_string(),
-            Severity::Info => "info".to_string(),
-        }
-    }
 }
 
 pub fn lint_yaml<P: AsRef<Path>>(file_path: P) -> Result<LintResult> {
@@ -1074,6 +2256,7 @@ pub fn lint_yaml<P: AsRef<Path>>(file_path: P) -> Result<LintResult> {
     let result = LintResult {
         file: path.to_string_lossy().to_string(),
         issues: vec![],
+        scalar_start_lines: HashMap::new(),
     };
 
     Ok(result)
@@ -1102,4 +2285,191 @@ mod tests {
         assert!(config.rules.contains_key("line-length"));
         assert!(config.rules.contains_key("indentation"));
     }
+
+    #[test]
+    fn test_install_uses_configured_thread_count() {
+        let options = ProcessingOptions {
+            jobs: Some(2),
+            ..Default::default()
+        };
+        let processor = FileProcessor::new(options);
+        let threads = processor.install(rayon::current_num_threads).unwrap();
+        assert_eq!(threads, 2);
+    }
+
+    #[test]
+    fn test_apply_rule_option_overrides() {
+        let mut config =
+            load_config_from_str("extends: default\nrules:\n  line-length:\n    max: 100\n    allow-non-breakable-words: false\n  document-start: disable\n")
+                .unwrap();
+        apply_rule_option_overrides(
+            &mut config,
+            &[
+                "line-length.max=120".to_string(),
+                "document-start.present=false".to_string(),
+            ],
+        )
+        .unwrap();
+
+        let settings: rules::line_length::LineLengthConfig =
+            config.rule_options("line-length").unwrap();
+        assert_eq!(settings.max_length, 120);
+        assert!(!settings.allow_non_breakable_words);
+        assert!(!config
+            .get_enabled_rules()
+            .contains(&"document-start".to_string()));
+
+        for invalid in [
+            "line-length",
+            "line-length=1",
+            "no-such-rule.max=1",
+            "line-length.=1",
+        ] {
+            assert!(
+                apply_rule_option_overrides(&mut config, &[invalid.to_string()]).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_layers_apply_in_precedence_order() {
+        let base = load_config_from_str(
+            "extends: default\n\
+             rules:\n  line-length:\n    max: 80\n\
+             profiles:\n  \
+               ci:\n    rules:\n      line-length:\n        max: 100\n  \
+               local:\n    rules:\n      line-length:\n        max: 150\n",
+        )
+        .unwrap();
+        let max_length = |layers: config::ConfigLayers| {
+            let mut config = base.clone();
+            layers.apply(&mut config).unwrap();
+            let enabled = config.is_rule_enabled("line-length");
+            let settings: rules::line_length::LineLengthConfig =
+                config.rule_options("line-length").unwrap();
+            enabled.then_some(settings.max_length)
+        };
+
+        assert_eq!(max_length(config::ConfigLayers::default()), Some(80));
+        let env = config::ConfigLayers {
+            env_profile: Some("local".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(max_length(env.clone()), Some(150));
+        let cli = config::ConfigLayers {
+            profile: Some("ci".to_string()),
+            ..env.clone()
+        };
+        assert_eq!(max_length(cli.clone()), Some(100));
+        let set = config::ConfigLayers {
+            set_options: vec!["line-length.max=90".to_string()],
+            ..cli.clone()
+        };
+        assert_eq!(max_length(set.clone()), Some(90));
+        let disabled = config::ConfigLayers {
+            disable: vec!["line-length".to_string()],
+            ..set
+        };
+        assert_eq!(max_length(disabled), None);
+    }
+
+    #[test]
+    fn test_rule_ignore_in_both_config_formats() {
+        let native = load_config_from_str(
+            "rules:\n  line-length:\n    enabled: true\n    ignore:\n      - \"generated/**\"\n      - legacy.yaml\nglobal:\n  default_severity: Error\n",
+        )
+        .unwrap();
+        let original = load_config_from_str(
+            "extends: default\nrules:\n  line-length:\n    max: 100\n    ignore: |\n      generated/**\n      legacy.yaml\n",
+        )
+        .unwrap();
+
+        for config in [&native, &original] {
+            let ignored = |path: &str| config.is_ignored_by_rule("line-length", Path::new(path));
+            assert!(ignored("generated/a/b.yaml"));
+            assert!(ignored("./legacy.yaml"));
+            assert!(ignored("sub/legacy.yaml"));
+            assert!(!ignored("src/generated.yaml"));
+            assert!(!config.is_ignored_by_rule("trailing-spaces", Path::new("legacy.yaml")));
+        }
+
+        // --set keeps the rule's ignore patterns
+        let mut config = original.clone();
+        apply_rule_option_overrides(&mut config, &["line-length.max=120".to_string()]).unwrap();
+        assert!(config.is_ignored_by_rule("line-length", Path::new("legacy.yaml")));
+    }
+
+    #[test]
+    fn test_rules_are_built_from_typed_options() {
+        let original = load_config_from_str(
+            "extends: default\n\
+             rules:\n  \
+               truthy:\n    allowed-values: ['yes', 'no']\n  \
+               empty-lines:\n    max: 0\n  \
+               braces:\n    forbid: non-empty\n  \
+               commas:\n    max-spaces-after: 3\n",
+        )
+        .unwrap();
+        let rules = rule_pool::RuleSet::from_config(&original);
+        let issues = |content: &str, rule_id: &str| {
+            rules
+                .iter()
+                .filter(|rule| rule.rule_id() == rule_id)
+                .map(|rule| rule.check(content, "test.yaml").len())
+                .sum::<usize>()
+        };
+        assert_eq!(issues("---\na: yes\nb: true\n", "truthy"), 1);
+        assert_eq!(issues("---\na: 1\n\nb: 2\n", "empty-lines"), 1);
+        assert_eq!(issues("---\na: {b: 1}\nc: {}\n", "braces"), 1);
+        assert_eq!(issues("---\na: [1,   2, [3,    4]]\n", "commas"), 1);
+        assert!(original.invalid_settings().is_empty());
+
+        // Native `settings` use snake_case names, and `max_length` for `max`
+        let native = load_config_from_str(
+            "rules:\n  line-length:\n    enabled: true\n    settings:\n      max_length: 100\n      allow_non_breakable_words: false\n  commas:\n    enabled: true\n    settings:\n      max_spaces_before: -1\n      min_spaces_after: 0\nglobal:\n  default_severity: Error\n",
+        )
+        .unwrap();
+        let commas: rules::commas::CommasConfig = native.rule_options("commas").unwrap();
+        assert_eq!(
+            (
+                commas.max_spaces_before,
+                commas.min_spaces_after,
+                commas.max_spaces_after
+            ),
+            (-1, 0, 1)
+        );
+        let options: rules::line_length::LineLengthConfig =
+            native.rule_options("line-length").unwrap();
+        assert_eq!(options.max_length, 100);
+        assert!(!options.allow_non_breakable_words);
+
+        // Values of the wrong type are reported by option, and the rule keeps its defaults
+        let invalid =
+            load_config_from_str("extends: default\nrules:\n  line-length:\n    max: long\n")
+                .unwrap();
+        let problems = invalid.invalid_settings();
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].starts_with("invalid option 'max' for rule 'line-length': invalid type"),
+            "{}",
+            problems[0]
+        );
+        assert!(rules::factory::RuleFactory::new()
+            .create_rule_with_config("line-length", &invalid)
+            .is_some());
+    }
+
+    #[test]
+    fn test_unsupported_locale_is_reported() {
+        let config = load_config_from_str("extends: default\nlocale: en_US.UTF-8\n").unwrap();
+        assert!(config.invalid_settings().is_empty());
+
+        let config = load_config_from_str("extends: default\nlocale: sv_SE.UTF-8\n").unwrap();
+        assert_eq!(
+            config.invalid_settings(),
+            vec!["unsupported locale 'sv_SE.UTF-8', key-ordering compares keys by code point"]
+        );
+    }
 }
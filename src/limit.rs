@@ -0,0 +1,135 @@
+//! Caps on reported issues from `--max-issues` and `--max-issues-per-file`,
+//! so pathological inputs cannot flood the output.
+
+use crate::{IssueCounts, LintIssue};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Issue caps shared by all worker threads of a run. Issues over a cap are
+/// dropped but still counted, so the exit code reflects them; once the total
+/// cap is reached, files not yet started are not linted at all.
+#[derive(Debug, Default)]
+pub struct IssueLimit {
+    total: Option<usize>,
+    per_file: Option<usize>,
+    reported: AtomicUsize,
+    dropped: Mutex<IssueCounts>,
+    skipped_files: AtomicUsize,
+}
+
+impl IssueLimit {
+    pub fn new(total: Option<usize>, per_file: Option<usize>) -> Self {
+        Self {
+            total,
+            per_file,
+            ..Self::default()
+        }
+    }
+
+    /// Whether the total cap has been reached
+    pub fn is_exhausted(&self) -> bool {
+        self.total
+            .is_some_and(|total| self.reported.load(Ordering::Relaxed) >= total)
+    }
+
+    /// Record a file that was not linted because the total cap was reached
+    pub fn skip_file(&self) {
+        self.skipped_files.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Keep the issues of one file that fit under both caps, in order
+    pub fn truncate(&self, issues: &mut Vec<(LintIssue, String)>) {
+        let mut keep = self
+            .per_file
+            .map_or(issues.len(), |per_file| per_file.min(issues.len()));
+        if let Some(total) = self.total {
+            let reported = self.reported.fetch_add(keep, Ordering::Relaxed);
+            keep = keep.min(total.saturating_sub(reported));
+        }
+        if keep == issues.len() {
+            return;
+        }
+
+        let mut dropped = self.dropped.lock().unwrap();
+        for (issue, rule) in issues.drain(keep..) {
+            dropped.add_issue(&issue, &rule);
+        }
+    }
+
+    /// Issues linted but not reported
+    pub fn dropped(&self) -> IssueCounts {
+        *self.dropped.lock().unwrap()
+    }
+
+    /// Files not linted because the total cap was reached
+    pub fn skipped_files(&self) -> usize {
+        self.skipped_files.load(Ordering::Relaxed)
+    }
+
+    /// A notice saying what was left out, `None` when nothing was
+    pub fn truncation_notice(&self) -> Option<String> {
+        let dropped = self.dropped().total();
+        let skipped = self.skipped_files();
+        if dropped == 0 && skipped == 0 {
+            return None;
+        }
+        let mut notice = format!("Output truncated: {} more issue(s) not reported", dropped);
+        if skipped > 0 {
+            notice.push_str(&format!(", {} file(s) not linted", skipped));
+        }
+        Some(notice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+
+    fn issues(severities: &[Severity]) -> Vec<(LintIssue, String)> {
+        severities
+            .iter()
+            .enumerate()
+            .map(|(i, &severity)| {
+                (
+                    LintIssue {
+                        line: i + 1,
+                        column: 1,
+                        message: "issue".to_string(),
+                        code: "rule/issue".to_string(),
+                        severity,
+                    },
+                    "rule".to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_issue_limit_caps_per_file_and_total() {
+        let limit = IssueLimit::new(Some(3), Some(2));
+        let mut first = issues(&[Severity::Warning, Severity::Warning, Severity::Error]);
+        limit.truncate(&mut first);
+        assert_eq!(first.len(), 2);
+        assert!(!limit.is_exhausted());
+
+        let mut second = issues(&[Severity::Warning, Severity::Error]);
+        limit.truncate(&mut second);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].0.line, 1);
+        assert!(limit.is_exhausted());
+
+        let mut third = issues(&[Severity::Error]);
+        limit.truncate(&mut third);
+        assert!(third.is_empty());
+        limit.skip_file();
+
+        let dropped = limit.dropped();
+        assert_eq!((dropped.errors, dropped.warnings), (3, 0));
+        assert_eq!(
+            limit.truncation_notice().unwrap(),
+            "Output truncated: 3 more issue(s) not reported, 1 file(s) not linted"
+        );
+        assert!(IssueLimit::new(Some(5), None).truncation_notice().is_none());
+    }
+}
@@ -0,0 +1,282 @@
+//! In-memory linting API for embedders.
+//!
+//! `Linter` runs the configured rules against a string without touching the
+//! filesystem or any reporter. `check` returns every issue sorted by position,
+//! while `check_iter` runs one rule at a time and yields its issues lazily, so
+//! callers that only need the first few diagnostics can stop early without
+//! running the remaining rules.
+
+use crate::analysis::ContentAnalysis;
+use crate::directives::{ConfigureRegion, DirectiveState};
+use crate::rule_pool::RuleSet;
+use crate::rules::Rule;
+use crate::{config, FileProcessor, FileRules, LintIssue};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+pub struct Linter {
+    rules: RuleSet,
+    config: Option<Arc<config::Config>>,
+}
+
+impl Linter {
+    /// Linter with the default rule set
+    pub fn new() -> Self {
+        Self::with_config(config::Config::default())
+    }
+
+    pub fn with_config(config: config::Config) -> Self {
+        Self {
+            rules: RuleSet::from_config(&config),
+            config: Some(Arc::new(config)),
+        }
+    }
+
+    /// All issues in `content`, sorted by line and column. `file_path` is only
+    /// used for per-rule `ignore` patterns and is never read.
+    pub fn check(&self, content: &str, file_path: &str) -> Vec<(LintIssue, String)> {
+        FileProcessor::check_file_content(&self.rules, content, file_path, &self.config, None)
+            .issues
+    }
+
+    /// Issues in `content`, produced lazily one rule at a time. Issues are
+    /// grouped by rule in rule order rather than sorted by position, but are
+    /// otherwise those of `check`: path `overrides:`, `# yamllint-rs:`
+    /// modelines, `configure` directives and severity overrides all apply.
+    pub fn check_iter<'a>(&'a self, content: &'a str, file_path: &'a str) -> CheckIter<'a> {
+        CheckIter {
+            linter: self,
+            content,
+            file_path,
+            state: None,
+            next_rule: 0,
+            pending: Vec::new().into_iter(),
+            pending_rule: "",
+        }
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Analysis shared by every rule, built when the first issue is requested
+struct CheckState {
+    /// Rules and config of this file when they differ from the linter's
+    file_rules: Option<FileRules>,
+    analysis: ContentAnalysis,
+    directives: DirectiveState,
+    configure_regions: Vec<ConfigureRegion>,
+}
+
+impl CheckState {
+    fn rules_and_config<'s>(
+        &'s self,
+        linter: &'s Linter,
+    ) -> (&'s [Box<dyn Rule>], &'s Option<Arc<config::Config>>) {
+        match &self.file_rules {
+            Some((rules, config)) => (rules, config),
+            None => (&linter.rules, &linter.config),
+        }
+    }
+}
+
+/// Iterator returned by [`Linter::check_iter`]
+pub struct CheckIter<'a> {
+    linter: &'a Linter,
+    content: &'a str,
+    file_path: &'a str,
+    state: Option<CheckState>,
+    next_rule: usize,
+    pending: std::vec::IntoIter<LintIssue>,
+    pending_rule: &'static str,
+}
+
+impl<'a> CheckIter<'a> {
+    fn state(&mut self) -> &CheckState {
+        let (linter, content, file_path) = (self.linter, self.content, self.file_path);
+        self.state.get_or_insert_with(|| {
+            let file_rules = FileProcessor::file_rules(content, file_path, &linter.config);
+            let (rules, config) = match &file_rules {
+                Some((rules, config)) => (rules, config),
+                None => (&linter.rules, &linter.config),
+            };
+            let all_rule_ids: HashSet<String> =
+                rules.iter().map(|r| r.rule_id().to_string()).collect();
+            let mut directives = DirectiveState::new(all_rule_ids);
+            directives.parse_from_content(content);
+            let configure_regions = FileProcessor::configure_regions(content, config);
+            CheckState {
+                file_rules,
+                analysis: ContentAnalysis::analyze(content),
+                directives,
+                configure_regions,
+            }
+        })
+    }
+}
+
+impl<'a> Iterator for CheckIter<'a> {
+    type Item = (LintIssue, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rule_id = self.pending_rule;
+            if let Some(state) = &self.state {
+                let (_, config) = state.rules_and_config(self.linter);
+                for issue in self.pending.by_ref() {
+                    if !state.directives.is_rule_disabled(issue.line, rule_id) {
+                        let mut item = (issue, rule_id.to_string());
+                        FileProcessor::apply_severity_overrides(
+                            self.file_path,
+                            config,
+                            std::slice::from_mut(&mut item),
+                        );
+                        return Some(item);
+                    }
+                }
+            }
+
+            let (linter, content, file_path) = (self.linter, self.content, self.file_path);
+            let next_rule = self.next_rule;
+            let state = self.state();
+            let (rules, config) = state.rules_and_config(linter);
+            let rule = rules.get(next_rule)?;
+            let rule_id = rule.rule_id();
+            let issues = if FileProcessor::should_run_rule_for_file(rule_id, file_path, config) {
+                FileProcessor::check_rule_with_regions(
+                    rule.as_ref(),
+                    content,
+                    file_path,
+                    &state.analysis,
+                    config,
+                    &state.configure_regions,
+                )
+            } else {
+                Vec::new()
+            };
+            self.next_rule += 1;
+            self.pending = issues.into_iter();
+            self.pending_rule = rule_id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_iter_matches_check() {
+        let linter = Linter::new();
+        let content = "key: value   \nother:  value\n";
+
+        let mut streamed: Vec<_> = linter.check_iter(content, "test.yaml").collect();
+        streamed.sort_by(|a, b| a.0.line.cmp(&b.0.line).then(a.0.column.cmp(&b.0.column)));
+        let collected = linter.check(content, "test.yaml");
+
+        assert!(!collected.is_empty());
+        assert_eq!(streamed.len(), collected.len());
+        for ((a, rule_a), (b, rule_b)) in streamed.iter().zip(&collected) {
+            assert_eq!((a.line, a.column, rule_a), (b.line, b.column, rule_b));
+        }
+    }
+
+    #[test]
+    fn test_check_iter_applies_severity_by_path() {
+        let mut config = config::Config::default();
+        config.severity_by_path = vec![config::SeverityOverride {
+            files: "legacy/**".to_string(),
+            level: crate::Severity::Info,
+            rules: vec!["trailing-spaces".to_string()],
+        }];
+        let linter = Linter::with_config(config);
+        let content = "key: value   
+other:  value
+";
+
+        let severities = |issues: Vec<(LintIssue, String)>| {
+            let mut severities: Vec<_> = issues
+                .into_iter()
+                .map(|(issue, rule)| (issue.line, issue.column, rule, issue.severity))
+                .collect();
+            severities.sort();
+            severities
+        };
+        let streamed = severities(linter.check_iter(content, "legacy/a.yaml").collect());
+        assert_eq!(streamed, severities(linter.check(content, "legacy/a.yaml")));
+        assert!(streamed.contains(&(1, 11, "trailing-spaces".to_string(), crate::Severity::Info)));
+    }
+
+    #[test]
+    fn test_check_iter_uses_path_overrides() {
+        let config = crate::load_config_from_str(
+            "extends: default\noverrides:\n  - files: ['legacy/**']\n    rules:\n      trailing-spaces: disable\n      line-length: {max: 10}\n",
+        )
+        .unwrap();
+        let linter = Linter::with_config(config);
+        let content = "---\nkey: a long value   \n";
+
+        for path in ["legacy/a.yaml", "a.yaml"] {
+            let mut streamed: Vec<_> = linter
+                .check_iter(content, path)
+                .map(|(issue, rule)| (issue.line, issue.column, rule))
+                .collect();
+            streamed.sort();
+            let mut collected: Vec<_> = linter
+                .check(content, path)
+                .into_iter()
+                .map(|(issue, rule)| (issue.line, issue.column, rule))
+                .collect();
+            collected.sort();
+            assert_eq!(streamed, collected);
+        }
+        let rules: Vec<_> = linter
+            .check_iter(content, "legacy/a.yaml")
+            .map(|(_, rule)| rule)
+            .collect();
+        assert_eq!(rules, vec!["line-length"]);
+    }
+
+    #[test]
+    fn test_check_iter_applies_configure_directives() {
+        let linter = Linter::new();
+        let wide = "x".repeat(95);
+        let content = format!(
+            "# yamllint configure rule:line-length max=100\n---\nk1: {wide}\n\
+             # yamllint configure rule:line-length max=90\nk2: {wide}\n\
+             # yamllint configure rule:line-length reset\nk3: {wide}\n"
+        );
+
+        let lines = |issues: Vec<(LintIssue, String)>| {
+            let mut lines: Vec<_> = issues
+                .into_iter()
+                .filter(|(_, rule)| rule == "line-length")
+                .map(|(issue, _)| issue.line)
+                .collect();
+            lines.sort();
+            lines
+        };
+        let streamed = lines(linter.check_iter(&content, "test.yaml").collect());
+        assert_eq!(streamed, lines(linter.check(&content, "test.yaml")));
+        assert_eq!(streamed, vec![5]);
+    }
+
+    #[test]
+    fn test_check_iter_respects_directives_and_stops_early() {
+        let linter = Linter::new();
+        let content = "a: 1  # yamllint disable-line rule:trailing-spaces   \nb: 2   \nc: 3   \n";
+
+        let issues: Vec<_> = linter
+            .check_iter(content, "test.yaml")
+            .filter(|(_, rule)| rule == "trailing-spaces")
+            .collect();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].0.line, 2);
+
+        let first: Vec<_> = linter.check_iter(content, "test.yaml").take(1).collect();
+        assert_eq!(first.len(), 1);
+    }
+}
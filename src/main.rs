@@ -1,17 +1,52 @@
-use clap::Parser;
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use rayon::prelude::*;
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process;
-use yamllint_rs::{discover_config_file, load_config, FileProcessor, ProcessingOptions};
+use std::sync::Arc;
+use yamllint_rs::cache::{LintCache, CACHE_DIR};
+use yamllint_rs::config::{Config, ConfigLayers};
+use yamllint_rs::git::ChangedLines;
+use yamllint_rs::infer::StyleSurvey;
+use yamllint_rs::init::{starter_config, Preset};
+use yamllint_rs::limit::IssueLimit;
+use yamllint_rs::reporter::{Grouping, ReporterKind, ReporterSpec, SortOrder};
+use yamllint_rs::rules::factory::RuleFactory;
+use yamllint_rs::rules::registry::RuleRegistry;
+use yamllint_rs::timing::RuleTimings;
+use yamllint_rs::{
+    config_file_from_env, discover_config_file, discover_config_file_for_path, dump_config,
+    load_config, load_config_from_str, load_config_hierarchy, user_config_file, FileProcessor,
+    IssueCounts, ProcessingOptions, Severity,
+};
 
 #[derive(Parser)]
 #[command(name = "yamllint-rs")]
 #[command(about = "A YAML linter written in Rust")]
 #[command(version)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
-    /// YAML file(s) to lint
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// YAML file(s) to lint, or `-` to read from stdin
     files: Vec<String>,
 
+    /// Also lint the files listed in PATH, one per line, or `-` to read the
+    /// list from stdin (e.g. `git diff --name-only | yamllint-rs --files-from -`)
+    #[arg(long, value_name = "PATH")]
+    files_from: Option<String>,
+
+    /// Entries in the --files-from list are separated by NUL bytes instead of
+    /// newlines, as printed by `git diff -z` or `find -print0`
+    #[arg(short = '0', long = "null", requires = "files_from")]
+    null_separated: bool,
+
+    /// Path to report and match config against when linting stdin
+    #[arg(long, value_name = "PATH", requires = "files")]
+    stdin_filename: Option<String>,
+
     /// Recursive directory processing
     #[arg(short, long)]
     recursive: bool,
@@ -20,78 +55,993 @@ struct Cli {
     #[arg(short, long)]
     verbose: bool,
 
-    /// Configuration file path
-    #[arg(short, long)]
+    /// Configuration file path; defaults to `$YAMLLINT_CONFIG_FILE`, then the
+    /// nearest `.yamllint`
+    #[arg(short, long, short_alias = 'C')]
     config: Option<String>,
 
-    /// Configuration file path (alias for --config, -c)
-    #[arg(short = 'C', long, hide = true)]
-    config_upper: Option<String>,
+    /// Configuration given inline as a YAML document, e.g.
+    /// "{extends: default, rules: {line-length: {max: 120}}}"
+    #[arg(short = 'd', long, value_name = "YAML", conflicts_with = "config")]
+    config_data: Option<String>,
+
+    /// Fail when no project config is found for a path instead of linting it
+    /// with the user's config or the defaults (also `$YAMLLINT_RS_REQUIRE_CONFIG`)
+    #[arg(long)]
+    require_config: bool,
+
+    /// Use only cached copies of configs extended from a URL instead of
+    /// downloading them
+    #[arg(long, global = true)]
+    offline: bool,
 
     /// Automatically fix fixable issues
     #[arg(long)]
     fix: bool,
 
-    /// Output format (standard, colored)
-    #[arg(short, long, default_value = "auto")]
+    /// Output format (standard, colored, auto)
+    #[arg(short, long, default_value = "auto", value_parser = parse_format)]
     format: String,
 
+    /// Number of files to lint in parallel (defaults to one per CPU)
+    #[arg(short, long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+
     /// Disable progress updates
     #[arg(long)]
     no_progress: bool,
+
+    /// Reporter to emit results with, as `name` or `name:path` (standard, colored,
+    /// auto, json, sarif). May be repeated.
+    #[arg(long = "reporter", value_name = "REPORTER")]
+    reporters: Vec<String>,
+
+    /// Replace scalar values with placeholders in all output
+    #[arg(long)]
+    redact_values: bool,
+
+    #[command(flatten)]
+    overrides: ConfigOverrides,
+
+    /// Only report errors; warnings and infos are hidden and never affect the exit code
+    #[arg(long)]
+    no_warnings: bool,
+
+    /// Return a non-zero exit code (2) when only warnings are found; same as `--fail-on warning`
+    #[arg(short, long)]
+    strict: bool,
+
+    /// Lowest severity that fails the run, independent of what is printed
+    /// [default: error]
+    #[arg(long, value_enum, value_name = "SEVERITY", conflicts_with = "strict")]
+    fail_on: Option<FailOn>,
+
+    /// Print the time each rule spent checking files to stderr at the end
+    /// of the run, slowest first
+    #[arg(long, conflicts_with = "fix")]
+    profile_rules: bool,
+
+    /// Order of reported files and issues; files are always listed by path
+    #[arg(long, value_enum, value_name = "ORDER", default_value = "file")]
+    sort_by: SortBy,
+
+    /// Stop after reporting N issues in total; files not yet started are
+    /// not linted
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "fix")]
+    max_issues: Option<u64>,
+
+    /// Report at most N issues per file
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    max_issues_per_file: Option<u64>,
+
+    /// Print a table of issue counts per group of files instead of listing
+    /// issues on stdout; `owner` groups by the CODEOWNERS file
+    #[arg(long, value_enum, value_name = "GROUP")]
+    group_by: Option<GroupBy>,
+
+    /// Reuse results for files unchanged since the last `--cache` run, stored in .yamllint-cache
+    #[arg(long)]
+    cache: bool,
+
+    /// Only lint files changed since HEAD, reporting issues on changed lines only
+    #[arg(long, conflicts_with_all = ["changed_since", "fix"])]
+    diff: bool,
+
+    /// Like --diff, but compared with the merge base of HEAD and REF (e.g. origin/main)
+    #[arg(long, value_name = "REF", conflicts_with = "fix")]
+    changed_since: Option<String>,
+
+    /// Lint files even if config `ignore`/`ignore-from-file` patterns or `.gitignore` exclude them
+    #[arg(long, conflicts_with = "ignore_patterns")]
+    no_ignore: bool,
+
+    /// Walk into files excluded by `.gitignore`, `.ignore` or git's exclude
+    /// files; config `ignore` patterns still apply
+    #[arg(long)]
+    no_gitignore: bool,
+
+    /// Walk into hidden directories such as `.github` (never `.git`) and lint
+    /// hidden files matching `yaml-files`
+    #[arg(long)]
+    hidden: bool,
+
+    /// Follow symbolic links when walking directories; links that loop back
+    /// to an ancestor or point nowhere are skipped with a warning
+    #[arg(long)]
+    follow_symlinks: bool,
+
+    /// Also lint files without an extension, such as `Procfile`, when their
+    /// content looks like YAML
+    #[arg(long)]
+    detect_yaml: bool,
+
+    /// Print the files that would be linted, without linting them
+    #[arg(long, conflicts_with_all = ["fix", "reporters"])]
+    list_files: bool,
+}
+
+/// Command-line changes applied on top of the loaded config
+#[derive(Args, Clone)]
+struct ConfigOverrides {
+    /// Only run these rules (comma-separated), overriding config
+    #[arg(long, value_name = "RULES", value_delimiter = ',')]
+    enable_only: Vec<String>,
+
+    /// Disable these rules (comma-separated), overriding config
+    #[arg(long, value_name = "RULES", value_delimiter = ',')]
+    disable: Vec<String>,
+
+    /// Config profile to merge over the base rules (defaults to $YAMLLINT_RS_PROFILE)
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Override a rule option, e.g. `line-length.max=120`. May be repeated.
+    #[arg(long = "set", value_name = "RULE.OPTION=VALUE")]
+    set_options: Vec<String>,
+
+    /// Skip files matching this pattern (e.g. `build/` or `*.generated.yaml`),
+    /// in addition to the config's `ignore`. May be repeated.
+    #[arg(long = "ignore-pattern", value_name = "PATTERN")]
+    ignore_patterns: Vec<String>,
+
+    /// Lint files with these extensions (comma-separated) when walking
+    /// directories, replacing the config's `yaml-files`
+    #[arg(long, value_name = "EXTS", value_delimiter = ',')]
+    extensions: Vec<String>,
+
+    /// Fail on unknown rules, unknown rule options and invalid option values
+    /// in the config instead of warning about them
+    #[arg(long)]
+    strict_config: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List every available rule with its default severity, fix support and
+    /// description; fixes turned off by the discovered config's
+    /// `exclude-from-fix` show as `off`
+    Rules {
+        /// Print a table, or JSON including each rule's default options
+        #[arg(long, value_enum, default_value_t = RulesFormat::Table)]
+        format: RulesFormat,
+    },
+    /// Print a JSON Schema describing one of the tool's outputs
+    Schema {
+        #[arg(value_enum)]
+        name: SchemaName,
+    },
+    /// Inspect the configuration the linter would use
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Fetch and lint the pinned real-world YAML corpus
+    #[cfg(feature = "corpus")]
+    Corpus {
+        #[command(subcommand)]
+        command: CorpusCommand,
+    },
+}
+
+#[cfg(feature = "corpus")]
+#[derive(Subcommand)]
+enum CorpusCommand {
+    /// Clone or update every repository in the manifest at its pinned revision
+    Fetch {
+        #[command(flatten)]
+        location: CorpusLocation,
+    },
+    /// Lint the fetched corpus, reporting file and issue counts and timings
+    Run {
+        #[command(flatten)]
+        location: CorpusLocation,
+
+        /// yamllint-format config file (defaults to `extends: default`)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Compare findings with the reference `yamllint` on the PATH; exits 1 on differences
+        #[arg(long)]
+        parity: bool,
+    },
+}
+
+#[cfg(feature = "corpus")]
+#[derive(Args)]
+struct CorpusLocation {
+    /// Manifest of pinned repositories
+    #[arg(long, default_value = yamllint_rs::corpus::MANIFEST)]
+    manifest: PathBuf,
+
+    /// Directory the repositories are fetched into
+    #[arg(long, default_value = yamllint_rs::corpus::CORPUS_DIR)]
+    dir: PathBuf,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the fully resolved configuration as YAML
+    Dump {
+        /// File or directory whose config is resolved (defaults to the working directory)
+        path: Option<String>,
+
+        /// Configuration file path; defaults to `$YAMLLINT_CONFIG_FILE`, then
+        /// the nearest `.yamllint`
+        #[arg(short, long, short_alias = 'C')]
+        config: Option<String>,
+
+        /// Configuration given inline as a YAML document
+        #[arg(short = 'd', long, value_name = "YAML", conflicts_with = "config")]
+        config_data: Option<String>,
+
+        #[command(flatten)]
+        overrides: Box<ConfigOverrides>,
+    },
+    /// Print a config matching the style of existing YAML files
+    Infer {
+        /// Files or directories to survey
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+    },
+    /// Write a commented starter config listing every rule with its options
+    Init {
+        /// Rules and levels to start from
+        #[arg(long, value_enum, default_value = "default")]
+        preset: InitPreset,
+
+        /// Replace the file if it already exists
+        #[arg(long)]
+        force: bool,
+
+        /// File to write, or `-` for stdout
+        #[arg(default_value = ".yamllint")]
+        path: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum InitPreset {
+    /// The rules and options used without a config file
+    Default,
+    /// Warnings only; no comment, document-start or truthy rules
+    Relaxed,
+    /// Errors only, plus float, octal and anchor checks
+    Strict,
 }
 
-fn main() -> anyhow::Result<()> {
+impl From<InitPreset> for Preset {
+    fn from(preset: InitPreset) -> Self {
+        match preset {
+            InitPreset::Default => Preset::Default,
+            InitPreset::Relaxed => Preset::Relaxed,
+            InitPreset::Strict => Preset::Strict,
+        }
+    }
+}
+
+/// Lowest severity that makes the exit code non-zero
+#[derive(Clone, Copy, ValueEnum)]
+enum FailOn {
+    Info,
+    Warning,
+    Error,
+    Never,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortBy {
+    /// Each file's issues by line and column
+    File,
+    /// Each file's issues from errors to infos, then by line
+    Severity,
+}
+
+impl From<SortBy> for SortOrder {
+    fn from(sort_by: SortBy) -> Self {
+        match sort_by {
+            SortBy::File => SortOrder::File,
+            SortBy::Severity => SortOrder::Severity,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GroupBy {
+    /// Top-level directory of each file
+    Dir,
+    /// Owners from .github/CODEOWNERS, CODEOWNERS or docs/CODEOWNERS
+    Owner,
+}
+
+impl From<GroupBy> for Grouping {
+    fn from(group_by: GroupBy) -> Self {
+        match group_by {
+            GroupBy::Dir => Grouping::Directory,
+            GroupBy::Owner => Grouping::Owner,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RulesFormat {
+    Table,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SchemaName {
+    /// The `--reporter json` report
+    Report,
+}
+
+/// Set to a non-empty value other than `0` or `false` for `--require-config`
+const REQUIRE_CONFIG_ENV: &str = "YAMLLINT_RS_REQUIRE_CONFIG";
+
+/// No errors, and no warnings in strict mode
+const EXIT_OK: i32 = 0;
+/// At least one error-level issue
+const EXIT_ERRORS: i32 = 1;
+/// Warnings (or infos) at or above the `--fail-on` threshold, but no errors
+const EXIT_STRICT_WARNINGS: i32 = 2;
+/// At least one file could not be read; the others were linted, and this
+/// takes precedence over the codes for their issues
+const EXIT_UNREADABLE_FILES: i32 = 3;
+/// The linter itself failed (bad config, unreadable file list, ...)
+const EXIT_FAILURE: i32 = 255;
+
+fn main() {
     let cli = Cli::parse();
+    match run(&cli) {
+        Ok(code) => process::exit(code),
+        Err(error) => {
+            eprintln!("Error: {:?}", error);
+            process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+impl FailOn {
+    /// The severity threshold, or `None` for `never`
+    fn threshold(self) -> Option<Severity> {
+        match self {
+            FailOn::Info => Some(Severity::Info),
+            FailOn::Warning => Some(Severity::Warning),
+            FailOn::Error => Some(Severity::Error),
+            FailOn::Never => None,
+        }
+    }
+}
 
-    if cli.files.is_empty() {
+fn exit_code(counts: &IssueCounts, fail_on: FailOn) -> i32 {
+    if counts.unreadable_files > 0 {
+        return EXIT_UNREADABLE_FILES;
+    }
+    let Some(threshold) = fail_on.threshold() else {
+        return EXIT_OK;
+    };
+    let fails = |severity: Severity, count: usize| count > 0 && severity.is_at_least(threshold);
+    if fails(Severity::Error, counts.errors + counts.invalid_files) {
+        EXIT_ERRORS
+    } else if fails(Severity::Warning, counts.warnings) || fails(Severity::Info, counts.infos) {
+        EXIT_STRICT_WARNINGS
+    } else {
+        EXIT_OK
+    }
+}
+
+fn run(cli: &Cli) -> anyhow::Result<i32> {
+    yamllint_rs::remote_config::set_offline(cli.offline);
+    if let Some(command) = &cli.command {
+        return run_command(command);
+    }
+    validate_args(cli)?;
+
+    // Diff-aware runs lint changed files under the working directory by default
+    let diff_mode = cli.diff || cli.changed_since.is_some();
+    let default_files = [".".to_string()];
+    let listed_files;
+    let files = match (cli.files.as_slice(), &cli.files_from) {
+        (files, Some(list)) => {
+            listed_files = [files, &read_file_list(list, cli.null_separated)?].concat();
+            if listed_files.is_empty() {
+                // An empty list, e.g. no changed files, has nothing to lint
+                return Ok(EXIT_OK);
+            }
+            &listed_files[..]
+        }
+        ([], None) if diff_mode => &default_files[..],
+        (files, None) => files,
+    };
+
+    if files.is_empty() {
         println!("Hello from yamllint-rs! 🦀");
         println!("Usage: yamllint-rs <file1> [file2] ...");
         println!("       yamllint-rs <directory>");
-        return Ok(());
+        return Ok(EXIT_OK);
     }
 
-    let options = ProcessingOptions {
+    let mut options = ProcessingOptions {
         recursive: cli.recursive,
         verbose: cli.verbose,
         output_format: yamllint_rs::detect_output_format(&cli.format),
         show_progress: !cli.no_progress,
+        redact_values: cli.redact_values,
+        no_warnings: cli.no_warnings,
+        jobs: cli.jobs.map(usize::from),
+        changed_lines: None,
+        cache: cli
+            .cache
+            .then(|| Arc::new(LintCache::load(Path::new(CACHE_DIR)))),
+        no_ignore: cli.no_ignore,
+        no_gitignore: cli.no_gitignore,
+        hidden: cli.hidden,
+        follow_symlinks: cli.follow_symlinks,
+        detect_yaml: cli.detect_yaml,
+        sort_by: Some(cli.sort_by.into()),
+        rule_timings: cli.profile_rules.then(|| Arc::new(RuleTimings::new())),
+        issue_limit: (cli.max_issues.is_some() || cli.max_issues_per_file.is_some()).then(|| {
+            Arc::new(IssueLimit::new(
+                cli.max_issues.map(|max| max as usize),
+                cli.max_issues_per_file.map(|max| max as usize),
+            ))
+        }),
     };
 
-    let config_path = cli.config.as_deref().or(cli.config_upper.as_deref());
-    let processor = if let Some(config_path) = config_path {
-        if cli.verbose {
-            println!("Loading config from: {}", config_path);
+    if diff_mode {
+        let changed_lines = ChangedLines::from_git(cli.changed_since.as_deref())?;
+        options.changed_lines = Some(Arc::new(changed_lines));
+    }
+
+    let mut reporter_specs = cli
+        .reporters
+        .iter()
+        .map(|spec| ReporterSpec::parse(spec))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if let Some(group_by) = cli.group_by {
+        if reporter_specs.iter().any(|spec| spec.output.is_none()) {
+            anyhow::bail!(
+                "--group-by prints to stdout; give other reporters a file, e.g. json:report.json"
+            );
         }
-        let config = load_config(config_path)?;
-        if cli.fix {
-            FileProcessor::with_config_and_fix_mode(options.clone(), config)
-        } else {
-            FileProcessor::with_config(options.clone(), config)
+        reporter_specs.push(ReporterSpec {
+            kind: ReporterKind::Rollup(group_by.into()),
+            output: None,
+        });
+    }
+
+    // An explicit, inline or `YAMLLINT_CONFIG_FILE` config applies to every
+    // path; otherwise each path uses the nearest `.yamllint` above it (falling
+    // back to the working directory's), so roots with their own config are
+    // linted with it while sharing one report and exit code. Discovered
+    // configs refine the ones above them, and so do `.yamllint` files found
+    // while walking directories.
+    let explicit_config = match (&cli.config, &cli.config_data) {
+        (Some(config_path), _) => Some(PathBuf::from(config_path)),
+        (None, Some(_)) => None,
+        (None, None) => config_file_from_env(),
+    };
+    let discovered = explicit_config.is_none() && cli.config_data.is_none();
+    let require_config = cli.require_config
+        || std::env::var(REQUIRE_CONFIG_ENV)
+            .is_ok_and(|value| !matches!(value.as_str(), "" | "0" | "false"));
+    let mut groups: Vec<(Option<PathBuf>, Vec<&String>)> = Vec::new();
+    for path_str in files {
+        let config_path = match &explicit_config {
+            Some(config_path) => Some(config_path.clone()),
+            None if cli.config_data.is_some() => None,
+            None => {
+                let lookup_path = match (path_str.as_str(), &cli.stdin_filename) {
+                    ("-", Some(stdin_filename)) => stdin_filename,
+                    _ => path_str,
+                };
+                let project_config =
+                    discover_config_file_for_path(lookup_path).or_else(discover_config_file);
+                if project_config.is_none() && require_config {
+                    anyhow::bail!(
+                        "No config file found for {} and a config is required: add a .yamllint, \
+                         or pass --config or -d",
+                        lookup_path
+                    );
+                }
+                project_config.or_else(user_config_file)
+            }
+        };
+        match groups.iter_mut().find(|(path, _)| *path == config_path) {
+            Some((_, paths)) => paths.push(path_str),
+            None => groups.push((config_path, vec![path_str])),
         }
-    } else if let Some(config_path) = discover_config_file() {
-        if cli.verbose {
-            println!("Found config file: {}", config_path.display());
+    }
+
+    if cli.list_files {
+        for (config_path, paths) in &groups {
+            let processor =
+                build_processor(cli, options.clone(), config_path.as_deref(), discovered)?;
+            for path in paths {
+                let path = match (path.as_str(), &cli.stdin_filename) {
+                    ("-", Some(stdin_filename)) => stdin_filename.as_str(),
+                    ("-", None) => "stdin",
+                    (path, _) => path,
+                };
+                for file in processor.list_files(path)? {
+                    println!("{}", file.display());
+                }
+            }
         }
-        let config = load_config(config_path)?;
-        if cli.fix {
-            FileProcessor::with_config_and_fix_mode(options.clone(), config)
-        } else {
-            FileProcessor::with_config(options.clone(), config)
+        return Ok(EXIT_OK);
+    }
+
+    let mut processors: Vec<(FileProcessor, Vec<&String>)> = Vec::new();
+    for (config_path, paths) in groups {
+        let processor = build_processor(cli, options.clone(), config_path.as_deref(), discovered)?;
+        let processor = match processors.first() {
+            Some((first, _)) => processor.with_shared_reporter(first),
+            None => processor.with_reporters(&reporter_specs)?,
+        };
+        processors.push((processor, paths));
+    }
+
+    let mut counts = IssueCounts::default();
+    for (processor, paths) in &processors {
+        counts.merge(lint_paths(cli, processor, paths)?);
+    }
+
+    processors[0].0.finish_reports()?;
+    if let Some(timings) = &options.rule_timings {
+        eprint!("{}", timings.table());
+    }
+    if let Some(cache) = &options.cache {
+        cache.save()?;
+    }
+
+    if counts.invalid_files > 0 {
+        eprintln!(
+            "{} file(s) could not be linted: binary or not UTF-8 (see invalid-encoding)",
+            counts.invalid_files
+        );
+    }
+    if let Some(limit) = &options.issue_limit {
+        counts.merge(limit.dropped());
+        if let Some(notice) = limit.truncation_notice() {
+            eprintln!("{}", notice);
         }
+    }
+    if counts.unreadable_files > 0 {
+        eprintln!(
+            "{} file(s) could not be read (see file-error)",
+            counts.unreadable_files
+        );
+    }
+
+    let fail_on = match cli.fail_on {
+        Some(fail_on) => fail_on,
+        None if cli.strict => FailOn::Warning,
+        None => FailOn::Error,
+    };
+    Ok(exit_code(&counts, fail_on))
+}
+
+fn run_command(command: &Command) -> anyhow::Result<i32> {
+    match command {
+        Command::Rules { format } => {
+            let config = match config_file_from_env() {
+                Some(config_path) => Some(load_config(config_path)?),
+                None => fallback_config_file()
+                    .map(load_config_hierarchy)
+                    .transpose()?,
+            };
+            match format {
+                RulesFormat::Table => print_rules(config.as_ref()),
+                RulesFormat::Json => print_rules_json(config.as_ref())?,
+            }
+        }
+        Command::Schema {
+            name: SchemaName::Report,
+        } => print!("{}", yamllint_rs::reporter::REPORT_SCHEMA),
+        Command::Config {
+            command:
+                ConfigCommand::Dump {
+                    path,
+                    config,
+                    config_data,
+                    overrides,
+                },
+        } => {
+            overrides.validate()?;
+            let (config_path, discovered) = match (config, config_data) {
+                (Some(config), _) => (Some(PathBuf::from(config)), false),
+                (None, Some(_)) => (None, false),
+                (None, None) => match config_file_from_env() {
+                    Some(config_path) => (Some(config_path), false),
+                    None => (
+                        path.as_ref()
+                            .and_then(discover_config_file_for_path)
+                            .or_else(fallback_config_file),
+                        true,
+                    ),
+                },
+            };
+            let config = resolve_config(
+                config_data.as_deref(),
+                config_path.as_deref(),
+                discovered,
+                overrides,
+            )?;
+            match (&config_path, config_data) {
+                (Some(config_path), _) => println!("# Loaded from {}", config_path.display()),
+                (None, Some(_)) => println!("# Loaded from inline config (-d)"),
+                (None, None) => println!("# No config file found; built-in defaults"),
+            }
+            print!("{}", dump_config(&config)?);
+        }
+        Command::Config {
+            command: ConfigCommand::Infer { paths },
+        } => {
+            let processor = FileProcessor::with_config(
+                ProcessingOptions {
+                    recursive: true,
+                    show_progress: false,
+                    ..ProcessingOptions::default()
+                },
+                Config::default(),
+            );
+            let mut survey = StyleSurvey::new();
+            for path in paths {
+                for file in processor.list_files(path)? {
+                    match std::fs::read_to_string(&file) {
+                        Ok(content) => survey.add_file(&content),
+                        Err(error) => eprintln!("Warning: {}: {}; skipped", file.display(), error),
+                    }
+                }
+            }
+            if survey.files() == 0 {
+                anyhow::bail!("No YAML files found to infer a config from");
+            }
+            print!("{}", survey.to_config());
+        }
+        Command::Config {
+            command:
+                ConfigCommand::Init {
+                    preset,
+                    force,
+                    path,
+                },
+        } => {
+            let starter = starter_config((*preset).into());
+            if path.as_os_str() == "-" {
+                print!("{}", starter);
+                return Ok(EXIT_OK);
+            }
+            if path.exists() && !force {
+                anyhow::bail!(
+                    "{} already exists; use --force to replace it",
+                    path.display()
+                );
+            }
+            std::fs::write(path, starter)
+                .with_context(|| format!("Cannot write {}", path.display()))?;
+            println!(
+                "Wrote {} ({} preset)",
+                path.display(),
+                Preset::from(*preset).as_str()
+            );
+        }
+        #[cfg(feature = "corpus")]
+        Command::Corpus { command } => return run_corpus(command),
+    }
+    Ok(EXIT_OK)
+}
+
+#[cfg(feature = "corpus")]
+fn run_corpus(command: &CorpusCommand) -> anyhow::Result<i32> {
+    use yamllint_rs::corpus;
+
+    match command {
+        CorpusCommand::Fetch { location } => {
+            corpus::fetch(&corpus::Manifest::load(&location.manifest)?, &location.dir)?;
+            Ok(EXIT_OK)
+        }
+        CorpusCommand::Run {
+            location,
+            config,
+            parity,
+        } => {
+            let manifest = corpus::Manifest::load(&location.manifest)?;
+            let reports = corpus::run(&manifest, &location.dir, config.as_deref(), *parity)?;
+            let mut exact = true;
+            for report in &reports {
+                println!(
+                    "{}: {} files, {} issues in {:.2?}",
+                    report.name, report.files, report.issues, report.elapsed
+                );
+                if let Some(parity) = &report.parity {
+                    println!(
+                        "  parity: {} matching, {} only in yamllint, {} only in yamllint-rs",
+                        parity.matched,
+                        parity.only_reference.len(),
+                        parity.only_ours.len()
+                    );
+                    for (rule, count) in parity.mismatches_by_rule().iter().take(5) {
+                        println!("    {}: {}", rule, count);
+                    }
+                    exact &= parity.is_exact();
+                }
+            }
+            Ok(if exact { EXIT_OK } else { EXIT_ERRORS })
+        }
+    }
+}
+
+/// `yes`, `no`, or `off` when the rule can fix but the config excludes it
+fn fix_support(can_fix: bool, fix_excluded: bool) -> &'static str {
+    match (can_fix, fix_excluded) {
+        (true, true) => "off",
+        (true, false) => "yes",
+        (false, _) => "no",
+    }
+}
+
+fn is_fix_excluded(config: Option<&Config>, rule_id: &str) -> bool {
+    config.is_some_and(|config| config.is_fix_excluded(rule_id))
+}
+
+fn print_rules(config: Option<&Config>) {
+    let registry = RuleRegistry::new();
+    let mut rule_ids = registry.get_rule_ids();
+    rule_ids.sort();
+    let id_width = rule_ids.iter().map(String::len).max().unwrap_or(0);
+
+    println!(
+        "{:<id_width$}  {:<8}  {:<3}  {:<7}  DESCRIPTION",
+        "RULE", "SEVERITY", "FIX", "DEFAULT"
+    );
+    for metadata in rule_ids
+        .iter()
+        .filter_map(|id| registry.get_rule_metadata(id))
+    {
+        println!(
+            "{:<id_width$}  {:<8}  {:<3}  {:<7}  {}",
+            metadata.id,
+            metadata.default_severity.to_string(),
+            fix_support(metadata.can_fix, is_fix_excluded(config, metadata.id)),
+            if metadata.enabled_by_default {
+                "on"
+            } else {
+                "off"
+            },
+            metadata.description
+        );
+    }
+}
+
+fn print_rules_json(config: Option<&Config>) -> anyhow::Result<()> {
+    let registry = RuleRegistry::new();
+    let factory = RuleFactory::new();
+    let mut rule_ids = registry.get_rule_ids();
+    rule_ids.sort();
+
+    let rules: Vec<serde_json::Value> = rule_ids
+        .iter()
+        .filter_map(|id| registry.get_rule_metadata(id))
+        .map(|metadata| {
+            serde_json::json!({
+                "id": metadata.id,
+                "name": metadata.name,
+                "description": metadata.description,
+                "default_severity": metadata.default_severity.to_string(),
+                "can_fix": metadata.can_fix,
+                "fix_excluded": is_fix_excluded(config, metadata.id),
+                "enabled_by_default": metadata.enabled_by_default,
+                "options": factory.default_config_json(metadata.id),
+            })
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&rules)?);
+    Ok(())
+}
+
+fn parse_format(format: &str) -> Result<String, String> {
+    match format {
+        "standard" | "colored" | "auto" => Ok(format.to_string()),
+        "json" | "sarif" => Err(format!(
+            "'{}' is a reporter, not an output format; use --reporter {}",
+            format, format
+        )),
+        _ => Err("expected one of: standard, colored, auto".to_string()),
+    }
+}
+
+/// Paths listed in `list` (a file, or `-` for stdin), separated by newlines
+/// or, with `null_separated`, NUL bytes. Empty entries are skipped.
+fn read_file_list(list: &str, null_separated: bool) -> anyhow::Result<Vec<String>> {
+    let bytes = if list == "-" {
+        let mut bytes = Vec::new();
+        std::io::stdin().read_to_end(&mut bytes)?;
+        bytes
     } else {
-        if cli.fix {
-            FileProcessor::with_fix_mode(options.clone())
-        } else {
-            FileProcessor::with_default_rules(options.clone())
+        std::fs::read(list).with_context(|| format!("Cannot read file list {}", list))?
+    };
+    Ok(parse_file_list(&bytes, null_separated))
+}
+
+fn parse_file_list(bytes: &[u8], null_separated: bool) -> Vec<String> {
+    let separator = if null_separated { b'\0' } else { b'\n' };
+    bytes
+        .split(|&byte| byte == separator)
+        .map(|entry| String::from_utf8_lossy(entry).into_owned())
+        .map(|entry| {
+            if null_separated {
+                entry
+            } else {
+                entry.trim_end_matches('\r').to_string()
+            }
+        })
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// Reject flag combinations clap cannot express declaratively
+fn validate_args(cli: &Cli) -> anyhow::Result<()> {
+    let stdin_count = cli.files.iter().filter(|path| *path == "-").count();
+    if stdin_count > 1 {
+        anyhow::bail!("stdin (`-`) can only be given once");
+    }
+    if stdin_count == 1 && cli.files_from.as_deref() == Some("-") {
+        anyhow::bail!("stdin cannot be both linted (`-`) and read as --files-from");
+    }
+    if stdin_count == 1 && cli.fix {
+        anyhow::bail!("--fix cannot be used when linting stdin");
+    }
+    if stdin_count == 0 && cli.stdin_filename.is_some() {
+        anyhow::bail!("--stdin-filename requires `-` among the files to lint");
+    }
+    cli.overrides.validate()
+}
+
+impl ConfigOverrides {
+    /// The environment and these flags as layers over a loaded config
+    fn layers(&self) -> ConfigLayers {
+        ConfigLayers {
+            profile: self.profile.clone(),
+            set_options: self.set_options.clone(),
+            enable_only: self.enable_only.clone(),
+            disable: self.disable.clone(),
+            ignore_patterns: self.ignore_patterns.clone(),
+            extensions: self.extensions.clone(),
+            ..ConfigLayers::from_env()
+        }
+    }
+
+    /// Reject rule names in `--enable-only`/`--disable` that do not exist
+    fn validate(&self) -> anyhow::Result<()> {
+        let known_rules = RuleRegistry::new().get_rule_ids();
+        if let Some(unknown) = self
+            .enable_only
+            .iter()
+            .chain(&self.disable)
+            .find(|rule| !known_rules.contains(rule))
+        {
+            anyhow::bail!("Unknown rule: {}", unknown);
         }
+        Ok(())
+    }
+}
+
+/// Config for paths with no `.yamllint` above them: the working directory's,
+/// or else the user's personal one
+fn fallback_config_file() -> Option<PathBuf> {
+    discover_config_file().or_else(user_config_file)
+}
+
+/// Build the processor for paths sharing `config_path`. A `discovered`
+/// config also hands files under nested `.yamllint` files their own config.
+fn build_processor(
+    cli: &Cli,
+    options: ProcessingOptions,
+    config_path: Option<&Path>,
+    discovered: bool,
+) -> anyhow::Result<FileProcessor> {
+    if let Some(config_path) = config_path.filter(|_| cli.verbose) {
+        println!("Loading config from: {}", config_path.display());
+    }
+    let config = resolve_config(
+        cli.config_data.as_deref(),
+        config_path,
+        discovered,
+        &cli.overrides,
+    )?;
+
+    let processor = if cli.fix {
+        FileProcessor::with_config_and_fix_mode(options, config)
+    } else {
+        FileProcessor::with_config(options, config)
+    };
+    if !discovered {
+        return Ok(processor);
+    }
+    let overrides = cli.overrides.clone();
+    Ok(processor.with_nested_configs(
+        config_path,
+        Box::new(move |path| resolve_config(None, Some(path), true, &overrides)),
+    ))
+}
+
+/// Load the inline config, the config file (refining the `.yamllint` files
+/// above it when it was `discovered`) or the defaults, then layer the
+/// environment and command-line overrides over it (see [`ConfigLayers`])
+fn resolve_config(
+    config_data: Option<&str>,
+    config_path: Option<&Path>,
+    discovered: bool,
+    overrides: &ConfigOverrides,
+) -> anyhow::Result<Config> {
+    let mut config = if let Some(config_data) = config_data {
+        load_config_from_str(config_data).context("Invalid inline config (-d)")?
+    } else if let Some(config_path) = config_path.filter(|_| discovered) {
+        load_config_hierarchy(config_path)?
+    } else if let Some(config_path) = config_path {
+        load_config(config_path)?
+    } else {
+        Config::default()
     };
+    overrides.layers().apply(&mut config)?;
 
+    let problems = config.invalid_settings();
+    if !problems.is_empty() {
+        let source = match (config_data, config_path) {
+            (Some(_), _) => "inline config (-d)".to_string(),
+            (None, Some(config_path)) => config_path.display().to_string(),
+            (None, None) => "config".to_string(),
+        };
+        if overrides.strict_config {
+            anyhow::bail!("Invalid {}: {}", source, problems.join("; "));
+        }
+        for problem in problems {
+            eprintln!("Warning: {}: {}", source, problem);
+        }
+    }
+    Ok(config)
+}
+
+fn lint_paths(
+    cli: &Cli,
+    processor: &FileProcessor,
+    paths: &[&String],
+) -> anyhow::Result<IssueCounts> {
     let mut directories = Vec::new();
     let mut files = Vec::new();
+    let mut counts = IssueCounts::default();
 
-    for path_str in &cli.files {
-        let path = Path::new(path_str);
+    for path_str in paths {
+        if path_str.as_str() == "-" {
+            let mut content = Vec::new();
+            std::io::stdin().read_to_end(&mut content)?;
+            let stdin_filename = cli.stdin_filename.as_deref().unwrap_or("stdin");
+            let result = processor.process_bytes(content, stdin_filename)?;
+            counts.add_result(&result);
+            continue;
+        }
+
+        let path = Path::new(path_str.as_str());
         if cli.recursive || path.is_dir() {
             directories.push(path_str);
         } else {
@@ -99,12 +1049,8 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
-    let mut total_issues = 0;
-
-    if !directories.is_empty() {
-        for path in directories {
-            total_issues += processor.process_directory(path)?;
-        }
+    for path in directories {
+        counts.merge(processor.process_directory_counts(path.as_str())?);
     }
 
     if !files.is_empty() {
@@ -112,22 +1058,20 @@ fn main() -> anyhow::Result<()> {
             if cli.verbose {
                 println!("Processing {} files in parallel...", files.len());
             }
-            let results: Result<Vec<_>, _> = files
-                .par_iter()
-                .map(|file| processor.process_file(file))
-                .collect();
+            let results: Result<Vec<_>, _> = processor.install(|| {
+                files
+                    .par_iter()
+                    .map(|file| processor.process_file(file.as_str()))
+                    .collect()
+            })?;
             for result in results? {
-                total_issues += result.issues.len();
+                counts.add_result(&result);
             }
         } else {
-            let result = processor.process_file(&files[0])?;
-            total_issues += result.issues.len();
+            let result = processor.process_file(files[0].as_str())?;
+            counts.add_result(&result);
         }
     }
 
-    if total_issues > 0 {
-        process::exit(1);
-    }
-
-    Ok(())
+    Ok(counts)
 }
@@ -0,0 +1,127 @@
+//! Process-wide cache of compiled regular expressions.
+//!
+//! Rules and config options that take user patterns (truthy fixes, secrets
+//! key and value patterns, `severity-by-path` globs) compile them through
+//! this cache, so a pattern is compiled once per process no matter how many
+//! rule instances, files or runs use it. Compiled `Regex` values share their
+//! internals, so handing out clones is cheap.
+//!
+//! Rule options holding patterns deserialize through [`deserialize_regex`]
+//! and its variants, so a pattern that does not compile is reported by the
+//! config check as an invalid option instead of silently never matching.
+
+use lazy_static::lazy_static;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Flags a pattern is compiled with, part of the cache key
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PatternFlags {
+    pub case_insensitive: bool,
+}
+
+lazy_static! {
+    static ref CACHE: RwLock<HashMap<(String, PatternFlags), Regex>> = RwLock::new(HashMap::new());
+}
+
+/// Compiled `pattern`, from the cache when it was compiled before
+pub fn regex(pattern: &str) -> Result<Regex, regex::Error> {
+    regex_with_flags(pattern, PatternFlags::default())
+}
+
+/// Compiled `pattern` with the given flags, from the cache when it was
+/// compiled before. Invalid patterns are not cached.
+pub fn regex_with_flags(pattern: &str, flags: PatternFlags) -> Result<Regex, regex::Error> {
+    let key = (pattern.to_string(), flags);
+    if let Some(regex) = CACHE.read().unwrap().get(&key) {
+        return Ok(regex.clone());
+    }
+
+    let regex = RegexBuilder::new(pattern)
+        .case_insensitive(flags.case_insensitive)
+        .build()?;
+    CACHE.write().unwrap().insert(key, regex.clone());
+    Ok(regex)
+}
+
+/// Deserialize a pattern, failing when it does not compile
+pub fn deserialize_regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+    regex(&pattern).map_err(serde::de::Error::custom)?;
+    Ok(pattern)
+}
+
+/// Like [`deserialize_regex`], for an optional pattern
+pub fn deserialize_optional_regex<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    deserialize_regex(deserializer).map(Some)
+}
+
+/// Deserialize a list of patterns, failing when one does not compile
+pub fn deserialize_regexes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<String>, D::Error> {
+    let patterns = Vec::<String>::deserialize(deserializer)?;
+    for pattern in &patterns {
+        regex(pattern).map_err(serde::de::Error::custom)?;
+    }
+    Ok(patterns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regex_is_compiled_once_per_pattern_and_flags() {
+        let pattern = r"^pattern-cache-test-\d+$";
+        let first = regex(pattern).unwrap();
+        let key = (pattern.to_string(), PatternFlags::default());
+        assert!(CACHE.read().unwrap().contains_key(&key));
+        let second = regex(pattern).unwrap();
+        assert_eq!(first.as_str(), second.as_str());
+
+        let insensitive = regex_with_flags(
+            pattern,
+            PatternFlags {
+                case_insensitive: true,
+            },
+        )
+        .unwrap();
+        assert!(insensitive.is_match("PATTERN-CACHE-TEST-1"));
+        assert!(!first.is_match("PATTERN-CACHE-TEST-1"));
+
+        assert!(regex("(unclosed").is_err());
+        let key = ("(unclosed".to_string(), PatternFlags::default());
+        assert!(!CACHE.read().unwrap().contains_key(&key));
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Options {
+        #[serde(deserialize_with = "deserialize_regex")]
+        pattern: String,
+        #[serde(default, deserialize_with = "deserialize_regexes")]
+        patterns: Vec<String>,
+    }
+
+    #[test]
+    fn test_deserialize_rejects_patterns_that_do_not_compile() {
+        let options: Options =
+            serde_json::from_value(serde_json::json!({ "pattern": "^a+$", "patterns": ["b"] }))
+                .unwrap();
+        assert_eq!(options.pattern, "^a+$");
+        assert_eq!(options.patterns, vec!["b"]);
+
+        let error = serde_json::from_value::<Options>(serde_json::json!({ "pattern": "(" }))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("regex parse error"), "{}", error);
+        assert!(serde_json::from_value::<Options>(
+            serde_json::json!({ "pattern": "a", "patterns": ["b", "["] })
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,296 @@
+//! Rich diagnostics rendered with miette (`pretty-diagnostics` feature).
+//!
+//! Each issue is shown with a window of the source around it, a label naming
+//! the rule, and the rule's registry description as help text. Files whose
+//! source cannot be read back (stdin, deleted files) fall back to the
+//! standard one-line format, as does every file under `--redact-values`,
+//! since the source window would show the values the messages hide.
+
+use crate::formatter::{Formatter, StandardFormatter};
+use crate::reporter::{Reporter, ReporterKind};
+use crate::rules::registry::RuleRegistry;
+use crate::{FixOutcome, LintIssue, LintResult, Severity};
+use anyhow::Result;
+use miette::{
+    Diagnostic, GraphicalReportHandler, GraphicalTheme, LabeledSpan, NamedSource, SourceCode,
+};
+use std::fmt;
+use std::io::Write;
+
+/// Reporter rendering miette-style source windows for every issue
+pub struct PrettyReporter {
+    sink: Box<dyn Write + Send>,
+    handler: GraphicalReportHandler,
+    registry: RuleRegistry,
+    fix_outcomes: Vec<FixOutcome>,
+    redact_values: bool,
+}
+
+impl PrettyReporter {
+    pub fn new(sink: Box<dyn Write + Send>) -> Self {
+        Self {
+            sink,
+            handler: GraphicalReportHandler::new_themed(GraphicalTheme::default()),
+            registry: RuleRegistry::new(),
+            fix_outcomes: Vec::new(),
+            redact_values: false,
+        }
+    }
+
+    /// Never show source windows, for `--redact-values`
+    pub fn with_redact_values(mut self, redact_values: bool) -> Self {
+        self.redact_values = redact_values;
+        self
+    }
+
+    /// Render every issue of a file whose content is `source`
+    pub fn render(&self, result: &LintResult, source: String) -> Result<String> {
+        let line_starts = line_starts(&source);
+        let source = NamedSource::new(&result.file, source);
+        let mut output = String::new();
+
+        for (issue, rule_name) in &result.issues {
+            let diagnostic = IssueDiagnostic {
+                issue,
+                rule_name,
+                help: self
+                    .registry
+                    .get_rule_metadata(rule_name)
+                    .map(|metadata| metadata.description),
+                source: &source,
+                offset: issue_offset(source.inner(), &line_starts, issue),
+            };
+            self.handler.render_report(&mut output, &diagnostic)?;
+            output.push('\n');
+        }
+        Ok(output)
+    }
+}
+
+impl Reporter for PrettyReporter {
+    fn report(&mut self, result: &LintResult) -> Result<()> {
+        if result.issues.is_empty() {
+            return Ok(());
+        }
+
+        let source = if self.redact_values {
+            None
+        } else {
+            std::fs::read_to_string(&result.file).ok()
+        };
+        let output = match source {
+            Some(source) => self.render(result, source)?,
+            None => {
+                let formatter = StandardFormatter;
+                let mut output = formatter.format_filename(&result.file);
+                output.push('\n');
+                for (issue, rule_name) in &result.issues {
+                    output.push_str(&formatter.format_issue(issue, rule_name));
+                }
+                output
+            }
+        };
+
+        self.sink.write_all(output.as_bytes())?;
+        Ok(())
+    }
+
+    fn report_fix(&mut self, outcome: &FixOutcome) -> Result<()> {
+        self.fix_outcomes.push(outcome.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.fix_outcomes.sort_by(|a, b| a.file.cmp(&b.file));
+        for outcome in self.fix_outcomes.drain(..) {
+            let line = StandardFormatter.format_fix_outcome(&outcome);
+            self.sink.write_all(line.as_bytes())?;
+        }
+        self.sink.flush()?;
+        Ok(())
+    }
+
+    fn kind(&self) -> ReporterKind {
+        ReporterKind::Pretty
+    }
+}
+
+/// Byte offset where each line starts
+fn line_starts(source: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+        .collect()
+}
+
+/// Byte offset of an issue's 1-based line and column, clamped to the line
+fn issue_offset(source: &str, line_starts: &[usize], issue: &LintIssue) -> usize {
+    let Some(&start) = line_starts.get(issue.line.saturating_sub(1)) else {
+        return source.len();
+    };
+    let line = source[start..].split('\n').next().unwrap_or("");
+    let column = line
+        .char_indices()
+        .nth(issue.column.saturating_sub(1))
+        .map(|(index, _)| index)
+        .unwrap_or(line.len());
+    start + column
+}
+
+/// A single lint issue presented as a miette diagnostic
+struct IssueDiagnostic<'a> {
+    issue: &'a LintIssue,
+    rule_name: &'a str,
+    help: Option<&'static str>,
+    source: &'a NamedSource<String>,
+    offset: usize,
+}
+
+impl fmt::Debug for IssueDiagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IssueDiagnostic")
+            .field("issue", self.issue)
+            .field("rule_name", &self.rule_name)
+            .finish()
+    }
+}
+
+impl fmt::Display for IssueDiagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.issue.message)
+    }
+}
+
+impl std::error::Error for IssueDiagnostic<'_> {}
+
+impl Diagnostic for IssueDiagnostic<'_> {
+    fn code<'b>(&'b self) -> Option<Box<dyn fmt::Display + 'b>> {
+        Some(Box::new(&self.issue.code))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(match self.issue.severity {
+            Severity::Error => miette::Severity::Error,
+            Severity::Warning => miette::Severity::Warning,
+            Severity::Info => miette::Severity::Advice,
+        })
+    }
+
+    fn help<'b>(&'b self) -> Option<Box<dyn fmt::Display + 'b>> {
+        self.help
+            .filter(|help| !help.is_empty())
+            .map(|help| Box::new(help) as Box<dyn fmt::Display>)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(std::iter::once(LabeledSpan::new(
+            Some(self.rule_name.to_string()),
+            self.offset,
+            0,
+        ))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_render_shows_source_window_and_help() {
+        let result = LintResult {
+            file: "test.yaml".to_string(),
+            issues: vec![(
+                LintIssue {
+                    line: 2,
+                    column: 11,
+                    message: "trailing spaces".to_string(),
+                    code: "trailing-spaces/trailing-spaces".to_string(),
+                    severity: Severity::Error,
+                },
+                "trailing-spaces".to_string(),
+            )],
+            scalar_start_lines: HashMap::new(),
+        };
+
+        let reporter = PrettyReporter {
+            sink: Box::new(std::io::sink()),
+            handler: GraphicalReportHandler::new_themed(GraphicalTheme::none()),
+            registry: RuleRegistry::new(),
+            fix_outcomes: Vec::new(),
+            redact_values: false,
+        };
+        let output = reporter
+            .render(&result, "---\nkey: value   \n".to_string())
+            .unwrap();
+
+        assert!(output.contains("trailing-spaces/trailing-spaces"));
+        assert!(output.contains("test.yaml:2:11"));
+        assert!(output.contains("key: value"));
+        assert!(output.contains("`-- trailing-spaces"));
+        assert!(output.contains("help:"));
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_report_with_redact_values_hides_source() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"password: hunter2\n").unwrap();
+        let result = LintResult {
+            file: file.path().to_string_lossy().into_owned(),
+            issues: vec![(
+                LintIssue {
+                    line: 1,
+                    column: 11,
+                    message: "forbidden value '<redacted>'".to_string(),
+                    code: "forbidden-values/forbidden".to_string(),
+                    severity: Severity::Error,
+                },
+                "forbidden-values".to_string(),
+            )],
+            scalar_start_lines: HashMap::new(),
+        };
+
+        let buffer = SharedBuffer::default();
+        let mut reporter = PrettyReporter::new(Box::new(buffer.clone())).with_redact_values(true);
+        reporter.report(&result).unwrap();
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+
+        assert!(output.contains("forbidden value '<redacted>'"));
+        assert!(!output.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_issue_offset_clamps_to_line() {
+        let source = "a: 1\nb: é\n";
+        let starts = line_starts(source);
+        let issue = |line, column| LintIssue {
+            line,
+            column,
+            message: String::new(),
+            code: String::new(),
+            severity: Severity::Error,
+        };
+        assert_eq!(issue_offset(source, &starts, &issue(1, 1)), 0);
+        assert_eq!(issue_offset(source, &starts, &issue(2, 4)), 8);
+        assert_eq!(issue_offset(source, &starts, &issue(2, 99)), 10);
+        assert_eq!(issue_offset(source, &starts, &issue(9, 1)), source.len());
+    }
+}
@@ -0,0 +1,125 @@
+//! Redaction of scalar values in lint output.
+//!
+//! Rules quote scalar values taken from the linted document with single
+//! quotes (`forbidden value 'abc'`), while keys and anchor names use double
+//! quotes. Redaction replaces every single-quoted span so reports keep their
+//! structure without leaking values. A test runs every registered rule over
+//! values they report to hold new rules to this convention.
+
+use crate::LintIssue;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Placeholder substituted for redacted values
+pub const PLACEHOLDER: &str = "<redacted>";
+
+lazy_static! {
+    static ref QUOTED_VALUE: Regex = Regex::new(r"'(?:[^']|'')*'").unwrap();
+}
+
+/// Replace every single-quoted value in a message with the placeholder
+pub fn redact_values(message: &str) -> String {
+    QUOTED_VALUE
+        .replace_all(message, format!("'{}'", PLACEHOLDER).as_str())
+        .into_owned()
+}
+
+/// Redact the messages of a list of issues in place
+pub fn redact_issues(issues: &mut [(LintIssue, String)]) {
+    for (issue, _) in issues.iter_mut() {
+        issue.message = redact_values(&issue.message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_single_quoted_values() {
+        assert_eq!(
+            redact_values("forbidden value 'hunter2' for key \"password\""),
+            "forbidden value '<redacted>' for key \"password\""
+        );
+        assert_eq!(
+            redact_values("values 'a' and 'it''s' differ"),
+            "values '<redacted>' and '<redacted>' differ"
+        );
+    }
+
+    #[test]
+    fn test_every_rule_message_is_redacted() {
+        use crate::rules::factory::RuleFactory;
+        use crate::rules::float_values::FloatValuesRule;
+        use crate::rules::forbidden_values::ForbiddenValuesRule;
+        use crate::rules::Rule;
+
+        let values = [
+            "0777",
+            "0o644",
+            "1e3",
+            ".nan",
+            "-.inf",
+            "NO",
+            "2023-01-02",
+            "12:30:00",
+            "1:30",
+            "hunter2",
+            "kilo",
+        ];
+        let content = "---\nmode: 0777\nnew: 0o644\nratio: 1e3\nmissing: .nan\nlow: -.inf\ncountry: NO\nreleased: 2023-01-02\nstart: 12:30:00\nduration: 1:30\npassword: hunter2\nlist:\n  - zulu\n  - kilo\n";
+
+        let factory = RuleFactory::new();
+        let mut rules: Vec<Box<dyn Rule>> = factory
+            .registry()
+            .get_rule_ids()
+            .iter()
+            .filter_map(|rule_id| factory.create_rule(rule_id))
+            .collect();
+        // Rules reporting nothing with their defaults
+        rules.push(Box::new(FloatValuesRule::with_config(
+            serde_json::from_value(serde_json::json!({
+                "forbid-nan": true,
+                "forbid-inf": true,
+                "forbid-scientific-notation": true
+            }))
+            .unwrap(),
+        )));
+        rules.push(Box::new(ForbiddenValuesRule::with_config(
+            serde_json::from_value(serde_json::json!({"patterns": [{"pattern": "hunter"}]}))
+                .unwrap(),
+        )));
+
+        let mut issues: Vec<(LintIssue, String)> = rules
+            .iter()
+            .flat_map(|rule| {
+                rule.check(content, "test.yaml")
+                    .into_iter()
+                    .map(|issue| (issue, rule.rule_id().to_string()))
+            })
+            .collect();
+        for value in values {
+            assert!(
+                issues
+                    .iter()
+                    .any(|(issue, _)| issue.message.contains(value)),
+                "{} is not reported",
+                value
+            );
+        }
+
+        redact_issues(&mut issues);
+        let leaking: Vec<_> = issues
+            .iter()
+            .filter(|(issue, _)| values.iter().any(|value| issue.message.contains(value)))
+            .map(|(issue, rule_id)| format!("{}: {}", rule_id, issue.message))
+            .collect();
+        assert_eq!(leaking, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_redact_leaves_other_messages_untouched() {
+        let message = "duplication of key \"name\" in mapping";
+        assert_eq!(redact_values(message), message);
+    }
+}
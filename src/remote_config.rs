@@ -0,0 +1,110 @@
+//! Configs extended from a URL.
+//!
+//! `extends` may name an `http://` or `https://` URL, so an organization can
+//! publish one lint policy for all of its repositories. Configs are
+//! downloaded with `curl` and cached per user. A cached copy younger than
+//! [`MAX_AGE`] is used without downloading the config again, and an older one
+//! is used, with a warning, when the download fails. In offline mode (see
+//! [`set_offline`]) nothing is downloaded and only cached copies are used.
+
+use crate::config_hash::hash_hex;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Age below which a cached config is used without downloading it again
+pub const MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Use only cached copies of remote configs, e.g. for `--offline`
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Whether an `extends` value names a remote config
+pub fn is_url(extends: &str) -> bool {
+    extends.starts_with("https://") || extends.starts_with("http://")
+}
+
+/// Content of the config at `url`, from the cache or downloaded
+pub fn fetch(url: &str) -> Result<String> {
+    let cache = cache_path(url);
+    let cached = || {
+        cache
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+    };
+
+    if OFFLINE.load(Ordering::Relaxed) {
+        return cached()
+            .ok_or_else(|| anyhow::anyhow!("{} is not cached and downloads are off", url));
+    }
+    let age = cache
+        .as_ref()
+        .and_then(|path| path.metadata().ok()?.modified().ok()?.elapsed().ok());
+    if age.is_some_and(|age| age < MAX_AGE) {
+        if let Some(content) = cached() {
+            return Ok(content);
+        }
+    }
+
+    match download(url) {
+        Ok(content) => {
+            if let Some(path) = &cache {
+                // A cache that cannot be written only costs a download next time
+                let _ = path
+                    .parent()
+                    .map(std::fs::create_dir_all)
+                    .transpose()
+                    .and_then(|_| std::fs::write(path, &content));
+            }
+            Ok(content)
+        }
+        Err(error) => match cached() {
+            Some(content) => {
+                eprintln!("Warning: {:#}; using the cached copy", error);
+                Ok(content)
+            }
+            None => Err(error),
+        },
+    }
+}
+
+fn download(url: &str) -> Result<String> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location"])
+        .args(["--max-time", "30"])
+        .arg(url)
+        .output()
+        .context("Failed to run curl")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Cannot download {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8(output.stdout).with_context(|| format!("{} is not UTF-8 text", url))
+}
+
+/// Where the config at `url` is cached: `$XDG_CACHE_HOME/yamllint-rs`, or
+/// `~/.cache/yamllint-rs` when that variable is unset
+fn cache_path(url: &str) -> Option<PathBuf> {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    };
+    let cache_dir = env_dir("XDG_CACHE_HOME")
+        .or_else(|| env_dir("HOME").map(|home| home.join(".cache")))
+        .or_else(|| env_dir("LOCALAPPDATA"))?;
+    Some(
+        cache_dir
+            .join("yamllint-rs")
+            .join("remote-configs")
+            .join(hash_hex(url.as_bytes())),
+    )
+}
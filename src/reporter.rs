@@ -0,0 +1,792 @@
+//! Report sinks that fan lint results out to one or more outputs.
+
+use crate::codeowners::CodeOwners;
+use crate::formatter::{self, Formatter};
+use crate::{FixOutcome, IssueCounts, LintResult, OutputFormat};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Output format of a reporter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReporterKind {
+    Standard,
+    Colored,
+    Json,
+    Sarif,
+    /// Source windows with labels and help, with the `pretty-diagnostics` feature
+    #[cfg(feature = "pretty-diagnostics")]
+    Pretty,
+    /// Issue counts per group of files, with `--group-by`
+    Rollup(Grouping),
+}
+
+impl ReporterKind {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "standard" => Ok(ReporterKind::Standard),
+            "colored" => Ok(ReporterKind::Colored),
+            "auto" => Ok(match crate::detect_output_format("auto") {
+                OutputFormat::Standard => ReporterKind::Standard,
+                OutputFormat::Colored => ReporterKind::Colored,
+            }),
+            "json" => Ok(ReporterKind::Json),
+            "sarif" => Ok(ReporterKind::Sarif),
+            #[cfg(feature = "pretty-diagnostics")]
+            "pretty" => Ok(ReporterKind::Pretty),
+            #[cfg(not(feature = "pretty-diagnostics"))]
+            "pretty" => Err(anyhow::anyhow!(
+                "The pretty reporter requires building with the pretty-diagnostics feature"
+            )),
+            _ => Err(anyhow::anyhow!(
+                "Unknown reporter: {} (expected standard, colored, auto, json or sarif)",
+                name
+            )),
+        }
+    }
+
+    /// Whether this reporter produces a single document for the whole run
+    pub fn is_structured(&self) -> bool {
+        matches!(self, ReporterKind::Json | ReporterKind::Sarif)
+    }
+}
+
+impl From<OutputFormat> for ReporterKind {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::Standard => ReporterKind::Standard,
+            OutputFormat::Colored => ReporterKind::Colored,
+        }
+    }
+}
+
+/// How `--group-by` rolls files up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    /// By the top-level directory of the file's path
+    Directory,
+    /// By the owners `CODEOWNERS` assigns to the file
+    Owner,
+}
+
+/// A reporter requested on the command line, e.g. `colored` or `sarif:out.sarif`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReporterSpec {
+    pub kind: ReporterKind,
+    /// Destination file, stdout when `None`
+    pub output: Option<PathBuf>,
+}
+
+impl ReporterSpec {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (name, output) = match spec.split_once(':') {
+            Some((name, path)) if !path.is_empty() => (name, Some(PathBuf::from(path))),
+            Some((name, _)) => (name, None),
+            None => (spec, None),
+        };
+
+        Ok(Self {
+            kind: ReporterKind::from_name(name)?,
+            output,
+        })
+    }
+}
+
+/// A sink receiving lint results as files are processed
+pub trait Reporter: Send {
+    /// Report the result of a single file
+    fn report(&mut self, result: &LintResult) -> Result<()>;
+
+    /// Record what `--fix` did to a file; human-readable reporters print
+    /// these as a summary when the run finishes
+    fn report_fix(&mut self, _outcome: &FixOutcome) -> Result<()> {
+        Ok(())
+    }
+
+    /// Flush any buffered output once all files have been processed
+    fn finish(&mut self) -> Result<()>;
+
+    fn kind(&self) -> ReporterKind;
+}
+
+fn open_sink(output: &Option<PathBuf>) -> Result<Box<dyn Write + Send>> {
+    match output {
+        Some(path) => Ok(Box::new(BufWriter::new(File::create(path)?))),
+        None => Ok(Box::new(std::io::stdout())),
+    }
+}
+
+/// Line-oriented reporter for the standard and colored formats
+pub struct HumanReporter {
+    kind: ReporterKind,
+    formatter: Box<dyn Formatter>,
+    sink: Box<dyn Write + Send>,
+    fix_outcomes: Vec<FixOutcome>,
+}
+
+impl HumanReporter {
+    pub fn new(kind: ReporterKind, sink: Box<dyn Write + Send>) -> Self {
+        let format = match kind {
+            ReporterKind::Colored => OutputFormat::Colored,
+            _ => OutputFormat::Standard,
+        };
+        Self {
+            kind,
+            formatter: formatter::create_formatter(format),
+            sink,
+            fix_outcomes: Vec::new(),
+        }
+    }
+}
+
+impl Reporter for HumanReporter {
+    fn report(&mut self, result: &LintResult) -> Result<()> {
+        if result.issues.is_empty() {
+            return Ok(());
+        }
+
+        let mut output = String::with_capacity(result.issues.len() * 120);
+        output.push_str(&self.formatter.format_filename(&result.file));
+        output.push('\n');
+        for (issue, rule_name) in &result.issues {
+            output.push_str(&self.formatter.format_issue(issue, rule_name));
+        }
+
+        self.sink.write_all(output.as_bytes())?;
+        Ok(())
+    }
+
+    fn report_fix(&mut self, outcome: &FixOutcome) -> Result<()> {
+        self.fix_outcomes.push(outcome.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.fix_outcomes.sort_by(|a, b| a.file.cmp(&b.file));
+        for outcome in self.fix_outcomes.drain(..) {
+            let line = self.formatter.format_fix_outcome(&outcome);
+            self.sink.write_all(line.as_bytes())?;
+        }
+        self.sink.flush()?;
+        Ok(())
+    }
+
+    fn kind(&self) -> ReporterKind {
+        self.kind
+    }
+}
+
+/// JSON Schema of the `json` reporter's output
+pub const REPORT_SCHEMA: &str = include_str!("../schemas/report.schema.json");
+
+/// Where the report schema is published, emitted as the report's `$schema`
+pub const REPORT_SCHEMA_URI: &str =
+    "https://raw.githubusercontent.com/AvnerCohen/yamllint-rs/main/schemas/report.schema.json";
+
+/// Version of the JSON report format. The minor version grows when optional
+/// fields are added, the major version on any incompatible change.
+pub const REPORT_FORMAT_VERSION: &str = "1.0";
+
+/// Reporter emitting a single JSON document listing every issue
+pub struct JsonReporter {
+    sink: Box<dyn Write + Send>,
+    issues: Vec<serde_json::Value>,
+}
+
+impl JsonReporter {
+    pub fn new(sink: Box<dyn Write + Send>) -> Self {
+        Self {
+            sink,
+            issues: Vec::new(),
+        }
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn report(&mut self, result: &LintResult) -> Result<()> {
+        for issue in result.reported_issues() {
+            self.issues.push(serde_json::to_value(issue)?);
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let report = serde_json::json!({
+            "$schema": REPORT_SCHEMA_URI,
+            "version": REPORT_FORMAT_VERSION,
+            "issues": std::mem::take(&mut self.issues),
+        });
+        serde_json::to_writer_pretty(&mut self.sink, &report)?;
+        writeln!(self.sink)?;
+        self.sink.flush()?;
+        Ok(())
+    }
+
+    fn kind(&self) -> ReporterKind {
+        ReporterKind::Json
+    }
+}
+
+/// Reporter emitting a SARIF 2.1.0 log
+pub struct SarifReporter {
+    sink: Box<dyn Write + Send>,
+    results: Vec<serde_json::Value>,
+    rule_ids: Vec<String>,
+}
+
+impl SarifReporter {
+    pub fn new(sink: Box<dyn Write + Send>) -> Self {
+        Self {
+            sink,
+            results: Vec::new(),
+            rule_ids: Vec::new(),
+        }
+    }
+
+    fn sarif_level(severity: crate::Severity) -> &'static str {
+        match severity {
+            crate::Severity::Error => "error",
+            crate::Severity::Warning => "warning",
+            crate::Severity::Info => "note",
+        }
+    }
+}
+
+impl Reporter for SarifReporter {
+    fn report(&mut self, result: &LintResult) -> Result<()> {
+        let uri = result.file.replace('\\', "/");
+        for (issue, rule_name) in &result.issues {
+            if !self.rule_ids.contains(rule_name) {
+                self.rule_ids.push(rule_name.clone());
+            }
+            let mut properties = serde_json::json!({ "code": issue.code });
+            if let Some(scalar_start_line) = result.scalar_start_line(issue) {
+                properties["scalarStartLine"] = scalar_start_line.into();
+            }
+            self.results.push(serde_json::json!({
+                "ruleId": rule_name,
+                "level": Self::sarif_level(issue.severity),
+                "message": { "text": issue.message },
+                "properties": properties,
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri },
+                        "region": {
+                            "startLine": issue.line,
+                            "startColumn": issue.column,
+                        }
+                    }
+                }]
+            }));
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let registry = crate::rules::registry::RuleRegistry::new();
+        let mut rule_ids = std::mem::take(&mut self.rule_ids);
+        rule_ids.sort();
+        let rules: Vec<serde_json::Value> = rule_ids
+            .iter()
+            .map(|id| {
+                let description = registry
+                    .get_rule_metadata(id)
+                    .map(|metadata| metadata.description)
+                    .unwrap_or("");
+                serde_json::json!({
+                    "id": id,
+                    "shortDescription": { "text": description },
+                })
+            })
+            .collect();
+
+        let log = serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    }
+                },
+                "results": std::mem::take(&mut self.results),
+            }]
+        });
+
+        serde_json::to_writer_pretty(&mut self.sink, &log)?;
+        writeln!(self.sink)?;
+        self.sink.flush()?;
+        Ok(())
+    }
+
+    fn kind(&self) -> ReporterKind {
+        ReporterKind::Sarif
+    }
+}
+
+/// Group name of files directly in the working directory
+pub const ROOT_GROUP: &str = ".";
+
+/// Reporter printing a table of issue counts per top-level directory or
+/// per `CODEOWNERS` owner, so fixes can be routed to the teams owning them
+pub struct RollupReporter {
+    grouping: Grouping,
+    owners: Option<CodeOwners>,
+    sink: Box<dyn Write + Send>,
+    groups: BTreeMap<String, (usize, IssueCounts)>,
+}
+
+impl RollupReporter {
+    /// Grouping by owner reads the `CODEOWNERS` file of the working directory
+    pub fn new(grouping: Grouping, sink: Box<dyn Write + Send>) -> Result<Self> {
+        let owners = match grouping {
+            Grouping::Directory => None,
+            Grouping::Owner => {
+                let root = Path::new(".");
+                let path = CodeOwners::discover(root).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "--group-by owner needs a CODEOWNERS file (looked for {})",
+                        crate::codeowners::CODEOWNERS_LOCATIONS.join(", ")
+                    )
+                })?;
+                Some(CodeOwners::load(&path, Path::new(""))?)
+            }
+        };
+        Ok(Self {
+            grouping,
+            owners,
+            sink,
+            groups: BTreeMap::new(),
+        })
+    }
+
+    fn group(&self, file: &str) -> String {
+        let path = Path::new(file);
+        match &self.owners {
+            Some(owners) => owners.group(path),
+            None => top_level_dir(path),
+        }
+    }
+}
+
+/// First directory of `path`, or [`ROOT_GROUP`] for a file with no directory
+fn top_level_dir(path: &Path) -> String {
+    let mut components = path.components().filter(|component| {
+        !matches!(
+            component,
+            Component::CurDir | Component::RootDir | Component::Prefix(_)
+        )
+    });
+    match (components.next(), components.next()) {
+        (Some(dir), Some(_)) => dir.as_os_str().to_string_lossy().into_owned(),
+        _ => ROOT_GROUP.to_string(),
+    }
+}
+
+impl Reporter for RollupReporter {
+    fn report(&mut self, result: &LintResult) -> Result<()> {
+        if result.issues.is_empty() {
+            return Ok(());
+        }
+        let (files, counts) = self.groups.entry(self.group(&result.file)).or_default();
+        *files += 1;
+        counts.add_result(result);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let width = self
+            .groups
+            .keys()
+            .map(String::len)
+            .chain(["GROUP".len(), "TOTAL".len()])
+            .max()
+            .unwrap_or(0);
+        let row = |name: &str, files: usize, counts: &IssueCounts| {
+            format!(
+                "{:<width$}  {:>6}  {:>6}  {:>8}  {:>5}\n",
+                name, files, counts.errors, counts.warnings, counts.infos
+            )
+        };
+
+        let mut table = format!(
+            "{:<width$}  {:>6}  {:>6}  {:>8}  {:>5}\n",
+            "GROUP", "FILES", "ERRORS", "WARNINGS", "INFOS"
+        );
+        let mut total_files = 0;
+        let mut total = IssueCounts::default();
+        for (name, (files, counts)) in &self.groups {
+            table.push_str(&row(name, *files, counts));
+            total_files += files;
+            total.merge(*counts);
+        }
+        table.push_str(&row("TOTAL", total_files, &total));
+
+        self.sink.write_all(table.as_bytes())?;
+        self.sink.flush()?;
+        Ok(())
+    }
+
+    fn kind(&self) -> ReporterKind {
+        ReporterKind::Rollup(self.grouping)
+    }
+}
+
+/// Create a reporter writing to the destination named in the spec;
+/// `redact_values` keeps reporters from showing document source
+#[cfg_attr(not(feature = "pretty-diagnostics"), allow(unused_variables))]
+pub fn create_reporter(spec: &ReporterSpec, redact_values: bool) -> Result<Box<dyn Reporter>> {
+    let sink = open_sink(&spec.output)?;
+    Ok(match spec.kind {
+        ReporterKind::Standard | ReporterKind::Colored => {
+            Box::new(HumanReporter::new(spec.kind, sink))
+        }
+        ReporterKind::Json => Box::new(JsonReporter::new(sink)),
+        ReporterKind::Sarif => Box::new(SarifReporter::new(sink)),
+        #[cfg(feature = "pretty-diagnostics")]
+        ReporterKind::Pretty => {
+            Box::new(crate::pretty::PrettyReporter::new(sink).with_redact_values(redact_values))
+        }
+        ReporterKind::Rollup(grouping) => Box::new(RollupReporter::new(grouping, sink)?),
+    })
+}
+
+/// Order in which buffered results are reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// By file path, each file's issues by line and column
+    File,
+    /// By file path, each file's issues from most to least severe, then by line
+    Severity,
+}
+
+/// Fans every result out to all configured reporters. With a sort order,
+/// results are held until `finish` and reported in that order, so output
+/// does not depend on which worker thread finished first.
+#[derive(Default)]
+pub struct MultiReporter {
+    reporters: Vec<Box<dyn Reporter>>,
+    sort_order: Option<SortOrder>,
+    pending: Vec<LintResult>,
+}
+
+impl MultiReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Buffer results and report them in `order` when the run finishes;
+    /// `None` reports each result as it arrives
+    pub fn with_sort_order(mut self, order: Option<SortOrder>) -> Self {
+        self.sort_order = order;
+        self
+    }
+
+    /// A single human-readable reporter writing to stdout
+    pub fn stdout(format: OutputFormat) -> Self {
+        let mut multi = Self::new();
+        multi.add(Box::new(HumanReporter::new(
+            format.into(),
+            Box::new(std::io::stdout()),
+        )));
+        multi
+    }
+
+    /// Build reporters from specs, falling back to `default_format` on stdout
+    /// when no spec writes to stdout
+    pub fn from_specs(
+        specs: &[ReporterSpec],
+        default_format: OutputFormat,
+        redact_values: bool,
+    ) -> Result<Self> {
+        let mut multi = if specs.iter().any(|spec| spec.output.is_none()) {
+            Self::new()
+        } else {
+            Self::stdout(default_format)
+        };
+        for spec in specs {
+            multi.add(create_reporter(spec, redact_values)?);
+        }
+        Ok(multi)
+    }
+
+    pub fn add(&mut self, reporter: Box<dyn Reporter>) {
+        self.reporters.push(reporter);
+    }
+
+    pub fn report(&mut self, result: &LintResult) -> Result<()> {
+        if self.sort_order.is_some() {
+            self.pending.push(result.clone());
+            return Ok(());
+        }
+        self.report_now(result)
+    }
+
+    fn report_now(&mut self, result: &LintResult) -> Result<()> {
+        for reporter in &mut self.reporters {
+            reporter.report(result)?;
+        }
+        Ok(())
+    }
+
+    pub fn report_fix(&mut self, outcome: &FixOutcome) -> Result<()> {
+        for reporter in &mut self.reporters {
+            reporter.report_fix(outcome)?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(&mut self) -> Result<()> {
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.sort_by(|a, b| a.file.cmp(&b.file));
+        for mut result in pending {
+            if self.sort_order == Some(SortOrder::Severity) {
+                // Stable, so equally severe issues keep their line order
+                result
+                    .issues
+                    .sort_by_key(|(issue, _)| std::cmp::Reverse(issue.severity));
+            }
+            self.report_now(&result)?;
+        }
+
+        for reporter in &mut self.reporters {
+            reporter.finish()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LintIssue, Severity};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn sample_result() -> LintResult {
+        LintResult {
+            file: "test.yaml".to_string(),
+            issues: vec![(
+                LintIssue {
+                    line: 3,
+                    column: 7,
+                    message: "trailing spaces".to_string(),
+                    code: "trailing-spaces/trailing-spaces".to_string(),
+                    severity: Severity::Error,
+                },
+                "trailing-spaces".to_string(),
+            )],
+            scalar_start_lines: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_top_level_dir_groups() {
+        assert_eq!(
+            top_level_dir(Path::new("./services/api/app.yaml")),
+            "services"
+        );
+        assert_eq!(top_level_dir(Path::new("charts/values.yaml")), "charts");
+        assert_eq!(top_level_dir(Path::new("./root.yaml")), ROOT_GROUP);
+        assert_eq!(top_level_dir(Path::new("/srv/app.yaml")), "srv");
+    }
+
+    #[test]
+    fn test_parse_reporter_spec() {
+        let spec = ReporterSpec::parse("sarif:out.sarif").unwrap();
+        assert_eq!(spec.kind, ReporterKind::Sarif);
+        assert_eq!(spec.output, Some(PathBuf::from("out.sarif")));
+
+        let spec = ReporterSpec::parse("colored").unwrap();
+        assert_eq!(spec.kind, ReporterKind::Colored);
+        assert_eq!(spec.output, None);
+
+        assert!(ReporterSpec::parse("xml").is_err());
+    }
+
+    #[test]
+    fn test_fix_summary_is_sorted_and_skips_structured_reporters() {
+        let human = SharedBuffer::default();
+        let json = SharedBuffer::default();
+
+        let mut multi = MultiReporter::new();
+        multi.add(Box::new(HumanReporter::new(
+            ReporterKind::Standard,
+            Box::new(human.clone()),
+        )));
+        multi.add(Box::new(JsonReporter::new(Box::new(json.clone()))));
+
+        let outcome = |file: &str, fixed, remaining| FixOutcome {
+            file: file.to_string(),
+            fixed,
+            fixable: fixed,
+            remaining,
+        };
+        multi.report_fix(&outcome("b.yaml", 2, 0)).unwrap();
+        multi.report_fix(&outcome("c.yaml", 0, 0)).unwrap();
+        multi.report_fix(&outcome("a.yaml", 0, 1)).unwrap();
+        multi.finish().unwrap();
+
+        assert_eq!(
+            human.contents(),
+            "Found 1 non-fixable issues in a.yaml\n\
+             Fixed 2 issues in b.yaml (2 fixable, 0 remaining)\n"
+        );
+        let report: serde_json::Value = serde_json::from_str(&json.contents()).unwrap();
+        assert_eq!(report["issues"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_multi_reporter_fans_out() {
+        let human = SharedBuffer::default();
+        let sarif = SharedBuffer::default();
+
+        let mut multi = MultiReporter::new();
+        multi.add(Box::new(HumanReporter::new(
+            ReporterKind::Standard,
+            Box::new(human.clone()),
+        )));
+        multi.add(Box::new(SarifReporter::new(Box::new(sarif.clone()))));
+
+        multi.report(&sample_result()).unwrap();
+        multi.finish().unwrap();
+
+        assert!(human.contents().contains("test.yaml"));
+        assert!(human.contents().contains("trailing spaces"));
+
+        let log: serde_json::Value = serde_json::from_str(&sarif.contents()).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        let result = &log["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "trailing-spaces");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            3
+        );
+    }
+
+    #[test]
+    fn test_json_reporter() {
+        let buffer = SharedBuffer::default();
+        let mut reporter = JsonReporter::new(Box::new(buffer.clone()));
+        reporter.report(&sample_result()).unwrap();
+        reporter.finish().unwrap();
+
+        let report: serde_json::Value = serde_json::from_str(&buffer.contents()).unwrap();
+        assert_eq!(report["version"], REPORT_FORMAT_VERSION);
+        assert_eq!(report["$schema"], REPORT_SCHEMA_URI);
+        let issues = &report["issues"];
+        assert_eq!(issues[0]["file"], "test.yaml");
+        assert_eq!(issues[0]["level"], "error");
+        assert_eq!(issues[0]["column"], 7);
+        assert_eq!(issues[0]["code"], "trailing-spaces/trailing-spaces");
+        assert!(issues[0].get("scalar_start_line").is_none());
+    }
+
+    #[test]
+    fn test_lint_result_display_and_serialize() {
+        let mut result = sample_result();
+        result.scalar_start_lines.insert(3, 2);
+        assert_eq!(
+            result.to_string(),
+            "test.yaml:3:7: [error] trailing spaces (trailing-spaces)\n"
+        );
+
+        // The JSON reporter's issues are the serialized result's, plus `file`
+        let value = serde_json::to_value(&result).unwrap();
+        let mut issue = value["issues"][0].clone();
+        assert_eq!(issue["scalar_start_line"], 2);
+        issue["file"] = value["file"].clone();
+
+        let buffer = SharedBuffer::default();
+        let mut reporter = JsonReporter::new(Box::new(buffer.clone()));
+        reporter.report(&result).unwrap();
+        reporter.finish().unwrap();
+        let report: serde_json::Value = serde_json::from_str(&buffer.contents()).unwrap();
+        assert_eq!(report["issues"][0], issue);
+
+        let (lint_issue, _) = &result.issues[0];
+        let round_trip: LintIssue =
+            serde_json::from_value(serde_json::to_value(lint_issue).unwrap()).unwrap();
+        assert_eq!(round_trip.severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_report_schema_matches_output() {
+        let schema: serde_json::Value = serde_json::from_str(REPORT_SCHEMA).unwrap();
+        assert_eq!(schema["$id"], REPORT_SCHEMA_URI);
+        assert_eq!(
+            schema["properties"]["version"]["const"],
+            REPORT_FORMAT_VERSION
+        );
+
+        let buffer = SharedBuffer::default();
+        let mut reporter = JsonReporter::new(Box::new(buffer.clone()));
+        reporter.report(&sample_result()).unwrap();
+        reporter.finish().unwrap();
+        let report: serde_json::Value = serde_json::from_str(&buffer.contents()).unwrap();
+
+        for field in schema["required"].as_array().unwrap() {
+            assert!(report.get(field.as_str().unwrap()).is_some(), "{}", field);
+        }
+        let issue_schema = &schema["$defs"]["issue"];
+        let issue = report["issues"][0].as_object().unwrap();
+        for field in issue_schema["required"].as_array().unwrap() {
+            assert!(issue.contains_key(field.as_str().unwrap()), "{}", field);
+        }
+        for field in issue.keys() {
+            assert!(
+                issue_schema["properties"].get(field).is_some(),
+                "{} missing from schema",
+                field
+            );
+        }
+    }
+
+    #[test]
+    fn test_reporters_include_scalar_start_line() {
+        let mut result = sample_result();
+        result.scalar_start_lines.insert(3, 1);
+
+        let json = SharedBuffer::default();
+        let mut reporter = JsonReporter::new(Box::new(json.clone()));
+        reporter.report(&result).unwrap();
+        reporter.finish().unwrap();
+        let report: serde_json::Value = serde_json::from_str(&json.contents()).unwrap();
+        assert_eq!(report["issues"][0]["scalar_start_line"], 1);
+
+        let sarif = SharedBuffer::default();
+        let mut reporter = SarifReporter::new(Box::new(sarif.clone()));
+        reporter.report(&result).unwrap();
+        reporter.finish().unwrap();
+        let log: serde_json::Value = serde_json::from_str(&sarif.contents()).unwrap();
+        assert_eq!(
+            log["runs"][0]["results"][0]["properties"]["scalarStartLine"],
+            1
+        );
+    }
+}
@@ -1,42 +1,82 @@
-use crate::rules::{factory::RuleFactory, registry::RuleRegistry, Rule};
-use std::sync::Arc;
+//! The rules a run checks files with.
+//!
+//! Rules only take `&self` while checking and `Rule` requires `Send + Sync`,
+//! so a `RuleSet` is built once from the config and every worker thread
+//! borrows it: there are no locks, no per-thread copies and no per-file clones.
 
-pub struct RulePool {
-    factory: Arc<RuleFactory>,
-    registry: Arc<RuleRegistry>,
+use crate::config::Config;
+use crate::rules::{factory::RuleFactory, Rule};
+use std::ops::Deref;
+
+/// Immutable, configured rule instances shared by all workers of a run
+#[derive(Default)]
+pub struct RuleSet {
+    rules: Vec<Box<dyn Rule>>,
 }
 
-impl RulePool {
-    pub fn new() -> Self {
-        Self {
-            factory: Arc::new(RuleFactory::new()),
-            registry: Arc::new(RuleRegistry::new()),
-        }
+impl RuleSet {
+    pub fn new(rules: Vec<Box<dyn Rule>>) -> Self {
+        Self { rules }
     }
 
-    pub fn get_rule(&self, rule_id: &str) -> Option<Box<dyn Rule>> {
-        self.factory.create_rule(rule_id)
-    }
+    /// Enabled rules of `config`, configured with its settings and severities
+    pub fn from_config(config: &Config) -> Self {
+        let factory = RuleFactory::new();
+        let enabled_rules = config.get_enabled_rules();
+        let mut rules = factory.create_rules_by_ids_with_config(&enabled_rules, config);
 
-    pub fn get_enabled_rules(&self) -> Vec<Box<dyn Rule>> {
-        let enabled_rule_ids = self.registry.get_default_enabled_rules();
-        enabled_rule_ids
-            .iter()
-            .filter_map(|id| self.get_rule(id))
-            .collect()
+        for rule in &mut rules {
+            let severity = config.get_rule_severity(rule.rule_id());
+            rule.set_severity(severity);
+        }
+        Self { rules }
     }
 
-    pub fn get_rules_by_ids(&self, rule_ids: &[String]) -> Vec<Box<dyn Rule>> {
-        rule_ids.iter().filter_map(|id| self.get_rule(id)).collect()
+    /// Add a rule before the set is shared with workers
+    pub fn push(&mut self, rule: Box<dyn Rule>) {
+        self.rules.push(rule);
     }
+}
 
-    pub fn registry(&self) -> &RuleRegistry {
-        &self.registry
+impl Deref for RuleSet {
+    type Target = [Box<dyn Rule>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.rules
     }
 }
 
-impl Default for RulePool {
-    fn default() -> Self {
-        Self::new()
+// Workers borrow the set across threads, which needs no more than this
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<RuleSet>();
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+
+    #[test]
+    fn test_rule_set_is_shared_by_reference_across_workers() {
+        let mut config = Config::default();
+        config.set_rule_enabled("document-start", false);
+        let rule_set = RuleSet::from_config(&config);
+        assert!(!rule_set.is_empty());
+        assert!(rule_set
+            .iter()
+            .all(|rule| rule.rule_id() != "document-start"));
+
+        let rule_set = &rule_set;
+        let counts: Vec<usize> = (0..8)
+            .into_par_iter()
+            .map(|_| {
+                rule_set
+                    .iter()
+                    .map(|rule| rule.check("key: value   \n", "test.yaml").len())
+                    .sum()
+            })
+            .collect();
+        assert!(counts.iter().all(|&count| count == counts[0] && count > 0));
     }
 }
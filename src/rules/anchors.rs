@@ -1,8 +1,9 @@
 use crate::{LintIssue, Severity};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 use yaml_rust::scanner::{Scanner, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct AnchorsConfig {
     pub forbid_undeclared_aliases: bool,
     pub forbid_duplicated_anchors: bool,
@@ -93,6 +94,10 @@ impl crate::rules::Rule for AnchorsRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         false
     }
@@ -112,71 +117,81 @@ impl crate::rules::Rule for AnchorsRule {
 }
 
 impl AnchorsRule {
+    /// Anchors are tracked per document, as in yamllint: `---`, `...` and the
+    /// end of the stream close a document, and its unused anchors are
+    /// reported then.
     fn check_with_tokens(
         &self,
         tokens: &[Token],
         _token_analysis: &crate::analysis::TokenAnalysis,
     ) -> Vec<LintIssue> {
+        let config = self.config();
         let mut issues = Vec::new();
-        let mut anchors: HashMap<String, AnchorInfo> = HashMap::new();
+        // In declaration order, so unused anchors are reported in file order
+        let mut anchors: Vec<(String, AnchorInfo)> = Vec::new();
 
         for token in tokens {
             let Token(marker, token_type) = token;
 
-            if matches!(
-                token_type,
-                TokenType::StreamStart(_) | TokenType::DocumentStart | TokenType::DocumentEnd
-            ) {
-                anchors.clear();
-                continue;
-            }
-
-            if let TokenType::Anchor(anchor_name) = token_type {
-                if self.config().forbid_duplicated_anchors && anchors.contains_key(anchor_name) {
-                    issues.push(LintIssue {
-                        line: marker.line() + 1,
-                        column: marker.col() + 1,
-                        message: format!("found duplicated anchor \"{}\"", anchor_name),
-                        severity: self.get_severity(),
-                    });
+            match token_type {
+                TokenType::StreamStart(_)
+                | TokenType::StreamEnd
+                | TokenType::DocumentStart
+                | TokenType::DocumentEnd => {
+                    if config.forbid_unused_anchors {
+                        for (anchor_name, anchor_info) in &anchors {
+                            if !anchor_info.used {
+                                issues.push(LintIssue {
+                                    line: anchor_info.line,
+                                    column: anchor_info.column + 1,
+                                    message: format!("found unused anchor \"{}\"", anchor_name),
+                                    code: "anchors/unused-anchor".to_string(),
+                                    severity: self.get_severity(),
+                                });
+                            }
+                        }
+                    }
+                    anchors.clear();
                 }
-
-                anchors.insert(
-                    anchor_name.clone(),
-                    AnchorInfo {
+                TokenType::Anchor(anchor_name) => {
+                    let info = AnchorInfo {
                         line: marker.line(),
                         column: marker.col(),
                         used: false,
-                    },
-                );
-            }
-
-            if let TokenType::Alias(alias_name) = token_type {
-                if self.config().forbid_undeclared_aliases && !anchors.contains_key(alias_name) {
-                    issues.push(LintIssue {
-                        line: marker.line() + 1,
-                        column: marker.col() + 1,
-                        message: format!("found undeclared alias \"{}\"", alias_name),
-                        severity: self.get_severity(),
-                    });
+                    };
+                    match anchors.iter_mut().find(|(name, _)| name == anchor_name) {
+                        Some((_, existing)) => {
+                            if config.forbid_duplicated_anchors {
+                                issues.push(LintIssue {
+                                    line: marker.line(),
+                                    column: marker.col() + 1,
+                                    message: format!("found duplicated anchor \"{}\"", anchor_name),
+                                    code: "anchors/duplicated-anchor".to_string(),
+                                    severity: self.get_severity(),
+                                });
+                            }
+                            // Later aliases refer to the latest declaration
+                            *existing = info;
+                        }
+                        None => anchors.push((anchor_name.clone(), info)),
+                    }
                 }
-
-                if let Some(anchor_info) = anchors.get_mut(alias_name) {
-                    anchor_info.used = true;
-                }
-            }
-        }
-
-        if self.config().forbid_unused_anchors {
-            for (anchor_name, anchor_info) in &anchors {
-                if !anchor_info.used {
-                    issues.push(LintIssue {
-                        line: anchor_info.line + 1,
-                        column: anchor_info.column + 1,
-                        message: format!("found unused anchor \"{}\"", anchor_name),
-                        severity: self.get_severity(),
-                    });
+                TokenType::Alias(alias_name) => {
+                    match anchors.iter_mut().find(|(name, _)| name == alias_name) {
+                        Some((_, anchor_info)) => anchor_info.used = true,
+                        None if config.forbid_undeclared_aliases => {
+                            issues.push(LintIssue {
+                                line: marker.line(),
+                                column: marker.col() + 1,
+                                message: format!("found undeclared alias \"{}\"", alias_name),
+                                code: "anchors/undeclared-alias".to_string(),
+                                severity: self.get_severity(),
+                            });
+                        }
+                        None => {}
+                    }
                 }
+                _ => {}
             }
         }
 
@@ -311,6 +326,30 @@ mod tests {
         assert!(issues[0].message.contains("unused anchor"));
     }
 
+    #[test]
+    fn test_anchors_are_tracked_per_document() {
+        let rule = AnchorsRule::with_config(AnchorsConfig {
+            forbid_undeclared_aliases: true,
+            forbid_duplicated_anchors: true,
+            forbid_unused_anchors: true,
+        });
+        let content = "---\na: &x 1\nb: &y 2\nc: &x 3\nd: *x\n---\ne: *y\nf: &z 4\n...\n";
+        let issues: Vec<_> = rule
+            .check(content, "test.yaml")
+            .into_iter()
+            .map(|issue| (issue.line, issue.column, issue.code))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (4, 4, "anchors/duplicated-anchor".to_string()),
+                (3, 4, "anchors/unused-anchor".to_string()),
+                (7, 4, "anchors/undeclared-alias".to_string()),
+                (8, 4, "anchors/unused-anchor".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_anchors_fix_no_changes() {
         let rule = AnchorsRule::new();
@@ -90,7 +90,7 @@ impl<T> BaseRuleWithRegex<T> {
 
     pub fn get_or_compile_pattern(&mut self, pattern: &str) -> Result<&Regex, regex::Error> {
         if !self.compiled_patterns.contains_key(pattern) {
-            let regex = Regex::new(pattern)?;
+            let regex = crate::patterns::regex(pattern)?;
             self.compiled_patterns.insert(pattern.to_string(), regex);
         }
         Ok(self.compiled_patterns.get(pattern).unwrap())
@@ -104,11 +104,18 @@ impl<T> BaseRuleWithRegex<T> {
 }
 
 pub trait LintIssueBuilder {
-    fn create_issue(line: usize, column: usize, message: String, severity: Severity) -> LintIssue {
+    fn create_issue(
+        line: usize,
+        column: usize,
+        message: String,
+        code: &str,
+        severity: Severity,
+    ) -> LintIssue {
         LintIssue {
             line,
             column,
             message,
+            code: code.to_string(),
             severity,
         }
     }
@@ -117,9 +124,10 @@ pub trait LintIssueBuilder {
         line: usize,
         column: usize,
         message: String,
+        code: &str,
         severity: Severity,
     ) -> LintIssue {
-        Self::create_issue(line, column, message, severity)
+        Self::create_issue(line, column, message, code, severity)
     }
 }
 
@@ -1,7 +1,9 @@
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 use yaml_rust::scanner::{Scanner, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct BracesConfig {
     pub forbid: ForbidSetting,
     pub min_spaces_inside: i32,
@@ -17,6 +19,38 @@ pub enum ForbidSetting {
     NonEmpty,
 }
 
+/// Serialized as in yamllint: `false`, `true` or `non-empty`
+impl Serialize for ForbidSetting {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ForbidSetting::False => serializer.serialize_bool(false),
+            ForbidSetting::True => serializer.serialize_bool(true),
+            ForbidSetting::NonEmpty => serializer.serialize_str("non-empty"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ForbidSetting {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Forbid {
+            Bool(bool),
+            Name(String),
+        }
+
+        match Forbid::deserialize(deserializer)? {
+            Forbid::Bool(false) => Ok(ForbidSetting::False),
+            Forbid::Bool(true) => Ok(ForbidSetting::True),
+            Forbid::Name(name) if name == "non-empty" => Ok(ForbidSetting::NonEmpty),
+            Forbid::Name(name) => Err(serde::de::Error::custom(format!(
+                "expected true, false or non-empty, got '{}'",
+                name
+            ))),
+        }
+    }
+}
+
 impl Default for ForbidSetting {
     fn default() -> Self {
         ForbidSetting::False
@@ -109,6 +143,10 @@ impl crate::rules::Rule for BracesRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         true
     }
@@ -137,6 +175,8 @@ impl BracesRule {
         max: i32,
         min_desc: &str,
         max_desc: &str,
+        min_code: &str,
+        max_code: &str,
     ) -> Option<LintIssue> {
         if token_marker.line() != next_marker.line() {
             return None;
@@ -156,6 +196,7 @@ impl BracesRule {
                 line: token_marker.line() + 1,
                 column: next_marker.col() + 1,
                 message: max_desc.to_string(),
+                code: max_code.to_string(),
                 severity: self.get_severity(),
             });
         }
@@ -165,6 +206,7 @@ impl BracesRule {
                 line: token_marker.line() + 1,
                 column: next_marker.col() + 1,
                 message: min_desc.to_string(),
+                code: min_code.to_string(),
                 severity: self.get_severity(),
             });
         }
@@ -182,6 +224,8 @@ impl BracesRule {
         max: i32,
         min_desc: &str,
         max_desc: &str,
+        min_code: &str,
+        max_code: &str,
     ) -> Option<LintIssue> {
         if prev_marker.line() != token_marker.line() {
             return None;
@@ -252,6 +296,7 @@ impl BracesRule {
                 line: token_marker.line() + 1,
                 column: token_marker.col() + 1,
                 message: max_desc.to_string(),
+                code: max_code.to_string(),
                 severity: self.get_severity(),
             });
         }
@@ -261,6 +306,7 @@ impl BracesRule {
                 line: token_marker.line() + 1,
                 column: token_marker.col() + 1,
                 message: min_desc.to_string(),
+                code: min_code.to_string(),
                 severity: self.get_severity(),
             });
         }
@@ -289,6 +335,7 @@ impl BracesRule {
                             line: marker.line() + 1,
                             column: marker.col() + 1,
                             message: "forbidden flow mapping".to_string(),
+                            code: "braces/forbidden".to_string(),
                             severity: self.get_severity(),
                         });
                     } else if let Some(next) = next_token {
@@ -313,6 +360,8 @@ impl BracesRule {
                                 max,
                                 "too few spaces inside empty braces",
                                 "too many spaces inside empty braces",
+                                "braces/too-few-spaces-inside-empty",
+                                "braces/too-many-spaces-inside-empty",
                             ) {
                                 issues.push(issue);
                             }
@@ -322,6 +371,7 @@ impl BracesRule {
                                     line: marker.line() + 1,
                                     column: marker.col() + 1,
                                     message: "forbidden flow mapping".to_string(),
+                                    code: "braces/forbidden".to_string(),
                                     severity: self.get_severity(),
                                 });
                             } else {
@@ -333,6 +383,8 @@ impl BracesRule {
                                     self.config().max_spaces_inside,
                                     "too few spaces inside braces",
                                     "too many spaces inside braces",
+                                    "braces/too-few-spaces-inside",
+                                    "braces/too-many-spaces-inside",
                                 ) {
                                     issues.push(issue);
                                 }
@@ -353,6 +405,8 @@ impl BracesRule {
                                 self.config().max_spaces_inside,
                                 "too few spaces inside braces",
                                 "too many spaces inside braces",
+                                "braces/too-few-spaces-inside",
+                                "braces/too-many-spaces-inside",
                             ) {
                                 issues.push(issue);
                             }
@@ -525,4 +579,32 @@ mod tests {
         assert!(!fix_result.changed);
         assert_eq!(fix_result.fixes_applied, 0);
     }
+
+    #[test]
+    fn test_braces_spacing_codes() {
+        let rule = BracesRule::with_config(BracesConfig {
+            forbid: ForbidSetting::False,
+            min_spaces_inside: 2,
+            max_spaces_inside: 2,
+            min_spaces_inside_empty: 2,
+            max_spaces_inside_empty: 2,
+        });
+        let content = "a: { x }\nb: {   x   }\nc: { }\nd: {   }\n";
+        let codes: Vec<_> = rule
+            .check(content, "test.yaml")
+            .into_iter()
+            .map(|issue| issue.code)
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                "braces/too-few-spaces-inside",
+                "braces/too-few-spaces-inside",
+                "braces/too-many-spaces-inside",
+                "braces/too-many-spaces-inside",
+                "braces/too-few-spaces-inside-empty",
+                "braces/too-many-spaces-inside-empty",
+            ]
+        );
+    }
 }
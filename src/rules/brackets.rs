@@ -1,7 +1,9 @@
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 use yaml_rust::scanner::{Scanner, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct BracketsConfig {
     pub forbid: bool,
     pub min_spaces_inside: i32,
@@ -96,6 +98,10 @@ impl crate::rules::Rule for BracketsRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         true
     }
@@ -180,6 +186,8 @@ impl BracketsRule {
         max: i32,
         min_desc: &str,
         max_desc: &str,
+        min_code: &str,
+        max_code: &str,
     ) -> Option<LintIssue> {
         if token_marker.line() != next_marker.line() {
             return None;
@@ -238,6 +246,7 @@ impl BracketsRule {
                     line: token_marker.line() + 1,
                     column: next_marker.col() + 1,
                     message: max_desc.to_string(),
+                    code: max_code.to_string(),
                     severity: self.get_severity(),
                 });
             }
@@ -249,6 +258,7 @@ impl BracketsRule {
                     line: token_marker.line() + 1,
                     column: next_marker.col() + 1,
                     message: min_desc.to_string(),
+                    code: min_code.to_string(),
                     severity: self.get_severity(),
                 });
             }
@@ -267,6 +277,8 @@ impl BracketsRule {
         max: i32,
         min_desc: &str,
         max_desc: &str,
+        min_code: &str,
+        max_code: &str,
     ) -> Option<LintIssue> {
         if prev_marker.line() != token_marker.line() {
             return None;
@@ -385,6 +397,7 @@ impl BracketsRule {
                     line: token_marker.line() + 1,
                     column: token_marker.col() + 1,
                     message: max_desc.to_string(),
+                    code: max_code.to_string(),
                     severity: self.get_severity(),
                 });
             }
@@ -396,6 +409,7 @@ impl BracketsRule {
                     line: token_marker.line() + 1,
                     column: token_marker.col() + 1,
                     message: min_desc.to_string(),
+                    code: min_code.to_string(),
                     severity: self.get_severity(),
                 });
             }
@@ -467,6 +481,7 @@ impl BracketsRule {
                             line: marker.line() + 1,
                             column: marker.col() + 1,
                             message: "forbidden flow sequence".to_string(),
+                            code: "brackets/forbidden".to_string(),
                             severity: self.get_severity(),
                         });
                     } else if let Some(next) = next_token {
@@ -491,6 +506,8 @@ impl BracketsRule {
                                 max,
                                 "too few spaces inside empty brackets",
                                 "too many spaces inside empty brackets",
+                                "brackets/too-few-spaces-inside-empty",
+                                "brackets/too-many-spaces-inside-empty",
                             ) {
                                 issues.push(issue);
                             }
@@ -503,6 +520,8 @@ impl BracketsRule {
                                 self.config().max_spaces_inside,
                                 "too few spaces inside brackets",
                                 "too many spaces inside brackets",
+                                "brackets/too-few-spaces-inside",
+                                "brackets/too-many-spaces-inside",
                             ) {
                                 issues.push(issue);
                             }
@@ -699,6 +718,8 @@ impl BracketsRule {
                                 self.config().max_spaces_inside,
                                 "too few spaces inside brackets",
                                 "too many spaces inside brackets",
+                                "brackets/too-few-spaces-inside",
+                                "brackets/too-many-spaces-inside",
                             ) {
                                 issues.push(issue);
                             }
@@ -899,4 +920,30 @@ actual_flow: [ value1, value2 ]
             bracket_issues
         );
     }
+
+    #[test]
+    fn test_brackets_spacing_codes() {
+        let rule = BracketsRule::with_config(BracketsConfig {
+            forbid: false,
+            min_spaces_inside: 2,
+            max_spaces_inside: 2,
+            min_spaces_inside_empty: 2,
+            max_spaces_inside_empty: 2,
+        });
+        let content = "a: [ x ]\nb: [   x   ]\nc: [ ]\nd: [   ]\n";
+        let codes: Vec<_> = rule
+            .check(content, "test.yaml")
+            .into_iter()
+            .map(|issue| issue.code)
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                "brackets/too-few-spaces-inside",
+                "brackets/too-many-spaces-inside",
+                "brackets/too-few-spaces-inside-empty",
+                "brackets/too-many-spaces-inside-empty",
+            ]
+        );
+    }
 }
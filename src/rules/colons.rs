@@ -1,7 +1,9 @@
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 use yaml_rust::scanner::{Scanner, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct ColonsConfig {
     pub max_spaces_before: i32,
     pub max_spaces_after: i32,
@@ -90,6 +92,10 @@ impl crate::rules::Rule for ColonsRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         true
     }
@@ -192,6 +198,7 @@ impl ColonsRule {
                                         line: marker.line() + 1,
                                         column: marker.col() + 1,
                                         message: "too many spaces before colon".to_string(),
+                                        code: "colons/too-many-spaces-before".to_string(),
                                         severity: self.get_severity(),
                                     });
                                 }
@@ -210,6 +217,7 @@ impl ColonsRule {
                                             line: marker.line() + 1,
                                             column: marker.col() + 1,
                                             message: "too many spaces after colon".to_string(),
+                                            code: "colons/too-many-spaces-after".to_string(),
                                             severity: self.get_severity(),
                                         });
                                     }
@@ -233,6 +241,8 @@ impl ColonsRule {
                                         line: marker.line() + 1,
                                         column: marker.col() + 1,
                                         message: "too many spaces after question mark".to_string(),
+                                        code: "colons/too-many-spaces-after-question-mark"
+                                            .to_string(),
                                         severity: self.get_severity(),
                                     });
                                 }
@@ -458,6 +468,7 @@ mod tests {
         assert_eq!(issues.len(), 2);
         assert!(issues[0].message.contains("too many spaces before colon"));
         assert!(issues[1].message.contains("too many spaces before colon"));
+        assert_eq!(issues[0].code, "colons/too-many-spaces-before");
     }
 
     #[test]
@@ -1,31 +1,158 @@
 use super::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct CommasConfig {
+    /// `-1` disables the check
     pub max_spaces_before: i32,
+    /// `-1` disables the check
     pub min_spaces_after: i32,
+    /// `-1` disables the check
     pub max_spaces_after: i32,
 }
 
+impl Default for CommasConfig {
+    fn default() -> Self {
+        Self {
+            max_spaces_before: 0,
+            min_spaces_after: 1,
+            max_spaces_after: 1,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CommasRule {
-    config: CommasConfig,
+    base: crate::rules::base::BaseRule<CommasConfig>,
+}
+
+/// The spaces around one `,` of a flow collection, in character indices
+struct Comma {
+    line: usize,
+    /// 0-based column of the comma
+    column: usize,
+    /// Start of the spaces before the comma, `None` when the comma starts
+    /// its line
+    before: Option<usize>,
+    index: usize,
+    /// End of the spaces after the comma, `None` when nothing follows it on
+    /// its line
+    after: Option<usize>,
 }
 
 impl CommasRule {
     pub fn new() -> Self {
+        Self::with_config(CommasConfig::default())
+    }
+
+    pub fn with_config(config: CommasConfig) -> Self {
         Self {
-            config: CommasConfig {
-                max_spaces_before: 0,
-                min_spaces_after: 1,
-                max_spaces_after: 1,
-            },
+            base: crate::rules::base::BaseRule::new(config),
         }
     }
 
-    pub fn with_config(config: CommasConfig) -> Self {
-        Self { config }
+    pub fn config(&self) -> &CommasConfig {
+        self.base.config()
+    }
+
+    /// Every flow entry separator, nested collections included. Commas in
+    /// scalars and comments are not tokens, so they are never checked.
+    fn commas(tokens: &[Token], chars: &[char]) -> Vec<Comma> {
+        let is_space = |c: &char| *c == ' ' || *c == '\t';
+        tokens
+            .iter()
+            .filter(|Token(_, token)| matches!(token, TokenType::FlowEntry))
+            .map(|Token(marker, _)| {
+                let index = marker.index();
+                let spaces_before = chars[..index].iter().rev().take_while(|c| is_space(c));
+                let before = index - spaces_before.count();
+                let spaces_after = chars[index + 1..].iter().take_while(|c| is_space(c));
+                let after = index + 1 + spaces_after.count();
+                Comma {
+                    line: marker.line(),
+                    column: marker.col(),
+                    before: (before > 0 && chars[before - 1] != '\n').then_some(before),
+                    index,
+                    after: chars
+                        .get(after)
+                        .filter(|c| !matches!(c, '\n' | '\r' | '#'))
+                        .map(|_| after),
+                }
+            })
+            .collect()
+    }
+
+    /// Lines and columns follow yamllint: spaces before and too many spaces
+    /// after are reported on the last space, too few after on the next token
+    fn check_with_tokens(&self, content: &str, tokens: &[Token]) -> Vec<LintIssue> {
+        let config = self.config();
+        let chars: Vec<char> = content.chars().collect();
+        let mut issues = Vec::new();
+        let issue = |comma: &Comma, column: usize, message: String, code: &str| LintIssue {
+            line: comma.line,
+            column,
+            message,
+            code: format!("commas/{}", code),
+            severity: self.get_severity(),
+        };
+
+        for comma in Self::commas(tokens, &chars) {
+            if config.max_spaces_before != -1 {
+                match comma.before {
+                    None => issues.push(issue(
+                        &comma,
+                        comma.column.max(1),
+                        "too many spaces before comma".to_string(),
+                        "too-many-spaces-before",
+                    )),
+                    Some(before) if comma.index - before > config.max_spaces_before as usize => {
+                        issues.push(issue(
+                            &comma,
+                            comma.column,
+                            format!(
+                                "too many spaces before comma ({} > {})",
+                                comma.index - before,
+                                config.max_spaces_before
+                            ),
+                            "too-many-spaces-before",
+                        ))
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            let Some(after) = comma.after else {
+                continue;
+            };
+            let spaces = after - comma.index - 1;
+            let next_column = comma.column + 1 + spaces;
+            if config.max_spaces_after != -1 && spaces > config.max_spaces_after as usize {
+                issues.push(issue(
+                    &comma,
+                    next_column,
+                    format!(
+                        "too many spaces after comma ({} > {})",
+                        spaces, config.max_spaces_after
+                    ),
+                    "too-many-spaces-after",
+                ));
+            } else if config.min_spaces_after != -1 && spaces < config.min_spaces_after as usize {
+                issues.push(issue(
+                    &comma,
+                    next_column + 1,
+                    format!(
+                        "too few spaces after comma ({} < {})",
+                        spaces, config.min_spaces_after
+                    ),
+                    "too-few-spaces-after",
+                ));
+            }
+        }
+
+        issues
     }
 }
 
@@ -47,142 +174,93 @@ impl Rule for CommasRule {
     }
 
     fn get_severity(&self) -> Severity {
-        self.default_severity()
+        self.base.get_severity(self.default_severity())
     }
 
-    fn set_severity(&mut self, _severity: Severity) {}
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
 
     fn has_severity_override(&self) -> bool {
-        false
+        self.base.has_severity_override()
     }
 
-    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
-        let mut issues = Vec::new();
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
 
-        for (line_num, line) in content.lines().enumerate() {
-            let line_num = line_num + 1;
-
-            for (char_pos, ch) in line.char_indices() {
-                if ch == ',' {
-                    if self.config.max_spaces_before >= 0 {
-                        let before_comma: String = line.chars().take(char_pos).collect();
-                        let trailing_spaces = before_comma.len() - before_comma.trim_end().len();
-                        if trailing_spaces > self.config.max_spaces_before as usize {
-                            issues.push(LintIssue {
-                                line: line_num,
-                                column: char_pos + 1,
-                                message: format!(
-                                    "too many spaces before comma ({} > {})",
-                                    trailing_spaces, self.config.max_spaces_before
-                                ),
-                                severity: self.get_severity(),
-                            });
-                        }
-                    }
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(content, &tokens)
+    }
 
-                    if self.config.min_spaces_after >= 0 || self.config.max_spaces_after >= 0 {
-                        let after_comma: String = line.chars().skip(char_pos + 1).collect();
-                        let leading_spaces = after_comma.len() - after_comma.trim_start().len();
-
-                        if self.config.min_spaces_after >= 0
-                            && leading_spaces < self.config.min_spaces_after as usize
-                        {
-                            issues.push(LintIssue {
-                                line: line_num,
-                                column: char_pos + 1,
-                                message: format!(
-                                    "too few spaces after comma ({} < {})",
-                                    leading_spaces, self.config.min_spaces_after
-                                ),
-                                severity: self.get_severity(),
-                            });
-                        }
-
-                        if self.config.max_spaces_after >= 0
-                            && leading_spaces > self.config.max_spaces_after as usize
-                        {
-                            issues.push(LintIssue {
-                                line: line_num,
-                                column: char_pos + 1,
-                                message: format!(
-                                    "too many spaces after comma ({} > {})",
-                                    leading_spaces, self.config.max_spaces_after
-                                ),
-                                severity: self.get_severity(),
-                            });
-                        }
-                    }
-                }
-            }
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(content, &token_analysis.tokens),
+            None => self.check(content, file_path),
         }
-
-        issues
     }
 
     fn can_fix(&self) -> bool {
         true
     }
 
+    /// Trims or pads the spaces around each comma to the nearest allowed
+    /// count. Commas that start their line are left alone.
     fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
-        let mut fixed_lines = Vec::new();
-        let mut fixes_applied = 0;
-
-        for line in content.lines() {
-            let mut fixed_line = line.to_string();
-
-            for (char_pos, ch) in line.char_indices() {
-                if ch == ',' {
-                    let before_comma: String = line.chars().take(char_pos).collect();
-                    let after_comma: String = line.chars().skip(char_pos + 1).collect();
-
-                    let before_trimmed = before_comma.trim_end();
-                    let before_spaces = before_comma.len() - before_trimmed.len();
-                    let target_before_spaces = if self.config.max_spaces_before >= 0 {
-                        self.config.max_spaces_before as usize
-                    } else {
-                        before_spaces
-                    };
-
-                    let after_trimmed = after_comma.trim_start();
-                    let after_spaces = after_comma.len() - after_trimmed.len();
-                    let target_after_spaces = if self.config.min_spaces_after >= 0 {
-                        self.config.min_spaces_after as usize
-                    } else if self.config.max_spaces_after >= 0 {
-                        self.config.max_spaces_after as usize
-                    } else {
-                        after_spaces
-                    };
-
-                    if before_spaces != target_before_spaces || after_spaces != target_after_spaces
-                    {
-                        let new_before =
-                            format!("{}{}", before_trimmed, " ".repeat(target_before_spaces));
-                        let new_after =
-                            format!("{}{}", " ".repeat(target_after_spaces), after_trimmed);
-
-                        let after_comma_part: String = line.chars().skip(char_pos + 1).collect();
-                        fixed_line = format!("{},{}{}", new_before, new_after, after_comma_part);
-                        fixes_applied += 1;
-                        break;
-                    }
-                }
+        let config = self.config();
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        let chars: Vec<char> = content.chars().collect();
+        let clamp = |spaces: usize, min: i32, max: i32| {
+            let spaces = if max == -1 {
+                spaces
+            } else {
+                spaces.min(max as usize)
+            };
+            if min == -1 {
+                spaces
+            } else {
+                spaces.max(min as usize)
             }
+        };
 
-            fixed_lines.push(fixed_line);
+        // Runs of spaces to replace: start, end and wanted length
+        let mut edits = Vec::new();
+        for comma in Self::commas(&tokens, &chars) {
+            if let Some(before) = comma.before {
+                let spaces = comma.index - before;
+                let wanted = clamp(spaces, -1, config.max_spaces_before);
+                if wanted != spaces {
+                    edits.push((before, comma.index, wanted));
+                }
+            }
+            if let Some(after) = comma.after {
+                let spaces = after - comma.index - 1;
+                let wanted = clamp(spaces, config.min_spaces_after, config.max_spaces_after);
+                if wanted != spaces {
+                    edits.push((comma.index + 1, after, wanted));
+                }
+            }
         }
 
-        let fixed_content = if content.ends_with('\n') {
-            fixed_lines.join("\n") + "\n"
-        } else {
-            fixed_lines.join("\n")
-        };
-
-        let changed = fixes_applied > 0;
+        let mut fixed_content = String::with_capacity(content.len());
+        let mut position = 0;
+        for &(start, end, wanted) in &edits {
+            fixed_content.extend(&chars[position..start]);
+            fixed_content.push_str(&" ".repeat(wanted));
+            position = end;
+        }
+        fixed_content.extend(&chars[position..]);
 
         super::FixResult {
             content: fixed_content,
-            changed,
-            fixes_applied,
+            changed: !edits.is_empty(),
+            fixes_applied: edits.len(),
         }
     }
 }
@@ -239,7 +317,7 @@ mod tests {
         let content = "list: [a, b ,  c]";
         let fix_result = rule.fix(content, "test.yaml");
         assert!(fix_result.changed);
-        assert_eq!(fix_result.fixes_applied, 1);
+        assert_eq!(fix_result.fixes_applied, 2);
         assert!(fix_result.content.contains("list: [a, b, c]"));
     }
 
@@ -251,4 +329,63 @@ mod tests {
         assert!(!fix_result.changed);
         assert_eq!(fix_result.fixes_applied, 0);
     }
+
+    fn positions(rule: &CommasRule, content: &str) -> Vec<(usize, usize, String)> {
+        rule.check(content, "test.yaml")
+            .into_iter()
+            .map(|issue| (issue.line, issue.column, issue.code))
+            .collect()
+    }
+
+    #[test]
+    fn test_commas_only_checks_flow_entries() {
+        let rule = CommasRule::new();
+        let content =
+            "---\ntext: a,b ,c\nquoted: \"x ,y\"\n# a ,b\nlist: [a, {b: 1,c: 2}, [d ,  e]]\n";
+        assert_eq!(
+            positions(&rule, content),
+            vec![
+                (5, 17, "commas/too-few-spaces-after".to_string()),
+                (5, 26, "commas/too-many-spaces-before".to_string()),
+                (5, 29, "commas/too-many-spaces-after".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commas_options_and_multiline_collections() {
+        let rule = CommasRule::with_config(CommasConfig {
+            max_spaces_before: 2,
+            min_spaces_after: 0,
+            max_spaces_after: 3,
+        });
+        let content = "---\nlist: [a  , b,c,    d]\nflow: [\n  a,\n  b\n  , c,   # comment\n]\n";
+        assert_eq!(
+            positions(&rule, content),
+            vec![
+                (2, 20, "commas/too-many-spaces-after".to_string()),
+                (6, 2, "commas/too-many-spaces-before".to_string()),
+            ]
+        );
+
+        let disabled = CommasRule::with_config(CommasConfig {
+            max_spaces_before: -1,
+            min_spaces_after: -1,
+            max_spaces_after: -1,
+        });
+        assert!(disabled.check(content, "test.yaml").is_empty());
+    }
+
+    #[test]
+    fn test_commas_fix_nested_collections() {
+        let rule = CommasRule::new();
+        let content = "a: [1 ,2,   {b: x ,c: y}]\ntext: a ,b\nflow: [\n  a\n  , b]\n";
+        let fix_result = rule.fix(content, "test.yaml");
+        assert_eq!(
+            fix_result.content,
+            "a: [1, 2, {b: x, c: y}]\ntext: a ,b\nflow: [\n  a\n  , b]\n"
+        );
+        assert_eq!(fix_result.fixes_applied, 5);
+        assert_eq!(rule.check(&fix_result.content, "test.yaml").len(), 1);
+    }
 }
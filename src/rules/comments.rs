@@ -1,8 +1,11 @@
+use crate::analysis::ContentAnalysis;
 use crate::rules::base::BaseRule;
 use crate::rules::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct CommentsConfig {
     pub min_spaces_from_content: usize,
 }
@@ -53,11 +56,18 @@ impl CommentsRule {
         self.base.has_severity_override()
     }
 
-    pub fn create_issue(&self, line: usize, column: usize, message: String) -> LintIssue {
+    pub fn create_issue(
+        &self,
+        line: usize,
+        column: usize,
+        message: String,
+        code: &str,
+    ) -> LintIssue {
         LintIssue {
             line,
             column,
             message,
+            code: code.to_string(),
             severity: self.get_severity(),
         }
     }
@@ -98,6 +108,10 @@ impl Rule for CommentsRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         true
     }
@@ -105,14 +119,31 @@ impl Rule for CommentsRule {
     fn check(&self, content: &str, file_path: &str) -> Vec<LintIssue> {
         self.check_impl(content, file_path)
     }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        _file_path: &str,
+        analysis: &ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        self.check_lines(content, analysis)
+    }
 }
 
 impl CommentsRule {
     pub fn check_impl(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        self.check_lines(content, &ContentAnalysis::analyze(content))
+    }
+
+    fn check_lines(&self, content: &str, analysis: &ContentAnalysis) -> Vec<LintIssue> {
         let mut issues = Vec::new();
 
         for (line_num, line) in content.lines().enumerate() {
             let line_num = line_num + 1;
+            // A `#` inside a literal or folded scalar is text, not a comment
+            if analysis.in_block_scalar(line_num) {
+                continue;
+            }
 
             if let Some(comment_pos) = line.find('#') {
                 let before_comment: String = line.chars().take(comment_pos).collect();
@@ -127,6 +158,7 @@ impl CommentsRule {
                             line_num,
                             comment_pos + 1,
                             "too few spaces before comment".to_string(),
+                            "comments/too-few-spaces-before",
                         ));
                     }
                 }
@@ -137,10 +169,15 @@ impl CommentsRule {
     }
 
     pub fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
+        let analysis = ContentAnalysis::analyze(content);
         let mut fixed_lines = Vec::new();
         let mut fixes_applied = 0;
 
-        for line in content.lines() {
+        for (line_num, line) in content.lines().enumerate() {
+            if analysis.in_block_scalar(line_num + 1) {
+                fixed_lines.push(line.to_string());
+                continue;
+            }
             if let Some(comment_pos) = line.find('#') {
                 let before_comment: String = line.chars().take(comment_pos).collect();
                 if !before_comment.trim().is_empty() {
@@ -232,4 +269,17 @@ mod tests {
         assert!(!fix_result.changed);
         assert_eq!(fix_result.fixes_applied, 0);
     }
+
+    #[test]
+    fn test_comments_skips_block_scalars() {
+        let rule = CommentsRule::new();
+        let content = "script: >\n  curl example.com/#anchor\nkey: value # comment\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 3);
+
+        let fix_result = rule.fix(content, "test.yaml");
+        assert_eq!(fix_result.fixes_applied, 1);
+        assert!(fix_result.content.contains("curl example.com/#anchor\n"));
+    }
 }
@@ -1,11 +1,20 @@
 use super::Rule;
+use crate::analysis::ContentAnalysis;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct CommentsIndentationConfig {
     pub indent: usize,
 }
 
+impl Default for CommentsIndentationConfig {
+    fn default() -> Self {
+        Self { indent: 2 }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CommentsIndentationRule {
     config: CommentsIndentationConfig,
@@ -15,7 +24,7 @@ pub struct CommentsIndentationRule {
 impl CommentsIndentationRule {
     pub fn new() -> Self {
         Self {
-            config: CommentsIndentationConfig { indent: 2 },
+            config: CommentsIndentationConfig::default(),
             severity_override: None,
         }
     }
@@ -58,13 +67,27 @@ impl Rule for CommentsIndentationRule {
         self.severity_override.is_some()
     }
 
-    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().config).unwrap_or_default()
+    }
+
+    fn check(&self, content: &str, file_path: &str) -> Vec<LintIssue> {
+        self.check_with_analysis(content, file_path, &ContentAnalysis::analyze(content))
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        _file_path: &str,
+        analysis: &ContentAnalysis,
+    ) -> Vec<LintIssue> {
         let mut issues = Vec::new();
 
         for (line_num, line) in content.lines().enumerate() {
             let line_num = line_num + 1;
 
-            if line.trim().is_empty() {
+            // A `#` inside a literal or folded scalar is text, not a comment
+            if line.trim().is_empty() || analysis.in_block_scalar(line_num) {
                 continue;
             }
 
@@ -81,6 +104,7 @@ impl Rule for CommentsIndentationRule {
                                 (current_indent / self.config.indent + 1) * self.config.indent,
                                 current_indent
                             ),
+                            code: "comments-indentation/wrong-indentation".to_string(),
                             severity: self.get_severity(),
                         });
                     }
@@ -96,13 +120,14 @@ impl Rule for CommentsIndentationRule {
     }
 
     fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
+        let analysis = ContentAnalysis::analyze(content);
         let mut fixed_lines = Vec::new();
         let mut fixes_applied = 0;
 
-        for line in content.lines() {
+        for (line_num, line) in content.lines().enumerate() {
             let mut fixed_line = line.to_string();
 
-            if line.trim().is_empty() {
+            if line.trim().is_empty() || analysis.in_block_scalar(line_num + 1) {
                 fixed_lines.push(fixed_line);
                 continue;
             }
@@ -199,4 +224,19 @@ mod tests {
         assert!(!fix_result.changed);
         assert_eq!(fix_result.fixes_applied, 0);
     }
+
+    #[test]
+    fn test_comments_indentation_skips_block_scalars() {
+        let rule = CommentsIndentationRule::new();
+        let content = "script: |\n   # not a comment\n   echo hi\n # comment\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 4);
+
+        let fix_result = rule.fix(content, "test.yaml");
+        assert_eq!(
+            fix_result.content,
+            "script: |\n   # not a comment\n   echo hi\n  # comment\n"
+        );
+    }
 }
@@ -0,0 +1,271 @@
+use super::Rule;
+use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, Token, TokenType};
+
+/// The types of the YAML 1.1 tag repository, written with the `!!` handle
+const STANDARD_TAGS: &[&str] = &[
+    "!!binary",
+    "!!bool",
+    "!!float",
+    "!!int",
+    "!!map",
+    "!!merge",
+    "!!null",
+    "!!omap",
+    "!!pairs",
+    "!!seq",
+    "!!set",
+    "!!str",
+    "!!timestamp",
+    "!!value",
+];
+
+/// The short forms of CloudFormation intrinsic functions
+const CLOUDFORMATION_TAGS: &[&str] = &[
+    "!And",
+    "!Base64",
+    "!Cidr",
+    "!Condition",
+    "!Equals",
+    "!FindInMap",
+    "!GetAZs",
+    "!GetAtt",
+    "!If",
+    "!ImportValue",
+    "!Join",
+    "!Not",
+    "!Or",
+    "!Ref",
+    "!Select",
+    "!Split",
+    "!Sub",
+];
+
+/// A named set of tags that are allowed together
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TagPreset {
+    /// CloudFormation intrinsic functions such as `!Ref` and `!GetAtt`
+    Cloudformation,
+}
+
+impl TagPreset {
+    fn tags(self) -> &'static [&'static str] {
+        match self {
+            TagPreset::Cloudformation => CLOUDFORMATION_TAGS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct CustomTagsConfig {
+    /// Allow the standard `!!` tags such as `!!str` and `!!binary`
+    pub standard_tags: bool,
+    /// Other tags allowed, such as `!Ref` or `!!python/tuple`
+    pub allowed: Vec<String>,
+    /// Named sets of allowed tags
+    pub presets: Vec<TagPreset>,
+}
+
+impl Default for CustomTagsConfig {
+    fn default() -> Self {
+        Self {
+            standard_tags: true,
+            allowed: Vec::new(),
+            presets: Vec::new(),
+        }
+    }
+}
+
+/// A tag as written, with verbatim standard tags such as
+/// `!<tag:yaml.org,2002:str>` in their `!!str` short form
+fn tag_name(handle: &str, suffix: &str) -> String {
+    if !handle.is_empty() {
+        return format!("{}{}", handle, suffix);
+    }
+    match suffix.strip_prefix("tag:yaml.org,2002:") {
+        Some(name) => format!("!!{}", name),
+        // The non-specific `!` tag
+        None if suffix == "!" => suffix.to_string(),
+        None => format!("!<{}>", suffix),
+    }
+}
+
+/// Flags tags outside an allow-list, such as `!!python/object` that makes
+/// unsafe loaders run code, or application tags like `!Ref` that other
+/// tools cannot load
+#[derive(Debug, Clone)]
+pub struct CustomTagsRule {
+    base: crate::rules::base::BaseRule<CustomTagsConfig>,
+}
+
+impl CustomTagsRule {
+    pub fn new() -> Self {
+        Self::with_config(CustomTagsConfig::default())
+    }
+
+    pub fn with_config(config: CustomTagsConfig) -> Self {
+        Self {
+            base: crate::rules::base::BaseRule::new(config),
+        }
+    }
+
+    pub fn config(&self) -> &CustomTagsConfig {
+        self.base.config()
+    }
+
+    pub fn set_config(&mut self, config: CustomTagsConfig) {
+        self.base.set_config(config);
+    }
+
+    fn is_allowed(&self, tag: &str) -> bool {
+        let config = self.config();
+        tag == "!"
+            || (config.standard_tags && STANDARD_TAGS.contains(&tag))
+            || config.allowed.iter().any(|allowed| allowed == tag)
+            || config
+                .presets
+                .iter()
+                .any(|preset| preset.tags().contains(&tag))
+    }
+
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+
+        for Token(marker, token) in tokens {
+            if let TokenType::Tag(handle, suffix) = token {
+                let tag = tag_name(handle, suffix);
+                if !self.is_allowed(&tag) {
+                    issues.push(LintIssue {
+                        line: marker.line(),
+                        column: marker.col() + 1,
+                        message: format!("tag \"{}\" is not allowed", tag),
+                        code: "custom-tags/not-allowed".to_string(),
+                        severity: self.get_severity(),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+impl Default for CustomTagsRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for CustomTagsRule {
+    fn rule_id(&self) -> &'static str {
+        "custom-tags"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "Custom Tags"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Flags tags outside an allow-list, such as !!python/object."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_tags_rule_default() {
+        let rule = CustomTagsRule::new();
+        assert_eq!(rule.rule_id(), "custom-tags");
+        assert_eq!(rule.default_severity(), Severity::Error);
+        assert!(!rule.is_enabled_by_default());
+        assert!(!rule.can_fix());
+    }
+
+    #[test]
+    fn test_custom_tags_reports_unknown_tags() {
+        let rule = CustomTagsRule::new();
+        let content = "name: !!str 42\nverbatim: !<tag:yaml.org,2002:int> 1\nplain: ! text\nobject: !!python/object:os.system {}\nbucket: !Ref Bucket\nother: !<tag:example.com,2024:thing> x\n";
+        let issues = rule.check(content, "test.yaml");
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.line, issue.column, issue.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (4, 9, "tag \"!!python/object:os.system\" is not allowed"),
+                (5, 9, "tag \"!Ref\" is not allowed"),
+                (6, 8, "tag \"!<tag:example.com,2024:thing>\" is not allowed"),
+            ]
+        );
+        assert_eq!(issues[0].code, "custom-tags/not-allowed");
+    }
+
+    #[test]
+    fn test_custom_tags_allow_list_and_presets() {
+        let content =
+            "bucket: !Ref Bucket\narn: !GetAtt Bucket.Arn\ncustom: !Env HOME\nset: !!set {a}\n";
+        let rule = CustomTagsRule::with_config(CustomTagsConfig {
+            presets: vec![TagPreset::Cloudformation],
+            ..Default::default()
+        });
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 3);
+
+        let rule = CustomTagsRule::with_config(CustomTagsConfig {
+            standard_tags: false,
+            allowed: vec!["!Env".to_string()],
+            presets: vec![TagPreset::Cloudformation],
+        });
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 4);
+    }
+}
@@ -0,0 +1,167 @@
+use super::{base::BaseRule, registry::RuleRegistry, Rule};
+use crate::directives::{malformed_directive, malformed_modeline, DirectiveState};
+use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DirectiveSyntaxConfig {}
+
+/// Flags `# yamllint ...` comments that would be silently ignored because
+/// they do not follow the directive syntax or name unknown rules
+#[derive(Debug, Clone)]
+pub struct DirectiveSyntaxRule {
+    base: BaseRule<DirectiveSyntaxConfig>,
+    known_rules: HashSet<String>,
+}
+
+impl DirectiveSyntaxRule {
+    pub fn new() -> Self {
+        Self {
+            base: BaseRule::new(DirectiveSyntaxConfig::default()),
+            known_rules: RuleRegistry::new().get_rule_ids().into_iter().collect(),
+        }
+    }
+
+    pub fn get_severity(&self) -> Severity {
+        self.base.get_severity(Severity::Warning)
+    }
+
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    pub fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    pub fn check_impl(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+
+        for (line_num, line) in content.lines().enumerate() {
+            let comment = if line.trim_start().starts_with('#') {
+                Some(line.trim_start())
+            } else {
+                DirectiveState::extract_inline_comment(line)
+            };
+            let Some(comment) = comment else {
+                continue;
+            };
+
+            let is_known_rule = |rule: &str| self.known_rules.contains(rule);
+            if let Some(message) = malformed_directive(comment, is_known_rule)
+                .or_else(|| malformed_modeline(comment, line_num + 1, is_known_rule))
+            {
+                issues.push(LintIssue {
+                    line: line_num + 1,
+                    column: line.len() - comment.len() + 1,
+                    message,
+                    code: "directive-syntax/malformed-directive".to_string(),
+                    severity: self.get_severity(),
+                });
+            }
+        }
+
+        issues
+    }
+}
+
+impl Default for DirectiveSyntaxRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for DirectiveSyntaxRule {
+    fn rule_id(&self) -> &'static str {
+        "directive-syntax"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "Directive Syntax"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Flags yamllint directive comments that are malformed or name unknown rules."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
+    fn check(&self, content: &str, file_path: &str) -> Vec<LintIssue> {
+        self.check_impl(content, file_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directive_syntax_rule_default() {
+        let rule = DirectiveSyntaxRule::new();
+        assert_eq!(rule.rule_id(), "directive-syntax");
+        assert_eq!(rule.default_severity(), Severity::Warning);
+        assert!(rule.is_enabled_by_default());
+        assert!(!rule.can_fix());
+    }
+
+    #[test]
+    fn test_directive_syntax_accepts_valid_directives() {
+        let rule = DirectiveSyntaxRule::new();
+        let content = "# yamllint disable rule:line-length\nkey: value  # yamllint disable-line\n# yamllint enable\n# yamllint reports 0 issues for this file\n";
+        let issues = rule.check(content, "test.yaml");
+        assert!(issues.is_empty(), "unexpected issues: {:?}", issues);
+    }
+
+    #[test]
+    fn test_directive_syntax_flags_near_misses() {
+        let rule = DirectiveSyntaxRule::new();
+        let content = "# yamllint disable rule line-length\nkey: value  # yamllint disable-line rule:line-lenght\n#yamllint enable\n# yamllint disabel\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 4);
+        assert_eq!((issues[0].line, issues[0].column), (1, 1));
+        assert!(issues[0].message.contains("malformed directive"));
+        assert_eq!((issues[1].line, issues[1].column), (2, 13));
+        assert!(issues[1].message.contains("unknown rule \"line-lenght\""));
+        assert_eq!(issues[2].line, 3);
+        assert_eq!(issues[3].line, 4);
+    }
+
+    #[test]
+    fn test_directive_syntax_checks_modelines() {
+        let rule = DirectiveSyntaxRule::new();
+        let valid = "# yamllint-rs: line-length.max=120 indentation.spaces=4\nkey: value\n";
+        assert!(rule.check(valid, "test.yaml").is_empty());
+
+        let issues = rule.check("# yamllint-rs: line-lenght.max=120\n", "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0]
+            .message
+            .contains("unknown rule \"line-lenght\" in modeline"));
+
+        let issues = rule.check("# yamllint-rs: max=120\n", "test.yaml");
+        assert!(issues[0].message.contains("malformed modeline entry"));
+
+        let issues = rule.check("---\n# yamllint-rs: line-length.max=120\n", "test.yaml");
+        assert_eq!(issues[0].line, 2);
+        assert!(issues[0].message.contains("only read on the first line"));
+    }
+}
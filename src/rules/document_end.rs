@@ -1,25 +1,37 @@
 use super::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct DocumentEndConfig {
     pub present: bool,
 }
 
+impl Default for DocumentEndConfig {
+    fn default() -> Self {
+        Self { present: true }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DocumentEndRule {
-    config: DocumentEndConfig,
+    base: crate::rules::base::BaseRule<DocumentEndConfig>,
 }
 
 impl DocumentEndRule {
     pub fn new() -> Self {
+        Self::with_config(DocumentEndConfig::default())
+    }
+
+    pub fn with_config(config: DocumentEndConfig) -> Self {
         Self {
-            config: DocumentEndConfig { present: true },
+            base: crate::rules::base::BaseRule::new(config),
         }
     }
 
-    pub fn with_config(config: DocumentEndConfig) -> Self {
-        Self { config }
+    pub fn config(&self) -> &DocumentEndConfig {
+        self.base.config()
     }
 }
 
@@ -41,13 +53,19 @@ impl Rule for DocumentEndRule {
     }
 
     fn get_severity(&self) -> Severity {
-        self.default_severity()
+        self.base.get_severity(self.default_severity())
     }
 
-    fn set_severity(&mut self, _severity: Severity) {}
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
 
     fn has_severity_override(&self) -> bool {
-        false
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
     }
 
     fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
@@ -57,23 +75,27 @@ impl Rule for DocumentEndRule {
             return issues;
         }
 
-        let last_line = content.lines().last().unwrap_or("");
-        let has_document_end = last_line.trim() == "...";
+        if !self.config().present {
+            // Every marker is forbidden, as in yamllint
+            for (line, _) in document_end_markers(content) {
+                issues.push(LintIssue {
+                    line: line + 1,
+                    column: 1,
+                    message: "found forbidden document end \"...\"".to_string(),
+                    code: "document-end/forbidden".to_string(),
+                    severity: self.get_severity(),
+                });
+            }
+            return issues;
+        }
 
-        if self.config.present && !has_document_end {
-            let line_count = content.lines().count();
+        let last_line = content.lines().last().unwrap_or("");
+        if last_line.trim() != "..." {
             issues.push(LintIssue {
-                line: line_count,
+                line: content.lines().count(),
                 column: 1,
                 message: "missing document end marker (...)".to_string(),
-                severity: self.get_severity(),
-            });
-        } else if !self.config.present && has_document_end {
-            let line_count = content.lines().count();
-            issues.push(LintIssue {
-                line: line_count,
-                column: 1,
-                message: "document end marker (...) should not be present".to_string(),
+                code: "document-end/missing".to_string(),
                 severity: self.get_severity(),
             });
         }
@@ -86,51 +108,80 @@ impl Rule for DocumentEndRule {
     }
 
     fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
+        let unchanged = || super::FixResult {
+            content: content.to_string(),
+            changed: false,
+            fixes_applied: 0,
+        };
         if content.trim().is_empty() {
-            return super::FixResult {
-                content: content.to_string(),
-                changed: false,
-                fixes_applied: 0,
-            };
+            return unchanged();
         }
 
-        let last_line = content.lines().last().unwrap_or("");
-        let has_document_end = last_line.trim() == "...";
-
-        let mut fixed_content = content.to_string();
-        let mut fixes_applied = 0;
-
-        if self.config.present && !has_document_end {
-            if content.ends_with('\n') {
-                fixed_content = format!("{}...\n", content.trim_end());
-            } else {
-                fixed_content = format!("{}\n...", content);
-            }
-            fixes_applied = 1;
-        } else if !self.config.present && has_document_end {
-            let lines: Vec<&str> = content.lines().collect();
-            if lines.len() > 1 {
-                fixed_content = lines[..lines.len() - 1].join("\n");
-            } else {
-                fixed_content = "".to_string();
-            }
-            fixes_applied = 1;
+        if !self.config().present {
+            let removable: Vec<usize> = document_end_markers(content)
+                .into_iter()
+                .filter_map(|(line, removable)| removable.then_some(line))
+                .collect();
+            let fixed_content: String = content
+                .split_inclusive('\n')
+                .enumerate()
+                .filter(|(index, _)| !removable.contains(index))
+                .map(|(_, line)| line)
+                .collect();
+            return super::FixResult {
+                content: fixed_content,
+                changed: !removable.is_empty(),
+                fixes_applied: removable.len(),
+            };
         }
 
-        if content.ends_with('\n') && !fixed_content.ends_with('\n') {
-            fixed_content.push('\n');
+        if content.lines().last().unwrap_or("").trim() == "..." {
+            return unchanged();
         }
-
-        let changed = fixes_applied > 0;
+        let fixed_content = if content.ends_with('\n') {
+            format!("{}...\n", content.trim_end())
+        } else {
+            format!("{}\n...", content)
+        };
 
         super::FixResult {
             content: fixed_content,
-            changed,
-            fixes_applied,
+            changed: true,
+            fixes_applied: 1,
         }
     }
 }
 
+/// `...` at column 1, alone or followed by whitespace (`... # comment`)
+pub(crate) fn is_document_end_marker(line: &str) -> bool {
+    line.strip_prefix("...")
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+}
+
+/// 0-based lines of every `...` marker, with whether it can be removed:
+/// only when the stream ends or a `---` follows it, since a document after
+/// it would otherwise merge into the one it ends, and directives after it
+/// require it
+fn document_end_markers(content: &str) -> Vec<(usize, bool)> {
+    let lines: Vec<&str> = content.lines().collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_document_end_marker(line))
+        .map(|(index, _)| {
+            let next = lines[index + 1..].iter().find(|line| {
+                let trimmed = line.trim();
+                !trimmed.is_empty() && !trimmed.starts_with('#')
+            });
+            let removable = next.is_none_or(|line| {
+                super::document_start::is_document_start_marker(line)
+                    || is_document_end_marker(line)
+            });
+            (index, removable)
+        })
+        .collect()
+}
+
 impl Default for DocumentEndRule {
     fn default() -> Self {
         Self::new()
@@ -186,4 +237,24 @@ mod tests {
         assert!(!fix_result.changed);
         assert_eq!(fix_result.fixes_applied, 0);
     }
+
+    #[test]
+    fn test_document_end_forbids_every_marker() {
+        let rule = DocumentEndRule::with_config(DocumentEndConfig { present: false });
+        let content = "a: 1\n...\n---\nb: 2\n...\nc: 3\n...\n%YAML 1.2\n---\nd: 4\n... # end\n";
+        let issues = rule.check(content, "t.yaml");
+        assert_eq!(
+            issues.iter().map(|issue| issue.line).collect::<Vec<_>>(),
+            vec![2, 5, 7, 11]
+        );
+        assert_eq!(issues[0].message, "found forbidden document end \"...\"");
+
+        // Markers before a bare document or directives stay
+        let fixed = rule.fix(content, "t.yaml");
+        assert_eq!(fixed.fixes_applied, 2);
+        assert_eq!(
+            fixed.content,
+            "a: 1\n---\nb: 2\n...\nc: 3\n...\n%YAML 1.2\n---\nd: 4\n"
+        );
+    }
 }
@@ -1,25 +1,37 @@
 use super::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct DocumentStartConfig {
     pub present: bool,
 }
 
+impl Default for DocumentStartConfig {
+    fn default() -> Self {
+        Self { present: true }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DocumentStartRule {
-    config: DocumentStartConfig,
+    base: crate::rules::base::BaseRule<DocumentStartConfig>,
 }
 
 impl DocumentStartRule {
     pub fn new() -> Self {
+        Self::with_config(DocumentStartConfig::default())
+    }
+
+    pub fn with_config(config: DocumentStartConfig) -> Self {
         Self {
-            config: DocumentStartConfig { present: true },
+            base: crate::rules::base::BaseRule::new(config),
         }
     }
 
-    pub fn with_config(config: DocumentStartConfig) -> Self {
-        Self { config }
+    pub fn config(&self) -> &DocumentStartConfig {
+        self.base.config()
     }
 }
 
@@ -41,42 +53,46 @@ impl Rule for DocumentStartRule {
     }
 
     fn get_severity(&self) -> Severity {
-        self.default_severity()
+        self.base.get_severity(self.default_severity())
     }
 
-    fn set_severity(&mut self, _severity: Severity) {}
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
 
     fn has_severity_override(&self) -> bool {
-        false
+        self.base.has_severity_override()
     }
 
-    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
-        let mut issues = Vec::new();
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
 
-        if content.trim().is_empty() {
-            return issues;
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        if !self.config().present {
+            // Every marker is forbidden, as in yamllint
+            return document_start_markers(content)
+                .into_iter()
+                .map(|(line, _)| LintIssue {
+                    line: line + 1,
+                    column: 1,
+                    message: "found forbidden document start \"---\"".to_string(),
+                    code: "document-start/forbidden".to_string(),
+                    severity: self.get_severity(),
+                })
+                .collect();
         }
 
-        let first_line = content.lines().next().unwrap_or("");
-        let has_document_start = first_line.trim() == "---";
-
-        if self.config.present && !has_document_start {
-            issues.push(LintIssue {
-                line: 1,
+        match DocumentStart::find(content) {
+            Some(start) if !start.has_marker => vec![LintIssue {
+                line: start.line + 1,
                 column: 1,
                 message: "missing document start \"---\"".to_string(),
+                code: "document-start/missing".to_string(),
                 severity: self.get_severity(),
-            });
-        } else if !self.config.present && has_document_start {
-            issues.push(LintIssue {
-                line: 1,
-                column: 1,
-                message: "document start marker (---) should not be present".to_string(),
-                severity: self.get_severity(),
-            });
+            }],
+            _ => Vec::new(),
         }
-
-        issues
     }
 
     fn can_fix(&self) -> bool {
@@ -84,54 +100,123 @@ impl Rule for DocumentStartRule {
     }
 
     fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
-        if content.trim().is_empty() {
-            return super::FixResult {
-                content: content.to_string(),
-                changed: false,
-                fixes_applied: 0,
-            };
+        if !self.config().present {
+            return remove_markers(content);
         }
 
-        let first_line = content.lines().next().unwrap_or("");
-        let has_document_start = first_line.trim() == "---";
+        let unchanged = || super::FixResult {
+            content: content.to_string(),
+            changed: false,
+            fixes_applied: 0,
+        };
+        let Some(start) = DocumentStart::find(content).filter(|start| !start.has_marker) else {
+            return unchanged();
+        };
 
-        let mut fixed_content = content.to_string();
-        let mut fixes_applied = 0;
+        let line_start: usize = content
+            .split_inclusive('\n')
+            .take(start.line)
+            .map(str::len)
+            .sum();
+        let line = content[line_start..]
+            .split_inclusive('\n')
+            .next()
+            .unwrap_or("");
+        let newline = if line.ends_with("\r\n") { "\r\n" } else { "\n" };
 
-        if self.config.present && !has_document_start {
-            if content.ends_with('\n') {
-                fixed_content = format!("---\n{}", content);
-            } else {
-                fixed_content = format!("---\n{}\n", content);
-            }
-            fixes_applied = 1;
-        } else if !self.config.present && has_document_start {
-            let lines: Vec<&str> = content.lines().collect();
-            if lines.len() > 1 {
-                fixed_content = lines[1..].join("\n");
-            } else {
-                fixed_content = "".to_string();
+        super::FixResult {
+            content: format!(
+                "{}---{}{}",
+                &content[..line_start],
+                newline,
+                &content[line_start..]
+            ),
+            changed: true,
+            fixes_applied: 1,
+        }
+    }
+}
+
+/// Removes the `---` markers that can go without merging documents. Content
+/// on a marker line itself is kept.
+fn remove_markers(content: &str) -> super::FixResult {
+    let removable: Vec<usize> = document_start_markers(content)
+        .into_iter()
+        .filter_map(|(line, removable)| removable.then_some(line))
+        .collect();
+
+    let mut fixed_content = String::with_capacity(content.len());
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        if removable.contains(&index) {
+            let rest = line[3..].trim_start_matches([' ', '\t']);
+            if !(rest.trim().is_empty() || rest.starts_with('#')) {
+                fixed_content.push_str(rest);
             }
-            fixes_applied = 1;
+        } else {
+            fixed_content.push_str(line);
         }
+    }
+
+    super::FixResult {
+        content: fixed_content,
+        changed: !removable.is_empty(),
+        fixes_applied: removable.len(),
+    }
+}
+
+/// 0-based lines of every `---` marker, with whether it can be removed. Only
+/// a marker starting the stream without directives can: after `...` a
+/// document must start with `---`, and directives need one too.
+fn document_start_markers(content: &str) -> Vec<(usize, bool)> {
+    let mut markers = Vec::new();
+    let mut at_stream_start = true;
 
-        if content.ends_with('\n') && !fixed_content.ends_with('\n') {
-            fixed_content.push('\n');
-        } else if !content.ends_with('\n') && fixed_content.ends_with('\n') && !self.config.present
-        {
-            fixed_content.pop();
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
         }
+        if is_document_start_marker(line) {
+            markers.push((index, at_stream_start));
+        }
+        at_stream_start = false;
+    }
 
-        let changed = fixes_applied > 0;
+    markers
+}
 
-        super::FixResult {
-            content: fixed_content,
-            changed,
-            fixes_applied,
+/// Where the first document starts. Like yamllint, leading blank lines,
+/// comments and `%` directives are skipped; the first line after them
+/// either is the `---` marker or is content that should follow one.
+struct DocumentStart {
+    /// 0-based index of the marker or first content line
+    line: usize,
+    has_marker: bool,
+}
+
+impl DocumentStart {
+    /// `None` when the file has no content (only blank lines and comments)
+    fn find(content: &str) -> Option<Self> {
+        for (index, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') || line.starts_with('%') {
+                continue;
+            }
+            return Some(Self {
+                line: index,
+                has_marker: is_document_start_marker(line),
+            });
         }
+        None
     }
 }
 
+/// `---` at column 1, alone or followed by whitespace (`--- # comment`, `--- !tag`)
+pub(crate) fn is_document_start_marker(line: &str) -> bool {
+    line.strip_prefix("---")
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+}
+
 impl Default for DocumentStartRule {
     fn default() -> Self {
         Self::new()
@@ -187,4 +272,123 @@ mod tests {
         assert!(!fix_result.changed);
         assert_eq!(fix_result.fixes_applied, 0);
     }
+
+    /// Issue lines for every combination of leading comments, blank lines and
+    /// directives, as reported by yamllint
+    #[test]
+    fn test_document_start_skips_leading_comments_and_directives() {
+        let prefixes = [
+            ("", 0),
+            ("# license\n", 1),
+            ("\n", 1),
+            ("# license\n# header\n\n", 3),
+            ("%YAML 1.2\n", 1),
+            ("# license\n%YAML 1.2\n%TAG ! tag:example.com,2000:\n", 3),
+            ("\n# license\n\n%YAML 1.2\n# after directive\n", 5),
+        ];
+        let present = DocumentStartRule::new();
+        let forbidden = DocumentStartRule::with_config(DocumentStartConfig { present: false });
+
+        for (prefix, skipped) in prefixes {
+            let with_marker = format!("{}---\nkey: value\n", prefix);
+            let without_marker = format!("{}key: value\n", prefix);
+            let lines = |issues: Vec<LintIssue>| -> Vec<usize> {
+                issues.iter().map(|issue| issue.line).collect()
+            };
+
+            assert_eq!(
+                lines(present.check(&with_marker, "t.yaml")),
+                Vec::<usize>::new(),
+                "{:?}",
+                prefix
+            );
+            assert_eq!(
+                lines(present.check(&without_marker, "t.yaml")),
+                vec![skipped + 1],
+                "{:?}",
+                prefix
+            );
+            assert_eq!(
+                lines(forbidden.check(&with_marker, "t.yaml")),
+                vec![skipped + 1],
+                "{:?}",
+                prefix
+            );
+            assert_eq!(
+                lines(forbidden.check(&without_marker, "t.yaml")),
+                Vec::<usize>::new(),
+                "{:?}",
+                prefix
+            );
+
+            // Fixing inserts the marker after the prefix
+            let fixed = present.fix(&without_marker, "t.yaml");
+            assert_eq!(fixed.content, with_marker, "{:?}", prefix);
+        }
+    }
+
+    #[test]
+    fn test_document_start_marker_forms() {
+        let rule = DocumentStartRule::new();
+        for content in [
+            "--- # comment\nkey: value\n",
+            "--- !!map\nkey: value\n",
+            "--- {a: 1}\n",
+        ] {
+            assert!(rule.check(content, "t.yaml").is_empty(), "{:?}", content);
+        }
+        assert_eq!(rule.check("---key: value\n", "t.yaml").len(), 1);
+        assert!(rule.check("# only a comment\n\n", "t.yaml").is_empty());
+    }
+
+    #[test]
+    fn test_document_start_fix_forbidden() {
+        let rule = DocumentStartRule::with_config(DocumentStartConfig { present: false });
+        let fixed = rule.fix("# license\n--- # start\nkey: value\n", "t.yaml");
+        assert_eq!(fixed.content, "# license\nkey: value\n");
+        let fixed = rule.fix("--- {a: 1}\r\n", "t.yaml");
+        assert_eq!(fixed.content, "{a: 1}\r\n");
+        // The marker cannot be removed after directives
+        assert!(!rule.fix("%YAML 1.2\n---\nkey: value\n", "t.yaml").changed);
+
+        let rule = DocumentStartRule::new();
+        let fixed = rule.fix("# license\r\nkey: value\r\n", "t.yaml");
+        assert_eq!(fixed.content, "# license\r\n---\r\nkey: value\r\n");
+    }
+
+    #[test]
+    fn test_document_start_forbids_every_marker() {
+        let rule = DocumentStartRule::with_config(DocumentStartConfig { present: false });
+        let content =
+            "---\na: 1\n---\nb: 2\n...\n# next\n--- # c\nc: 3\n...\n%YAML 1.2\n---\nd: 4\n";
+        let issues = rule.check(content, "t.yaml");
+        assert_eq!(
+            issues.iter().map(|issue| issue.line).collect::<Vec<_>>(),
+            vec![1, 3, 7, 11]
+        );
+        assert_eq!(issues[0].message, "found forbidden document start \"---\"");
+
+        // Only the marker starting the stream goes; the others separate documents
+        let fixed = rule.fix(content, "t.yaml");
+        assert_eq!(fixed.fixes_applied, 1);
+        assert_eq!(
+            fixed.content,
+            "a: 1\n---\nb: 2\n...\n# next\n--- # c\nc: 3\n...\n%YAML 1.2\n---\nd: 4\n"
+        );
+    }
+
+    #[test]
+    fn test_document_start_fix_forbidden_keeps_documents_parsing() {
+        let rule = DocumentStartRule::with_config(DocumentStartConfig { present: false });
+        for content in [
+            "%YAML 1.2\n---\na: 1\n...\n---\nb: |\n  x\n...\n",
+            "---\na: 1\n...\n---\nb: 2\n---\nc: 3\n",
+        ] {
+            let fixed = rule.fix(content, "t.yaml");
+            let documents = yaml_rust::YamlLoader::load_from_str(&fixed.content)
+                .unwrap_or_else(|error| panic!("{:?}: {}", fixed.content, error));
+            let expected = yaml_rust::YamlLoader::load_from_str(content).unwrap();
+            assert_eq!(documents, expected);
+        }
+    }
 }
@@ -1,167 +1,116 @@
 use super::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct EmptyLinesConfig {
     pub max: usize,
     pub max_start: usize,
     pub max_end: usize,
 }
 
+impl Default for EmptyLinesConfig {
+    fn default() -> Self {
+        Self {
+            max: 2,
+            max_start: 0,
+            max_end: 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct EmptyLinesRule {
-    config: EmptyLinesConfig,
+    base: crate::rules::base::BaseRule<EmptyLinesConfig>,
 }
 
 impl EmptyLinesRule {
     pub fn new() -> Self {
+        Self::with_config(EmptyLinesConfig::default())
+    }
+
+    pub fn with_config(config: EmptyLinesConfig) -> Self {
         Self {
-            config: EmptyLinesConfig {
-                max: 2,
-                max_start: 0,
-                max_end: 0,
-            },
+            base: crate::rules::base::BaseRule::new(config),
         }
     }
 
-    pub fn with_config(config: EmptyLinesConfig) -> Self {
-        Self { config }
+    pub fn config(&self) -> &EmptyLinesConfig {
+        self.base.config()
     }
 
+    /// Port of yamllint's check. Only the start and end of the file use
+    /// `max-start` and `max-end`; document markers (`---`, `...`) are content
+    /// like any other line, so blank lines next to them count against `max`.
     fn check_empty_lines(&self, content: &str) -> Vec<LintIssue> {
         let mut issues = Vec::new();
-        let lines: Vec<&str> = content.lines().collect();
-
-        if lines.is_empty() {
-            return issues;
-        }
-
-        for (line_no, line) in lines.iter().enumerate() {
-            if line.trim().is_empty() {
-                let line_start = self.get_line_start_position(content, line_no);
-                let line_end = line_start + line.len();
-
-                if self.is_last_blank_line_of_series(content, line_end) {
-                    let blank_lines = self.count_consecutive_blank_lines(content, line_start);
-                    let max_allowed =
-                        self.get_max_allowed_for_position(content, line_start, line_end);
-
-                    if blank_lines > max_allowed {
-                        issues.push(LintIssue {
-                            line: line_no + 1,
-                            column: 1,
-                            message: format!(
-                                "too many blank lines ({} > {})",
-                                blank_lines, max_allowed
-                            ),
-                            severity: Severity::Warning,
-                        });
-                    }
-                }
+        let bytes = content.as_bytes();
+
+        let mut line_start = 0;
+        for (line_no, line) in content.split_inclusive('\n').enumerate() {
+            let start = line_start;
+            line_start += line.len();
+            let end = start + line_without_newline(line).len();
+            if start != end || end >= bytes.len() {
+                continue;
             }
-        }
-
-        issues
-    }
 
-    fn get_line_start_position(&self, content: &str, line_no: usize) -> usize {
-        let mut pos = 0;
-        for (i, line) in content.lines().enumerate() {
-            if i == line_no {
-                return pos;
+            // Only report the last blank line of a series
+            let rest = &bytes[end..];
+            if rest.starts_with(b"\n\n") || rest.starts_with(b"\r\n\r\n") {
+                continue;
             }
-            pos += line.len() + 1;
-        }
-        pos
-    }
-
-    fn is_last_blank_line_of_series(&self, content: &str, line_end: usize) -> bool {
-        let check_pos = line_end + 1;
 
-        if check_pos + 2 <= content.len() {
-            let next_chars: String = content.chars().skip(check_pos).take(2).collect();
-            if next_chars == "\n\n" {
-                return false;
+            let mut blank_lines = 0;
+            let mut pos = start;
+            while pos >= 2 && &bytes[pos - 2..pos] == b"\r\n" {
+                blank_lines += 1;
+                pos -= 2;
             }
-        }
-        if check_pos + 4 <= content.len() {
-            let next_chars: String = content.chars().skip(check_pos).take(4).collect();
-            if next_chars == "\r\n\r\n" {
-                return false;
+            while pos >= 1 && bytes[pos - 1] == b'\n' {
+                blank_lines += 1;
+                pos -= 1;
             }
-        }
-        true
-    }
-
-    fn count_consecutive_blank_lines(&self, content: &str, start: usize) -> usize {
-        let mut blank_lines = 0;
-        let mut pos = start;
 
-        while pos >= 2 {
-            let prev_chars: String = content.chars().skip(pos - 2).take(2).collect();
-            if prev_chars == "\r\n" {
+            let mut max = self.config().max;
+            if pos == 0 {
+                // The first line has no preceding newline
                 blank_lines += 1;
-                pos -= 2;
-            } else {
-                break;
+                max = self.config().max_start;
             }
-        }
-
-        while pos >= 1 && content.chars().nth(pos - 1) == Some('\n') {
-            let newline_pos = pos - 1;
-
-            let is_separator = if newline_pos + 1 < content.len() {
-                let char_after: String = content.chars().skip(newline_pos + 1).take(1).collect();
-
-                char_after != "\n" && char_after != "\r"
-            } else {
-                false
-            };
-
-            if is_separator {
-                break;
+            if rest == b"\n" || rest == b"\r\n" {
+                // A file that is a single newline is allowed
+                if end == 0 {
+                    continue;
+                }
+                max = self.config().max_end;
             }
 
-            blank_lines += 1;
-            pos -= 1;
+            if blank_lines > max {
+                issues.push(LintIssue {
+                    line: line_no + 1,
+                    column: 1,
+                    message: format!("too many blank lines ({} > {})", blank_lines, max),
+                    code: "empty-lines/too-many-blank-lines".to_string(),
+                    severity: self.get_severity(),
+                });
+            }
         }
 
-        blank_lines
+        issues
     }
+}
 
-    fn get_max_allowed_for_position(
-        &self,
-        content: &str,
-        line_start: usize,
-        line_end: usize,
-    ) -> usize {
-        let mut max = self.config.max;
-
-        if line_start == 0 {
-            max = self.config.max_start;
-        }
-
-        if (line_end == content.len() - 1 && content.chars().nth(line_end) == Some('\n'))
-            || (line_end == content.len() - 2)
-        {
-            if line_end == content.len() - 2 {
-                let end_chars: String = content.chars().skip(line_end).take(2).collect();
-                if end_chars == "\r\n" {
-                    if line_end == 0 {
-                        return 0;
-                    }
-                    max = self.config.max_end;
-                }
-            } else {
-                if line_end == 0 {
-                    return 0;
-                }
-                max = self.config.max_end;
-            }
-        }
+/// `line` without its `\n` or `\r\n` terminator
+fn line_without_newline(line: &str) -> &str {
+    line.strip_suffix('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .unwrap_or(line)
+}
 
-        max
-    }
+fn is_blank_line(line: &str) -> bool {
+    line_without_newline(line).is_empty()
 }
 
 impl Rule for EmptyLinesRule {
@@ -190,13 +139,19 @@ impl Rule for EmptyLinesRule {
     }
 
     fn get_severity(&self) -> Severity {
-        self.default_severity()
+        self.base.get_severity(self.default_severity())
     }
 
-    fn set_severity(&mut self, _severity: Severity) {}
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
 
     fn has_severity_override(&self) -> bool {
-        false
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
     }
 
     fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
@@ -204,45 +159,45 @@ impl Rule for EmptyLinesRule {
     }
 
     fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
-        let mut fixed_content = String::new();
-        let lines: Vec<&str> = content.lines().collect();
+        // Only blank lines are dropped; every other line is kept byte for byte
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let mut fixed_content = String::with_capacity(content.len());
+        let mut fixes_applied = 0;
 
         let mut i = 0;
         while i < lines.len() {
-            let line = lines[i];
-
-            if line.trim().is_empty() {
-                let mut empty_count = 0;
-                let mut j = i;
-                while j < lines.len() && lines[j].trim().is_empty() {
-                    empty_count += 1;
-                    j += 1;
-                }
-
-                let max_empty = if i == 0 {
-                    self.config.max_start
-                } else if j == lines.len() {
-                    self.config.max_end
-                } else {
-                    self.config.max
-                };
-
-                for _ in 0..empty_count.min(max_empty) {
-                    fixed_content.push('\n');
-                }
+            if !is_blank_line(lines[i]) {
+                fixed_content.push_str(lines[i]);
+                i += 1;
+                continue;
+            }
 
-                i = j;
+            let mut j = i;
+            while j < lines.len() && is_blank_line(lines[j]) {
+                j += 1;
+            }
+            let max_empty = if content == "\n" || content == "\r\n" {
+                1
+            } else if j == lines.len() {
+                self.config().max_end
+            } else if i == 0 {
+                self.config().max_start
             } else {
+                self.config().max
+            };
+            if j - i > max_empty {
+                fixes_applied += 1;
+            }
+            for line in &lines[i..j.min(i + max_empty)] {
                 fixed_content.push_str(line);
-                fixed_content.push('\n');
-                i += 1;
             }
+            i = j;
         }
 
         super::FixResult {
-            content: fixed_content.clone(),
             changed: fixed_content != content,
-            fixes_applied: 0,
+            content: fixed_content,
+            fixes_applied,
         }
     }
 }
@@ -255,7 +210,7 @@ mod tests {
     fn test_empty_lines_rule_default() {
         let rule = EmptyLinesRule::new();
         assert_eq!(rule.rule_id(), "empty-lines");
-        assert_eq!(rule.config.max, 2);
+        assert_eq!(rule.config().max, 2);
         assert_eq!(rule.default_severity(), Severity::Warning);
         assert!(rule.is_enabled_by_default());
         assert!(rule.can_fix());
@@ -289,9 +244,10 @@ mod tests {
     #[test]
     fn test_empty_lines_fix() {
         let rule = EmptyLinesRule::new();
-        let content = "key1: value1\n\n\nkey2: value2";
+        let content = "key1: value1\n\n\n\nkey2: value2";
         let fix_result = rule.fix(content, "test.yaml");
         assert!(fix_result.changed);
+        assert_eq!(fix_result.content, "key1: value1\n\n\nkey2: value2");
     }
 
     #[test]
@@ -316,4 +272,106 @@ mod tests {
             issues
         );
     }
+
+    fn issue_lines(rule: &EmptyLinesRule, content: &str) -> Vec<(usize, String)> {
+        rule.check(content, "test.yaml")
+            .into_iter()
+            .map(|issue| (issue.line, issue.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_lines_document_markers_count_as_content() {
+        let rule = EmptyLinesRule::with_config(EmptyLinesConfig {
+            max: 1,
+            max_start: 0,
+            max_end: 0,
+        });
+
+        // Blank lines after `---` and before `...` are interior: `max` applies
+        assert!(issue_lines(&rule, "---\n\nkey: value\n\n...\n").is_empty());
+        assert_eq!(
+            issue_lines(&rule, "---\n\n\nkey: value\n"),
+            vec![(3, "too many blank lines (2 > 1)".to_string())]
+        );
+        assert_eq!(
+            issue_lines(&rule, "key: a\n...\n\n\n---\nkey: b\n"),
+            vec![(4, "too many blank lines (2 > 1)".to_string())]
+        );
+
+        // Only the file boundaries use max-start and max-end
+        assert_eq!(
+            issue_lines(&rule, "\n---\nkey: value\n...\n\n"),
+            vec![
+                (1, "too many blank lines (1 > 0)".to_string()),
+                (5, "too many blank lines (1 > 0)".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_lines_counts_lines_not_bytes() {
+        let rule = EmptyLinesRule::new();
+        assert_eq!(
+            issue_lines(&rule, "a: é\n\n\n\nb: 1\n"),
+            vec![(4, "too many blank lines (3 > 2)".to_string())]
+        );
+        assert_eq!(
+            issue_lines(&rule, "a: 1\r\n\r\n\r\n\r\nb: 1\r\n"),
+            vec![(4, "too many blank lines (3 > 2)".to_string())]
+        );
+        assert!(issue_lines(&rule, "\n").is_empty());
+        assert!(issue_lines(&rule, "").is_empty());
+    }
+
+    #[test]
+    fn test_empty_lines_fix_only_touches_blank_runs() {
+        let rule = EmptyLinesRule::with_config(EmptyLinesConfig {
+            max: 1,
+            max_start: 0,
+            max_end: 0,
+        });
+        let fixed = rule.fix(
+            "\r\n---\r\n\r\n\r\nkey: value  \r\n...\r\n\r\n",
+            "test.yaml",
+        );
+        assert_eq!(fixed.content, "---\r\n\r\nkey: value  \r\n...\r\n");
+        assert_eq!(fixed.fixes_applied, 3);
+
+        // A missing final newline is left to new-line-at-end-of-file
+        let fixed = rule.fix("---\n\nkey: value", "test.yaml");
+        assert!(!fixed.changed);
+        assert!(!rule.fix("\n", "test.yaml").changed);
+    }
+
+    #[test]
+    fn test_empty_lines_fixed_content_passes_the_check() {
+        let contents = [
+            "\n\n\na: 1\n\n\n\n\nb: 2\n\n\n",
+            "---\n\n\n\na: 1\n...\n\n\n\n---\nb: 2\n",
+            "\r\n\r\na: 1\r\n\r\n\r\n\r\nb: 2\r\n\r\n",
+            "\n\n\n",
+        ];
+        for (max, max_start, max_end) in [(2, 0, 0), (1, 1, 1), (0, 2, 0), (3, 0, 2)] {
+            let mut rule = EmptyLinesRule::with_config(EmptyLinesConfig {
+                max,
+                max_start,
+                max_end,
+            });
+            rule.set_severity(Severity::Error);
+            for content in contents {
+                let issues = rule.check(content, "test.yaml");
+                assert!(issues.iter().all(|issue| issue.severity == Severity::Error));
+                let fixed = rule.fix(content, "test.yaml");
+                assert_eq!(fixed.changed, !issues.is_empty(), "{:?}", content);
+                assert!(
+                    rule.check(&fixed.content, "test.yaml").is_empty(),
+                    "{:?} with {:?} fixed to {:?}",
+                    content,
+                    (max, max_start, max_end),
+                    fixed.content
+                );
+            }
+        }
+    }
 }
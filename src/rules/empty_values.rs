@@ -1,30 +1,89 @@
 use super::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct EmptyValuesConfig {
-    pub forbid_empty: bool,
+    /// Flag `key:` with no value in block mappings
+    pub forbid_in_block_mappings: bool,
+    /// Flag `key:` with no value in flow mappings, e.g. `{key: }`
+    pub forbid_in_flow_mappings: bool,
+    /// Flag `-` entries with no value in block sequences
+    pub forbid_in_block_sequences: bool,
+}
+
+impl Default for EmptyValuesConfig {
+    fn default() -> Self {
+        Self {
+            forbid_in_block_mappings: true,
+            forbid_in_flow_mappings: true,
+            forbid_in_block_sequences: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct EmptyValuesRule {
-    config: EmptyValuesConfig,
+    base: crate::rules::base::BaseRule<EmptyValuesConfig>,
 }
 
 impl EmptyValuesRule {
     pub fn new() -> Self {
+        Self::with_config(EmptyValuesConfig::default())
+    }
+
+    pub fn with_config(config: EmptyValuesConfig) -> Self {
         Self {
-            config: EmptyValuesConfig { forbid_empty: true },
+            base: crate::rules::base::BaseRule::new(config),
         }
     }
 
-    pub fn with_config(config: EmptyValuesConfig) -> Self {
-        Self { config }
+    pub fn config(&self) -> &EmptyValuesConfig {
+        self.base.config()
     }
 
-    fn is_empty_value(&self, value: &str) -> bool {
-        let trimmed = value.trim();
-        trimmed.is_empty() || trimmed == "null" || trimmed == "~" || trimmed == "\"\""
+    /// Implicit null values, found like yamllint does: a `:` directly
+    /// followed by the next key or the end of its mapping, or a `-` directly
+    /// followed by the next entry or the end of its sequence. Explicit
+    /// `null`, `~` and `""` values are not empty.
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let config = self.config();
+        let mut issues = Vec::new();
+
+        for pair in tokens.windows(2) {
+            let (Token(marker, token), Token(_, next)) = (&pair[0], &pair[1]);
+            let problem = match (token, next) {
+                (TokenType::Value, TokenType::Key | TokenType::BlockEnd)
+                    if config.forbid_in_block_mappings =>
+                {
+                    ("block-mapping", "empty value in block mapping")
+                }
+                (TokenType::Value, TokenType::FlowEntry | TokenType::FlowMappingEnd)
+                    if config.forbid_in_flow_mappings =>
+                {
+                    ("flow-mapping", "empty value in flow mapping")
+                }
+                (
+                    TokenType::BlockEntry,
+                    TokenType::Key | TokenType::BlockEnd | TokenType::BlockEntry,
+                ) if config.forbid_in_block_sequences => {
+                    ("block-sequence", "empty value in block sequence")
+                }
+                _ => continue,
+            };
+            // Reported just after the `:` or `-`, as yamllint does
+            issues.push(LintIssue {
+                line: marker.line(),
+                column: marker.col() + 2,
+                message: problem.1.to_string(),
+                code: format!("empty-values/{}", problem.0),
+                severity: self.get_severity(),
+            });
+        }
+
+        issues
     }
 }
 
@@ -46,40 +105,36 @@ impl Rule for EmptyValuesRule {
     }
 
     fn get_severity(&self) -> Severity {
-        self.default_severity()
+        self.base.get_severity(self.default_severity())
     }
 
-    fn set_severity(&mut self, _severity: Severity) {}
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
 
     fn has_severity_override(&self) -> bool {
-        false
+        self.base.has_severity_override()
     }
 
-    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
-        let mut issues = Vec::new();
-
-        for (line_num, line) in content.lines().enumerate() {
-            let line_num = line_num + 1;
-
-            if line.trim().starts_with('#') || line.trim().is_empty() {
-                continue;
-            }
-
-            if let Some(colon_pos) = line.find(':') {
-                let value_part = line[colon_pos + 1..].trim();
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
 
-                if self.config.forbid_empty && self.is_empty_value(value_part) {
-                    issues.push(LintIssue {
-                        line: line_num,
-                        column: colon_pos + 2,
-                        message: "empty value not allowed".to_string(),
-                        severity: self.get_severity(),
-                    });
-                }
-            }
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
         }
-
-        issues
     }
 
     fn can_fix(&self) -> bool {
@@ -106,6 +161,13 @@ mod tests {
     use super::*;
     use crate::Severity;
 
+    fn positions(rule: &EmptyValuesRule, content: &str) -> Vec<(usize, usize, String)> {
+        rule.check(content, "test.yaml")
+            .into_iter()
+            .map(|issue| (issue.line, issue.column, issue.message))
+            .collect()
+    }
+
     #[test]
     fn test_empty_values_rule_default() {
         let rule = EmptyValuesRule::new();
@@ -118,26 +180,70 @@ mod tests {
     #[test]
     fn test_empty_values_check_clean_values() {
         let rule = EmptyValuesRule::new();
-        let content = "key1: value1\nkey2: \"not empty\"\nkey3: 42";
-        let issues = rule.check(content, "test.yaml");
-        assert!(issues.is_empty());
+        let content = "key1: value1\nkey2: \"not empty\"\nkey3: 42\nkey4: null\nkey5: ~\n\
+                       key6: \"\"\nnested:\n  key: value\nlist:\n  - item\nflow: {a: 1, b: ~}\n";
+        assert!(rule.check(content, "test.yaml").is_empty());
     }
 
     #[test]
-    fn test_empty_values_check_empty_values() {
+    fn test_empty_values_in_block_mappings() {
         let rule = EmptyValuesRule::new();
-        let content = "key1: \nkey2: \"\"\nkey3: null";
-        let issues = rule.check(content, "test.yaml");
-        assert_eq!(issues.len(), 3);
-        assert!(issues[0].message.contains("empty value not allowed"));
-        assert!(issues[1].message.contains("empty value not allowed"));
-        assert!(issues[2].message.contains("empty value not allowed"));
+        let content = "---\nblock-mapping:\nnested:\n  empty:\n  full: 1\nlast:\n";
+        assert_eq!(
+            positions(&rule, content),
+            vec![
+                (2, 15, "empty value in block mapping".to_string()),
+                (4, 9, "empty value in block mapping".to_string()),
+                (6, 6, "empty value in block mapping".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_values_in_flow_mappings() {
+        let rule = EmptyValuesRule::new();
+        let content = "---\nflow: {a: , b: 1, c: }\n";
+        assert_eq!(
+            positions(&rule, content),
+            vec![
+                (2, 10, "empty value in flow mapping".to_string()),
+                (2, 21, "empty value in flow mapping".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_values_in_block_sequences() {
+        let rule = EmptyValuesRule::new();
+        let content = "---\nlist:\n  -\n  - item\n  -\nindentless:\n-\n- item\n";
+        assert_eq!(
+            positions(&rule, content),
+            vec![
+                (3, 4, "empty value in block sequence".to_string()),
+                (5, 4, "empty value in block sequence".to_string()),
+                (7, 2, "empty value in block sequence".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_values_options_disable_each_check() {
+        let rule = EmptyValuesRule::with_config(EmptyValuesConfig {
+            forbid_in_block_mappings: false,
+            forbid_in_flow_mappings: true,
+            forbid_in_block_sequences: false,
+        });
+        let content = "---\nkey:\nflow: {a: }\nlist:\n  -\n";
+        assert_eq!(
+            positions(&rule, content),
+            vec![(3, 10, "empty value in flow mapping".to_string())]
+        );
     }
 
     #[test]
     fn test_empty_values_fix_no_changes() {
         let rule = EmptyValuesRule::new();
-        let content = "key1: \nkey2: \"\"\nkey3: null";
+        let content = "key1:\nkey2: \"\"\nkey3: null";
         let fix_result = rule.fix(content, "test.yaml");
         assert!(!fix_result.changed);
         assert_eq!(fix_result.fixes_applied, 0);
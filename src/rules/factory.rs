@@ -37,10 +37,28 @@ impl RuleFactory {
             "empty-lines" => Some(Box::new(EmptyLinesRule::new())),
             "anchors" => Some(Box::new(AnchorsRule::new())),
             "new-lines" => Some(Box::new(NewLinesRule::new())),
+            "secrets" => Some(Box::new(SecretsRule::new())),
+            "forbidden-values" => Some(Box::new(ForbiddenValuesRule::new())),
+            "key-naming" => Some(Box::new(KeyNamingRule::new())),
+            "file-size" => Some(Box::new(FileSizeRule::new())),
+            "norway-problem" => Some(Box::new(NorwayProblemRule::new())),
+            "timestamp-values" => Some(Box::new(TimestampValuesRule::new())),
+            "non-ascii" => Some(Box::new(NonAsciiRule::new())),
+            "sequence-ordering" => Some(Box::new(SequenceOrderingRule::new())),
+            "custom-tags" => Some(Box::new(CustomTagsRule::new())),
+            "no-aliases" => Some(Box::new(NoAliasesRule::new())),
+            "directive-syntax" => Some(Box::new(DirectiveSyntaxRule::new())),
+            "yaml-syntax" => Some(Box::new(YamlSyntaxRule::new())),
             _ => None,
         }
     }
 
+    /// Default options of a rule, serialized with yamllint option names
+    pub fn default_config_json(&self, rule_id: &str) -> Option<serde_json::Value> {
+        self.create_rule(rule_id)
+            .map(|rule| rule.default_config_json())
+    }
+
     pub fn create_default_rules(&self) -> Vec<Box<dyn Rule>> {
         let default_rule_ids = self.registry.get_default_enabled_rules();
         default_rule_ids
@@ -67,96 +85,80 @@ impl RuleFactory {
             .collect()
     }
 
-    fn create_line_length_rule_with_config(&self, config: &crate::config::Config) -> Box<dyn Rule> {
-        let mut rule = LineLengthRule::new();
-        if let Some(line_config) =
-            config.get_rule_settings::<crate::config::LineLengthConfig>("line-length")
-        {
-            rule.set_config(crate::rules::line_length::LineLengthConfig {
-                max_length: line_config.max_length,
-                allow_non_breakable_words: line_config.allow_non_breakable_words,
-                allow_non_breakable_inline_mappings: line_config
-                    .allow_non_breakable_inline_mappings,
-            });
-        }
-        Box::new(rule)
-    }
-
-    fn create_indentation_rule_with_config(&self, config: &crate::config::Config) -> Box<dyn Rule> {
-        let mut rule = IndentationRule::new();
-
-        let indent_config = config
-            .get_rule_settings::<crate::config::IndentationConfig>("indentation")
-            .or_else(|| {
-                config.rules.get("indentation").and_then(|rule_config| {
-                    let mut spaces = None;
-                    let mut indent_sequences = None;
-                    let mut check_multi_line_strings = None;
-                    let mut ignore = None;
-
-                    if let Some(spaces_val) =
-                        rule_config.other.get("spaces").and_then(|v| v.as_u64())
-                    {
-                        spaces = Some(spaces_val as usize);
-                    }
-                    if let Some(indent_val) = rule_config.other.get("indent-sequences") {
-                        if let Some(bool_val) = indent_val.as_bool() {
-                            indent_sequences = Some(bool_val);
-                        }
-                    }
-                    if let Some(check_val) = rule_config
-                        .other
-                        .get("check-multi-line-strings")
-                        .and_then(|v| v.as_bool())
-                    {
-                        check_multi_line_strings = Some(check_val);
-                    }
-                    if let Some(ignore_val) = rule_config.other.get("ignore") {
-                        if let Some(s) = ignore_val.as_str() {
-                            ignore = Some(s.to_string());
-                        }
-                    }
-
-                    Some(crate::config::IndentationConfig {
-                        spaces,
-                        indent_sequences,
-                        check_multi_line_strings,
-                        ignore,
-                    })
+    /// `rule_id` with the options `config` gives it, over the rule's
+    /// defaults; `None` for unknown rules and an error for options of the
+    /// wrong type
+    pub fn try_create_rule_with_config(
+        &self,
+        rule_id: &str,
+        config: &crate::config::Config,
+    ) -> Option<Result<Box<dyn Rule>, serde_json::Error>> {
+        let rule = match rule_id {
+            "line-length" => configured(config, rule_id, LineLengthRule::with_config),
+            "trailing-spaces" => configured(config, rule_id, TrailingSpacesRule::with_config),
+            "comments" => configured(config, rule_id, CommentsRule::with_config),
+            "truthy" => configured(config, rule_id, TruthyRule::with_config),
+            "comments-indentation" => {
+                configured(config, rule_id, CommentsIndentationRule::with_config)
+            }
+            "new-line-at-end-of-file" => {
+                configured(config, rule_id, NewLineAtEndOfFileRule::with_config)
+            }
+            "braces" => configured(config, rule_id, BracesRule::with_config),
+            "brackets" => configured(config, rule_id, BracketsRule::with_config),
+            "colons" => configured(config, rule_id, ColonsRule::with_config),
+            "commas" => configured(config, rule_id, CommasRule::with_config),
+            "hyphens" => configured(config, rule_id, HyphensRule::with_config),
+            "quoted-strings" => configured(config, rule_id, QuotedStringsRule::with_config),
+            "indentation" => configured(config, rule_id, |options| {
+                let ignore = config.rules.get(rule_id).and_then(|c| c.ignore.clone());
+                IndentationRule::with_config(indentation::IndentationConfig {
+                    ignore_patterns: IndentationRule::parse_ignore_patterns(ignore),
+                    ..options
                 })
-            });
-
-        if let Some(indent_config) = indent_config {
-            rule.set_config(crate::rules::indentation::IndentationConfig {
-                spaces: indent_config.spaces.unwrap_or(2),
-                indent_sequences: indent_config.indent_sequences.unwrap_or(true),
-                check_multi_line_strings: indent_config.check_multi_line_strings.unwrap_or(false),
-                ignore_patterns: crate::rules::indentation::IndentationRule::parse_ignore_patterns(
-                    indent_config.ignore,
-                ),
-            });
-        }
-        Box::new(rule)
+            }),
+            "document-start" => configured(config, rule_id, DocumentStartRule::with_config),
+            "document-end" => configured(config, rule_id, DocumentEndRule::with_config),
+            "empty-values" => configured(config, rule_id, EmptyValuesRule::with_config),
+            "float-values" => configured(config, rule_id, FloatValuesRule::with_config),
+            "octal-values" => configured(config, rule_id, OctalValuesRule::with_config),
+            "key-duplicates" => configured(config, rule_id, KeyDuplicatesRule::with_config),
+            "key-ordering" => configured(config, rule_id, |options| {
+                KeyOrderingRule::with_config(key_ordering::KeyOrderingConfig {
+                    locale: config.locale.clone(),
+                    ..options
+                })
+            }),
+            "empty-lines" => configured(config, rule_id, EmptyLinesRule::with_config),
+            "anchors" => configured(config, rule_id, AnchorsRule::with_config),
+            "new-lines" => configured(config, rule_id, NewLinesRule::with_config),
+            "secrets" => configured(config, rule_id, SecretsRule::with_config),
+            "forbidden-values" => configured(config, rule_id, ForbiddenValuesRule::with_config),
+            "key-naming" => configured(config, rule_id, KeyNamingRule::with_config),
+            "file-size" => configured(config, rule_id, FileSizeRule::with_config),
+            "norway-problem" => configured(config, rule_id, NorwayProblemRule::with_config),
+            "timestamp-values" => configured(config, rule_id, TimestampValuesRule::with_config),
+            "non-ascii" => configured(config, rule_id, NonAsciiRule::with_config),
+            "sequence-ordering" => configured(config, rule_id, SequenceOrderingRule::with_config),
+            "custom-tags" => configured(config, rule_id, CustomTagsRule::with_config),
+            "no-aliases" => configured(config, rule_id, NoAliasesRule::with_config),
+            "directive-syntax" => Ok(Box::new(DirectiveSyntaxRule::new()) as Box<dyn Rule>),
+            "yaml-syntax" => Ok(Box::new(YamlSyntaxRule::new()) as Box<dyn Rule>),
+            _ => return None,
+        };
+        Some(rule)
     }
 
+    /// `rule_id` configured from `config`, or with its defaults when its
+    /// options are invalid (the config check reports those)
     pub fn create_rule_with_config(
         &self,
         rule_id: &str,
         config: &crate::config::Config,
     ) -> Option<Box<dyn Rule>> {
-        match rule_id {
-            "line-length" => Some(self.create_line_length_rule_with_config(config)),
-            "indentation" => Some(self.create_indentation_rule_with_config(config)),
-            "trailing-spaces" => {
-                let mut rule = TrailingSpacesRule::new();
-                let allow = config
-                    .get_rule_settings::<crate::config::TrailingSpacesConfig>("trailing-spaces")
-                    .map(|c| c.allow)
-                    .unwrap_or(false);
-                rule.set_config(crate::rules::trailing_spaces::TrailingSpacesConfig { allow });
-                Some(Box::new(rule))
-            }
-            _ => self.create_rule(rule_id),
+        match self.try_create_rule_with_config(rule_id, config)? {
+            Ok(rule) => Some(rule),
+            Err(_) => self.create_rule(rule_id),
         }
     }
 
@@ -165,6 +167,19 @@ impl RuleFactory {
     }
 }
 
+/// A rule built from its typed options in `config`
+fn configured<T, R>(
+    config: &crate::config::Config,
+    rule_id: &str,
+    build: impl FnOnce(T) -> R,
+) -> Result<Box<dyn Rule>, serde_json::Error>
+where
+    T: serde::de::DeserializeOwned + Default,
+    R: Rule + 'static,
+{
+    Ok(Box::new(build(config.rule_options(rule_id)?)))
+}
+
 impl Default for RuleFactory {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,177 @@
+use crate::rules::base::BaseRule;
+use crate::rules::Rule;
+use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct FileSizeConfig {
+    /// Most lines a file may have, -1 for no limit
+    pub max_lines: i64,
+    /// Most bytes a file may have, -1 for no limit
+    pub max_bytes: i64,
+}
+
+impl Default for FileSizeConfig {
+    fn default() -> Self {
+        Self {
+            max_lines: 1000,
+            max_bytes: -1,
+        }
+    }
+}
+
+/// Flags files too long or too large, to steer teams towards splitting
+/// monolithic config files
+#[derive(Debug, Clone)]
+pub struct FileSizeRule {
+    base: BaseRule<FileSizeConfig>,
+}
+
+impl FileSizeRule {
+    pub fn new() -> Self {
+        Self::with_config(FileSizeConfig::default())
+    }
+
+    pub fn with_config(config: FileSizeConfig) -> Self {
+        Self {
+            base: BaseRule::new(config),
+        }
+    }
+
+    pub fn config(&self) -> &FileSizeConfig {
+        self.base.config()
+    }
+
+    pub fn set_config(&mut self, config: FileSizeConfig) {
+        self.base.set_config(config);
+    }
+
+    pub fn check_impl(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        let config = self.config();
+
+        let lines = content.lines().count();
+        if config.max_lines >= 0 && lines as i64 > config.max_lines {
+            // Reported on the first line over the limit
+            issues.push(LintIssue {
+                line: config.max_lines as usize + 1,
+                column: 1,
+                message: format!("too many lines ({} > {})", lines, config.max_lines),
+                code: "file-size/too-many-lines".to_string(),
+                severity: self.get_severity(),
+            });
+        }
+
+        if config.max_bytes >= 0 && content.len() as i64 > config.max_bytes {
+            issues.push(LintIssue {
+                line: 1,
+                column: 1,
+                message: format!(
+                    "file too large ({} > {} bytes)",
+                    content.len(),
+                    config.max_bytes
+                ),
+                code: "file-size/too-large".to_string(),
+                severity: self.get_severity(),
+            });
+        }
+
+        issues
+    }
+}
+
+impl Default for FileSizeRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for FileSizeRule {
+    fn rule_id(&self) -> &'static str {
+        "file-size"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "File Size"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Limits the number of lines and bytes in a file."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, content: &str, file_path: &str) -> Vec<LintIssue> {
+        self.check_impl(content, file_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_size_rule_default() {
+        let rule = FileSizeRule::new();
+        assert_eq!(rule.rule_id(), "file-size");
+        assert_eq!(rule.default_severity(), Severity::Warning);
+        assert!(!rule.is_enabled_by_default());
+        assert!(!rule.can_fix());
+        assert!(rule
+            .check(&"key: value\n".repeat(1000), "test.yaml")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_file_size_too_many_lines() {
+        let rule = FileSizeRule::with_config(FileSizeConfig {
+            max_lines: 3,
+            max_bytes: -1,
+        });
+        assert!(rule.check("a: 1\nb: 2\nc: 3\n", "test.yaml").is_empty());
+
+        let issues = rule.check("a: 1\nb: 2\nc: 3\nd: 4\ne: 5\n", "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (4, 1));
+        assert_eq!(issues[0].message, "too many lines (5 > 3)");
+        assert_eq!(issues[0].code, "file-size/too-many-lines");
+    }
+
+    #[test]
+    fn test_file_size_too_large() {
+        let rule = FileSizeRule::with_config(FileSizeConfig {
+            max_lines: -1,
+            max_bytes: 10,
+        });
+        assert!(rule.check("key: value", "test.yaml").is_empty());
+
+        let issues = rule.check("key: välue\n", "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (1, 1));
+        assert_eq!(issues[0].message, "file too large (12 > 10 bytes)");
+        assert_eq!(issues[0].code, "file-size/too-large");
+    }
+}
@@ -1,50 +1,100 @@
 use super::Rule;
 use crate::{LintIssue, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, TScalarStyle, Token, TokenType};
 
-#[derive(Debug, Clone)]
+lazy_static! {
+    static ref NUMERAL_BEFORE_DECIMAL: Regex =
+        Regex::new(r"^[-+]?\.[0-9]+([eE][-+]?[0-9]+)?$").unwrap();
+    static ref SCIENTIFIC_NOTATION: Regex =
+        Regex::new(r"^[-+]?(\.[0-9]+|[0-9]+(\.[0-9]*)?)[eE][-+]?[0-9]+$").unwrap();
+    static ref NAN: Regex = Regex::new(r"^(\.nan|\.NaN|\.NAN)$").unwrap();
+    static ref INF: Regex = Regex::new(r"^[-+]?(\.inf|\.Inf|\.INF)$").unwrap();
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct FloatValuesConfig {
+    /// Flag floats without a numeral before the decimal point, e.g. `.5`
+    pub require_numeral_before_decimal: bool,
+    /// Flag floats in scientific notation, e.g. `1e3`
+    pub forbid_scientific_notation: bool,
+    /// Flag `.nan`
     pub forbid_nan: bool,
+    /// Flag `.inf` and `-.inf`
     pub forbid_inf: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct FloatValuesRule {
-    config: FloatValuesConfig,
+    base: crate::rules::base::BaseRule<FloatValuesConfig>,
 }
 
 impl FloatValuesRule {
     pub fn new() -> Self {
-        Self {
-            config: FloatValuesConfig {
-                forbid_nan: true,
-                forbid_inf: true,
-            },
-        }
+        Self::with_config(FloatValuesConfig::default())
     }
 
     pub fn with_config(config: FloatValuesConfig) -> Self {
-        Self { config }
+        Self {
+            base: crate::rules::base::BaseRule::new(config),
+        }
     }
 
-    fn is_forbidden_float(&self, value: &str) -> Option<String> {
-        let trimmed = value.trim();
+    pub fn config(&self) -> &FloatValuesConfig {
+        self.base.config()
+    }
 
-        if self.config.forbid_nan && (trimmed == ".NaN" || trimmed == ".nan" || trimmed == ".NAN") {
-            return Some("NaN".to_string());
+    /// Code and message for a forbidden plain scalar `value`
+    fn problem(&self, value: &str) -> Option<(&'static str, String)> {
+        let config = self.config();
+        if config.forbid_nan && NAN.is_match(value) {
+            return Some(("nan", format!("forbidden not a number value '{}'", value)));
+        }
+        if config.forbid_inf && INF.is_match(value) {
+            return Some(("inf", format!("forbidden infinite value '{}'", value)));
         }
+        if config.forbid_scientific_notation && SCIENTIFIC_NOTATION.is_match(value) {
+            return Some((
+                "scientific-notation",
+                format!("forbidden scientific notation '{}'", value),
+            ));
+        }
+        if config.require_numeral_before_decimal && NUMERAL_BEFORE_DECIMAL.is_match(value) {
+            return Some((
+                "numeral-before-decimal",
+                format!("forbidden decimal missing 0 prefix '{}'", value),
+            ));
+        }
+        None
+    }
+
+    /// Plain scalars only, as yamllint does: quoted strings and tagged
+    /// values such as `!!str .inf` are never floats
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        let mut previous: Option<&TokenType> = None;
 
-        if self.config.forbid_inf
-            && (trimmed == ".inf"
-                || trimmed == ".Inf"
-                || trimmed == ".INF"
-                || trimmed == "-.inf"
-                || trimmed == "-.Inf"
-                || trimmed == "-.INF")
-        {
-            return Some("infinity".to_string());
+        for Token(marker, token) in tokens {
+            if let TokenType::Scalar(TScalarStyle::Plain, value) = token {
+                if !matches!(previous, Some(TokenType::Tag(..))) {
+                    if let Some((code, message)) = self.problem(value) {
+                        issues.push(LintIssue {
+                            line: marker.line(),
+                            column: marker.col() + 1,
+                            message,
+                            code: format!("float-values/{}", code),
+                            severity: self.get_severity(),
+                        });
+                    }
+                }
+            }
+            previous = Some(token);
         }
 
-        None
+        issues
     }
 }
 
@@ -66,40 +116,36 @@ impl Rule for FloatValuesRule {
     }
 
     fn get_severity(&self) -> Severity {
-        self.default_severity()
+        self.base.get_severity(self.default_severity())
     }
 
-    fn set_severity(&mut self, _severity: Severity) {}
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
 
     fn has_severity_override(&self) -> bool {
-        false
+        self.base.has_severity_override()
     }
 
-    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
-        let mut issues = Vec::new();
-
-        for (line_num, line) in content.lines().enumerate() {
-            let line_num = line_num + 1;
-
-            if line.trim().starts_with('#') || line.trim().is_empty() {
-                continue;
-            }
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
 
-            if let Some(colon_pos) = line.find(':') {
-                let value_part = line[colon_pos + 1..].trim();
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
 
-                if let Some(forbidden_type) = self.is_forbidden_float(value_part) {
-                    issues.push(LintIssue {
-                        line: line_num,
-                        column: colon_pos + 2,
-                        message: format!("forbidden {} value", forbidden_type),
-                        severity: self.get_severity(),
-                    });
-                }
-            }
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
         }
-
-        issues
     }
 
     fn can_fix(&self) -> bool {
@@ -135,30 +181,81 @@ mod tests {
         assert!(!rule.can_fix());
     }
 
+    fn all_forbidden() -> FloatValuesRule {
+        FloatValuesRule::with_config(FloatValuesConfig {
+            require_numeral_before_decimal: true,
+            forbid_scientific_notation: true,
+            forbid_nan: true,
+            forbid_inf: true,
+        })
+    }
+
+    fn problems(rule: &FloatValuesRule, content: &str) -> Vec<(usize, usize, String)> {
+        rule.check(content, "test.yaml")
+            .into_iter()
+            .map(|issue| (issue.line, issue.column, issue.code))
+            .collect()
+    }
+
     #[test]
-    fn test_float_values_check_clean_values() {
+    fn test_float_values_allows_everything_by_default() {
         let rule = FloatValuesRule::new();
-        let content = "normal_float: 3.14\ninteger: 42\nstring: \"hello\"";
-        let issues = rule.check(content, "test.yaml");
-        assert!(issues.is_empty());
+        let content = "a: .5\nb: 1e3\nc: .nan\nd: -.inf\n";
+        assert!(rule.check(content, "test.yaml").is_empty());
     }
 
     #[test]
-    fn test_float_values_check_forbidden_nan() {
-        let rule = FloatValuesRule::new();
-        let content = "nan_value: .NaN";
-        let issues = rule.check(content, "test.yaml");
-        assert_eq!(issues.len(), 1);
-        assert!(issues[0].message.contains("forbidden NaN value"));
+    fn test_float_values_check_clean_values() {
+        let rule = all_forbidden();
+        let content =
+            "normal_float: 3.14\ninteger: 42\nstring: \"hello\"\nzero: 0.5\nversion: 1.2.3\n";
+        assert!(rule.check(content, "test.yaml").is_empty());
     }
 
     #[test]
-    fn test_float_values_check_forbidden_inf() {
-        let rule = FloatValuesRule::new();
-        let content = "inf_value: .inf";
-        let issues = rule.check(content, "test.yaml");
-        assert_eq!(issues.len(), 1);
-        assert!(issues[0].message.contains("forbidden infinity value"));
+    fn test_float_values_check_each_option() {
+        let rule = all_forbidden();
+        let content = "---\nnan: .NaN\ninf: -.inf\nsci: 10e-6\nsci-frac: .5e+2\nno-zero: -.5\nlist: [+.INF, .1]\n";
+        assert_eq!(
+            problems(&rule, content),
+            vec![
+                (2, 6, "float-values/nan".to_string()),
+                (3, 6, "float-values/inf".to_string()),
+                (4, 6, "float-values/scientific-notation".to_string()),
+                (5, 11, "float-values/scientific-notation".to_string()),
+                (6, 10, "float-values/numeral-before-decimal".to_string()),
+                (7, 8, "float-values/inf".to_string()),
+                (7, 15, "float-values/numeral-before-decimal".to_string()),
+            ]
+        );
+        let issues = rule.check("key: .nan\n", "test.yaml");
+        assert_eq!(issues[0].message, "forbidden not a number value '.nan'");
+    }
+
+    #[test]
+    fn test_float_values_skips_quoted_and_tagged_scalars() {
+        let rule = all_forbidden();
+        let content = "a: \".nan\"\nb: '1e3'\nc: !!str .inf\nd: |\n  .5\n";
+        assert!(rule.check(content, "test.yaml").is_empty());
+    }
+
+    #[test]
+    fn test_float_values_messages_are_redacted() {
+        let rule = all_forbidden();
+        let issues = rule.check("a: .nan\nb: -.inf\nc: 1e3\nd: .5\n", "test.yaml");
+        let redacted: Vec<_> = issues
+            .iter()
+            .map(|issue| crate::redact::redact_values(&issue.message))
+            .collect();
+        assert_eq!(
+            redacted,
+            vec![
+                "forbidden not a number value '<redacted>'",
+                "forbidden infinite value '<redacted>'",
+                "forbidden scientific notation '<redacted>'",
+                "forbidden decimal missing 0 prefix '<redacted>'",
+            ]
+        );
     }
 
     #[test]
@@ -0,0 +1,315 @@
+use crate::patterns;
+use crate::{LintIssue, Severity};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, Token, TokenType};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ForbiddenValuesConfig {
+    /// Values to report, each with its own message and level
+    pub patterns: Vec<ForbiddenValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ForbiddenValue {
+    /// Regular expression searched for in scalar values
+    #[serde(deserialize_with = "patterns::deserialize_regex")]
+    pub pattern: String,
+    /// Regular expression the value's key must match; any value when unset
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "patterns::deserialize_optional_regex"
+    )]
+    pub key: Option<String>,
+    /// Message reported instead of the default one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Level reported instead of the rule's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<Severity>,
+}
+
+/// A configured pattern with its regexes compiled
+#[derive(Debug, Clone)]
+struct CompiledValue {
+    value: Regex,
+    key: Option<Regex>,
+    message: Option<String>,
+    level: Option<Severity>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ForbiddenValuesRule {
+    base: crate::rules::base::BaseRule<ForbiddenValuesConfig>,
+    compiled: Vec<CompiledValue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Expect {
+    Nothing,
+    Key,
+    Value,
+}
+
+impl ForbiddenValuesRule {
+    pub fn new() -> Self {
+        Self::with_config(ForbiddenValuesConfig::default())
+    }
+
+    pub fn with_config(config: ForbiddenValuesConfig) -> Self {
+        let mut rule = Self {
+            base: crate::rules::base::BaseRule::new(config.clone()),
+            compiled: Vec::new(),
+        };
+        rule.set_config(config);
+        rule
+    }
+
+    pub fn config(&self) -> &ForbiddenValuesConfig {
+        self.base.config()
+    }
+
+    pub fn set_config(&mut self, config: ForbiddenValuesConfig) {
+        self.compiled = config
+            .patterns
+            .iter()
+            .filter_map(|entry| {
+                Some(CompiledValue {
+                    value: patterns::regex(&entry.pattern).ok()?,
+                    key: match &entry.key {
+                        Some(key) => Some(patterns::regex(key).ok()?),
+                        None => None,
+                    },
+                    message: entry.message.clone(),
+                    level: entry.level,
+                })
+            })
+            .collect();
+        self.base.set_config(config);
+    }
+
+    pub fn get_severity(&self) -> Severity {
+        self.base.get_severity(Severity::Error)
+    }
+
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    pub fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    /// The first pattern `value` (under `key`) matches
+    fn forbidden(&self, key: Option<&str>, value: &str) -> Option<&CompiledValue> {
+        self.compiled.iter().find(|entry| {
+            entry.value.is_match(value)
+                && match &entry.key {
+                    Some(regex) => key.is_some_and(|key| regex.is_match(key)),
+                    None => true,
+                }
+        })
+    }
+
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        if self.compiled.is_empty() {
+            return issues;
+        }
+
+        let mut expect = Expect::Nothing;
+        let mut current_key: Option<String> = None;
+
+        for token in tokens {
+            let Token(marker, token_type) = token;
+
+            match token_type {
+                TokenType::Key => {
+                    expect = Expect::Key;
+                    current_key = None;
+                }
+                TokenType::Value => expect = Expect::Value,
+                TokenType::Anchor(_) | TokenType::Tag(_, _) => {}
+                TokenType::Scalar(_, value) => {
+                    if expect == Expect::Key {
+                        current_key = Some(value.clone());
+                    } else {
+                        let key = if expect == Expect::Value {
+                            current_key.as_deref()
+                        } else {
+                            None
+                        };
+                        if let Some(entry) = self.forbidden(key, value) {
+                            // Single-quoted so `--redact-values` can hide the value
+                            let message = entry.message.clone().unwrap_or_else(|| {
+                                let value = value.replace('\'', "''");
+                                match key {
+                                    Some(key) => {
+                                        format!("forbidden value '{}' for key \"{}\"", value, key)
+                                    }
+                                    None => format!("forbidden value '{}'", value),
+                                }
+                            });
+                            issues.push(LintIssue {
+                                line: marker.line(),
+                                column: marker.col() + 1,
+                                message,
+                                code: "forbidden-values/forbidden-value".to_string(),
+                                severity: entry.level.unwrap_or_else(|| self.get_severity()),
+                            });
+                        }
+                    }
+                    expect = Expect::Nothing;
+                }
+                _ => expect = Expect::Nothing,
+            }
+        }
+
+        issues
+    }
+
+    pub fn check_impl(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let scanner = Scanner::new(content.chars());
+        let tokens: Vec<_> = scanner.collect();
+        self.check_with_tokens(&tokens)
+    }
+}
+
+impl Default for ForbiddenValuesRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl crate::rules::Rule for ForbiddenValuesRule {
+    fn rule_id(&self) -> &'static str {
+        "forbidden-values"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "Forbidden Values"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Flags scalar values matching configured regular expressions."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, content: &str, file_path: &str) -> Vec<LintIssue> {
+        self.check_impl(content, file_path)
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        if let Some(token_analysis) = analysis.tokens() {
+            self.check_with_tokens(&token_analysis.tokens)
+        } else {
+            self.check_impl(content, file_path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rule;
+
+    fn entry(pattern: &str) -> ForbiddenValue {
+        ForbiddenValue {
+            pattern: pattern.to_string(),
+            key: None,
+            message: None,
+            level: None,
+        }
+    }
+
+    #[test]
+    fn test_forbidden_values_rule_default() {
+        let rule = ForbiddenValuesRule::new();
+        assert_eq!(rule.rule_id(), "forbidden-values");
+        assert_eq!(rule.default_severity(), Severity::Error);
+        assert!(!rule.is_enabled_by_default());
+        assert!(!rule.can_fix());
+        assert!(rule.check("image: nginx:latest\n", "test.yaml").is_empty());
+    }
+
+    #[test]
+    fn test_forbidden_values_reports_matching_values() {
+        let rule = ForbiddenValuesRule::with_config(ForbiddenValuesConfig {
+            patterns: vec![entry(r":latest$")],
+        });
+        let content = "image: nginx:latest\nsidecar: envoy:1.29\nimages:\n  - redis:latest\nnginx:latest: key\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 2);
+        assert_eq!((issues[0].line, issues[0].column), (1, 8));
+        assert_eq!(
+            issues[0].message,
+            "forbidden value 'nginx:latest' for key \"image\""
+        );
+        assert_eq!(issues[0].code, "forbidden-values/forbidden-value");
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!((issues[1].line, issues[1].column), (4, 5));
+        assert_eq!(issues[1].message, "forbidden value 'redis:latest'");
+    }
+
+    #[test]
+    fn test_forbidden_values_key_message_and_level() {
+        let rule = ForbiddenValuesRule::with_config(ForbiddenValuesConfig {
+            patterns: vec![
+                ForbiddenValue {
+                    key: Some(r"(?i)^password$".to_string()),
+                    message: Some("use a secret reference for passwords".to_string()),
+                    level: Some(Severity::Warning),
+                    ..entry(r"^[^$]")
+                },
+                entry(r"\.internal\.example\.com$"),
+            ],
+        });
+        let content = "user: admin\nPassword: hunter2\npassword: ${DB_PASSWORD}\nhost: db.internal.example.com\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].line, 2);
+        assert_eq!(issues[0].message, "use a secret reference for passwords");
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[1].line, 4);
+        assert_eq!(issues[1].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_forbidden_values_rejects_invalid_patterns() {
+        let options = serde_json::json!({ "patterns": [{ "pattern": "(unclosed" }] });
+        assert!(serde_json::from_value::<ForbiddenValuesConfig>(options).is_err());
+        let options = serde_json::json!({ "patterns": [{ "pattern": "x", "lvl": "error" }] });
+        assert!(serde_json::from_value::<ForbiddenValuesConfig>(options).is_err());
+    }
+}
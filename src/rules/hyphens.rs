@@ -1,7 +1,9 @@
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 use yaml_rust::scanner::{Scanner, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct HyphensConfig {
     pub max_spaces_after: i32,
 }
@@ -88,6 +90,10 @@ impl crate::rules::Rule for HyphensRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         true
     }
@@ -162,6 +168,7 @@ impl HyphensRule {
                                     spaces_after,
                                     self.config().max_spaces_after
                                 ),
+                                code: "hyphens/too-many-spaces-after".to_string(),
                                 severity: self.get_severity(),
                             });
                         }
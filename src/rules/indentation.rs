@@ -1,4 +1,5 @@
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 use yaml_rust::scanner::{Scanner, Token, TokenType};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -34,19 +35,66 @@ impl Parent {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct IndentationConfig {
     pub spaces: usize,
-    pub indent_sequences: bool,
+    pub indent_sequences: IndentSequences,
     pub check_multi_line_strings: bool,
+    #[serde(skip)]
     pub ignore_patterns: Vec<String>,
 }
 
+/// Whether sequences under a key are indented, as in yamllint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentSequences {
+    True,
+    False,
+    /// Either style is accepted
+    Whatever,
+    /// Either style, as long as the whole file uses the first one seen
+    Consistent,
+}
+
+/// Serialized as in yamllint: `true`, `false`, `whatever` or `consistent`
+impl Serialize for IndentSequences {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            IndentSequences::True => serializer.serialize_bool(true),
+            IndentSequences::False => serializer.serialize_bool(false),
+            IndentSequences::Whatever => serializer.serialize_str("whatever"),
+            IndentSequences::Consistent => serializer.serialize_str("consistent"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IndentSequences {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Setting {
+            Bool(bool),
+            Name(String),
+        }
+
+        match Setting::deserialize(deserializer)? {
+            Setting::Bool(true) => Ok(IndentSequences::True),
+            Setting::Bool(false) => Ok(IndentSequences::False),
+            Setting::Name(name) if name == "whatever" => Ok(IndentSequences::Whatever),
+            Setting::Name(name) if name == "consistent" => Ok(IndentSequences::Consistent),
+            Setting::Name(name) => Err(serde::de::Error::custom(format!(
+                "expected true, false, whatever or consistent, got '{}'",
+                name
+            ))),
+        }
+    }
+}
+
 impl Default for IndentationConfig {
     fn default() -> Self {
         Self {
             spaces: 2,
-            indent_sequences: true,
+            indent_sequences: IndentSequences::True,
             check_multi_line_strings: false,
             ignore_patterns: Vec::new(),
         }
@@ -127,6 +175,10 @@ impl crate::rules::Rule for IndentationRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         true
     }
@@ -146,6 +198,30 @@ impl crate::rules::Rule for IndentationRule {
 }
 
 impl IndentationRule {
+    /// Column the entries of a sequence under a key at `key_indent` should
+    /// start at, when they start at `found`. `indented` is the style in force:
+    /// fixed by `true` or `false`, and for `consistent` set by the first
+    /// sequence seen
+    fn sequence_indent(
+        &self,
+        key_indent: usize,
+        found: usize,
+        indented: &mut Option<bool>,
+    ) -> usize {
+        let indented_column = key_indent + self.config().spaces;
+        match *indented {
+            Some(true) => indented_column,
+            Some(false) => key_indent,
+            None if found == key_indent || found == indented_column => {
+                if self.config().indent_sequences == IndentSequences::Consistent {
+                    *indented = Some(found == indented_column);
+                }
+                found
+            }
+            None => indented_column,
+        }
+    }
+
     fn detect_indent(&self, _base_indent: usize, next: &Token) -> usize {
         let Token(marker, _) = next;
         marker.col()
@@ -188,6 +264,11 @@ impl IndentationRule {
 
         let mut cur_line: usize = 0;
         let mut reported_error_for_key = false;
+        let mut indented_sequences = match self.config().indent_sequences {
+            IndentSequences::True => Some(true),
+            IndentSequences::False => Some(false),
+            IndentSequences::Whatever | IndentSequences::Consistent => None,
+        };
         for (idx, token) in tokens.iter().enumerate() {
             let Token(marker, ref token_type) = token;
             let next_token = tokens.get(idx + 1);
@@ -196,6 +277,19 @@ impl IndentationRule {
                 !matches!(token_type, TokenType::StreamStart(_) | TokenType::StreamEnd);
             let first_in_line = is_visible && marker.line() > cur_line;
 
+            // Sequences indented like their key have no end token; the first
+            // token back at or left of their entries ends them
+            if first_in_line
+                && !matches!(token_type, TokenType::BlockEntry)
+                && stack.len() >= 3
+                && stack.last().is_some_and(|p| {
+                    p.parent_type == ParentType::BlockEnt && p.indent >= marker.col()
+                })
+                && stack[stack.len() - 2].parent_type == ParentType::Val
+            {
+                stack.pop();
+            }
+
             if stack.len() >= 2
                 && stack.last().unwrap().parent_type == ParentType::Val
                 && !matches!(
@@ -204,6 +298,7 @@ impl IndentationRule {
                         | TokenType::Tag(_, _)
                         | TokenType::Alias(_)
                         | TokenType::BlockEntry
+                        | TokenType::BlockSequenceStart
                 )
             {
                 if stack[stack.len() - 2].parent_type == ParentType::Key {
@@ -299,6 +394,36 @@ impl IndentationRule {
 
                 // Calculate expected indentation based on context
                 let expected = match token_type {
+                    // An indented sequence starts where its key's children
+                    // do, or at the key itself without `indent-sequences`
+                    TokenType::BlockSequenceStart
+                        if stack.len() >= 3
+                            && stack[stack.len() - 2].parent_type == ParentType::Val =>
+                    {
+                        self.sequence_indent(
+                            stack[stack.len() - 3].indent,
+                            found_indentation,
+                            &mut indented_sequences,
+                        )
+                    }
+                    // Later entries line up with the sequence's first one
+                    TokenType::BlockEntry
+                        if stack.len() >= 2
+                            && stack[stack.len() - 2].parent_type == ParentType::BlockSeq =>
+                    {
+                        stack[stack.len() - 2].indent
+                    }
+                    TokenType::BlockEntry
+                        if stack.len() >= 3
+                            && stack[stack.len() - 2].parent_type == ParentType::Val
+                            && stack[stack.len() - 3].parent_type == ParentType::Key =>
+                    {
+                        self.sequence_indent(
+                            stack[stack.len() - 3].indent,
+                            found_indentation,
+                            &mut indented_sequences,
+                        )
+                    }
                     TokenType::BlockEntry => {
                         // For BlockEntry (list item), expected indent depends on parent context
                         // Find the mapping key that contains this sequence
@@ -346,6 +471,7 @@ impl IndentationRule {
                             line: marker.line() + 1,
                             column: found_indentation + 1,
                             message,
+                            code: "indentation/wrong-indentation".to_string(),
                             severity: self.get_severity(),
                         });
                     }
@@ -498,4 +624,53 @@ mod tests {
         let issues = rule.check(content, "test.yaml");
         assert!(issues.is_empty());
     }
+
+    #[test]
+    fn test_indentation_nested_sequence_entries() {
+        let rule = IndentationRule::new();
+        let content = "rules:\n  truthy:\n    allowed:\n      - a\n      - b\n  other: 1\n";
+        assert!(rule.check(content, "test.yaml").is_empty());
+    }
+
+    #[test]
+    fn test_indentation_without_indent_sequences() {
+        let rule = IndentationRule::with_config(IndentationConfig {
+            spaces: 4,
+            indent_sequences: IndentSequences::False,
+            ..IndentationConfig::default()
+        });
+        let content = "server:\n    ports:\n    - 80\n    - 443\n    name: web\n";
+        assert!(rule.check(content, "test.yaml").is_empty());
+
+        let content = "server:\n    ports:\n        - 80\n        - 443\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("expected 4 but found 8"));
+    }
+
+    #[test]
+    fn test_indentation_indent_sequences_whatever_and_consistent() {
+        let config = |indent_sequences| IndentationConfig {
+            indent_sequences,
+            ..IndentationConfig::default()
+        };
+        let mixed = "a:\n  - 1\nb:\n- 2\nc:\n    - 3\n";
+
+        let whatever = IndentationRule::with_config(config(IndentSequences::Whatever));
+        let issues = whatever.check(mixed, "test.yaml");
+        assert_eq!(issues.len(), 1, "{:?}", issues);
+        assert!(issues[0].message.contains("expected 2 but found 4"));
+
+        let consistent = IndentationRule::with_config(config(IndentSequences::Consistent));
+        let issues = consistent.check(mixed, "test.yaml");
+        assert_eq!(issues.len(), 2, "{:?}", issues);
+        assert!(issues[0].message.contains("expected 2 but found 0"));
+        assert!(issues[1].message.contains("expected 2 but found 4"));
+
+        let flush_first = "a:\n- 1\nb:\n  - 2\n";
+        let issues = consistent.check(flush_first, "test.yaml");
+        assert_eq!(issues.len(), 1, "{:?}", issues);
+        assert!(issues[0].message.contains("expected 0 but found 2"));
+        assert!(whatever.check(flush_first, "test.yaml").is_empty());
+    }
 }
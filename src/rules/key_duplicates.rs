@@ -1,5 +1,6 @@
 use crate::{LintIssue, Severity};
-use yaml_rust::scanner::{Scanner, Token, TokenType};
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, TScalarStyle, Token, TokenType};
 
 #[derive(Debug, Clone, PartialEq)]
 enum ParentType {
@@ -10,7 +11,8 @@ enum ParentType {
 #[derive(Debug, Clone)]
 struct Parent {
     parent_type: ParentType,
-    keys: Vec<String>,
+    /// Keys seen so far, with whether each is a merge key
+    keys: Vec<(bool, String)>,
 }
 
 impl Parent {
@@ -22,7 +24,8 @@ impl Parent {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct KeyDuplicatesConfig {
     pub forbid_duplicated_merge_keys: bool,
 }
@@ -109,6 +112,10 @@ impl crate::rules::Rule for KeyDuplicatesRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         false
     }
@@ -152,31 +159,29 @@ impl KeyDuplicatesRule {
                     }
                 }
                 TokenType::Key => {
-                    if let Some(next_token) = tokens.get(i + 1) {
-                        let Token(_, next_token_type) = next_token;
-                        if let TokenType::Scalar(_scalar_type, key_value) = next_token_type {
-                            if !stack.is_empty()
-                                && stack.last().unwrap().parent_type == ParentType::Map
-                            {
-                                let current_parent = stack.last_mut().unwrap();
-
-                                if current_parent.keys.contains(key_value) {
-                                    if key_value != "<<"
-                                        || self.config().forbid_duplicated_merge_keys
-                                    {
-                                        issues.push(LintIssue {
-                                            line: marker.line() + 1,
-                                            column: marker.col() + 1,
-                                            message: format!(
-                                                "duplication of key \"{}\" in mapping",
-                                                key_value
-                                            ),
-                                            severity: self.get_severity(),
-                                        });
-                                    }
-                                } else {
-                                    current_parent.keys.push(key_value.clone());
-                                }
+                    if let Some(Token(_, TokenType::Scalar(style, key_value))) = tokens.get(i + 1) {
+                        if let Some(current_parent) = stack
+                            .last_mut()
+                            .filter(|parent| parent.parent_type == ParentType::Map)
+                        {
+                            // Only a plain `<<` is a merge key; a quoted one is an
+                            // ordinary key that merely looks like it
+                            let is_merge_key = *style == TScalarStyle::Plain && key_value == "<<";
+                            let key = (is_merge_key, key_value.clone());
+
+                            if !current_parent.keys.contains(&key) {
+                                current_parent.keys.push(key);
+                            } else if !is_merge_key || self.config().forbid_duplicated_merge_keys {
+                                issues.push(LintIssue {
+                                    line: marker.line(),
+                                    column: marker.col() + 1,
+                                    message: format!(
+                                        "duplication of key \"{}\" in mapping",
+                                        key_value
+                                    ),
+                                    code: "key-duplicates/duplicated-key".to_string(),
+                                    severity: self.get_severity(),
+                                });
                             }
                         }
                     }
@@ -302,6 +307,24 @@ merged:
         assert!(issues[0].message.contains("duplication of key \"<<\""));
     }
 
+    #[test]
+    fn test_key_duplicates_single_merge_key_is_never_a_duplicate() {
+        let rule = KeyDuplicatesRule::with_config(KeyDuplicatesConfig {
+            forbid_duplicated_merge_keys: true,
+        });
+
+        let content =
+            "---\nbase: &b {x: 1}\nm:\n  <<: *b\n  \"<<\": 2\n  '<<': 3\nn: {<<: *b, y: 1}\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1, "{:?}", issues);
+        assert_eq!((issues[0].line, issues[0].column), (6, 3));
+
+        let content = "---\nm:\n  <<: *b\n  y: 2\n  <<: *c\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (5, 3));
+    }
+
     #[test]
     fn test_key_duplicates_check_list_structure() {
         let rule = KeyDuplicatesRule::new();
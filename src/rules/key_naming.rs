@@ -0,0 +1,389 @@
+use crate::patterns;
+use crate::{LintIssue, Severity};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, Token, TokenType};
+
+/// Preset key naming styles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum KeyConvention {
+    #[default]
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[serde(rename = "kebab-case")]
+    KebabCase,
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    #[serde(rename = "PascalCase")]
+    PascalCase,
+}
+
+impl KeyConvention {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyConvention::SnakeCase => "snake_case",
+            KeyConvention::KebabCase => "kebab-case",
+            KeyConvention::CamelCase => "camelCase",
+            KeyConvention::PascalCase => "PascalCase",
+        }
+    }
+
+    fn pattern(&self) -> &'static str {
+        match self {
+            KeyConvention::SnakeCase => r"^[a-z][a-z0-9]*(_[a-z0-9]+)*$",
+            KeyConvention::KebabCase => r"^[a-z][a-z0-9]*(-[a-z0-9]+)*$",
+            KeyConvention::CamelCase => r"^[a-z][a-zA-Z0-9]*$",
+            KeyConvention::PascalCase => r"^[A-Z][a-zA-Z0-9]*$",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct KeyNamingConfig {
+    /// Naming style keys must follow, unless `pattern` is set
+    pub convention: KeyConvention,
+    /// Regular expression keys must match, replacing `convention`
+    #[serde(deserialize_with = "patterns::deserialize_regex")]
+    pub pattern: String,
+    /// Shallowest nesting depth checked, 1 being top-level keys
+    pub min_depth: usize,
+    /// Deepest nesting depth checked, -1 for no limit
+    pub max_depth: i64,
+    /// Dotted key paths, e.g. `metadata.labels`; when set, only keys nested
+    /// under one of them are checked
+    pub paths: Vec<String>,
+}
+
+impl Default for KeyNamingConfig {
+    fn default() -> Self {
+        Self {
+            convention: KeyConvention::default(),
+            pattern: String::new(),
+            min_depth: 1,
+            max_depth: -1,
+            paths: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyNamingRule {
+    base: crate::rules::base::BaseRule<KeyNamingConfig>,
+    regex: Option<Regex>,
+}
+
+/// An open flow or block collection, with the last key read for mappings
+#[derive(Debug)]
+enum Collection {
+    Mapping(Option<String>),
+    Sequence,
+}
+
+impl KeyNamingRule {
+    pub fn new() -> Self {
+        Self::with_config(KeyNamingConfig::default())
+    }
+
+    pub fn with_config(config: KeyNamingConfig) -> Self {
+        let mut rule = Self {
+            base: crate::rules::base::BaseRule::new(config.clone()),
+            regex: None,
+        };
+        rule.set_config(config);
+        rule
+    }
+
+    pub fn config(&self) -> &KeyNamingConfig {
+        self.base.config()
+    }
+
+    pub fn set_config(&mut self, config: KeyNamingConfig) {
+        let pattern = if config.pattern.is_empty() {
+            config.convention.pattern()
+        } else {
+            config.pattern.as_str()
+        };
+        self.regex = patterns::regex(pattern).ok();
+        self.base.set_config(config);
+    }
+
+    pub fn get_severity(&self) -> Severity {
+        self.base.get_severity(Severity::Error)
+    }
+
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    pub fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    /// Whether a key `depth` mappings deep, under the keys in `path`, is checked
+    fn is_checked(&self, depth: usize, path: &[&str]) -> bool {
+        let config = self.config();
+        if depth < config.min_depth || (config.max_depth >= 0 && depth as i64 > config.max_depth) {
+            return false;
+        }
+        if config.paths.is_empty() {
+            return true;
+        }
+        let path = path.join(".");
+        config.paths.iter().any(|prefix| {
+            path == *prefix
+                || path
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        let Some(regex) = &self.regex else {
+            return issues;
+        };
+
+        let mut stack: Vec<Collection> = Vec::new();
+        let mut expect_key = false;
+
+        for token in tokens {
+            let Token(marker, token_type) = token;
+
+            match token_type {
+                TokenType::BlockMappingStart | TokenType::FlowMappingStart => {
+                    stack.push(Collection::Mapping(None));
+                }
+                TokenType::BlockSequenceStart | TokenType::FlowSequenceStart => {
+                    stack.push(Collection::Sequence);
+                }
+                TokenType::BlockEnd | TokenType::FlowMappingEnd | TokenType::FlowSequenceEnd => {
+                    stack.pop();
+                }
+                TokenType::DocumentStart | TokenType::DocumentEnd => stack.clear(),
+                TokenType::Key => {
+                    expect_key = true;
+                    if let Some(Collection::Mapping(key)) = stack.last_mut() {
+                        *key = None;
+                    }
+                }
+                TokenType::Anchor(_) | TokenType::Tag(_, _) => {}
+                TokenType::Scalar(_, value) if expect_key => {
+                    expect_key = false;
+                    let Some((Collection::Mapping(key), parents)) = stack.split_last_mut() else {
+                        continue;
+                    };
+                    *key = Some(value.clone());
+                    if value == "<<" || regex.is_match(value) {
+                        continue;
+                    }
+
+                    let path: Vec<&str> = parents
+                        .iter()
+                        .filter_map(|collection| match collection {
+                            Collection::Mapping(key) => key.as_deref(),
+                            Collection::Sequence => None,
+                        })
+                        .collect();
+                    let depth = 1 + parents
+                        .iter()
+                        .filter(|collection| matches!(collection, Collection::Mapping(_)))
+                        .count();
+                    if !self.is_checked(depth, &path) {
+                        continue;
+                    }
+
+                    let expected = if self.config().pattern.is_empty() {
+                        self.config().convention.as_str().to_string()
+                    } else {
+                        format!("pattern \"{}\"", self.config().pattern)
+                    };
+                    issues.push(LintIssue {
+                        line: marker.line(),
+                        column: marker.col() + 1,
+                        message: format!("key \"{}\" does not match {}", value, expected),
+                        code: "key-naming/invalid-name".to_string(),
+                        severity: self.get_severity(),
+                    });
+                }
+                _ => expect_key = false,
+            }
+        }
+
+        issues
+    }
+
+    pub fn check_impl(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let scanner = Scanner::new(content.chars());
+        let tokens: Vec<_> = scanner.collect();
+        self.check_with_tokens(&tokens)
+    }
+}
+
+impl Default for KeyNamingRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl crate::rules::Rule for KeyNamingRule {
+    fn rule_id(&self) -> &'static str {
+        "key-naming"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "Key Naming"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Enforces a naming convention or pattern for mapping keys."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, content: &str, file_path: &str) -> Vec<LintIssue> {
+        self.check_impl(content, file_path)
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        if let Some(token_analysis) = analysis.tokens() {
+            self.check_with_tokens(&token_analysis.tokens)
+        } else {
+            self.check_impl(content, file_path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rule;
+
+    #[test]
+    fn test_key_naming_rule_default() {
+        let rule = KeyNamingRule::new();
+        assert_eq!(rule.rule_id(), "key-naming");
+        assert_eq!(rule.default_severity(), Severity::Error);
+        assert!(!rule.is_enabled_by_default());
+        assert!(!rule.can_fix());
+    }
+
+    #[test]
+    fn test_key_naming_snake_case() {
+        let rule = KeyNamingRule::new();
+        let content = "first_key: 1\nsecondKey:\n  nested-key: [a, b]\n  items:\n    - inner_ok: 1\n      Bad_Key: 2\n  <<: {flow_key: 1, flowKey: 2}\n";
+        let issues = rule.check(content, "test.yaml");
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.line, issue.column))
+            .collect();
+        assert_eq!(found, vec![(2, 1), (3, 3), (6, 7), (7, 21)]);
+        assert_eq!(
+            issues[0].message,
+            "key \"secondKey\" does not match snake_case"
+        );
+        assert_eq!(issues[0].code, "key-naming/invalid-name");
+    }
+
+    #[test]
+    fn test_key_naming_conventions_and_pattern() {
+        let content = "camelCase: 1\nkebab-case: 2\nPascalCase: 3\n";
+        let count =
+            |config: KeyNamingConfig| KeyNamingRule::with_config(config).check(content, "t").len();
+        let with = |convention| KeyNamingConfig {
+            convention,
+            ..Default::default()
+        };
+        assert_eq!(count(with(KeyConvention::CamelCase)), 2);
+        assert_eq!(count(with(KeyConvention::KebabCase)), 2);
+        assert_eq!(count(with(KeyConvention::PascalCase)), 2);
+
+        let rule = KeyNamingRule::with_config(KeyNamingConfig {
+            pattern: "^[a-z-]+$".to_string(),
+            ..Default::default()
+        });
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0].message,
+            "key \"camelCase\" does not match pattern \"^[a-z-]+$\""
+        );
+    }
+
+    #[test]
+    fn test_key_naming_depth_and_paths() {
+        let content = "apiVersion: v1\nmetadata:\n  creationTimestamp: null\n  labels:\n    app.kubernetes.io/name: web\n    team_name: core\nspec:\n  containers:\n    - imagePullPolicy: Always\n";
+
+        let rule = KeyNamingRule::with_config(KeyNamingConfig {
+            convention: KeyConvention::CamelCase,
+            max_depth: 2,
+            ..Default::default()
+        });
+        assert!(rule.check(content, "test.yaml").is_empty());
+
+        let rule = KeyNamingRule::with_config(KeyNamingConfig {
+            convention: KeyConvention::CamelCase,
+            min_depth: 3,
+            ..Default::default()
+        });
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 2);
+        assert_eq!((issues[0].line, issues[1].line), (5, 6));
+
+        let rule = KeyNamingRule::with_config(KeyNamingConfig {
+            convention: KeyConvention::CamelCase,
+            paths: vec!["spec".to_string(), "metadata.labels".to_string()],
+            ..Default::default()
+        });
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].line, 5);
+
+        let rule = KeyNamingRule::with_config(KeyNamingConfig {
+            paths: vec!["spec".to_string()],
+            ..Default::default()
+        });
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 9);
+    }
+
+    #[test]
+    fn test_key_naming_options_from_yamllint_names() {
+        let options = serde_json::json!({ "convention": "kebab-case", "max-depth": 1 });
+        let config: KeyNamingConfig = serde_json::from_value(options).unwrap();
+        assert_eq!(config.convention, KeyConvention::KebabCase);
+        assert_eq!(config.max_depth, 1);
+        let options = serde_json::json!({ "convention": "Title Case" });
+        assert!(serde_json::from_value::<KeyNamingConfig>(options).is_err());
+        let options = serde_json::json!({ "pattern": "[a-" });
+        assert!(serde_json::from_value::<KeyNamingConfig>(options).is_err());
+    }
+}
@@ -1,27 +1,173 @@
+use super::sequence_ordering::path_matches;
 use super::Rule;
+use crate::patterns;
 use crate::{LintIssue, Severity};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use yaml_rust::scanner::{Marker, Scanner, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct KeyOrderingConfig {
     pub require_alphabetical: bool,
+    /// Compare keys ignoring case, so `Name` and `name` sort together
+    pub case_insensitive: bool,
+    /// Keys matching any of these regular expressions from their start are
+    /// not checked, as in yamllint
+    #[serde(deserialize_with = "patterns::deserialize_regexes")]
+    pub ignored_keys: Vec<String>,
+    /// Explicit key orders for the mappings under given key paths
+    pub orders: Vec<KeyOrder>,
+    /// The config's top-level `locale`, not an option of the rule
+    #[serde(skip)]
+    pub locale: Option<String>,
+}
+
+impl Default for KeyOrderingConfig {
+    fn default() -> Self {
+        Self {
+            require_alphabetical: true,
+            case_insensitive: false,
+            ignored_keys: Vec::new(),
+            orders: Vec::new(),
+            locale: None,
+        }
+    }
+}
+
+/// The order of the keys of the mappings under `path`. Listed keys come
+/// first, in the order given; other keys follow them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyOrder {
+    /// Dotted key path of the mappings, where `*` matches any one key and
+    /// `**` any number of keys; the top-level mapping when empty
+    pub path: String,
+    pub keys: Vec<String>,
+}
+
+impl KeyOrder {
+    fn applies_to(&self, path: &[String]) -> bool {
+        if self.path.is_empty() {
+            return path.is_empty();
+        }
+        let pattern: Vec<&str> = self.path.split('.').collect();
+        path_matches(&pattern, path)
+    }
+}
+
+/// A mapping key and where it starts
+#[derive(Debug)]
+struct Key {
+    name: String,
+    marker: Marker,
+}
+
+/// An open collection while walking the tokens
+#[derive(Debug)]
+enum Collection {
+    Mapping {
+        path: Vec<String>,
+        keys: Vec<Key>,
+    },
+    Sequence {
+        path: Vec<String>,
+        /// A block sequence whose entries are not indented under their key,
+        /// which has no end token of its own
+        indentless: bool,
+    },
+}
+
+/// Languages whose collation [`collate`] models: those sorting accented
+/// letters with their base letter
+const COLLATED_LANGUAGES: &[&str] = &["de", "en", "fr", "it", "nl", "pt"];
+
+/// The language of a locale name such as `en_US.UTF-8` or `de_DE@euro`
+fn locale_language(locale: &str) -> &str {
+    locale.split(['_', '.', '@']).next().unwrap_or(locale)
+}
+
+/// Whether `locale` compares keys by code point: the `C` and `POSIX` locales
+/// and their UTF-8 variants
+fn is_byte_order_locale(locale: &str) -> bool {
+    matches!(locale_language(locale), "" | "C" | "POSIX")
+}
+
+/// Whether `key-ordering` knows how `locale` sorts keys. Other locales, such
+/// as `sv_SE` where `å` sorts after `z`, fall back to code point order.
+pub fn is_supported_locale(locale: &str) -> bool {
+    is_byte_order_locale(locale) || COLLATED_LANGUAGES.contains(&locale_language(locale))
+}
+
+/// Compare keys like `strcoll` does under a Latin-script locale such as
+/// `en_US.UTF-8`: letters first by their base letter ignoring punctuation,
+/// then unaccented before accented, then lowercase before uppercase
+fn collate(a: &str, b: &str) -> Ordering {
+    fn levels(key: &str) -> (Vec<char>, Vec<bool>, Vec<bool>) {
+        let mut base = Vec::new();
+        let mut accented = Vec::new();
+        let mut uppercase = Vec::new();
+        for c in key.chars().filter(|c| c.is_alphanumeric()) {
+            let lower = c.to_lowercase().next().unwrap_or(c);
+            let letters = base_letters(lower);
+            accented.push(letters[0] != lower);
+            uppercase.push(lower != c);
+            base.extend(letters.iter().filter(|&&letter| letter != '\0'));
+        }
+        (base, accented, uppercase)
+    }
+    levels(a).cmp(&levels(b)).then_with(|| a.cmp(b))
+}
+
+/// Letters a lowercase letter sorts as at the first level; the second is
+/// `'\0'` unless it expands to two, like `ß`
+fn base_letters(c: char) -> [char; 2] {
+    let base = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'ŕ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'ţ' | 'ť' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        'æ' => return ['a', 'e'],
+        'œ' => return ['o', 'e'],
+        'ß' => return ['s', 's'],
+        _ => c,
+    };
+    [base, '\0']
 }
 
 #[derive(Debug, Clone)]
 pub struct KeyOrderingRule {
     config: KeyOrderingConfig,
+    ignored_keys: Vec<Regex>,
 }
 
 impl KeyOrderingRule {
     pub fn new() -> Self {
-        Self {
-            config: KeyOrderingConfig {
-                require_alphabetical: true,
-            },
-        }
+        Self::with_config(KeyOrderingConfig::default())
     }
 
     pub fn with_config(config: KeyOrderingConfig) -> Self {
-        Self { config }
+        // Anchored at the start like Python's `re.match`
+        let ignored_keys = config
+            .ignored_keys
+            .iter()
+            .filter_map(|pattern| patterns::regex(&format!("^(?:{})", pattern)).ok())
+            .collect();
+        Self {
+            config,
+            ignored_keys,
+        }
     }
 
     fn extract_keys(&self, content: &str) -> Vec<(usize, String)> {
@@ -45,16 +191,165 @@ impl KeyOrderingRule {
         keys
     }
 
-    fn check_alphabetical_order(&self, keys: &[(usize, String)]) -> Vec<usize> {
-        let mut violations = Vec::new();
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        if self.config.case_insensitive {
+            let (a, b) = (a.to_lowercase(), b.to_lowercase());
+            return self.compare_case_sensitive(&a, &b);
+        }
+        self.compare_case_sensitive(a, b)
+    }
 
-        for i in 1..keys.len() {
-            if keys[i].1 < keys[i - 1].1 {
-                violations.push(keys[i].0);
+    fn compare_case_sensitive(&self, a: &str, b: &str) -> Ordering {
+        match self.config.locale.as_deref() {
+            Some(locale) if !is_byte_order_locale(locale) && is_supported_locale(locale) => {
+                collate(a, b)
             }
+            _ => a.cmp(b),
         }
+    }
 
-        violations
+    fn is_ignored(&self, key: &str) -> bool {
+        self.ignored_keys.iter().any(|regex| regex.is_match(key))
+    }
+
+    /// The keys of each mapping in the order they appear, with the key path
+    /// of the mapping
+    fn mappings(tokens: &[Token]) -> Vec<(Vec<String>, Vec<Key>)> {
+        fn close(stack: &mut Vec<Collection>, found: &mut Vec<(Vec<String>, Vec<Key>)>) {
+            if let Some(Collection::Mapping { path, keys }) = stack.pop() {
+                found.push((path, keys));
+            }
+        }
+
+        let mut found = Vec::new();
+        let mut stack: Vec<Collection> = Vec::new();
+        let mut expect_key = false;
+
+        for Token(marker, token) in tokens {
+            let is_indentless = matches!(
+                stack.last(),
+                Some(Collection::Sequence {
+                    indentless: true,
+                    ..
+                })
+            );
+            // The key path of a collection starting here
+            let path = match stack.last() {
+                Some(Collection::Mapping { path, keys }) => {
+                    let mut path = path.clone();
+                    path.extend(keys.last().map(|key| key.name.clone()));
+                    path
+                }
+                Some(Collection::Sequence { path, .. }) => path.clone(),
+                None => Vec::new(),
+            };
+
+            match token {
+                TokenType::BlockMappingStart | TokenType::FlowMappingStart => {
+                    stack.push(Collection::Mapping {
+                        path,
+                        keys: Vec::new(),
+                    });
+                }
+                TokenType::BlockSequenceStart | TokenType::FlowSequenceStart => {
+                    stack.push(Collection::Sequence {
+                        path,
+                        indentless: false,
+                    });
+                }
+                TokenType::BlockEntry => {
+                    if let Some(Collection::Mapping { .. }) = stack.last() {
+                        stack.push(Collection::Sequence {
+                            path,
+                            indentless: true,
+                        });
+                    }
+                }
+                TokenType::Key => {
+                    if is_indentless {
+                        stack.pop();
+                    }
+                    expect_key = true;
+                    continue;
+                }
+                TokenType::BlockEnd => {
+                    if is_indentless {
+                        stack.pop();
+                    }
+                    close(&mut stack, &mut found);
+                }
+                TokenType::FlowMappingEnd | TokenType::FlowSequenceEnd => {
+                    close(&mut stack, &mut found);
+                }
+                TokenType::Scalar(_, value) if expect_key => {
+                    if let Some(Collection::Mapping { keys, .. }) = stack.last_mut() {
+                        keys.push(Key {
+                            name: value.clone(),
+                            marker: *marker,
+                        });
+                    }
+                }
+                TokenType::DocumentStart | TokenType::DocumentEnd | TokenType::StreamEnd => {
+                    while !stack.is_empty() {
+                        close(&mut stack, &mut found);
+                    }
+                }
+                TokenType::Anchor(_) | TokenType::Tag(_, _) => continue,
+                _ => {}
+            }
+            expect_key = false;
+        }
+
+        found
+    }
+
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+
+        for (path, keys) in Self::mappings(tokens) {
+            let order = self
+                .config
+                .orders
+                .iter()
+                .find(|order| order.applies_to(&path));
+            // Listed keys by their position, then the others
+            let rank = |key: &str| {
+                order
+                    .and_then(|order| order.keys.iter().position(|listed| listed == key))
+                    .unwrap_or(usize::MAX)
+            };
+
+            let keys: Vec<&Key> = keys
+                .iter()
+                .filter(|key| !self.is_ignored(&key.name))
+                .collect();
+            for pair in keys.windows(2) {
+                let (previous, key) = (pair[0], pair[1]);
+                let message = match rank(&key.name).cmp(&rank(&previous.name)) {
+                    Ordering::Less => format!(
+                        "key \"{}\" should come before \"{}\"",
+                        key.name, previous.name
+                    ),
+                    Ordering::Equal
+                        if self.config.require_alphabetical
+                            && self.compare(&key.name, &previous.name) == Ordering::Less =>
+                    {
+                        "keys not in alphabetical order".to_string()
+                    }
+                    _ => continue,
+                };
+                issues.push(LintIssue {
+                    line: key.marker.line(),
+                    column: key.marker.col() + 1,
+                    message,
+                    code: "key-ordering/wrong-order".to_string(),
+                    severity: self.get_severity(),
+                });
+            }
+        }
+
+        issues.sort_by_key(|issue| (issue.line, issue.column));
+        issues
     }
 }
 
@@ -85,24 +380,25 @@ impl Rule for KeyOrderingRule {
         false
     }
 
-    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
-        let mut issues = Vec::new();
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().config).unwrap_or_default()
+    }
 
-        if self.config.require_alphabetical {
-            let keys = self.extract_keys(content);
-            let violations = self.check_alphabetical_order(&keys);
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
 
-            for line_num in violations {
-                issues.push(LintIssue {
-                    line: line_num,
-                    column: 1,
-                    message: "keys not in alphabetical order".to_string(),
-                    severity: self.get_severity(),
-                });
-            }
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
         }
-
-        issues
     }
 
     fn can_fix(&self) -> bool {
@@ -122,7 +418,7 @@ impl Rule for KeyOrderingRule {
         }
 
         let mut sorted_keys = keys.clone();
-        sorted_keys.sort_by(|a, b| a.1.cmp(&b.1));
+        sorted_keys.sort_by(|a, b| self.compare(&a.1, &b.1));
 
         let needs_reordering = keys.iter().zip(sorted_keys.iter()).any(|(a, b)| a.1 != b.1);
 
@@ -226,4 +522,121 @@ mod tests {
         assert!(!fix_result.changed);
         assert_eq!(fix_result.fixes_applied, 0);
     }
+
+    #[test]
+    fn test_key_ordering_locale_collation() {
+        let c_locale = KeyOrderingRule::with_config(KeyOrderingConfig {
+            locale: Some("C.UTF-8".to_string()),
+            ..KeyOrderingConfig::default()
+        });
+        let en_us = KeyOrderingRule::with_config(KeyOrderingConfig {
+            locale: Some("en_US.UTF-8".to_string()),
+            ..KeyOrderingConfig::default()
+        });
+
+        let content = "t-shirt: 1\nT-shirt: 2\nt-shirts: 3\nT-shirts: 4\n";
+        assert!(en_us.check(content, "test.yaml").is_empty());
+        assert_eq!(c_locale.check(content, "test.yaml").len(), 2);
+
+        let content = "hair: 1\nhaïr: 2\nhais: 3\nhaïssable: 4\nstraße: 5\nstrasse2: 6\n";
+        assert!(en_us.check(content, "test.yaml").is_empty());
+        assert_eq!(c_locale.check(content, "test.yaml").len(), 2);
+
+        let content = "b: 1\nÅ: 2\n";
+        assert_eq!(en_us.check(content, "test.yaml").len(), 1);
+
+        // Unmodelled locales compare by code point
+        let sv_se = KeyOrderingRule::with_config(KeyOrderingConfig {
+            locale: Some("sv_SE.UTF-8".to_string()),
+            ..KeyOrderingConfig::default()
+        });
+        assert!(sv_se.check("b: 1\nz: 2\nå: 3\n", "test.yaml").is_empty());
+    }
+
+    #[test]
+    fn test_key_ordering_supported_locales() {
+        for locale in ["C", "C.UTF-8", "POSIX", "en_US.UTF-8", "de_DE@euro", "fr"] {
+            assert!(is_supported_locale(locale), "{}", locale);
+        }
+        for locale in ["sv_SE.UTF-8", "es_ES", "tr_TR.UTF-8"] {
+            assert!(!is_supported_locale(locale), "{}", locale);
+        }
+    }
+
+    #[test]
+    fn test_key_ordering_checks_each_mapping() {
+        let rule = KeyOrderingRule::new();
+        let content = "a:\n  z: 1\n  y: 2\nb:\n  - d: 1\n    c: 2\n  - a: 1\nc: {f: 1, e: 2}\n";
+        let issues = rule.check(content, "test.yaml");
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.line, issue.column))
+            .collect();
+        assert_eq!(found, vec![(3, 3), (6, 5), (8, 11)]);
+    }
+
+    #[test]
+    fn test_key_ordering_ignored_keys_and_case() {
+        let content = "name: app\napiVersion: v1\nkind: Service\nmetadata: {}\n";
+        let rule = KeyOrderingRule::with_config(KeyOrderingConfig {
+            ignored_keys: vec!["name".to_string()],
+            ..KeyOrderingConfig::default()
+        });
+        assert!(rule.check(content, "test.yaml").is_empty());
+
+        // Patterns match from the start of the key, as in yamllint
+        let rule = KeyOrderingRule::with_config(KeyOrderingConfig {
+            ignored_keys: vec!["ame".to_string()],
+            ..KeyOrderingConfig::default()
+        });
+        assert_eq!(rule.check(content, "test.yaml").len(), 1);
+
+        let content = "apiVersion: v1\nKind: Service\nmetadata: {}\n";
+        assert_eq!(KeyOrderingRule::new().check(content, "test.yaml").len(), 1);
+        let rule = KeyOrderingRule::with_config(KeyOrderingConfig {
+            case_insensitive: true,
+            ..KeyOrderingConfig::default()
+        });
+        assert!(rule.check(content, "test.yaml").is_empty());
+    }
+
+    #[test]
+    fn test_key_ordering_path_orders() {
+        let rule = KeyOrderingRule::with_config(KeyOrderingConfig {
+            orders: vec![
+                KeyOrder {
+                    path: String::new(),
+                    keys: vec!["name".to_string(), "version".to_string()],
+                },
+                KeyOrder {
+                    path: "spec.containers".to_string(),
+                    keys: vec!["name".to_string(), "image".to_string()],
+                },
+            ],
+            ..KeyOrderingConfig::default()
+        });
+
+        let content = "name: app\nversion: 1\nspec:\n  containers:\n    - name: web\n      image: nginx\n      env: []\n      args: []\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (8, 7));
+        assert_eq!(issues[0].message, "keys not in alphabetical order");
+
+        let content =
+            "version: 1\nname: app\nspec:\n  containers:\n    - image: nginx\n      name: web\n";
+        let issues = rule.check(content, "test.yaml");
+        let found: Vec<_> = issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(found, vec![2, 6]);
+        assert_eq!(
+            issues[0].message,
+            "key \"name\" should come before \"version\""
+        );
+
+        let rule = KeyOrderingRule::with_config(KeyOrderingConfig {
+            require_alphabetical: false,
+            ..rule.config.clone()
+        });
+        let content = "name: app\nzone: a\nversion: 1\n";
+        assert_eq!(rule.check(content, "test.yaml").len(), 1);
+    }
 }
@@ -1,13 +1,18 @@
 use crate::rules::base::BaseRule;
 use crate::rules::Rule;
 use crate::{create_issue, LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 use yaml_rust::scanner::{Scanner, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct LineLengthConfig {
+    #[serde(rename = "max")]
     pub max_length: usize,
     pub allow_non_breakable_words: bool,
     pub allow_non_breakable_inline_mappings: bool,
+    /// How a line's length is measured
+    pub measure: LineLengthMeasure,
 }
 
 impl Default for LineLengthConfig {
@@ -16,6 +21,26 @@ impl Default for LineLengthConfig {
             max_length: 80,
             allow_non_breakable_words: true,
             allow_non_breakable_inline_mappings: false,
+            measure: LineLengthMeasure::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LineLengthMeasure {
+    /// Unicode characters, as yamllint counts them
+    #[default]
+    Characters,
+    /// Terminal display width, so wide characters such as CJK and emoji count as two
+    Width,
+}
+
+impl LineLengthMeasure {
+    fn length(self, line: &str) -> usize {
+        match self {
+            LineLengthMeasure::Characters => line.chars().count(),
+            LineLengthMeasure::Width => unicode_width::UnicodeWidthStr::width(line),
         }
     }
 }
@@ -94,6 +119,10 @@ impl Rule for LineLengthRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         false
     }
@@ -108,7 +137,7 @@ impl LineLengthRule {
         let mut issues = Vec::new();
 
         for (line_num, line) in content.lines().enumerate() {
-            let line_length = line.len();
+            let line_length = self.config().measure.length(line);
             if line_length > self.config().max_length {
                 if self.config().allow_non_breakable_words && self.has_non_breakable_content(line) {
                     continue;
@@ -128,6 +157,7 @@ impl LineLengthRule {
                         line_length,
                         self.config().max_length
                     ),
+                    "line-length/too-long",
                     self.get_severity()
                 ));
             }
@@ -228,6 +258,7 @@ mod tests {
             max_length: 100,
             allow_non_breakable_words: true,
             allow_non_breakable_inline_mappings: false,
+            measure: LineLengthMeasure::Characters,
         };
         let rule = LineLengthRule::with_config(config);
         assert_eq!(rule.config().max_length, 100);
@@ -247,6 +278,7 @@ mod tests {
             max_length: 10,
             allow_non_breakable_words: true,
             allow_non_breakable_inline_mappings: false,
+            measure: LineLengthMeasure::Characters,
         };
         let rule = LineLengthRule::with_config(config);
         let content = "short line\nthis is a very long line that exceeds the limit\nshort";
@@ -264,6 +296,7 @@ mod tests {
             max_length: 5,
             allow_non_breakable_words: true,
             allow_non_breakable_inline_mappings: false,
+            measure: LineLengthMeasure::Characters,
         };
         let rule = LineLengthRule::with_config(config);
         let content = "short\nthis is too long\nshort\nanother very long line here";
@@ -282,6 +315,7 @@ mod tests {
             max_length: 20,
             allow_non_breakable_words: true,
             allow_non_breakable_inline_mappings: false,
+            measure: LineLengthMeasure::Characters,
         };
         let rule = LineLengthRule::with_config(config);
 
@@ -297,6 +331,7 @@ mod tests {
             max_length: 20,
             allow_non_breakable_words: false,
             allow_non_breakable_inline_mappings: false,
+            measure: LineLengthMeasure::Characters,
         };
         let rule = LineLengthRule::with_config(config);
 
@@ -313,6 +348,7 @@ mod tests {
             max_length: 20,
             allow_non_breakable_words: true,
             allow_non_breakable_inline_mappings: false,
+            measure: LineLengthMeasure::Characters,
         };
         let rule = LineLengthRule::with_config(config);
 
@@ -329,6 +365,7 @@ mod tests {
             max_length: 10,
             allow_non_breakable_words: true,
             allow_non_breakable_inline_mappings: false,
+            measure: LineLengthMeasure::Characters,
         };
         let rule = LineLengthRule::with_config(config);
 
@@ -345,6 +382,7 @@ mod tests {
             max_length: 20,
             allow_non_breakable_words: true,
             allow_non_breakable_inline_mappings: false,
+            measure: LineLengthMeasure::Characters,
         };
         let rule = LineLengthRule::with_config(config);
 
@@ -419,4 +457,25 @@ normal: short line
             "Found {} line-length issues in inline mappings. yamllint reports 0 issues (allows with allow-non-breakable-inline-mappings). Issues: {:?}",
             length_issues.len(), length_issues);
     }
+
+    #[test]
+    fn test_line_length_measures_characters_or_display_width() {
+        let line = format!("key: {}", "é".repeat(10) + &"界".repeat(5));
+        let content = format!("{}\n", line);
+        let config = |measure| LineLengthConfig {
+            max_length: 20,
+            allow_non_breakable_words: false,
+            allow_non_breakable_inline_mappings: false,
+            measure,
+        };
+
+        // 20 characters, 40 bytes, 25 columns wide
+        let rule = LineLengthRule::with_config(config(LineLengthMeasure::Characters));
+        assert!(rule.check(&content, "test.yaml").is_empty());
+
+        let rule = LineLengthRule::with_config(config(LineLengthMeasure::Width));
+        let issues = rule.check(&content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "line too long (25 > 20 characters)");
+    }
 }
@@ -52,11 +52,13 @@ macro_rules! create_rule {
                 line: usize,
                 column: usize,
                 message: String,
+                code: &str,
             ) -> $crate::LintIssue {
                 $crate::LintIssue {
                     line,
                     column,
                     message,
+                    code: code.to_string(),
                     severity: self.get_severity(),
                 }
             }
@@ -206,11 +208,12 @@ macro_rules! create_regex_rule {
 
 #[macro_export]
 macro_rules! create_issue {
-    ($line:expr, $column:expr, $message:expr, $severity:expr) => {
+    ($line:expr, $column:expr, $message:expr, $code:expr, $severity:expr) => {
         LintIssue {
             line: $line,
             column: $column,
             message: $message,
+            code: $code.to_string(),
             severity: $severity,
         }
     };
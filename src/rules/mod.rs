@@ -21,6 +21,12 @@ pub trait Rule: Send + Sync {
     fn set_severity(&mut self, severity: crate::Severity);
     fn has_severity_override(&self) -> bool;
 
+    /// Options a newly created rule runs with, serialized with yamllint
+    /// option names, for documentation and tooling
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::Value::Object(serde_json::Map::new())
+    }
+
     fn check(&self, content: &str, file_path: &str) -> Vec<LintIssue>;
 
     fn check_with_analysis(
@@ -56,22 +62,34 @@ pub mod colons;
 pub mod commas;
 pub mod comments;
 pub mod comments_indentation;
+pub mod custom_tags;
+pub mod directive_syntax;
 pub mod document_end;
 pub mod document_start;
 pub mod empty_lines;
 pub mod empty_values;
+pub mod file_size;
 pub mod float_values;
+pub mod forbidden_values;
 pub mod hyphens;
 pub mod indentation;
 pub mod key_duplicates;
+pub mod key_naming;
 pub mod key_ordering;
 pub mod line_length;
 pub mod new_line_at_end_of_file;
 pub mod new_lines;
+pub mod no_aliases;
+pub mod non_ascii;
+pub mod norway_problem;
 pub mod octal_values;
 pub mod quoted_strings;
+pub mod secrets;
+pub mod sequence_ordering;
+pub mod timestamp_values;
 pub mod trailing_spaces;
 pub mod truthy;
+pub mod yaml_syntax;
 
 pub use anchors::AnchorsRule;
 pub use braces::BracesRule;
@@ -80,19 +98,31 @@ pub use colons::ColonsRule;
 pub use commas::CommasRule;
 pub use comments::CommentsRule;
 pub use comments_indentation::CommentsIndentationRule;
+pub use custom_tags::CustomTagsRule;
+pub use directive_syntax::DirectiveSyntaxRule;
 pub use document_end::DocumentEndRule;
 pub use document_start::DocumentStartRule;
 pub use empty_lines::EmptyLinesRule;
 pub use empty_values::EmptyValuesRule;
+pub use file_size::FileSizeRule;
 pub use float_values::FloatValuesRule;
+pub use forbidden_values::ForbiddenValuesRule;
 pub use hyphens::HyphensRule;
 pub use indentation::IndentationRule;
 pub use key_duplicates::KeyDuplicatesRule;
+pub use key_naming::KeyNamingRule;
 pub use key_ordering::KeyOrderingRule;
 pub use line_length::LineLengthRule;
 pub use new_line_at_end_of_file::NewLineAtEndOfFileRule;
 pub use new_lines::NewLinesRule;
+pub use no_aliases::NoAliasesRule;
+pub use non_ascii::NonAsciiRule;
+pub use norway_problem::NorwayProblemRule;
 pub use octal_values::OctalValuesRule;
 pub use quoted_strings::QuotedStringsRule;
+pub use secrets::SecretsRule;
+pub use sequence_ordering::SequenceOrderingRule;
+pub use timestamp_values::TimestampValuesRule;
 pub use trailing_spaces::TrailingSpacesRule;
 pub use truthy::TruthyRule;
+pub use yaml_syntax::YamlSyntaxRule;
@@ -1,8 +1,10 @@
 use crate::rules::base::BaseRule;
 use crate::rules::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct NewLineAtEndOfFileConfig {
     pub require: bool,
 }
@@ -51,11 +53,18 @@ impl NewLineAtEndOfFileRule {
         self.base.has_severity_override()
     }
 
-    pub fn create_issue(&self, line: usize, column: usize, message: String) -> LintIssue {
+    pub fn create_issue(
+        &self,
+        line: usize,
+        column: usize,
+        message: String,
+        code: &str,
+    ) -> LintIssue {
         LintIssue {
             line,
             column,
             message,
+            code: code.to_string(),
             severity: self.get_severity(),
         }
     }
@@ -96,6 +105,10 @@ impl Rule for NewLineAtEndOfFileRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         true
     }
@@ -121,6 +134,7 @@ impl NewLineAtEndOfFileRule {
                 line_count,
                 last_line.len() + 1,
                 "no new line character at the end of file".to_string(),
+                "new-line-at-end-of-file/missing",
             ));
         }
 
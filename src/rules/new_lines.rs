@@ -1,27 +1,50 @@
 use super::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct NewLinesConfig {
+    /// `unix`, `dos`, `mac`, or `platform` for the host's line endings
+    #[serde(rename = "type")]
     pub line_type: String,
 }
 
+impl Default for NewLinesConfig {
+    fn default() -> Self {
+        Self {
+            line_type: "unix".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NewLinesRule {
-    config: NewLinesConfig,
+    base: crate::rules::base::BaseRule<NewLinesConfig>,
 }
 
 impl NewLinesRule {
     pub fn new() -> Self {
+        Self::with_config(NewLinesConfig::default())
+    }
+
+    pub fn with_config(config: NewLinesConfig) -> Self {
         Self {
-            config: NewLinesConfig {
-                line_type: "unix".to_string(),
-            },
+            base: crate::rules::base::BaseRule::new(config),
         }
     }
 
-    pub fn with_config(config: NewLinesConfig) -> Self {
-        Self { config }
+    pub fn config(&self) -> &NewLinesConfig {
+        self.base.config()
+    }
+
+    /// The expected line ending type, with `platform` resolved for the host
+    fn line_type(&self) -> &str {
+        match self.config().line_type.as_str() {
+            "platform" if cfg!(windows) => "dos",
+            "platform" => "unix",
+            line_type => line_type,
+        }
     }
 
     fn check_newline_type(&self, content: &str) -> Vec<LintIssue> {
@@ -31,43 +54,34 @@ impl NewLinesRule {
             return issues;
         }
 
-        let _expected_newline = match self.config.line_type.as_str() {
-            "unix" => "\n",
-            "dos" => "\r\n",
-            "mac" => "\r",
-            _ => "\n",
-        };
-
-        let has_unix = content.contains('\n');
-        let has_dos = content.contains("\r\n");
-        let has_mac = content.contains('\r') && !content.contains("\r\n");
+        let dos = content.matches("\r\n").count();
+        let unix = content.matches('\n').count() - dos;
+        let mac = content.matches('\r').count() - dos;
 
-        let mut found_types = Vec::new();
-        if has_unix {
-            found_types.push("unix");
-        }
-        if has_dos {
-            found_types.push("dos");
-        }
-        if has_mac {
-            found_types.push("mac");
-        }
+        let found_types: Vec<&str> = [("unix", unix), ("dos", dos), ("mac", mac)]
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(line_type, _)| line_type)
+            .collect();
 
         if found_types.len() > 1 {
             issues.push(LintIssue {
                 line: 1,
                 column: 1,
                 message: format!("mixed line endings found: {}", found_types.join(", ")),
+                code: "new-lines/mixed-line-endings".to_string(),
                 severity: self.get_severity(),
             });
-        } else if !found_types.is_empty() && found_types[0] != self.config.line_type {
+        } else if !found_types.is_empty() && found_types[0] != self.line_type() {
             issues.push(LintIssue {
                 line: 1,
                 column: 1,
                 message: format!(
                     "wrong line ending type: expected {}, found {}",
-                    self.config.line_type, found_types[0]
+                    self.line_type(),
+                    found_types[0]
                 ),
+                code: "new-lines/wrong-line-ending".to_string(),
                 severity: self.get_severity(),
             });
         }
@@ -94,13 +108,19 @@ impl Rule for NewLinesRule {
     }
 
     fn get_severity(&self) -> Severity {
-        self.default_severity()
+        self.base.get_severity(self.default_severity())
     }
 
-    fn set_severity(&mut self, _severity: Severity) {}
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
 
     fn has_severity_override(&self) -> bool {
-        false
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
     }
 
     fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
@@ -120,7 +140,7 @@ impl Rule for NewLinesRule {
             };
         }
 
-        let target_newline = match self.config.line_type.as_str() {
+        let target_newline = match self.line_type() {
             "unix" => "\n",
             "dos" => "\r\n",
             "mac" => "\r",
@@ -189,7 +209,28 @@ mod tests {
         let content = "key: value\r\n";
         let issues = rule.check(content, "test.yaml");
         assert_eq!(issues.len(), 1);
-        assert!(issues[0].message.contains("mixed line endings"));
+        assert_eq!(
+            issues[0].message,
+            "wrong line ending type: expected unix, found dos"
+        );
+
+        let issues = rule.check("a: 1\r\nb: 2\n", "test.yaml");
+        assert_eq!(issues[0].message, "mixed line endings found: unix, dos");
+    }
+
+    #[test]
+    fn test_new_lines_platform_type_follows_the_host() {
+        let rule = NewLinesRule::with_config(NewLinesConfig {
+            line_type: "platform".to_string(),
+        });
+        let (native, foreign) = if cfg!(windows) {
+            ("key: value\r\n", "key: value\n")
+        } else {
+            ("key: value\n", "key: value\r\n")
+        };
+        assert!(rule.check(native, "test.yaml").is_empty());
+        assert_eq!(rule.check(foreign, "test.yaml").len(), 1);
+        assert_eq!(rule.fix(foreign, "test.yaml").content, native);
     }
 
     #[test]
@@ -0,0 +1,176 @@
+use super::Rule;
+use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, TScalarStyle, Token, TokenType};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct NoAliasesConfig {
+    /// Allow aliases as the value of a `<<` merge key, alone or in a flow
+    /// sequence such as `<<: [*base, *extra]`
+    pub allow_merge_keys: bool,
+}
+
+/// Flags every alias, for tooling such as JSON converters that does not
+/// resolve them; unlike `anchors`, declared aliases are reported too
+#[derive(Debug, Clone)]
+pub struct NoAliasesRule {
+    base: crate::rules::base::BaseRule<NoAliasesConfig>,
+}
+
+impl NoAliasesRule {
+    pub fn new() -> Self {
+        Self::with_config(NoAliasesConfig::default())
+    }
+
+    pub fn with_config(config: NoAliasesConfig) -> Self {
+        Self {
+            base: crate::rules::base::BaseRule::new(config),
+        }
+    }
+
+    pub fn config(&self) -> &NoAliasesConfig {
+        self.base.config()
+    }
+
+    pub fn set_config(&mut self, config: NoAliasesConfig) {
+        self.base.set_config(config);
+    }
+
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        // Whether the last value started is that of a merge key
+        let mut merge_value = false;
+        // Whether we are in the flow sequence of a merge key
+        let mut merge_list = false;
+
+        for (i, Token(marker, token)) in tokens.iter().enumerate() {
+            match token {
+                TokenType::Value => {
+                    merge_value = i >= 2
+                        && matches!(&tokens[i - 1].1, TokenType::Scalar(TScalarStyle::Plain, key) if key == "<<")
+                        && matches!(tokens[i - 2].1, TokenType::Key);
+                    continue;
+                }
+                TokenType::FlowSequenceStart if merge_value => merge_list = true,
+                TokenType::FlowSequenceEnd => merge_list = false,
+                TokenType::Alias(name) => {
+                    let in_merge = merge_value || merge_list;
+                    if !(in_merge && self.config().allow_merge_keys) {
+                        issues.push(LintIssue {
+                            line: marker.line(),
+                            column: marker.col() + 1,
+                            message: format!("found alias \"*{}\"", name),
+                            code: "no-aliases/alias".to_string(),
+                            severity: self.get_severity(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+            merge_value = false;
+        }
+
+        issues
+    }
+}
+
+impl Default for NoAliasesRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for NoAliasesRule {
+    fn rule_id(&self) -> &'static str {
+        "no-aliases"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "No Aliases"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Forbids aliases such as *defaults, optionally except in merge keys."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "base: &base {a: 1}\nextra: &extra {b: 2}\none:\n  <<: *base\nboth:\n  <<: [*base, *extra]\ncopy: *base\nlist: [*extra]\n\"<<\": *base\n";
+
+    #[test]
+    fn test_no_aliases_rule_default() {
+        let rule = NoAliasesRule::new();
+        assert_eq!(rule.rule_id(), "no-aliases");
+        assert_eq!(rule.default_severity(), Severity::Error);
+        assert!(!rule.is_enabled_by_default());
+        assert!(!rule.can_fix());
+    }
+
+    #[test]
+    fn test_no_aliases_reports_every_alias() {
+        let rule = NoAliasesRule::new();
+        let issues = rule.check(CONTENT, "test.yaml");
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.line, issue.column))
+            .collect();
+        assert_eq!(found, vec![(4, 7), (6, 8), (6, 15), (7, 7), (8, 8), (9, 7)]);
+        assert_eq!(issues[0].message, "found alias \"*base\"");
+        assert_eq!(issues[0].code, "no-aliases/alias");
+    }
+
+    #[test]
+    fn test_no_aliases_allow_merge_keys() {
+        let rule = NoAliasesRule::with_config(NoAliasesConfig {
+            allow_merge_keys: true,
+        });
+        let issues = rule.check(CONTENT, "test.yaml");
+        let found: Vec<_> = issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(found, vec![7, 8, 9]);
+    }
+}
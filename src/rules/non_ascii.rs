@@ -0,0 +1,272 @@
+use super::Rule;
+use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct NonAsciiConfig {
+    /// Only report invisible characters such as no-break and zero-width spaces
+    pub invisible_only: bool,
+}
+
+/// How an invisible character is fixed
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Invisible {
+    /// Whitespace that looks like a space and is replaced with one
+    Space(&'static str),
+    /// Zero-width and formatting characters, which are removed
+    ZeroWidth(&'static str),
+}
+
+impl Invisible {
+    /// The invisible character `c` is. A byte order mark is only one when it
+    /// is not at the start of the content.
+    fn of(c: char, at_start: bool) -> Option<Self> {
+        let invisible = match c {
+            '\u{00A0}' => Invisible::Space("no-break space"),
+            '\u{2000}'..='\u{200A}' => Invisible::Space("space"),
+            '\u{202F}' => Invisible::Space("narrow no-break space"),
+            '\u{205F}' => Invisible::Space("medium mathematical space"),
+            '\u{3000}' => Invisible::Space("ideographic space"),
+            '\u{2028}' => Invisible::Space("line separator"),
+            '\u{2029}' => Invisible::Space("paragraph separator"),
+            '\u{00AD}' => Invisible::ZeroWidth("soft hyphen"),
+            '\u{180E}' => Invisible::ZeroWidth("mongolian vowel separator"),
+            '\u{200B}' => Invisible::ZeroWidth("zero-width space"),
+            '\u{200C}' => Invisible::ZeroWidth("zero-width non-joiner"),
+            '\u{200D}' => Invisible::ZeroWidth("zero-width joiner"),
+            '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => {
+                Invisible::ZeroWidth("bidirectional control")
+            }
+            '\u{2060}' => Invisible::ZeroWidth("word joiner"),
+            '\u{2061}'..='\u{2064}' => Invisible::ZeroWidth("invisible operator"),
+            '\u{FEFF}' if !at_start => Invisible::ZeroWidth("byte order mark"),
+            _ => return None,
+        };
+        Some(invisible)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Invisible::Space(name) | Invisible::ZeroWidth(name) => name,
+        }
+    }
+}
+
+/// Flags non-ASCII characters, or only invisible ones, which break YAML
+/// parsing and comparisons in ways that are hard to see
+#[derive(Debug, Clone)]
+pub struct NonAsciiRule {
+    base: crate::rules::base::BaseRule<NonAsciiConfig>,
+}
+
+impl NonAsciiRule {
+    pub fn new() -> Self {
+        Self::with_config(NonAsciiConfig::default())
+    }
+
+    pub fn with_config(config: NonAsciiConfig) -> Self {
+        Self {
+            base: crate::rules::base::BaseRule::new(config),
+        }
+    }
+
+    pub fn config(&self) -> &NonAsciiConfig {
+        self.base.config()
+    }
+
+    pub fn set_config(&mut self, config: NonAsciiConfig) {
+        self.base.set_config(config);
+    }
+
+    pub fn check_impl(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+
+        for (line_num, line) in content.lines().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                let invisible = Invisible::of(c, line_num == 0 && column == 0);
+                let (message, code) = match invisible {
+                    Some(invisible) => (
+                        format!(
+                            "invisible character U+{:04X} ({})",
+                            c as u32,
+                            invisible.name()
+                        ),
+                        "non-ascii/invisible",
+                    ),
+                    None if c.is_ascii() || self.config().invisible_only => continue,
+                    // The leading byte order mark is an encoding marker
+                    None if c == '\u{FEFF}' => continue,
+                    None => (
+                        format!("non-ASCII character \"{}\" (U+{:04X})", c, c as u32),
+                        "non-ascii/non-ascii",
+                    ),
+                };
+                issues.push(LintIssue {
+                    line: line_num + 1,
+                    column: column + 1,
+                    message,
+                    code: code.to_string(),
+                    severity: self.get_severity(),
+                });
+            }
+        }
+
+        issues
+    }
+}
+
+impl Default for NonAsciiRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for NonAsciiRule {
+    fn rule_id(&self) -> &'static str {
+        "non-ascii"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "Non ASCII"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Flags non-ASCII characters, or only invisible ones such as zero-width spaces."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, content: &str, file_path: &str) -> Vec<LintIssue> {
+        self.check_impl(content, file_path)
+    }
+
+    fn can_fix(&self) -> bool {
+        true
+    }
+
+    /// Replaces invisible spaces with a space and removes zero-width
+    /// characters; other non-ASCII characters, and lines where the rule is
+    /// disabled by a directive, are left alone
+    fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
+        let directives = crate::directives::DirectiveState::for_rule(content, self.rule_id());
+        let mut fixed_content = String::with_capacity(content.len());
+        let mut fixes_applied = 0;
+        let mut line = 1;
+
+        for (index, c) in content.chars().enumerate() {
+            if c == '\n' {
+                line += 1;
+            }
+            if directives.is_rule_disabled(line, self.rule_id()) {
+                fixed_content.push(c);
+                continue;
+            }
+            match Invisible::of(c, index == 0) {
+                Some(Invisible::Space(_)) => fixed_content.push(' '),
+                Some(Invisible::ZeroWidth(_)) => {}
+                None => {
+                    fixed_content.push(c);
+                    continue;
+                }
+            }
+            fixes_applied += 1;
+        }
+
+        super::FixResult {
+            content: fixed_content,
+            changed: fixes_applied > 0,
+            fixes_applied,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_ascii_rule_default() {
+        let rule = NonAsciiRule::new();
+        assert_eq!(rule.rule_id(), "non-ascii");
+        assert_eq!(rule.default_severity(), Severity::Warning);
+        assert!(!rule.is_enabled_by_default());
+        assert!(rule.can_fix());
+    }
+
+    #[test]
+    fn test_non_ascii_reports_characters() {
+        let rule = NonAsciiRule::new();
+        let content = "\u{FEFF}name: café\nkey:\u{00A0}value\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 2);
+        assert_eq!((issues[0].line, issues[0].column), (1, 11));
+        assert_eq!(issues[0].message, "non-ASCII character \"é\" (U+00E9)");
+        assert_eq!(issues[0].code, "non-ascii/non-ascii");
+        assert_eq!((issues[1].line, issues[1].column), (2, 5));
+        assert_eq!(
+            issues[1].message,
+            "invisible character U+00A0 (no-break space)"
+        );
+        assert_eq!(issues[1].code, "non-ascii/invisible");
+    }
+
+    #[test]
+    fn test_non_ascii_invisible_only() {
+        let rule = NonAsciiRule::with_config(NonAsciiConfig {
+            invisible_only: true,
+        });
+        let content = "greeting: héllo 👋\ntoken: abc\u{200B}def\nlist:\n  - \u{FEFF}item\n";
+        let issues = rule.check(content, "test.yaml");
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.line, issue.column))
+            .collect();
+        assert_eq!(found, vec![(2, 11), (4, 5)]);
+        assert!(issues[1].message.contains("byte order mark"));
+    }
+
+    #[test]
+    fn test_non_ascii_fix_invisible_characters() {
+        let rule = NonAsciiRule::new();
+        let content = "\u{FEFF}key:\u{00A0}café\ntoken: abc\u{200B}def\u{2060}\n";
+        let result = rule.fix(content, "test.yaml");
+        assert!(result.changed);
+        assert_eq!(result.fixes_applied, 3);
+        assert_eq!(result.content, "\u{FEFF}key: café\ntoken: abcdef\n");
+    }
+
+    #[test]
+    fn test_non_ascii_fix_skips_disabled_lines() {
+        let rule = NonAsciiRule::new();
+        let content = "a: x\u{00A0}y  # yamllint disable-line rule:non-ascii\nb: x\u{00A0}y\n";
+        let result = rule.fix(content, "test.yaml");
+        assert_eq!(result.fixes_applied, 1);
+        assert_eq!(
+            result.content,
+            "a: x\u{00A0}y  # yamllint disable-line rule:non-ascii\nb: x y\n"
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use super::Rule;
+use crate::{LintIssue, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Marker, Scanner, TScalarStyle, Token, TokenType};
+
+lazy_static! {
+    /// YAML 1.1 booleans other than `true`/`false`, which mean the same in YAML 1.2
+    static ref BOOLEAN: Regex =
+        Regex::new(r"^(y|Y|yes|Yes|YES|n|N|no|No|NO|on|On|ON|off|Off|OFF)$").unwrap();
+    /// YAML 1.1 base 60 integers and floats, e.g. `1:30` for 90
+    static ref SEXAGESIMAL: Regex =
+        Regex::new(r"^[-+]?[0-9][0-9_]*(:[0-5]?[0-9])+(\.[0-9_]*)?$").unwrap();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct NorwayProblemConfig {
+    /// Also check mapping keys, e.g. GitHub Actions' `on:`
+    pub check_keys: bool,
+}
+
+impl Default for NorwayProblemConfig {
+    fn default() -> Self {
+        Self { check_keys: true }
+    }
+}
+
+/// What a YAML 1.1 parser turns an unquoted scalar into
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Coercion {
+    Boolean,
+    Sexagesimal,
+}
+
+impl Coercion {
+    fn of(value: &str) -> Option<Self> {
+        if BOOLEAN.is_match(value) {
+            Some(Coercion::Boolean)
+        } else if SEXAGESIMAL.is_match(value) {
+            Some(Coercion::Sexagesimal)
+        } else {
+            None
+        }
+    }
+}
+
+/// Flags plain scalars such as `NO` or `1:30` that YAML 1.1 parsers read as
+/// booleans or base 60 numbers instead of strings
+#[derive(Debug, Clone)]
+pub struct NorwayProblemRule {
+    base: crate::rules::base::BaseRule<NorwayProblemConfig>,
+}
+
+impl NorwayProblemRule {
+    pub fn new() -> Self {
+        Self::with_config(NorwayProblemConfig::default())
+    }
+
+    pub fn with_config(config: NorwayProblemConfig) -> Self {
+        Self {
+            base: crate::rules::base::BaseRule::new(config),
+        }
+    }
+
+    pub fn config(&self) -> &NorwayProblemConfig {
+        self.base.config()
+    }
+
+    pub fn set_config(&mut self, config: NorwayProblemConfig) {
+        self.base.set_config(config);
+    }
+
+    /// Plain, untagged scalars YAML 1.1 coerces, with their start and
+    /// whether they are keys
+    fn coerced_values<'a>(&self, tokens: &'a [Token]) -> Vec<(Marker, &'a str, Coercion, bool)> {
+        let mut found = Vec::new();
+        let mut previous: Option<&TokenType> = None;
+
+        for Token(marker, token) in tokens {
+            if let TokenType::Scalar(TScalarStyle::Plain, value) = token {
+                let is_key = matches!(previous, Some(TokenType::Key));
+                let skipped = match previous {
+                    Some(TokenType::Tag(..)) => true,
+                    _ => is_key && !self.config().check_keys,
+                };
+                if let Some(coercion) = Coercion::of(value).filter(|_| !skipped) {
+                    found.push((*marker, value.as_str(), coercion, is_key));
+                }
+            }
+            previous = Some(token);
+        }
+
+        found
+    }
+
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        self.coerced_values(tokens)
+            .into_iter()
+            .map(|(marker, value, coercion, is_key)| {
+                let (kind, code) = match coercion {
+                    Coercion::Boolean => ("a boolean", "norway-problem/boolean"),
+                    Coercion::Sexagesimal => ("a base 60 number", "norway-problem/sexagesimal"),
+                };
+                LintIssue {
+                    line: marker.line(),
+                    column: marker.col() + 1,
+                    // Values are single-quoted so `--redact-values` masks them
+                    message: if is_key {
+                        format!("unquoted key \"{}\" is {} in YAML 1.1", value, kind)
+                    } else {
+                        format!("unquoted value '{}' is {} in YAML 1.1", value, kind)
+                    },
+                    code: code.to_string(),
+                    severity: self.get_severity(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Rule for NorwayProblemRule {
+    fn rule_id(&self) -> &'static str {
+        "norway-problem"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "Norway Problem"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Flags unquoted scalars that YAML 1.1 reads as booleans or base 60 numbers."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
+        }
+    }
+
+    fn can_fix(&self) -> bool {
+        true
+    }
+
+    /// Double-quotes each reported scalar, keeping it a string
+    fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        let directives = crate::directives::DirectiveState::for_rule(content, self.rule_id());
+        let mut replacements = self
+            .coerced_values(&tokens)
+            .into_iter()
+            .filter(|(marker, ..)| !directives.is_rule_disabled(marker.line(), self.rule_id()))
+            .map(|(marker, value, _, _)| (marker.index(), value))
+            .peekable();
+
+        let mut fixed_content = String::with_capacity(content.len());
+        let mut fixes_applied = 0;
+        let mut chars = content.chars().enumerate();
+        while let Some((index, c)) = chars.next() {
+            match replacements.next_if(|(start, _)| *start == index) {
+                Some((_, value)) => {
+                    fixed_content.push('"');
+                    fixed_content.push_str(value);
+                    fixed_content.push('"');
+                    // Skip the rest of the quoted value
+                    for _ in 1..value.chars().count() {
+                        chars.next();
+                    }
+                    fixes_applied += 1;
+                }
+                None => fixed_content.push(c),
+            }
+        }
+
+        super::FixResult {
+            content: fixed_content,
+            changed: fixes_applied > 0,
+            fixes_applied,
+        }
+    }
+}
+
+impl Default for NorwayProblemRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_norway_problem_rule_default() {
+        let rule = NorwayProblemRule::new();
+        assert_eq!(rule.rule_id(), "norway-problem");
+        assert_eq!(rule.default_severity(), Severity::Warning);
+        assert!(!rule.is_enabled_by_default());
+        assert!(rule.can_fix());
+    }
+
+    #[test]
+    fn test_norway_problem_reports_coerced_scalars() {
+        let rule = NorwayProblemRule::new();
+        let content = "countries: [GB, NO, SE]\ndebug: off\nduration: 1:30\nenabled: true\nquoted: \"no\"\ntagged: !!str yes\ntime: 12:30:45.5\nversion: 1.30\nurl: http://host:80\non: push\n";
+        let issues = rule.check(content, "test.yaml");
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.line, issue.column, issue.code.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, 17, "norway-problem/boolean"),
+                (2, 8, "norway-problem/boolean"),
+                (3, 11, "norway-problem/sexagesimal"),
+                (7, 7, "norway-problem/sexagesimal"),
+                (10, 1, "norway-problem/boolean"),
+            ]
+        );
+        assert_eq!(
+            issues[0].message,
+            "unquoted value 'NO' is a boolean in YAML 1.1"
+        );
+        assert_eq!(
+            issues[2].message,
+            "unquoted value '1:30' is a base 60 number in YAML 1.1"
+        );
+        assert_eq!(
+            issues[4].message,
+            "unquoted key \"on\" is a boolean in YAML 1.1"
+        );
+
+        let rule = NorwayProblemRule::with_config(NorwayProblemConfig { check_keys: false });
+        assert_eq!(rule.check(content, "test.yaml").len(), 4);
+    }
+
+    #[test]
+    fn test_norway_problem_redacts_values_but_not_keys() {
+        let rule = NorwayProblemRule::new();
+        let redacted: Vec<_> = rule
+            .check(
+                "country: NO
+on: push
+",
+                "test.yaml",
+            )
+            .iter()
+            .map(|issue| crate::redact::redact_values(&issue.message))
+            .collect();
+        assert_eq!(
+            redacted,
+            vec![
+                "unquoted value '<redacted>' is a boolean in YAML 1.1",
+                "unquoted key \"on\" is a boolean in YAML 1.1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_norway_problem_fix_quotes_values() {
+        let rule = NorwayProblemRule::new();
+        let content = "country: NO\nlist:\n  - y\n  - n\nduration: 1:30\nname: Norway\n";
+        let result = rule.fix(content, "test.yaml");
+        assert!(result.changed);
+        assert_eq!(result.fixes_applied, 4);
+        assert_eq!(
+            result.content,
+            "country: \"NO\"\nlist:\n  - \"y\"\n  - \"n\"\nduration: \"1:30\"\nname: Norway\n"
+        );
+        assert!(rule.check(&result.content, "test.yaml").is_empty());
+    }
+}
@@ -1,49 +1,87 @@
 use super::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, TScalarStyle, Token, TokenType};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct OctalValuesConfig {
+    /// Flag YAML 1.1 octals with a leading zero, e.g. `0777`
     pub forbid_implicit_octal: bool,
+    /// Flag YAML 1.2 octals with a `0o` prefix, e.g. `0o777`
     pub forbid_explicit_octal: bool,
 }
 
+impl Default for OctalValuesConfig {
+    fn default() -> Self {
+        Self {
+            forbid_implicit_octal: true,
+            forbid_explicit_octal: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OctalValuesRule {
-    config: OctalValuesConfig,
+    base: crate::rules::base::BaseRule<OctalValuesConfig>,
+}
+
+fn is_octal_digits(digits: &str) -> bool {
+    !digits.is_empty() && digits.bytes().all(|b| (b'0'..=b'7').contains(&b))
 }
 
 impl OctalValuesRule {
     pub fn new() -> Self {
-        Self {
-            config: OctalValuesConfig {
-                forbid_implicit_octal: true,
-                forbid_explicit_octal: true,
-            },
-        }
+        Self::with_config(OctalValuesConfig::default())
     }
 
     pub fn with_config(config: OctalValuesConfig) -> Self {
-        Self { config }
+        Self {
+            base: crate::rules::base::BaseRule::new(config),
+        }
     }
 
-    fn is_forbidden_octal(&self, value: &str) -> Option<String> {
-        let trimmed = value.trim();
+    pub fn config(&self) -> &OctalValuesConfig {
+        self.base.config()
+    }
 
-        if self.config.forbid_implicit_octal && trimmed.starts_with('0') && trimmed.len() > 1 {
-            let rest = &trimmed[1..];
-            if rest.chars().all(|c| c.is_ascii_digit())
-                && !trimmed.starts_with("0x")
-                && !trimmed.starts_with("0b")
-            {
-                return Some("implicit octal".to_string());
-            }
+    /// Which octal form the plain scalar `value` is, if it is forbidden
+    fn forbidden_octal(&self, value: &str) -> Option<&'static str> {
+        let config = self.config();
+        if config.forbid_implicit_octal && value.starts_with('0') && is_octal_digits(&value[1..]) {
+            return Some("implicit octal");
+        }
+        if config.forbid_explicit_octal && value.starts_with("0o") && is_octal_digits(&value[2..]) {
+            return Some("explicit octal");
         }
+        None
+    }
 
-        if self.config.forbid_explicit_octal && trimmed.starts_with("0o") {
-            return Some("explicit octal".to_string());
+    /// Plain scalars only, as yamllint does: quoted strings and tagged
+    /// values such as `!!str 0777` are never octals
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        let mut previous: Option<&TokenType> = None;
+
+        for Token(marker, token) in tokens {
+            if let TokenType::Scalar(TScalarStyle::Plain, value) = token {
+                if !matches!(previous, Some(TokenType::Tag(..))) {
+                    if let Some(form) = self.forbidden_octal(value) {
+                        // Reported after the value, as yamllint does
+                        issues.push(LintIssue {
+                            line: marker.line(),
+                            column: marker.col() + value.chars().count() + 1,
+                            message: format!("forbidden {} value '{}'", form, value),
+                            code: format!("octal-values/forbidden-{}", form.replace(' ', "-")),
+                            severity: self.get_severity(),
+                        });
+                    }
+                }
+            }
+            previous = Some(token);
         }
 
-        None
+        issues
     }
 }
 
@@ -65,44 +103,36 @@ impl Rule for OctalValuesRule {
     }
 
     fn get_severity(&self) -> Severity {
-        self.default_severity()
+        self.base.get_severity(self.default_severity())
     }
 
-    fn set_severity(&mut self, _severity: Severity) {}
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
 
     fn has_severity_override(&self) -> bool {
-        false
+        self.base.has_severity_override()
     }
 
-    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
-        let mut issues = Vec::new();
-
-        for (line_num, line) in content.lines().enumerate() {
-            let line_num = line_num + 1;
-
-            if line.trim().starts_with('#') || line.trim().is_empty() {
-                continue;
-            }
-
-            if let Some(colon_pos) = line.find(':') {
-                let value_part = line[colon_pos + 1..].trim();
-
-                if value_part.starts_with('"') || value_part.starts_with('\'') {
-                    continue;
-                }
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
 
-                if let Some(forbidden_type) = self.is_forbidden_octal(value_part) {
-                    issues.push(LintIssue {
-                        line: line_num,
-                        column: colon_pos + 2,
-                        message: format!("forbidden {} value", forbidden_type),
-                        severity: self.get_severity(),
-                    });
-                }
-            }
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
         }
-
-        issues
     }
 
     fn can_fix(&self) -> bool {
@@ -164,6 +194,52 @@ mod tests {
         assert!(issues[0].message.contains("forbidden explicit octal value"));
     }
 
+    #[test]
+    fn test_octal_values_distinguishes_yaml_1_1_and_1_2_forms() {
+        let rule = OctalValuesRule::new();
+        let content =
+            "---\nmode: 0777\nnew: 0o644\nlist: [010, 0o7]\nzero: 0\ndecimal: 0999\nnot: 0o\n";
+        let issues: Vec<_> = rule
+            .check(content, "test.yaml")
+            .into_iter()
+            .map(|issue| (issue.line, issue.column, issue.message))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (2, 11, "forbidden implicit octal value '0777'".to_string()),
+                (3, 11, "forbidden explicit octal value '0o644'".to_string()),
+                (4, 11, "forbidden implicit octal value '010'".to_string()),
+                (4, 16, "forbidden explicit octal value '0o7'".to_string()),
+            ]
+        );
+
+        let implicit_only = OctalValuesRule::with_config(OctalValuesConfig {
+            forbid_implicit_octal: true,
+            forbid_explicit_octal: false,
+        });
+        assert_eq!(implicit_only.check(content, "test.yaml").len(), 2);
+    }
+
+    #[test]
+    fn test_octal_values_message_is_redacted() {
+        let rule = OctalValuesRule::new();
+        let issues = rule.check("mode: 0777\n", "test.yaml");
+        assert_eq!(
+            crate::redact::redact_values(&issues[0].message),
+            "forbidden implicit octal value '<redacted>'"
+        );
+    }
+
+    #[test]
+    fn test_octal_values_skips_quoted_and_tagged_scalars() {
+        let rule = OctalValuesRule::new();
+        let content = "a: \"0777\"\nb: '0o644'\nc: !!str 010\nd: >\n  0777\n0755: key\n";
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 6);
+    }
+
     #[test]
     fn test_octal_values_fix_no_changes() {
         let rule = OctalValuesRule::new();
@@ -1,12 +1,51 @@
 use super::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct QuotedStringsConfig {
+    /// `true`, `false` or `only-when-needed`; yamllint writes the first two
+    /// as booleans
+    #[serde(deserialize_with = "deserialize_required")]
     pub required: String,
+    /// `single` or `double`; `any` is read as no requirement
+    #[serde(deserialize_with = "deserialize_quote_type")]
     pub quote_type: Option<String>,
 }
 
+fn deserialize_required<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Required {
+        Bool(bool),
+        Name(String),
+    }
+
+    Ok(match Required::deserialize(deserializer)? {
+        Required::Bool(required) => required.to_string(),
+        Required::Name(required) => required,
+    })
+}
+
+fn deserialize_quote_type<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let quote_type = Option::<String>::deserialize(deserializer)?;
+    Ok(quote_type.filter(|quote_type| quote_type != "any"))
+}
+
+impl Default for QuotedStringsConfig {
+    fn default() -> Self {
+        Self {
+            required: "only-when-needed".to_string(),
+            quote_type: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct QuotedStringsRule {
     config: QuotedStringsConfig,
@@ -15,10 +54,7 @@ pub struct QuotedStringsRule {
 impl QuotedStringsRule {
     pub fn new() -> Self {
         Self {
-            config: QuotedStringsConfig {
-                required: "only-when-needed".to_string(),
-                quote_type: None,
-            },
+            config: QuotedStringsConfig::default(),
         }
     }
 
@@ -99,6 +135,10 @@ impl Rule for QuotedStringsRule {
         false
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().config).unwrap_or_default()
+    }
+
     fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
         let mut issues = Vec::new();
 
@@ -123,6 +163,7 @@ impl Rule for QuotedStringsRule {
                                 line: line_num,
                                 column: colon_pos + 2,
                                 message: "string value must be quoted".to_string(),
+                                code: "quoted-strings/not-quoted".to_string(),
                                 severity: self.get_severity(),
                             });
                         } else if !self.has_correct_quote_type(value_part) {
@@ -133,6 +174,7 @@ impl Rule for QuotedStringsRule {
                                     "string must use {} quotes",
                                     self.config.quote_type.as_ref().unwrap()
                                 ),
+                                code: "quoted-strings/wrong-quote-type".to_string(),
                                 severity: self.get_severity(),
                             });
                         }
@@ -143,6 +185,7 @@ impl Rule for QuotedStringsRule {
                                 line: line_num,
                                 column: colon_pos + 2,
                                 message: "string value must be quoted".to_string(),
+                                code: "quoted-strings/not-quoted".to_string(),
                                 severity: self.get_severity(),
                             });
                         } else if self.is_properly_quoted(value_part)
@@ -152,6 +195,7 @@ impl Rule for QuotedStringsRule {
                                 line: line_num,
                                 column: colon_pos + 2,
                                 message: "string value should not be quoted".to_string(),
+                                code: "quoted-strings/redundant-quotes".to_string(),
                                 severity: self.get_severity(),
                             });
                         } else if self.is_properly_quoted(value_part)
@@ -164,16 +208,24 @@ impl Rule for QuotedStringsRule {
                                     "string must use {} quotes",
                                     self.config.quote_type.as_ref().unwrap()
                                 ),
+                                code: "quoted-strings/wrong-quote-type".to_string(),
                                 severity: self.get_severity(),
                             });
                         }
                     }
+                    // `false`: quotes are optional, but must be of `quote-type`
                     _ => {
-                        if self.is_properly_quoted(value_part) {
+                        if self.is_properly_quoted(value_part)
+                            && !self.has_correct_quote_type(value_part)
+                        {
                             issues.push(LintIssue {
                                 line: line_num,
                                 column: colon_pos + 2,
-                                message: "string value should not be quoted".to_string(),
+                                message: format!(
+                                    "string must use {} quotes",
+                                    self.config.quote_type.as_ref().unwrap()
+                                ),
+                                code: "quoted-strings/wrong-quote-type".to_string(),
                                 severity: self.get_severity(),
                             });
                         }
@@ -256,9 +308,13 @@ impl Rule for QuotedStringsRule {
                         }
                     }
                     _ => {
-                        if self.is_properly_quoted(value_part) {
+                        if self.is_properly_quoted(value_part)
+                            && !self.has_correct_quote_type(value_part)
+                        {
                             let unquoted_value = value_part.trim_matches('"').trim_matches('\'');
-                            fixed_line = format!("{}: {}", &line[..colon_pos], unquoted_value);
+                            let new_value =
+                                format!("{}{}{}", quote_char, unquoted_value, quote_char);
+                            fixed_line = format!("{}: {}", &line[..colon_pos], new_value);
                             fixes_applied += 1;
                         }
                     }
@@ -55,7 +55,7 @@ impl RuleRegistry {
             name: "Comments",
             description: "Checks comment formatting",
             default_severity: Severity::Warning,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: true,
             fix_order: None,
             dependencies: vec![],
@@ -77,7 +77,7 @@ impl RuleRegistry {
             name: "Comments Indentation",
             description: "Checks comment indentation",
             default_severity: Severity::Warning,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: true,
             fix_order: None,
             dependencies: vec![],
@@ -99,7 +99,7 @@ impl RuleRegistry {
             name: "Braces",
             description: "Checks brace formatting",
             default_severity: Severity::Error,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: true,
             fix_order: None,
             dependencies: vec![],
@@ -110,7 +110,7 @@ impl RuleRegistry {
             name: "Brackets",
             description: "Checks bracket formatting",
             default_severity: Severity::Error,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: true,
             fix_order: None,
             dependencies: vec![],
@@ -121,7 +121,7 @@ impl RuleRegistry {
             name: "Colons",
             description: "Checks colon formatting",
             default_severity: Severity::Error,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: true,
             fix_order: None,
             dependencies: vec![],
@@ -132,7 +132,7 @@ impl RuleRegistry {
             name: "Commas",
             description: "Checks comma formatting",
             default_severity: Severity::Error,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: true,
             fix_order: None,
             dependencies: vec![],
@@ -143,7 +143,7 @@ impl RuleRegistry {
             name: "Hyphens",
             description: "Checks hyphen formatting",
             default_severity: Severity::Error,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: true,
             fix_order: None,
             dependencies: vec![],
@@ -154,7 +154,7 @@ impl RuleRegistry {
             name: "Quoted Strings",
             description: "Checks quoted string formatting",
             default_severity: Severity::Error,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: false,
             fix_order: None,
             dependencies: vec![],
@@ -165,7 +165,7 @@ impl RuleRegistry {
             name: "Indentation",
             description: "Checks indentation",
             default_severity: Severity::Error,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: true,
             fix_order: None,
             dependencies: vec![],
@@ -242,7 +242,7 @@ impl RuleRegistry {
             name: "Key Ordering",
             description: "Checks key ordering",
             default_severity: Severity::Error,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: false,
             fix_order: None,
             dependencies: vec![],
@@ -253,7 +253,7 @@ impl RuleRegistry {
             name: "Empty Lines",
             description: "Checks empty line formatting",
             default_severity: Severity::Error,
-            can_fix: false,
+            can_fix: true,
             enabled_by_default: true,
             fix_order: None,
             dependencies: vec![],
@@ -275,6 +275,143 @@ impl RuleRegistry {
             name: "New Lines",
             description: "Checks new line formatting",
             default_severity: Severity::Error,
+            can_fix: true,
+            enabled_by_default: true,
+            fix_order: None,
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "secrets",
+            name: "Secrets",
+            description: "Flags values that look like passwords, tokens or private keys",
+            default_severity: Severity::Warning,
+            can_fix: false,
+            enabled_by_default: false,
+            fix_order: None,
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "forbidden-values",
+            name: "Forbidden Values",
+            description: "Flags scalar values matching configured regular expressions",
+            default_severity: Severity::Error,
+            can_fix: false,
+            enabled_by_default: false,
+            fix_order: None,
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "key-naming",
+            name: "Key Naming",
+            description: "Enforces a naming convention or pattern for mapping keys",
+            default_severity: Severity::Error,
+            can_fix: false,
+            enabled_by_default: false,
+            fix_order: None,
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "file-size",
+            name: "File Size",
+            description: "Limits the number of lines and bytes in a file",
+            default_severity: Severity::Warning,
+            can_fix: false,
+            enabled_by_default: false,
+            fix_order: None,
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "norway-problem",
+            name: "Norway Problem",
+            description:
+                "Flags unquoted scalars that YAML 1.1 reads as booleans or base 60 numbers",
+            default_severity: Severity::Warning,
+            can_fix: true,
+            enabled_by_default: false,
+            // Before truthy, which would rewrite `NO` to `false` instead of quoting it
+            fix_order: Some(8),
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "timestamp-values",
+            name: "Timestamp Values",
+            description: "Flags unquoted scalars that look like dates or times",
+            default_severity: Severity::Warning,
+            can_fix: false,
+            enabled_by_default: false,
+            fix_order: None,
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "non-ascii",
+            name: "Non ASCII",
+            description:
+                "Flags non-ASCII characters, or only invisible ones such as zero-width spaces",
+            default_severity: Severity::Warning,
+            can_fix: true,
+            enabled_by_default: false,
+            // Before trailing-spaces, as invisible spaces at line ends become spaces
+            fix_order: Some(5),
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "sequence-ordering",
+            name: "Sequence Ordering",
+            description: "Checks that scalar items of block sequences are sorted",
+            default_severity: Severity::Warning,
+            can_fix: true,
+            enabled_by_default: false,
+            fix_order: Some(50),
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "custom-tags",
+            name: "Custom Tags",
+            description: "Flags tags outside an allow-list, such as !!python/object",
+            default_severity: Severity::Error,
+            can_fix: false,
+            enabled_by_default: false,
+            fix_order: None,
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "no-aliases",
+            name: "No Aliases",
+            description: "Forbids aliases such as *defaults, optionally except in merge keys",
+            default_severity: Severity::Error,
+            can_fix: false,
+            enabled_by_default: false,
+            fix_order: None,
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "directive-syntax",
+            name: "Directive Syntax",
+            description:
+                "Flags yamllint directive comments that are malformed or name unknown rules",
+            default_severity: Severity::Warning,
+            can_fix: false,
+            enabled_by_default: true,
+            fix_order: None,
+            dependencies: vec![],
+        });
+
+        self.register_rule(RuleMetadata {
+            id: "yaml-syntax",
+            name: "YAML Syntax",
+            description: "Reports YAML that cannot be parsed, like unclosed quotes or bad nesting",
+            default_severity: Severity::Error,
             can_fix: false,
             enabled_by_default: true,
             fix_order: None,
@@ -0,0 +1,533 @@
+use super::Rule;
+use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use yaml_rust::scanner::{Marker, Scanner, Token, TokenType};
+
+/// How sequence items are compared
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SequenceOrder {
+    /// By code point, so `B` sorts before `a`
+    #[default]
+    Alphabetical,
+    /// Ignoring case, with code point order breaking ties
+    CaseInsensitive,
+    /// Ignoring case, with runs of digits compared as numbers, so `v2`
+    /// sorts before `v10`
+    Natural,
+}
+
+impl SequenceOrder {
+    fn compare(self, a: &str, b: &str) -> Ordering {
+        match self {
+            SequenceOrder::Alphabetical => a.cmp(b),
+            SequenceOrder::CaseInsensitive => a
+                .to_lowercase()
+                .cmp(&b.to_lowercase())
+                .then_with(|| a.cmp(b)),
+            SequenceOrder::Natural => natural_chunks(a)
+                .cmp(&natural_chunks(b))
+                .then_with(|| a.cmp(b)),
+        }
+    }
+}
+
+/// Runs of digits as numbers and other runs lowercased, for natural ordering
+fn natural_chunks(value: &str) -> Vec<(String, u128)> {
+    let mut chunks = Vec::new();
+    let mut chars = value.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        let is_digit = c.is_ascii_digit();
+        let mut run = String::new();
+        while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit() == is_digit) {
+            run.push(c);
+            chars.next();
+        }
+        chunks.push(if is_digit {
+            (String::new(), run.parse().unwrap_or(u128::MAX))
+        } else {
+            (run.to_lowercase(), 0)
+        });
+    }
+    chunks
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct SequenceOrderingConfig {
+    /// How items are compared
+    pub order: SequenceOrder,
+    /// Dotted key paths of the sequences checked, where `*` matches any one
+    /// key and `**` any number of keys; every sequence when empty
+    pub paths: Vec<String>,
+}
+
+/// A scalar item of a block sequence
+#[derive(Debug, Clone)]
+struct Item {
+    value: String,
+    marker: Marker,
+}
+
+/// A block sequence of scalars under a key path matching `paths`
+#[derive(Debug)]
+struct ScalarSequence {
+    items: Vec<Item>,
+}
+
+/// An open collection while walking the tokens
+#[derive(Debug)]
+enum Collection {
+    Mapping {
+        path: Vec<String>,
+        key: Option<String>,
+    },
+    Sequence {
+        path: Vec<String>,
+        flow: bool,
+        /// A block sequence whose entries are not indented under their key,
+        /// which has no start and end tokens of its own
+        indentless: bool,
+        items: Vec<Item>,
+        scalars_only: bool,
+    },
+}
+
+/// Whether a dotted key path matches `pattern`, where `*` matches any one key
+/// and `**` any number of keys
+pub(crate) fn path_matches(pattern: &[&str], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| path_matches(rest, &path[skip..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(key, path)| {
+            (*segment == "*" || segment == key) && path_matches(rest, path)
+        }),
+    }
+}
+
+/// Flags block sequences of scalars whose items are out of order, e.g. to
+/// keep `dependencies:` lists sorted
+#[derive(Debug, Clone)]
+pub struct SequenceOrderingRule {
+    base: crate::rules::base::BaseRule<SequenceOrderingConfig>,
+}
+
+impl SequenceOrderingRule {
+    pub fn new() -> Self {
+        Self::with_config(SequenceOrderingConfig::default())
+    }
+
+    pub fn with_config(config: SequenceOrderingConfig) -> Self {
+        Self {
+            base: crate::rules::base::BaseRule::new(config),
+        }
+    }
+
+    pub fn config(&self) -> &SequenceOrderingConfig {
+        self.base.config()
+    }
+
+    pub fn set_config(&mut self, config: SequenceOrderingConfig) {
+        self.base.set_config(config);
+    }
+
+    fn is_checked(&self, path: &[String]) -> bool {
+        let paths = &self.config().paths;
+        paths.is_empty()
+            || paths.iter().any(|pattern| {
+                let pattern: Vec<&str> = pattern.split('.').collect();
+                path_matches(&pattern, path)
+            })
+    }
+
+    /// Close the collection on top of the stack, keeping it when it is a
+    /// checked block sequence of scalars
+    fn close(&self, stack: &mut Vec<Collection>, found: &mut Vec<ScalarSequence>) {
+        if let Some(Collection::Sequence {
+            path,
+            flow: false,
+            items,
+            scalars_only: true,
+            ..
+        }) = stack.pop()
+        {
+            if items.len() > 1 && self.is_checked(&path) {
+                found.push(ScalarSequence { items });
+            }
+        }
+    }
+
+    /// The key path of a collection starting under the top of the stack
+    fn open(stack: &mut [Collection]) -> Vec<String> {
+        match stack.last_mut() {
+            Some(Collection::Mapping { path, key }) => {
+                let mut path = path.clone();
+                path.extend(key.clone());
+                path
+            }
+            Some(Collection::Sequence {
+                path, scalars_only, ..
+            }) => {
+                *scalars_only = false;
+                path.clone()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn scalar_sequences(&self, tokens: &[Token]) -> Vec<ScalarSequence> {
+        let mut found = Vec::new();
+        let mut stack: Vec<Collection> = Vec::new();
+        let mut expect_key = false;
+
+        let is_indentless = |stack: &[Collection]| {
+            matches!(
+                stack.last(),
+                Some(Collection::Sequence {
+                    indentless: true,
+                    ..
+                })
+            )
+        };
+
+        for Token(marker, token) in tokens {
+            match token {
+                TokenType::BlockMappingStart | TokenType::FlowMappingStart => {
+                    let path = Self::open(&mut stack);
+                    stack.push(Collection::Mapping { path, key: None });
+                }
+                TokenType::BlockSequenceStart | TokenType::FlowSequenceStart => {
+                    let path = Self::open(&mut stack);
+                    stack.push(Collection::Sequence {
+                        path,
+                        flow: matches!(token, TokenType::FlowSequenceStart),
+                        indentless: false,
+                        items: Vec::new(),
+                        scalars_only: true,
+                    });
+                }
+                TokenType::BlockEntry => {
+                    if let Some(Collection::Mapping { .. }) = stack.last() {
+                        let path = Self::open(&mut stack);
+                        stack.push(Collection::Sequence {
+                            path,
+                            flow: false,
+                            indentless: true,
+                            items: Vec::new(),
+                            scalars_only: true,
+                        });
+                    }
+                }
+                TokenType::Key => {
+                    if is_indentless(&stack) {
+                        self.close(&mut stack, &mut found);
+                    }
+                    if let Some(Collection::Mapping { key, .. }) = stack.last_mut() {
+                        *key = None;
+                    }
+                    expect_key = true;
+                    continue;
+                }
+                TokenType::BlockEnd => {
+                    if is_indentless(&stack) {
+                        self.close(&mut stack, &mut found);
+                    }
+                    self.close(&mut stack, &mut found);
+                }
+                TokenType::FlowMappingEnd | TokenType::FlowSequenceEnd => {
+                    self.close(&mut stack, &mut found);
+                }
+                TokenType::Scalar(_, value) => match stack.last_mut() {
+                    Some(Collection::Mapping { key, .. }) if expect_key => {
+                        *key = Some(value.clone());
+                    }
+                    Some(Collection::Sequence { items, .. }) => items.push(Item {
+                        value: value.clone(),
+                        marker: *marker,
+                    }),
+                    _ => {}
+                },
+                TokenType::Alias(_) => {
+                    if let Some(Collection::Sequence { scalars_only, .. }) = stack.last_mut() {
+                        *scalars_only = false;
+                    }
+                }
+                TokenType::DocumentStart | TokenType::DocumentEnd | TokenType::StreamEnd => {
+                    stack.clear();
+                }
+                TokenType::Anchor(_) | TokenType::Tag(_, _) => continue,
+                _ => {}
+            }
+            expect_key = false;
+        }
+
+        found
+    }
+
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let order = self.config().order;
+        let mut issues = Vec::new();
+
+        for sequence in self.scalar_sequences(tokens) {
+            for pair in sequence.items.windows(2) {
+                if order.compare(&pair[1].value, &pair[0].value) == Ordering::Less {
+                    issues.push(LintIssue {
+                        line: pair[1].marker.line(),
+                        column: pair[1].marker.col() + 1,
+                        // Single-quoted so `--redact-values` can hide the item
+                        message: format!(
+                            "wrong ordering of item '{}' in sequence",
+                            pair[1].value.replace('\'', "''")
+                        ),
+                        code: "sequence-ordering/wrong-order".to_string(),
+                        severity: self.get_severity(),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+impl Default for SequenceOrderingRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for SequenceOrderingRule {
+    fn rule_id(&self) -> &'static str {
+        "sequence-ordering"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "Sequence Ordering"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Checks that scalar items of block sequences are sorted."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
+        }
+    }
+
+    fn can_fix(&self) -> bool {
+        true
+    }
+
+    /// Sorts the lines of sequences that have one `- item` line per item;
+    /// sequences with multi-line items or comments between items are left
+    /// as they are
+    fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        let order = self.config().order;
+
+        // Line bodies, each with its line ending kept apart
+        let mut lines: Vec<(&str, &str)> = content
+            .split_inclusive('\n')
+            .map(|line| {
+                let body = line.trim_end_matches(['\n', '\r']);
+                (body, &line[body.len()..])
+            })
+            .collect();
+        let mut fixes_applied = 0;
+        let directives = crate::directives::DirectiveState::for_rule(content, self.rule_id());
+
+        for sequence in self.scalar_sequences(&tokens) {
+            // Sorting moves every item, so one disabled item keeps them all
+            if sequence
+                .items
+                .iter()
+                .any(|item| directives.is_rule_disabled(item.marker.line(), self.rule_id()))
+            {
+                continue;
+            }
+            let first = sequence.items[0].marker.line() - 1;
+            let one_line_each = sequence.items.iter().enumerate().all(|(i, item)| {
+                let line = item.marker.line() - 1;
+                line == first + i
+                    && lines.get(line).is_some_and(|(body, _)| {
+                        let prefix: String = body.chars().take(item.marker.col()).collect();
+                        prefix.trim() == "-"
+                    })
+            });
+            if !one_line_each {
+                continue;
+            }
+
+            let mut sorted = sequence.items.clone();
+            sorted.sort_by(|a, b| order.compare(&a.value, &b.value));
+            let bodies: Vec<&str> = sorted
+                .iter()
+                .map(|item| lines[item.marker.line() - 1].0)
+                .collect();
+            for (i, body) in bodies.into_iter().enumerate() {
+                if lines[first + i].0 != body {
+                    lines[first + i].0 = body;
+                    fixes_applied += 1;
+                }
+            }
+        }
+
+        super::FixResult {
+            content: lines
+                .iter()
+                .map(|(body, end)| format!("{}{}", body, end))
+                .collect(),
+            changed: fixes_applied > 0,
+            fixes_applied,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_paths(paths: &[&str]) -> SequenceOrderingRule {
+        SequenceOrderingRule::with_config(SequenceOrderingConfig {
+            paths: paths.iter().map(|path| path.to_string()).collect(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_sequence_ordering_rule_default() {
+        let rule = SequenceOrderingRule::new();
+        assert_eq!(rule.rule_id(), "sequence-ordering");
+        assert_eq!(rule.default_severity(), Severity::Warning);
+        assert!(!rule.is_enabled_by_default());
+        assert!(rule.can_fix());
+    }
+
+    #[test]
+    fn test_sequence_ordering_reports_unsorted_scalar_sequences() {
+        let rule = SequenceOrderingRule::new();
+        let content = "dependencies:\n  - requests\n  - flask\n  - numpy\nindentless:\n- b\n- a\nflow: [b, a]\nmixed:\n  - b\n  - {a: 1}\nsorted:\n  - a\n  - b\n";
+        let issues = rule.check(content, "test.yaml");
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.line, issue.column))
+            .collect();
+        assert_eq!(found, vec![(3, 5), (7, 3)]);
+        assert_eq!(
+            issues[0].message,
+            "wrong ordering of item 'flask' in sequence"
+        );
+        assert_eq!(issues[0].code, "sequence-ordering/wrong-order");
+    }
+
+    #[test]
+    fn test_sequence_ordering_message_is_redacted() {
+        let rule = SequenceOrderingRule::new();
+        let issues = rule.check(
+            "- zulu
+- it's
+",
+            "test.yaml",
+        );
+        assert_eq!(
+            issues[0].message,
+            "wrong ordering of item 'it''s' in sequence"
+        );
+        assert_eq!(
+            crate::redact::redact_values(&issues[0].message),
+            "wrong ordering of item '<redacted>' in sequence"
+        );
+    }
+
+    #[test]
+    fn test_sequence_ordering_paths() {
+        let content = "dependencies:\n  - b\n  - a\ntools:\n  dependencies:\n    - d\n    - c\n  other:\n    - f\n    - e\n";
+        assert_eq!(with_paths(&["dependencies"]).check(content, "t").len(), 1);
+        assert_eq!(
+            with_paths(&["**.dependencies"]).check(content, "t").len(),
+            2
+        );
+        assert_eq!(with_paths(&["tools.*"]).check(content, "t").len(), 2);
+        assert_eq!(with_paths(&["tools.other"]).check(content, "t").len(), 1);
+        assert!(with_paths(&["missing"]).check(content, "t").is_empty());
+    }
+
+    #[test]
+    fn test_sequence_ordering_orders() {
+        let content = "items:\n  - Beta\n  - alpha\n  - v10\n  - v2\n";
+        let count = |order| {
+            SequenceOrderingRule::with_config(SequenceOrderingConfig {
+                order,
+                paths: Vec::new(),
+            })
+            .check(content, "test.yaml")
+            .len()
+        };
+        assert_eq!(count(SequenceOrder::Alphabetical), 0);
+        assert_eq!(count(SequenceOrder::CaseInsensitive), 1);
+        assert_eq!(count(SequenceOrder::Natural), 2);
+    }
+
+    #[test]
+    fn test_sequence_ordering_fix() {
+        let rule = SequenceOrderingRule::new();
+        let content = "deps:\n  - requests  # http\n  - \"flask\"\n  - numpy\nnested:\n  - - b\n    - a\nmultiline:\n  - b\n  # comment\n  - a\n";
+        let result = rule.fix(content, "test.yaml");
+        assert!(result.changed);
+        assert_eq!(result.fixes_applied, 3);
+        assert_eq!(
+            result.content,
+            "deps:\n  - \"flask\"\n  - numpy\n  - requests  # http\nnested:\n  - - b\n    - a\nmultiline:\n  - b\n  # comment\n  - a\n"
+        );
+        assert_eq!(rule.check(&result.content, "test.yaml").len(), 2);
+    }
+
+    #[test]
+    fn test_sequence_ordering_fix_skips_disabled_sequences() {
+        let rule = SequenceOrderingRule::new();
+        let content = "kept:\n  - b\n  - a  # yamllint disable-line rule:sequence-ordering\nsorted:\n  - d\n  - c\n";
+        let result = rule.fix(content, "test.yaml");
+        assert_eq!(result.fixes_applied, 2);
+        assert_eq!(
+            result.content,
+            "kept:\n  - b\n  - a  # yamllint disable-line rule:sequence-ordering\nsorted:\n  - c\n  - d\n"
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use super::Rule;
+use crate::{LintIssue, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Scanner, TScalarStyle, Token, TokenType};
+
+lazy_static! {
+    /// YAML 1.1 timestamps: a date, optionally followed by a time and zone
+    static ref DATE: Regex = Regex::new(
+        r"^[0-9]{4}-[0-9]{1,2}-[0-9]{1,2}(([Tt]|[ \t]+)[0-9]{1,2}:[0-9]{2}:[0-9]{2}(\.[0-9]*)?([ \t]*(Z|[-+][0-9]{1,2}(:[0-9]{2})?))?)?$"
+    )
+    .unwrap();
+    /// Times of day, which YAML 1.1 reads as base 60 numbers and other
+    /// loaders as times
+    static ref TIME: Regex =
+        Regex::new(r"^[0-9]{1,2}:[0-5][0-9](:[0-5][0-9](\.[0-9]+)?)?$").unwrap();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct TimestampValuesConfig {
+    /// Check dates and date-times such as `2023-01-02`
+    pub dates: bool,
+    /// Check times of day such as `12:30:00`
+    pub times: bool,
+    /// Report scalars with explicit tags such as `!!timestamp` too, so only
+    /// quoted or `!!str` values are accepted
+    pub require_quotes: bool,
+}
+
+impl Default for TimestampValuesConfig {
+    fn default() -> Self {
+        Self {
+            dates: true,
+            times: true,
+            require_quotes: false,
+        }
+    }
+}
+
+/// Flags unquoted scalars that look like dates or times, which some loaders
+/// turn into timestamp objects instead of strings
+#[derive(Debug, Clone)]
+pub struct TimestampValuesRule {
+    base: crate::rules::base::BaseRule<TimestampValuesConfig>,
+}
+
+impl TimestampValuesRule {
+    pub fn new() -> Self {
+        Self::with_config(TimestampValuesConfig::default())
+    }
+
+    pub fn with_config(config: TimestampValuesConfig) -> Self {
+        Self {
+            base: crate::rules::base::BaseRule::new(config),
+        }
+    }
+
+    pub fn config(&self) -> &TimestampValuesConfig {
+        self.base.config()
+    }
+
+    pub fn set_config(&mut self, config: TimestampValuesConfig) {
+        self.base.set_config(config);
+    }
+
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        let config = self.config();
+        let mut issues = Vec::new();
+        let mut previous: Option<&TokenType> = None;
+
+        for Token(marker, token) in tokens {
+            if let TokenType::Scalar(TScalarStyle::Plain, value) = token {
+                let accepted = match previous {
+                    Some(TokenType::Tag(handle, suffix)) => {
+                        (handle == "!!" && suffix == "str") || !config.require_quotes
+                    }
+                    _ => false,
+                };
+                let kind = if config.dates && DATE.is_match(value) {
+                    Some(("date", "timestamp-values/date"))
+                } else if config.times && TIME.is_match(value) {
+                    Some(("time", "timestamp-values/time"))
+                } else {
+                    None
+                };
+                if let Some((kind, code)) = kind.filter(|_| !accepted) {
+                    issues.push(LintIssue {
+                        line: marker.line(),
+                        column: marker.col() + 1,
+                        message: format!(
+                            "unquoted {} '{}' may be loaded as a timestamp",
+                            kind, value
+                        ),
+                        code: code.to_string(),
+                        severity: self.get_severity(),
+                    });
+                }
+            }
+            previous = Some(token);
+        }
+
+        issues
+    }
+}
+
+impl Rule for TimestampValuesRule {
+    fn rule_id(&self) -> &'static str {
+        "timestamp-values"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "Timestamp Values"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Flags unquoted scalars that look like dates or times."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
+
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
+        }
+    }
+}
+
+impl Default for TimestampValuesRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_values_rule_default() {
+        let rule = TimestampValuesRule::new();
+        assert_eq!(rule.rule_id(), "timestamp-values");
+        assert_eq!(rule.default_severity(), Severity::Warning);
+        assert!(!rule.is_enabled_by_default());
+        assert!(!rule.can_fix());
+    }
+
+    #[test]
+    fn test_timestamp_values_reports_unquoted_dates_and_times() {
+        let rule = TimestampValuesRule::new();
+        let content = "released: 2023-01-02\nbuilt: 2001-12-14t21:59:43.10-05:00\nspaced: 2001-12-14 21:59:43.10 -5\nstart: 12:30:00\nshort: 9:05\nquoted: \"2023-01-02\"\nversion: 2023-01\nport: 8080:80\n2024-05-01: launch\n";
+        let issues = rule.check(content, "test.yaml");
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.line, issue.column, issue.code.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, 11, "timestamp-values/date"),
+                (2, 8, "timestamp-values/date"),
+                (3, 9, "timestamp-values/date"),
+                (4, 8, "timestamp-values/time"),
+                (5, 8, "timestamp-values/time"),
+                (9, 1, "timestamp-values/date"),
+            ]
+        );
+        assert_eq!(
+            issues[0].message,
+            "unquoted date '2023-01-02' may be loaded as a timestamp"
+        );
+        assert_eq!(
+            crate::redact::redact_values(&issues[3].message),
+            "unquoted time '<redacted>' may be loaded as a timestamp"
+        );
+    }
+
+    #[test]
+    fn test_timestamp_values_options() {
+        let content =
+            "day: 2023-01-02\nat: 12:30\ntagged: !!timestamp 2023-01-02\nstring: !!str 12:30\n";
+        let count = |config: TimestampValuesConfig| {
+            TimestampValuesRule::with_config(config)
+                .check(content, "test.yaml")
+                .len()
+        };
+        assert_eq!(count(TimestampValuesConfig::default()), 2);
+        assert_eq!(
+            count(TimestampValuesConfig {
+                times: false,
+                ..Default::default()
+            }),
+            1
+        );
+        assert_eq!(
+            count(TimestampValuesConfig {
+                dates: false,
+                ..Default::default()
+            }),
+            1
+        );
+        assert_eq!(
+            count(TimestampValuesConfig {
+                require_quotes: true,
+                ..Default::default()
+            }),
+            3
+        );
+    }
+}
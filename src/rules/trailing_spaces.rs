@@ -1,7 +1,9 @@
 use super::{base::utils, base::BaseRule, FixResult, Rule};
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct TrailingSpacesConfig {
     pub allow: bool,
 }
@@ -54,11 +56,18 @@ impl TrailingSpacesRule {
         Severity::Error
     }
 
-    pub fn create_issue(&self, line: usize, column: usize, message: String) -> LintIssue {
+    pub fn create_issue(
+        &self,
+        line: usize,
+        column: usize,
+        message: String,
+        code: &str,
+    ) -> LintIssue {
         LintIssue {
             line,
             column,
             message,
+            code: code.to_string(),
             severity: self.get_severity(),
         }
     }
@@ -81,6 +90,7 @@ impl TrailingSpacesRule {
                         trailing_count,
                         if trailing_count == 1 { "" } else { "s" }
                     ),
+                    "trailing-spaces/trailing-spaces",
                 ));
             }
         }
@@ -118,6 +128,10 @@ impl Rule for TrailingSpacesRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
     fn can_fix(&self) -> bool {
         true
     }
@@ -188,6 +202,7 @@ mod tests {
         assert_eq!(issues.len(), 2);
         assert_eq!(issues[0].line, 1);
         assert!(issues[0].message.contains("trailing spaces"));
+        assert_eq!(issues[0].code, "trailing-spaces/trailing-spaces");
         assert_eq!(issues[1].line, 3);
     }
 
@@ -1,31 +1,44 @@
-use super::{
-    base::{utils, BaseRuleWithRegex, LintIssueBuilder},
-    Rule,
-};
+use super::Rule;
 use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+use yaml_rust::scanner::{Marker, Scanner, TScalarStyle, Token, TokenType};
 
-#[derive(Debug, Clone)]
+/// YAML 1.1 booleans, as yamllint lists them
+const TRUTHY: &[&str] = &[
+    "YES", "Yes", "yes", "NO", "No", "no", "TRUE", "True", "true", "FALSE", "False", "false", "ON",
+    "On", "on", "OFF", "Off", "off",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
 pub struct TruthyConfig {
     pub allowed_values: Vec<String>,
+    /// Also check mapping keys, e.g. GitHub Actions' `on:`
+    pub check_keys: bool,
+}
+
+impl Default for TruthyConfig {
+    fn default() -> Self {
+        Self {
+            allowed_values: vec!["false".to_string(), "true".to_string()],
+            check_keys: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct TruthyRule {
-    base: BaseRuleWithRegex<TruthyConfig>,
+    base: crate::rules::base::BaseRule<TruthyConfig>,
 }
 
 impl TruthyRule {
     pub fn new() -> Self {
-        Self {
-            base: BaseRuleWithRegex::new(TruthyConfig {
-                allowed_values: vec!["false".to_string(), "true".to_string()],
-            }),
-        }
+        Self::with_config(TruthyConfig::default())
     }
 
     pub fn with_config(config: TruthyConfig) -> Self {
         Self {
-            base: BaseRuleWithRegex::new(config),
+            base: crate::rules::base::BaseRule::new(config),
         }
     }
 
@@ -36,6 +49,62 @@ impl TruthyRule {
     pub fn set_config(&mut self, config: TruthyConfig) {
         self.base.set_config(config);
     }
+
+    /// Plain scalars that are truthy values outside `allowed-values`, with
+    /// their start. Quoted and tagged values are strings, and keys are
+    /// skipped unless `check-keys` is set.
+    fn forbidden_values<'a>(&self, tokens: &'a [Token]) -> Vec<(Marker, &'a str)> {
+        let config = self.config();
+        let mut found = Vec::new();
+        let mut previous: Option<&TokenType> = None;
+
+        for Token(marker, token) in tokens {
+            if let TokenType::Scalar(TScalarStyle::Plain, value) = token {
+                let skipped = match previous {
+                    Some(TokenType::Tag(..)) => true,
+                    Some(TokenType::Key) => !config.check_keys,
+                    _ => false,
+                };
+                if !skipped
+                    && TRUTHY.contains(&value.as_str())
+                    && !config.allowed_values.contains(value)
+                {
+                    found.push((*marker, value.as_str()));
+                }
+            }
+            previous = Some(token);
+        }
+
+        found
+    }
+
+    fn check_with_tokens(&self, tokens: &[Token]) -> Vec<LintIssue> {
+        // Sorted, as yamllint prints them
+        let mut allowed_values = self.config().allowed_values.clone();
+        allowed_values.sort();
+        let message = format!(
+            "truthy value should be one of [{}]",
+            allowed_values.join(", ")
+        );
+        self.forbidden_values(tokens)
+            .into_iter()
+            .map(|(marker, _)| LintIssue {
+                line: marker.line(),
+                column: marker.col() + 1,
+                message: message.clone(),
+                code: "truthy/forbidden-value".to_string(),
+                severity: self.get_severity(),
+            })
+            .collect()
+    }
+
+    fn get_replacement(&self, value: &str) -> Option<&'static str> {
+        match value.to_lowercase().as_str() {
+            "yes" | "on" | "true" => Some("true"),
+            "no" | "off" | "false" => Some("false"),
+            _ => None,
+        }
+    }
 }
 
 impl Rule for TruthyRule {
@@ -67,134 +136,68 @@ impl Rule for TruthyRule {
         self.base.has_severity_override()
     }
 
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(Self::new().config()).unwrap_or_default()
+    }
+
     fn check(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
-        let mut issues = Vec::new();
-
-        for (line_num, line) in content.lines().enumerate() {
-            let line_num = line_num + 1;
-
-            let words = line.split_whitespace();
-            for word in words {
-                let trimmed = word.trim_end_matches(',');
-                if self.is_truthy_value(trimmed)
-                    && !self
-                        .base
-                        .config()
-                        .allowed_values
-                        .contains(&trimmed.to_string())
-                {
-                    if let Some(pos) = line.find(trimmed) {
-                        issues.push(Self::create_issue(
-                            line_num,
-                            pos + 1,
-                            format!(
-                                "truthy value should be one of [{}]",
-                                self.base.config().allowed_values.join(", ")
-                            ),
-                            self.get_severity(),
-                        ));
-                    }
-                }
-            }
-        }
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        self.check_with_tokens(&tokens)
+    }
 
-        issues
+    fn check_with_analysis(
+        &self,
+        content: &str,
+        file_path: &str,
+        analysis: &crate::analysis::ContentAnalysis,
+    ) -> Vec<LintIssue> {
+        match analysis.tokens() {
+            Some(token_analysis) => self.check_with_tokens(&token_analysis.tokens),
+            None => self.check(content, file_path),
+        }
     }
 
     fn can_fix(&self) -> bool {
         true
     }
 
+    /// Replaces each reported value with `true` or `false`
     fn fix(&self, content: &str, _file_path: &str) -> super::FixResult {
-        let mut fixed_lines = Vec::new();
+        let tokens: Vec<Token> = Scanner::new(content.chars()).collect();
+        let mut replacements = self
+            .forbidden_values(&tokens)
+            .into_iter()
+            .filter_map(|(marker, value)| {
+                let replacement = self.get_replacement(value)?;
+                (replacement != value).then_some((marker.index(), value, replacement))
+            })
+            .peekable();
+
+        let mut fixed_content = String::with_capacity(content.len());
         let mut fixes_applied = 0;
-        let mut base = self.base.clone();
-
-        for line in content.lines() {
-            let mut fixed_line = line.to_string();
-
-            for word in line.split_whitespace() {
-                let trimmed = word.trim_end_matches(',');
-                if self.is_truthy_value(trimmed)
-                    && !self
-                        .base
-                        .config()
-                        .allowed_values
-                        .contains(&trimmed.to_string())
-                {
-                    let replacement = self.get_replacement(trimmed);
-                    if let Some(replacement) = replacement {
-                        if word == trimmed {
-                            let pattern = format!(r"\b{}\b", regex::escape(trimmed));
-                            if let Ok(regex) = base.get_or_compile_pattern(&pattern) {
-                                if regex.is_match(&fixed_line) {
-                                    fixed_line = regex
-                                        .replace_all(&fixed_line, replacement.as_str())
-                                        .to_string();
-                                    fixes_applied += 1;
-                                }
-                            }
-                        } else if word == format!("{},", trimmed) {
-                            let pattern = format!(r"\b{},", regex::escape(trimmed));
-                            if let Ok(regex) = base.get_or_compile_pattern(&pattern) {
-                                if regex.is_match(&fixed_line) {
-                                    fixed_line = regex
-                                        .replace_all(&fixed_line, &format!("{},", replacement))
-                                        .to_string();
-                                    fixes_applied += 1;
-                                }
-                            }
-                        }
+        let mut chars = content.chars().enumerate();
+        while let Some((index, c)) = chars.next() {
+            match replacements.next_if(|(start, _, _)| *start == index) {
+                Some((_, value, replacement)) => {
+                    fixed_content.push_str(replacement);
+                    // Skip the rest of the replaced value
+                    for _ in 1..value.chars().count() {
+                        chars.next();
                     }
+                    fixes_applied += 1;
                 }
+                None => fixed_content.push(c),
             }
-
-            fixed_lines.push(fixed_line);
         }
 
-        let fixed_content =
-            utils::join_lines_preserving_newlines(fixed_lines, content.ends_with('\n'));
-
-        let changed = fixes_applied > 0;
-
         super::FixResult {
             content: fixed_content,
-            changed,
+            changed: fixes_applied > 0,
             fixes_applied,
         }
     }
 }
 
-impl TruthyRule {
-    fn is_truthy_value(&self, value: &str) -> bool {
-        matches!(
-            value.to_lowercase().as_str(),
-            "yes"
-                | "no"
-                | "on"
-                | "off"
-                | "y"
-                | "n"
-                | "true"
-                | "false"
-                | "1"
-                | "0"
-                | "enable"
-                | "disable"
-                | "enabled"
-                | "disabled"
-        )
-    }
-
-    fn get_replacement(&self, value: &str) -> Option<String> {
-        match value.to_lowercase().as_str() {
-            "yes" | "y" | "on" | "1" | "enable" | "enabled" => Some("true".to_string()),
-            "no" | "n" | "off" | "0" | "disable" | "disabled" => Some("false".to_string()),
-            _ => None,
-        }
-    }
-}
-
 impl Default for TruthyRule {
     fn default() -> Self {
         Self::new()
@@ -229,7 +232,20 @@ mod tests {
         let content = "key: yes\nanother: no\nvalue: on";
         let issues = rule.check(content, "test.yaml");
         assert_eq!(issues.len(), 3);
-        assert!(issues[0].message.contains("truthy value should be one of"));
+        assert_eq!(
+            issues[0].message,
+            "truthy value should be one of [false, true]"
+        );
+
+        let rule = TruthyRule::with_config(TruthyConfig {
+            allowed_values: vec!["yes".to_string(), "no".to_string(), "true".to_string()],
+            ..TruthyConfig::default()
+        });
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(
+            issues[0].message,
+            "truthy value should be one of [no, true, yes]"
+        );
     }
 
     #[test]
@@ -243,6 +259,49 @@ mod tests {
         assert!(fix_result.content.contains("another: false"));
     }
 
+    #[test]
+    fn test_truthy_check_matches_yaml_1_1_booleans_only() {
+        let rule = TruthyRule::new();
+        let content =
+            "---\na: 1\nb: y\nc: enabled\nd: \"yes\"\ne: !!str on\nf: [True, OFF]\ng: |\n  no\n";
+        let issues: Vec<_> = rule
+            .check(content, "test.yaml")
+            .into_iter()
+            .map(|issue| (issue.line, issue.column))
+            .collect();
+        assert_eq!(issues, vec![(7, 5), (7, 11)]);
+    }
+
+    #[test]
+    fn test_truthy_check_keys() {
+        let content = "---\non:\n  push:\nyes: 1\nkey: no\n";
+        let rule = TruthyRule::new();
+        assert_eq!(rule.check(content, "test.yaml").len(), 3);
+
+        let rule = TruthyRule::with_config(TruthyConfig {
+            check_keys: false,
+            ..TruthyConfig::default()
+        });
+        let issues = rule.check(content, "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (5, 6));
+    }
+
+    #[test]
+    fn test_truthy_fix_only_rewrites_reported_values() {
+        let rule = TruthyRule::with_config(TruthyConfig {
+            check_keys: false,
+            ..TruthyConfig::default()
+        });
+        let content = "on:\n  push: {branches: [main], draft: Off}\nname: \"yes\"\nnote: yes or no\nflag: yes # yes\n";
+        let fix_result = rule.fix(content, "test.yaml");
+        assert_eq!(fix_result.fixes_applied, 2);
+        assert_eq!(
+            fix_result.content,
+            "on:\n  push: {branches: [main], draft: false}\nname: \"yes\"\nnote: yes or no\nflag: true # yes\n"
+        );
+    }
+
     #[test]
     fn test_truthy_fix_no_changes() {
         let rule = TruthyRule::new();
@@ -0,0 +1,141 @@
+use super::{base::BaseRule, Rule};
+use crate::{LintIssue, Severity};
+use serde::{Deserialize, Serialize};
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::Marker;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct YamlSyntaxConfig {}
+
+/// Reports scanner and parser errors, like yamllint's `syntax` errors, so
+/// malformed files are not silently half-checked by the other rules
+#[derive(Debug, Clone)]
+pub struct YamlSyntaxRule {
+    base: BaseRule<YamlSyntaxConfig>,
+}
+
+struct IgnoreEvents;
+
+impl MarkedEventReceiver for IgnoreEvents {
+    fn on_event(&mut self, _: Event, _: Marker) {}
+}
+
+impl YamlSyntaxRule {
+    pub fn new() -> Self {
+        Self {
+            base: BaseRule::new(YamlSyntaxConfig::default()),
+        }
+    }
+
+    pub fn check_impl(&self, content: &str, _file_path: &str) -> Vec<LintIssue> {
+        let mut parser = Parser::new(content.chars());
+        let Err(error) = parser.load(&mut IgnoreEvents, true) else {
+            return Vec::new();
+        };
+
+        // The scanner only exposes its message through `Display`, which
+        // appends the position we report separately
+        let marker = error.marker();
+        let position = format!(" at line {} column {}", marker.line(), marker.col() + 1);
+        let text = error.to_string();
+        let info = text.strip_suffix(&position).unwrap_or(&text);
+
+        vec![LintIssue {
+            line: marker.line().max(1),
+            column: marker.col() + 1,
+            message: format!("syntax error: {}", info),
+            code: "yaml-syntax/syntax-error".to_string(),
+            severity: self.get_severity(),
+        }]
+    }
+}
+
+impl Default for YamlSyntaxRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for YamlSyntaxRule {
+    fn rule_id(&self) -> &'static str {
+        "yaml-syntax"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "YAML Syntax"
+    }
+
+    fn rule_description(&self) -> &'static str {
+        "Reports YAML that cannot be parsed, like unclosed quotes or bad nesting."
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn get_severity(&self) -> Severity {
+        self.base.get_severity(self.default_severity())
+    }
+
+    fn set_severity(&mut self, severity: Severity) {
+        self.base.set_severity(severity);
+    }
+
+    fn has_severity_override(&self) -> bool {
+        self.base.has_severity_override()
+    }
+
+    fn default_config_json(&self) -> serde_json::Value {
+        serde_json::to_value(&Self::new().base.config).unwrap_or_default()
+    }
+
+    fn check(&self, content: &str, file_path: &str) -> Vec<LintIssue> {
+        self.check_impl(content, file_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_syntax_rule_default() {
+        let rule = YamlSyntaxRule::new();
+        assert_eq!(rule.rule_id(), "yaml-syntax");
+        assert_eq!(rule.default_severity(), Severity::Error);
+        assert!(rule.is_enabled_by_default());
+        assert!(!rule.can_fix());
+    }
+
+    #[test]
+    fn test_yaml_syntax_accepts_valid_yaml() {
+        let rule = YamlSyntaxRule::new();
+        let content = "---\nkey: value\nlist:\n  - a\n  - {b: 1}\n---\nother: doc\n";
+        assert!(rule.check(content, "test.yaml").is_empty());
+        assert!(rule.check("", "test.yaml").is_empty());
+    }
+
+    #[test]
+    fn test_yaml_syntax_reports_unclosed_quote() {
+        let rule = YamlSyntaxRule::new();
+        let issues = rule.check("key: \"value\nother: 1\n", "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "yaml-syntax/syntax-error");
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert!(issues[0].message.starts_with("syntax error: "));
+        assert!(issues[0].message.contains("quoted scalar"));
+        assert!(!issues[0].message.contains(" at line "));
+    }
+
+    #[test]
+    fn test_yaml_syntax_reports_bad_block_structure() {
+        let rule = YamlSyntaxRule::new();
+        let issues = rule.check("key: value\n- item\n", "test.yaml");
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (2, 1));
+        assert_eq!(
+            issues[0].message,
+            "syntax error: while parsing a block mapping, did not find expected key"
+        );
+    }
+}
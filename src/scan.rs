@@ -0,0 +1,93 @@
+//! Byte scanning helpers used by content analysis.
+//!
+//! With the `simd` feature these use `memchr`, which picks vectorized
+//! implementations for the running CPU at runtime. Without it they fall back
+//! to the standard library, so results are identical either way.
+
+/// Whether `haystack` contains `needle`
+#[inline]
+pub fn contains(haystack: &str, needle: u8) -> bool {
+    #[cfg(feature = "simd")]
+    {
+        memchr::memchr(needle, haystack.as_bytes()).is_some()
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        haystack.as_bytes().contains(&needle)
+    }
+}
+
+/// Whether `haystack` contains either `a` or `b`
+#[inline]
+pub fn contains_either(haystack: &str, a: u8, b: u8) -> bool {
+    #[cfg(feature = "simd")]
+    {
+        memchr::memchr2(a, b, haystack.as_bytes()).is_some()
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        haystack.bytes().any(|byte| byte == a || byte == b)
+    }
+}
+
+/// Lines of `content`, split like [`str::lines`]: on `\n`, with a trailing
+/// `\r` stripped and no empty line after a final newline
+pub fn lines(content: &str) -> impl Iterator<Item = &str> {
+    #[cfg(feature = "simd")]
+    {
+        let mut start = 0;
+        let mut newlines = memchr::memchr_iter(b'\n', content.as_bytes());
+        std::iter::from_fn(move || match newlines.next() {
+            Some(end) => {
+                let line = &content[start..end];
+                start = end + 1;
+                Some(line.strip_suffix('\r').unwrap_or(line))
+            }
+            None if start < content.len() => {
+                let line = &content[start..];
+                start = content.len();
+                Some(line)
+            }
+            None => None,
+        })
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        content.lines()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_match_str_lines() {
+        for content in [
+            "",
+            "a",
+            "a\n",
+            "a\r\nb",
+            "a\n\nb\n",
+            "\n",
+            "x\r\n\r\n",
+            "a\r",
+        ] {
+            let expected: Vec<&str> = content.lines().collect();
+            assert_eq!(
+                lines(content).collect::<Vec<_>>(),
+                expected,
+                "{:?}",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        assert!(contains("key: value", b':'));
+        assert!(!contains("key value", b':'));
+        assert!(contains_either("a [b", b'[', b']'));
+        assert!(!contains_either("a b", b'[', b']'));
+    }
+}
@@ -0,0 +1,146 @@
+//! Content-based YAML detection for `--detect-yaml`.
+//!
+//! Files without an extension, such as `Procfile`, are linted when their
+//! start looks like YAML: a shebang naming a YAML tool, a `---` or `%YAML`
+//! header, an editor modeline selecting YAML, or nothing but `key: value`
+//! and `- item` lines that parse as a mapping or sequence.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use yaml_rust::{Yaml, YamlLoader};
+
+/// Bytes read from a file to decide; the line heuristic only runs when the
+/// whole file fits
+const SNIFF_BYTES: usize = 64 * 1024;
+
+/// Lines looked at for headers and modelines
+const HEADER_LINES: usize = 5;
+
+lazy_static! {
+    /// Interpreters of executable YAML files
+    static ref YAML_SHEBANG: Regex = Regex::new(r"\b(yaml|yq|ansible-playbook)\b").unwrap();
+    /// Emacs `-*- mode: yaml -*-` and Vim `ft=yaml` modelines
+    static ref MODELINE: Regex = Regex::new(
+        r"(?i)(-\*-.*\bmode:\s*yaml\b.*-\*-|\bvim?:.*\b(ft|filetype|syntax)=yaml\b)"
+    )
+    .unwrap();
+    /// A block mapping entry or sequence entry starting a line
+    static ref ENTRY: Regex = Regex::new(r"^\s*(-(\s|$)|[^\s#\-\[{][^:]*:(\s|$))").unwrap();
+}
+
+/// Whether the file at `path` looks like YAML; unreadable and binary files
+/// do not
+pub fn looks_like_yaml_file(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let mut bytes = Vec::with_capacity(4096);
+    if file
+        .take(SNIFF_BYTES as u64 + 1)
+        .read_to_end(&mut bytes)
+        .is_err()
+    {
+        return false;
+    }
+    let complete = bytes.len() <= SNIFF_BYTES;
+    bytes.truncate(SNIFF_BYTES);
+
+    let content = match std::str::from_utf8(&bytes) {
+        Ok(content) => content,
+        // A multi-byte character cut at the end of a partial read
+        Err(error) if !complete && error.error_len().is_none() => {
+            std::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap_or_default()
+        }
+        Err(_) => return false,
+    };
+    !content.contains('\0') && looks_like_yaml(content, complete)
+}
+
+/// Whether `content`, the start of a file or all of it when `complete`,
+/// looks like YAML
+pub fn looks_like_yaml(content: &str, complete: bool) -> bool {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+    if let Some(shebang) = content
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("#!"))
+    {
+        return YAML_SHEBANG.is_match(shebang);
+    }
+
+    let header: Vec<&str> = content.lines().take(HEADER_LINES).collect();
+    if header.iter().any(|line| MODELINE.is_match(line)) {
+        return true;
+    }
+    let first_content = content
+        .lines()
+        .map(str::trim_end)
+        .find(|line| !line.is_empty() && !line.starts_with('#'));
+    match first_content {
+        Some(line) if line == "---" || line.starts_with("--- ") || line.starts_with("%YAML") => {
+            return true
+        }
+        None => return false,
+        Some(_) => {}
+    }
+
+    complete && looks_like_entries(content)
+}
+
+/// Whether every top-level line is an entry and the content loads as a
+/// mapping or sequence, as with a `Procfile`
+fn looks_like_entries(content: &str) -> bool {
+    let top_level_entries = content
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#') && !line.starts_with([' ', '\t'])
+        })
+        .all(|line| ENTRY.is_match(line));
+
+    top_level_entries
+        && matches!(
+            YamlLoader::load_from_str(content).as_deref(),
+            Ok([Yaml::Hash(_) | Yaml::Array(_), ..])
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_yaml_headers_and_shebangs() {
+        assert!(looks_like_yaml("# config\n---\nkey: value\n", true));
+        assert!(looks_like_yaml("%YAML 1.2\n---\n", true));
+        assert!(looks_like_yaml(
+            "#!/usr/bin/env ansible-playbook\n- hosts: all\n",
+            true
+        ));
+        assert!(looks_like_yaml("# vim: set ft=yaml:\nkey: [\n", false));
+        assert!(looks_like_yaml("# -*- mode: yaml -*-\n", false));
+
+        assert!(!looks_like_yaml("#!/bin/sh\nkey: value\n", true));
+        assert!(!looks_like_yaml("", true));
+        assert!(!looks_like_yaml("# only a comment\n", true));
+    }
+
+    #[test]
+    fn test_looks_like_yaml_entries() {
+        let procfile = "web: bundle exec rails server -p $PORT\nworker: bundle exec sidekiq\n";
+        assert!(looks_like_yaml(procfile, true));
+        assert!(!looks_like_yaml(procfile, false));
+        assert!(looks_like_yaml("- one\n- two\n", true));
+        assert!(looks_like_yaml("nested:\n  key: value\n", true));
+
+        assert!(!looks_like_yaml("[section]\nkey = value\n", true));
+        assert!(!looks_like_yaml("Just some text.\nNote: more text\n", true));
+        assert!(!looks_like_yaml("key: value\nnot an entry\n", true));
+        assert!(!looks_like_yaml("plain scalar\n", true));
+        assert!(!looks_like_yaml("{\"json\": true}\n", true));
+    }
+}
@@ -0,0 +1,127 @@
+//! Wall time spent in each rule across a run, collected with `--profile-rules`.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Name the shared per-file content analysis is reported under
+pub const ANALYSIS: &str = "(analysis)";
+
+/// Total time and number of files checked for one rule
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleTiming {
+    pub total: Duration,
+    pub files: usize,
+}
+
+/// Per-rule totals shared by all worker threads of a run
+#[derive(Debug, Default)]
+pub struct RuleTimings {
+    totals: Mutex<HashMap<&'static str, RuleTiming>>,
+}
+
+impl RuleTimings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the times measured while checking one file, taking the lock once
+    pub fn record_file(&self, times: &[(&'static str, Duration)]) {
+        let mut totals = self.totals.lock().unwrap();
+        for &(rule_id, elapsed) in times {
+            let timing = totals.entry(rule_id).or_default();
+            timing.total += elapsed;
+            timing.files += 1;
+        }
+    }
+
+    /// Totals by rule, slowest first
+    pub fn slowest_first(&self) -> Vec<(&'static str, RuleTiming)> {
+        let mut timings: Vec<_> = self
+            .totals
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(&rule_id, &timing)| (rule_id, timing))
+            .collect();
+        timings.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(b.0)));
+        timings
+    }
+
+    /// A table of [`Self::slowest_first`] with each rule's share of the total
+    pub fn table(&self) -> String {
+        let timings = self.slowest_first();
+        let total: Duration = timings.iter().map(|(_, timing)| timing.total).sum();
+        let id_width = timings
+            .iter()
+            .map(|(rule_id, _)| rule_id.len())
+            .chain(["RULE".len()])
+            .max()
+            .unwrap_or(0);
+
+        let mut table = format!(
+            "{:<id_width$}  {:>10}  {:>6}  {:>6}\n",
+            "RULE", "TIME (ms)", "SHARE", "FILES"
+        );
+        for (rule_id, timing) in timings {
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                timing.total.as_secs_f64() * 100.0 / total.as_secs_f64()
+            };
+            table.push_str(&format!(
+                "{:<id_width$}  {:>10.1}  {:>5.1}%  {:>6}\n",
+                rule_id,
+                timing.total.as_secs_f64() * 1000.0,
+                share,
+                timing.files
+            ));
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_timings_accumulate_across_files() {
+        let timings = RuleTimings::new();
+        timings.record_file(&[
+            ("line-length", Duration::from_millis(3)),
+            ("colons", Duration::from_millis(1)),
+        ]);
+        timings.record_file(&[
+            ("line-length", Duration::from_millis(3)),
+            ("colons", Duration::from_millis(1)),
+        ]);
+
+        assert_eq!(
+            timings.slowest_first(),
+            vec![
+                (
+                    "line-length",
+                    RuleTiming {
+                        total: Duration::from_millis(6),
+                        files: 2
+                    }
+                ),
+                (
+                    "colons",
+                    RuleTiming {
+                        total: Duration::from_millis(2),
+                        files: 2
+                    }
+                ),
+            ]
+        );
+
+        let table = timings.table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("RULE"));
+        assert!(lines[1].starts_with("line-length"));
+        assert!(lines[1].contains("6.0"));
+        assert!(lines[1].contains("75.0%"));
+    }
+}
@@ -0,0 +1,75 @@
+//! TOML config files.
+//!
+//! A config can be written as TOML instead of YAML, either as a dedicated
+//! `.yamllint.toml` or as a `[tool.yamllint-rs]` table in a TOML file the
+//! project already has (e.g. `pyproject.toml`). Either way it holds the same
+//! keys as a YAML config and is converted to a YAML value before parsing, so
+//! every config feature works the same in both formats.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// File name of the dedicated TOML config discovered next to linted files
+pub const TOML_CONFIG_FILE_NAME: &str = ".yamllint.toml";
+
+/// Shared TOML files searched for a `[tool.yamllint-rs]` table
+pub const SHARED_TOML_FILE_NAMES: &[&str] = &["pyproject.toml"];
+
+/// Whether the config at `path` is read as TOML
+pub fn is_toml(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "toml")
+}
+
+/// The config in the TOML document `content` read from `path`: its
+/// `[tool.yamllint-rs]` table, or the whole document for a dedicated
+/// `.yamllint.toml`
+pub fn config_value(content: &str, path: &Path) -> Result<serde_yaml::Value> {
+    let document: toml::Table = toml::from_str(content)?;
+    let value = match tool_table(&document) {
+        Some(table) => toml::Value::Table(table.clone()),
+        None if path
+            .file_name()
+            .is_some_and(|name| name == TOML_CONFIG_FILE_NAME) =>
+        {
+            toml::Value::Table(document)
+        }
+        None => anyhow::bail!("no [tool.yamllint-rs] table"),
+    };
+    serde_yaml::to_value(value).context("cannot convert TOML config")
+}
+
+/// Whether the shared TOML file at `path` configures yamllint-rs
+pub fn has_tool_table(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Table>(&content).ok())
+        .is_some_and(|document| tool_table(&document).is_some())
+}
+
+fn tool_table(document: &toml::Table) -> Option<&toml::Table> {
+    document.get("tool")?.get("yamllint-rs")?.as_table()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_value_reads_dedicated_files_and_tool_tables() {
+        let dedicated = config_value(
+            "extends = \"default\"\n[rules.line-length]\nmax = 120\n",
+            Path::new("dir/.yamllint.toml"),
+        )
+        .unwrap();
+        assert_eq!(dedicated["extends"], "default");
+        assert_eq!(dedicated["rules"]["line-length"]["max"], 120);
+
+        let shared = "[project]\nname = \"x\"\n\n[tool.yamllint-rs.rules]\ntruthy = \"disable\"\n";
+        let value = config_value(shared, Path::new("pyproject.toml")).unwrap();
+        assert_eq!(value["rules"]["truthy"], "disable");
+        assert!(value.get("project").is_none());
+
+        let error = config_value("[project]\n", Path::new("pyproject.toml")).unwrap_err();
+        assert_eq!(error.to_string(), "no [tool.yamllint-rs] table");
+    }
+}
@@ -0,0 +1,409 @@
+//! End-to-end tests running the compiled binary against the fixture tree in
+//! `tests/fixtures/cli`: exit codes, stdout/stderr separation, format
+//! selection, `--fix` file changes and directives.
+
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use walkdir::WalkDir;
+
+const FIXTURES: &str = "tests/fixtures/cli";
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(FIXTURES)
+        .join(name)
+}
+
+fn yamllint() -> assert_cmd::Command {
+    assert_cmd::Command::cargo_bin("yamllint-rs").unwrap()
+}
+
+/// Copy of the fixture tree that tests may modify
+fn fixture_copy() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let root = fixture("");
+    for entry in WalkDir::new(&root) {
+        let entry = entry.unwrap();
+        let target = temp_dir
+            .path()
+            .join(entry.path().strip_prefix(&root).unwrap());
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+        } else {
+            fs::copy(entry.path(), &target).unwrap();
+        }
+    }
+    temp_dir
+}
+
+#[test]
+fn test_exit_codes_per_fixture() {
+    let run = |args: &[&str], path: PathBuf| yamllint().args(args).arg(path).assert();
+
+    run(&[], fixture("clean.yaml")).code(0);
+    run(&[], fixture("warning.yaml")).code(0);
+    run(&["--strict"], fixture("warning.yaml")).code(2);
+    run(&[], fixture("nested/error.yaml")).code(1);
+    run(&[], fixture("")).code(1);
+    run(&["--fail-on", "never"], fixture("")).code(0);
+    run(&["--config", "no-such-config.yaml"], fixture("clean.yaml")).code(255);
+}
+
+#[test]
+fn test_reports_go_to_stdout_and_diagnostics_to_stderr() {
+    let output = yamllint()
+        .args(["--reporter", "json", "--profile-rules"])
+        .arg(fixture(""))
+        .assert()
+        .code(1)
+        .get_output()
+        .clone();
+
+    // Progress and timings must not corrupt the machine-readable report
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(!report["issues"].as_array().unwrap().is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("[Progress]"), "{}", stderr);
+    assert!(stderr.contains("trailing-spaces"), "{}", stderr);
+
+    yamllint()
+        .arg("--no-progress")
+        .arg(fixture("clean.yaml"))
+        .assert()
+        .code(0)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_format_selection() {
+    let run = |format: &str| {
+        let output = yamllint()
+            .args(["--no-progress", "--format", format])
+            .arg(fixture("nested/error.yaml"))
+            .assert()
+            .code(1)
+            .get_output()
+            .stdout
+            .clone();
+        String::from_utf8(output).unwrap()
+    };
+
+    let standard = run("standard");
+    assert!(standard.contains("trailing spaces") && !standard.contains('\x1b'));
+    assert!(run("colored").contains("\x1b["));
+    // Output is not a terminal here, so `auto` picks the standard format
+    assert_eq!(run("auto"), standard);
+
+    yamllint()
+        .args(["--format", "xml"])
+        .arg(fixture("clean.yaml"))
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn test_fix_rewrites_fixable_files_only() {
+    let tree = fixture_copy();
+    let clean_before = fs::read(tree.path().join("clean.yaml")).unwrap();
+
+    yamllint()
+        .current_dir(tree.path())
+        .args(["--no-progress", "--format", "standard", "--fix", "."])
+        .assert()
+        .code(0);
+
+    assert_eq!(
+        fs::read_to_string(tree.path().join("nested/error.yaml")).unwrap(),
+        "---\nname: error\n"
+    );
+    assert_eq!(
+        fs::read(tree.path().join("clean.yaml")).unwrap(),
+        clean_before
+    );
+
+    // Nothing is left to fix, and the tree now lints clean of errors
+    let before: Vec<Vec<u8>> = ["clean.yaml", "warning.yaml", "nested/error.yaml"]
+        .iter()
+        .map(|name| fs::read(tree.path().join(name)).unwrap())
+        .collect();
+    yamllint()
+        .current_dir(tree.path())
+        .args(["--no-progress", "--fix", "."])
+        .assert()
+        .code(0);
+    let after: Vec<Vec<u8>> = ["clean.yaml", "warning.yaml", "nested/error.yaml"]
+        .iter()
+        .map(|name| fs::read(tree.path().join(name)).unwrap())
+        .collect();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn test_directives_suppress_issues() {
+    let output = yamllint()
+        .args([
+            "--no-progress",
+            "--format",
+            "standard",
+            "--enable-only",
+            "trailing-spaces",
+        ])
+        .arg(fixture("directives.yaml"))
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = stdout
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().next())
+        .collect();
+    // Line 3 is inside `disable`, line 6 has `disable-line`; line 5 follows `enable`
+    assert_eq!(lines, vec!["5:14"], "{}", stdout);
+}
+
+#[test]
+fn test_unknown_config_settings_warn_or_fail_with_strict_config() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("a.yaml"), "---\nkey: value\n").unwrap();
+    let config = "extends: default\n\
+                  rules:\n  \
+                    line-lenght:\n    max: 100\n  \
+                    indentation:\n    spaces: 2\n    indent-sequence: false\n";
+
+    yamllint()
+        .current_dir(temp_dir.path())
+        .args(["--no-progress", "-d", config, "a.yaml"])
+        .assert()
+        .code(0)
+        .stderr(predicate::str::contains(
+            "Warning: inline config (-d): unknown rule 'line-lenght' (did you mean 'line-length'?)",
+        ))
+        .stderr(predicate::str::contains(
+            "unknown option 'indent-sequence' for rule 'indentation' \
+             (did you mean 'indent-sequences'?)",
+        ));
+
+    yamllint()
+        .current_dir(temp_dir.path())
+        .args(["--no-progress", "--strict-config", "-d", config, "a.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid inline config (-d)"));
+
+    // Options set from the command line are checked too
+    yamllint()
+        .current_dir(temp_dir.path())
+        .args([
+            "--no-progress",
+            "--strict-config",
+            "--set",
+            "line-length.maximum=90",
+        ])
+        .arg("a.yaml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown option 'maximum'"));
+
+    yamllint()
+        .current_dir(temp_dir.path())
+        .args(["--no-progress", "--strict-config", "-d", "extends: default"])
+        .arg("a.yaml")
+        .assert()
+        .code(0)
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_overrides_adjust_rules_for_matching_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let long_line = format!("---\nkey: {}\n", "x".repeat(95));
+    fs::create_dir_all(temp_dir.path().join("generated/deep")).unwrap();
+    fs::create_dir_all(temp_dir.path().join("helm")).unwrap();
+    fs::write(temp_dir.path().join("generated/deep/a.yaml"), &long_line).unwrap();
+    fs::write(temp_dir.path().join("helm/values.yaml"), "key: value\n").unwrap();
+    fs::write(temp_dir.path().join("b.yaml"), &long_line).unwrap();
+    fs::write(
+        temp_dir.path().join(".yamllint"),
+        "extends: default\n\
+         rules:\n  line-length:\n    max: 80\n    level: warning\n\
+         overrides:\n  \
+           - files: ['generated/**']\n    \
+             rules:\n      line-length:\n        max: 120\n  \
+           - files: 'helm/**'\n    \
+             rules:\n      document-start: disable\n",
+    )
+    .unwrap();
+
+    yamllint()
+        .current_dir(temp_dir.path())
+        .args(["--no-progress", "generated/deep/a.yaml", "helm/values.yaml"])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::is_empty());
+
+    // Files outside the globs keep the base rules
+    yamllint()
+        .current_dir(temp_dir.path())
+        .args(["--no-progress", "--format", "standard", "b.yaml"])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains(
+            "warning  line too long (100 > 80 characters)",
+        ));
+
+    // An override keeps the options it does not set
+    let longer_line = format!("---\nkey: {}\n", "x".repeat(125));
+    fs::write(temp_dir.path().join("generated/deep/a.yaml"), longer_line).unwrap();
+    yamllint()
+        .current_dir(temp_dir.path())
+        .args([
+            "--no-progress",
+            "--format",
+            "standard",
+            "generated/deep/a.yaml",
+        ])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains(
+            "warning  line too long (130 > 120 characters)",
+        ));
+}
+
+#[test]
+fn test_overrides_match_relative_to_the_config_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("ov");
+    fs::create_dir_all(project.join("generated")).unwrap();
+    fs::write(
+        project.join("generated/a.yaml"),
+        format!("---\nkey: {}\n", "x".repeat(95)),
+    )
+    .unwrap();
+    fs::write(
+        project.join(".yamllint"),
+        "extends: default\n\
+         overrides:\n  \
+           - files: ['generated/**']\n    \
+             rules:\n      line-length: disable\n",
+    )
+    .unwrap();
+
+    // From the parent, the discovered config still sees `generated/a.yaml`
+    yamllint()
+        .current_dir(temp_dir.path())
+        .args(["--no-progress", "-r", "ov"])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::is_empty());
+
+    // From inside the matched directory, with the config given explicitly
+    yamllint()
+        .current_dir(project.join("generated"))
+        .args(["--no-progress", "-c", "../.yamllint", "a.yaml"])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn test_extends_from_url_uses_the_cache_and_offline_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_home = temp_dir.path().join("cache");
+    // Nothing listens on the discard port, so downloads fail fast
+    let url = "http://127.0.0.1:9/org-yamllint.yaml";
+    fs::write(temp_dir.path().join("a.yaml"), "key: value\n").unwrap();
+    fs::write(
+        temp_dir.path().join(".yamllint"),
+        format!("extends: {}\n", url),
+    )
+    .unwrap();
+    let lint = |args: &[&str]| {
+        let mut cmd = yamllint();
+        cmd.current_dir(temp_dir.path())
+            .env("XDG_CACHE_HOME", &cache_home)
+            .arg("--no-progress")
+            .args(args)
+            .arg("a.yaml");
+        cmd
+    };
+
+    lint(&[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!("Cannot download {}", url)));
+    lint(&["--offline"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not cached"));
+
+    let cached = cache_home.join("yamllint-rs/remote-configs");
+    fs::create_dir_all(&cached).unwrap();
+    fs::write(
+        cached.join(yamllint_rs::config_hash::hash_hex(url.as_bytes())),
+        "extends: default\nrules:\n  document-start: disable\n",
+    )
+    .unwrap();
+    lint(&["--offline"]).assert().code(0);
+    // A fresh cached copy is used without downloading it again
+    lint(&[]).assert().code(0);
+
+    // Remote configs cannot reach back into the local tree
+    fs::write(
+        cached.join(yamllint_rs::config_hash::hash_hex(url.as_bytes())),
+        "extends: ../base.yaml\n",
+    )
+    .unwrap();
+    lint(&["--offline"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "cannot extend the local config ../base.yaml",
+        ));
+}
+
+#[test]
+fn test_require_config_fails_without_a_project_config() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("a.yaml"), "---\nkey: value\n").unwrap();
+    // A personal config does not satisfy the requirement
+    let user_config = temp_dir.path().join("xdg/yamllint");
+    fs::create_dir_all(&user_config).unwrap();
+    fs::write(user_config.join("config"), "extends: default\n").unwrap();
+    let lint = || {
+        let mut cmd = yamllint();
+        cmd.current_dir(temp_dir.path())
+            .env("XDG_CONFIG_HOME", temp_dir.path().join("xdg"))
+            .env_remove("YAMLLINT_CONFIG_FILE")
+            .env_remove("YAMLLINT_RS_REQUIRE_CONFIG")
+            .arg("--no-progress");
+        cmd
+    };
+
+    lint().arg("a.yaml").assert().code(0);
+    lint()
+        .args(["--require-config", "a.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No config file found for a.yaml and a config is required",
+        ));
+    lint()
+        .env("YAMLLINT_RS_REQUIRE_CONFIG", "1")
+        .arg("a.yaml")
+        .assert()
+        .failure();
+    lint()
+        .args(["--require-config", "-d", "extends: default", "a.yaml"])
+        .assert()
+        .code(0);
+
+    fs::write(temp_dir.path().join(".yamllint"), "extends: default\n").unwrap();
+    lint().args(["--require-config", "a.yaml"]).assert().code(0);
+}
@@ -1,6 +1,6 @@
 use std::fs;
 use tempfile::TempDir;
-use yamllint_rs::{discover_config_file_from_dir, load_config};
+use yamllint_rs::{discover_config_file_for_path, discover_config_file_from_dir, load_config};
 
 #[test]
 fn test_discover_config_file_not_found() {
@@ -214,3 +214,387 @@ invalid: yaml: content: [unclosed
         "Should fail to load invalid YAML config"
     );
 }
+
+#[test]
+fn test_discover_config_file_for_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_a = temp_dir.path().join("a");
+    let root_b = temp_dir.path().join("b");
+    fs::create_dir(&root_a).unwrap();
+    fs::create_dir(&root_b).unwrap();
+    fs::write(root_a.join(".yamllint"), "rules: {}\n").unwrap();
+    fs::write(root_b.join(".yamllint"), "rules: {}\n").unwrap();
+    fs::write(root_a.join("file.yaml"), "key: value\n").unwrap();
+
+    // Directories search from themselves, files from their parent
+    let for_dir = discover_config_file_for_path(&root_b).unwrap();
+    let for_file = discover_config_file_for_path(root_a.join("file.yaml")).unwrap();
+    assert_eq!(for_dir, root_b.canonicalize().unwrap().join(".yamllint"));
+    assert_eq!(for_file, root_a.canonicalize().unwrap().join(".yamllint"));
+}
+
+#[test]
+fn test_load_config_merges_fragments_in_lexical_order() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join(".yamllint");
+    fs::write(
+        &config_path,
+        "extends: default\nrules:\n  line-length:\n    max: 120\n    allow-non-breakable-words: false\n",
+    )
+    .unwrap();
+    let fragments = temp_dir.path().join(".yamllint.d");
+    fs::create_dir(&fragments).unwrap();
+    fs::write(
+        fragments.join("20-team.yaml"),
+        "rules:\n  line-length:\n    max: 90\n    level: warning\nignore: |\n  vendor/\n",
+    )
+    .unwrap();
+    fs::write(
+        fragments.join("10-base.yaml"),
+        "rules:\n  line-length:\n    max: 100\n",
+    )
+    .unwrap();
+    fs::write(fragments.join("README.md"), "not a fragment").unwrap();
+
+    let config = load_config(&config_path).unwrap();
+    assert!(config.is_file_ignored(
+        &temp_dir.path().join("vendor/a.yaml"),
+        Some(temp_dir.path())
+    ));
+
+    // 20-team.yaml wins over 10-base.yaml; options it leaves out keep the base value
+    let long_word = format!("---\nkey: {}\n", "x".repeat(90));
+    let issues: Vec<_> = yamllint_rs::linter::Linter::with_config(config)
+        .check(&long_word, "a.yaml")
+        .into_iter()
+        .filter(|(_, rule)| rule == "line-length")
+        .map(|(issue, _)| (issue.message, issue.severity))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![(
+            "line too long (95 > 90 characters)".to_string(),
+            yamllint_rs::Severity::Warning
+        )]
+    );
+}
+
+#[test]
+fn test_invalid_config_fragment_names_the_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join(".yamllint");
+    fs::write(&config_path, "extends: default\n").unwrap();
+    fs::create_dir(temp_dir.path().join(".yamllint.d")).unwrap();
+    fs::write(
+        temp_dir.path().join(".yamllint.d/bad.yaml"),
+        "extends: relaxed\n",
+    )
+    .unwrap();
+
+    let error = format!("{:#}", load_config(&config_path).unwrap_err());
+    assert!(error.contains("bad.yaml"), "{}", error);
+    assert!(error.contains("unsupported key `extends`"), "{}", error);
+}
+
+#[test]
+fn test_load_config_follows_extends_chain() {
+    let temp_dir = TempDir::new().unwrap();
+    let shared = temp_dir.path().join("shared");
+    fs::create_dir(&shared).unwrap();
+    fs::write(
+        shared.join("org.yaml"),
+        "extends: default\nignore: |\n  vendor/\nrules:\n  line-length:\n    max: 100\n    allow-non-breakable-words: false\n    level: warning\n  document-start: disable\n",
+    )
+    .unwrap();
+    // Paths are relative to the file that names them
+    fs::write(
+        shared.join("team.yaml"),
+        "extends: ./org.yaml\nrules:\n  line-length:\n    max: 90\n",
+    )
+    .unwrap();
+    let config_path = temp_dir.path().join(".yamllint");
+    fs::write(
+        &config_path,
+        "extends: shared/team.yaml\nrules:\n  document-start: enable\n  trailing-spaces: disable\n",
+    )
+    .unwrap();
+
+    let config = load_config(&config_path).unwrap();
+    assert!(config.is_file_ignored(
+        &temp_dir.path().join("vendor/a.yaml"),
+        Some(temp_dir.path())
+    ));
+    assert!(config.is_rule_enabled("document-start"));
+    assert!(!config.is_rule_enabled("trailing-spaces"));
+
+    // max comes from team.yaml; the level and the other option from org.yaml
+    let long_word = format!("---\nkey: {}\n", "x".repeat(90));
+    let issues: Vec<_> = yamllint_rs::linter::Linter::with_config(config)
+        .check(&long_word, "a.yaml")
+        .into_iter()
+        .filter(|(_, rule)| rule == "line-length")
+        .map(|(issue, _)| (issue.message, issue.severity))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![(
+            "line too long (95 > 90 characters)".to_string(),
+            yamllint_rs::Severity::Warning
+        )]
+    );
+}
+
+#[test]
+fn test_rule_mapping_enables_the_rule() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("base.yaml"),
+        "extends: default\nrules:\n  document-start: disable\n",
+    )
+    .unwrap();
+    let config_path = temp_dir.path().join(".yamllint");
+    fs::write(
+        &config_path,
+        "extends: base.yaml\nrules:\n  document-start:\n    present: true\n  secrets:\n    level: error\n  non-ascii:\n    enable: false\n    invisible-only: true\n",
+    )
+    .unwrap();
+
+    // Like yamllint, a mapping enables opt-in rules and rules disabled by
+    // the extended config, unless it says otherwise
+    let config = load_config(&config_path).unwrap();
+    assert!(config.is_rule_enabled("document-start"));
+    assert!(config.is_rule_enabled("secrets"));
+    assert!(!config.is_rule_enabled("non-ascii"));
+}
+
+#[test]
+fn test_extends_cycles_and_missing_files_are_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let a = temp_dir.path().join("a.yaml");
+    let b = temp_dir.path().join("b.yaml");
+    fs::write(&a, "extends: b.yaml\n").unwrap();
+    fs::write(&b, "extends: a.yaml\n").unwrap();
+    let error = format!("{:#}", load_config(&a).unwrap_err());
+    assert!(error.contains("Config extends itself"), "{}", error);
+
+    fs::write(&a, "extends: missing.yaml\n").unwrap();
+    let error = format!("{:#}", load_config(&a).unwrap_err());
+    assert!(
+        error.contains("Cannot read extended config") && error.contains("missing.yaml"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_config_file_env_sits_between_cli_and_discovery() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join(".yamllint"), "extends: default\n").unwrap();
+    fs::write(temp_dir.path().join("a.yaml"), "---\nkey: value   \n").unwrap();
+    let env_config = temp_dir.path().join("ci.yaml");
+    fs::write(
+        &env_config,
+        "extends: default\nrules:\n  trailing-spaces: disable\n",
+    )
+    .unwrap();
+    let lint = |args: &[&str], env: Option<&std::path::Path>| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .env_remove("YAMLLINT_CONFIG_FILE")
+            .args(["--no-progress", "-f", "standard"])
+            .args(args)
+            .arg("a.yaml");
+        if let Some(env) = env {
+            cmd.env("YAMLLINT_CONFIG_FILE", env);
+        }
+        cmd.output().unwrap().status.code()
+    };
+
+    // The discovered .yamllint reports the trailing spaces
+    assert_eq!(lint(&[], None), Some(1));
+    // The environment config wins over discovery
+    assert_eq!(lint(&[], Some(&env_config)), Some(0));
+    // -c and -d win over the environment
+    assert_eq!(lint(&["-c", ".yamllint"], Some(&env_config)), Some(1));
+    assert_eq!(
+        lint(&["-d", "extends: default"], Some(&env_config)),
+        Some(1)
+    );
+    // A missing environment config is an error rather than silently skipped
+    assert_eq!(
+        lint(&[], Some(&temp_dir.path().join("missing.yaml"))),
+        Some(255)
+    );
+}
+
+#[test]
+fn test_user_config_is_the_last_fallback() {
+    let home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(home.path().join("xdg/yamllint")).unwrap();
+    fs::write(
+        home.path().join("xdg/yamllint/config"),
+        "extends: default\nrules:\n  trailing-spaces: disable\n",
+    )
+    .unwrap();
+    fs::write(project.path().join("a.yaml"), "---\nkey: value   \n").unwrap();
+    let lint = |xdg: bool| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(project.path())
+            .env_remove("YAMLLINT_CONFIG_FILE")
+            .env("HOME", home.path())
+            .args(["--no-progress", "a.yaml"]);
+        match xdg {
+            true => cmd.env("XDG_CONFIG_HOME", home.path().join("xdg")),
+            false => cmd.env_remove("XDG_CONFIG_HOME"),
+        };
+        cmd.output().unwrap().status.code()
+    };
+
+    // Found through XDG_CONFIG_HOME, or not at all without it
+    assert_eq!(lint(true), Some(0));
+    assert_eq!(lint(false), Some(1));
+
+    // ~/.config is the default XDG location
+    fs::rename(home.path().join("xdg"), home.path().join(".config")).unwrap();
+    assert_eq!(lint(false), Some(0));
+
+    // A project config wins over the user's
+    fs::write(project.path().join(".yamllint"), "extends: default\n").unwrap();
+    assert_eq!(lint(false), Some(1));
+}
+
+#[test]
+fn test_nested_configs_refine_parent_configs() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("svc/deep")).unwrap();
+    fs::create_dir_all(root.join("other")).unwrap();
+    fs::write(
+        root.join(".yamllint"),
+        "extends: default\nrules:\n  line-length:\n    max: 20\n  trailing-spaces: disable\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("svc/.yamllint"),
+        "rules:\n  line-length:\n    max: 40\nignore: |\n  generated.yaml\n",
+    )
+    .unwrap();
+    let content = "---\nkey: value value value value   \n";
+    for file in [
+        "a.yaml",
+        "other/a.yaml",
+        "svc/a.yaml",
+        "svc/deep/a.yaml",
+        "svc/generated.yaml",
+    ] {
+        fs::write(root.join(file), content).unwrap();
+    }
+    let lint = |args: &[&str]| {
+        let output = assert_cmd::Command::cargo_bin("yamllint-rs")
+            .unwrap()
+            .current_dir(root)
+            .env_remove("YAMLLINT_CONFIG_FILE")
+            .arg("--no-progress")
+            .args(args)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // svc/ raises line-length.max and keeps the root's trailing-spaces: disable
+    let stdout = lint(&["."]);
+    assert!(stdout.contains("other/a.yaml"), "{}", stdout);
+    assert!(!stdout.contains("svc/"), "{}", stdout);
+    assert!(!stdout.contains("trailing-spaces"), "{}", stdout);
+
+    // Linting inside svc/ gives the same result, and its ignore is relative to it
+    assert!(!lint(&["svc"]).contains("svc/"));
+    let listed = lint(&["--list-files", "."]);
+    assert!(listed.contains("svc/deep/a.yaml"), "{}", listed);
+    assert!(!listed.contains("generated.yaml"), "{}", listed);
+
+    // `root: true` stops the refinement, and -c uses one file as written
+    fs::write(
+        root.join("svc/.yamllint"),
+        "root: true\nextends: default\nrules:\n  line-length:\n    max: 40\n",
+    )
+    .unwrap();
+    assert!(lint(&["svc"]).contains("trailing-spaces"));
+    let stdout = lint(&["-c", ".yamllint", "."]);
+    assert!(stdout.contains("svc/a.yaml"), "{}", stdout);
+}
+
+#[test]
+fn test_toml_configs_are_discovered_and_loaded() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("svc")).unwrap();
+    fs::create_dir_all(root.join("app")).unwrap();
+
+    // A pyproject.toml without the table is not a config
+    fs::write(root.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    assert_eq!(discover_config_file_from_dir(root.to_path_buf()), None);
+
+    fs::write(
+        root.join("pyproject.toml"),
+        "[project]\nname = \"x\"\n\n[tool.yamllint-rs]\nextends = \"default\"\n\n\
+         [tool.yamllint-rs.rules.line-length]\nmax = 100\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("svc/.yamllint.toml"),
+        "extends = \"../pyproject.toml\"\n\n[rules]\ndocument-start = \"disable\"\n",
+    )
+    .unwrap();
+
+    let found = discover_config_file_for_path(root.join("app/a.yaml")).unwrap();
+    assert!(found.ends_with("pyproject.toml"), "{}", found.display());
+    let config = load_config(&found).unwrap();
+    assert!(config.is_rule_enabled("document-start"));
+
+    let found = discover_config_file_for_path(root.join("svc/a.yaml")).unwrap();
+    assert!(found.ends_with("svc/.yamllint.toml"), "{}", found.display());
+    let config = load_config(&found).unwrap();
+    assert!(!config.is_rule_enabled("document-start"));
+    let line_length: yamllint_rs::rules::line_length::LineLengthConfig =
+        config.rule_options("line-length").unwrap();
+    assert_eq!(line_length.max_length, 100);
+
+    // A .yamllint wins over TOML configs in the same directory
+    fs::write(root.join("svc/.yamllint"), "extends: default\n").unwrap();
+    let found = discover_config_file_for_path(root.join("svc/a.yaml")).unwrap();
+    assert!(found.ends_with("svc/.yamllint"), "{}", found.display());
+}
+
+#[test]
+fn test_json_configs_are_discovered_and_loaded() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(
+        root.join(".yamllint.json"),
+        r#"{"extends": "default", "rules": {"document-start": "disable", "line-length": {"max": 100}}}"#,
+    )
+    .unwrap();
+    fs::write(root.join("a.yaml"), "key: value\n").unwrap();
+
+    let found = discover_config_file_for_path(root.join("a.yaml")).unwrap();
+    assert!(found.ends_with(".yamllint.json"), "{}", found.display());
+    let config = load_config(&found).unwrap();
+    assert!(!config.is_rule_enabled("document-start"));
+    let line_length: yamllint_rs::rules::line_length::LineLengthConfig =
+        config.rule_options("line-length").unwrap();
+    assert_eq!(line_length.max_length, 100);
+
+    // Any .json file can be given explicitly, and JSON errors are reported
+    fs::write(root.join("config.json"), r#"{"extends": "relaxed",}"#).unwrap();
+    assert!(load_config(root.join("config.json")).is_err());
+
+    assert_cmd::Command::cargo_bin("yamllint-rs")
+        .unwrap()
+        .current_dir(root)
+        .env_remove("YAMLLINT_CONFIG_FILE")
+        .args(["--no-progress", "a.yaml"])
+        .assert()
+        .code(0);
+}
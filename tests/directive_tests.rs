@@ -12,6 +12,7 @@ mod tests {
             show_progress: false,
             verbose: false,
             output_format: OutputFormat::Standard,
+            ..Default::default()
         };
         FileProcessor::with_default_rules(options)
     }
@@ -398,4 +399,84 @@ normal: line
             issues_in_range
         );
     }
+
+    #[test]
+    fn test_configure_directive_changes_options_until_reset() {
+        let wide = "x".repeat(95);
+        let content = format!(
+            "---\nk1: {wide}\n# yamllint configure rule:line-length max=120\nk2: {wide}\n\
+             k3: {}\n# yamllint configure rule:line-length reset\nk4: {wide}\n",
+            "y".repeat(125)
+        );
+
+        let temp_file = write_temp_file(&content);
+        let processor = create_processor();
+        let result = processor.process_file(temp_file.path()).unwrap();
+
+        let line_length: Vec<(usize, &str)> = result
+            .issues
+            .iter()
+            .filter(|(_, rule)| rule == "line-length")
+            .map(|(issue, _)| (issue.line, issue.message.as_str()))
+            .collect();
+        assert_eq!(
+            line_length,
+            vec![
+                (2, "line too long (99 > 80 characters)"),
+                (5, "line too long (129 > 120 characters)"),
+                (7, "line too long (99 > 80 characters)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_configure_directives_in_the_header_apply_to_the_whole_file() {
+        let wide = "x".repeat(95);
+        let content = format!(
+            "# yamllint configure rule:line-length max=120\n---\nk1: {wide}\n\
+             # yamllint configure rule:line-length max=90\nk2: {wide}\n\
+             # yamllint configure rule:line-length reset\nk3: {wide}\n"
+        );
+
+        let temp_file = write_temp_file(&content);
+        let processor = create_processor();
+        let result = processor.process_file(temp_file.path()).unwrap();
+
+        // A reset returns to the options the header set
+        let line_length: Vec<(usize, &str)> = result
+            .issues
+            .iter()
+            .filter(|(_, rule)| rule == "line-length")
+            .map(|(issue, _)| (issue.line, issue.message.as_str()))
+            .collect();
+        assert_eq!(line_length, vec![(5, "line too long (99 > 90 characters)")]);
+    }
+
+    #[test]
+    fn test_configure_directives_accumulate_and_malformed_ones_are_reported() {
+        let content = "---\n\
+                       # yamllint configure rule:line-length max=100\n\
+                       # yamllint configure rule:line-length level=warning\n\
+                       key: {}\n\
+                       # yamllint configure rule:line-length max\n\
+                       # yamllint configuration lives in .yamllint\n";
+        let content = content.replace("{}", &"z".repeat(100));
+
+        let temp_file = write_temp_file(&content);
+        let processor = create_processor();
+        let result = processor.process_file(temp_file.path()).unwrap();
+
+        let issues: Vec<(usize, &str, String)> = result
+            .issues
+            .iter()
+            .map(|(issue, rule)| (issue.line, rule.as_str(), issue.severity.to_string()))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (4, "line-length", "warning".to_string()),
+                (5, "directive-syntax", "warning".to_string()),
+            ]
+        );
+    }
 }
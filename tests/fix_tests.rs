@@ -207,3 +207,223 @@ fn test_rule_fix_capabilities() {
         .success()
         .stdout(predicate::str::contains("Fixed"));
 }
+
+/// Test that fixing many files in parallel prints one ordered summary after
+/// the formatted remaining issues
+#[test]
+fn test_fix_parallel_summary_is_ordered() {
+    let temp_dir = TempDir::new().unwrap();
+    for name in ["d", "b", "e", "a", "c"] {
+        fs::write(
+            temp_dir.path().join(format!("{}.yaml", name)),
+            "---\nkey: value   \n",
+        )
+        .unwrap();
+    }
+    fs::write(
+        temp_dir.path().join("long.yaml"),
+        format!("---\nkey: {}\n", "x".repeat(90)),
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--fix", "--format", "standard", "--no-progress"])
+        .arg(temp_dir.path());
+    let output = cmd.assert().code(1);
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+
+    let summary: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with("Fixed ") || line.starts_with("Found "))
+        .collect();
+    assert_eq!(summary.len(), 6);
+    assert!(summary[..5]
+        .iter()
+        .all(|line| line.starts_with("Fixed 1 issues")));
+    for (line, name) in summary.iter().zip(["a", "b", "c", "d", "e"]) {
+        assert!(line.contains(&format!("{}.yaml", name)), "{}", line);
+    }
+    assert!(summary[5].starts_with("Found 1 non-fixable issues"));
+
+    let issue_line = stdout.find("line too long").unwrap();
+    assert!(issue_line < stdout.find("Fixed ").unwrap());
+}
+
+#[test]
+fn test_exclude_from_fix_reports_without_fixing() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".yamllint"),
+        "extends: default\nexclude-from-fix:\n  - trailing-spaces\n",
+    )
+    .unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, "key: value   \n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--format", "standard", "--fix", "test.yaml"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("trailing spaces"));
+
+    // Other rules still fix; trailing-spaces is left alone
+    assert_eq!(
+        fs::read_to_string(&test_file).unwrap(),
+        "---\nkey: value   \n"
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .arg("rules")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    let fix_column = |rule: &str| {
+        stdout
+            .lines()
+            .find(|line| line.starts_with(&format!("{} ", rule)))
+            .and_then(|line| line.split_whitespace().nth(2))
+            .map(str::to_string)
+    };
+    assert_eq!(fix_column("trailing-spaces").as_deref(), Some("off"));
+    assert_eq!(fix_column("document-start").as_deref(), Some("yes"));
+}
+
+#[test]
+fn test_exclude_from_fix_rejects_unknown_rules() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, "---\nkey: value\n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["-d", "{extends: default, exclude-from-fix: [no-such-rule]}"])
+        .arg(&test_file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown rule 'no-such-rule' in exclude-from-fix",
+        ));
+}
+
+#[test]
+fn test_fix_honors_configure_directives_in_the_file_header() {
+    let temp_dir = TempDir::new().unwrap();
+    let configured = temp_dir.path().join("configured.yaml");
+    let content = "# yamllint configure rule:document-start present=false\n\
+                   # yamllint configure rule:truthy allowed-values=['yes','no']\n\
+                   key: yes\n";
+    fs::write(&configured, content).unwrap();
+    let plain = temp_dir.path().join("plain.yaml");
+    fs::write(&plain, "key: value\n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--fix", "--no-progress", "configured.yaml", "plain.yaml"])
+        .assert()
+        .success();
+
+    // The header's options apply to the whole file, fixes included
+    assert_eq!(fs::read_to_string(&configured).unwrap(), content);
+    assert_eq!(fs::read_to_string(&plain).unwrap(), "---\nkey: value\n");
+}
+
+/// Test that the opt-in norway-problem rule quotes coerced scalars before
+/// truthy can rewrite them, and leaves lines disabled by a directive alone
+#[test]
+fn test_fix_norway_problem_quotes_values() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    let config_file = temp_dir.path().join("config.yaml");
+
+    fs::write(
+        &test_file,
+        "---\ncountry: NO\nduration: 1:30\nlegacy: 1:30  # yamllint disable-line rule:norway-problem\n",
+    )
+    .unwrap();
+    fs::write(
+        &config_file,
+        "extends: default\nrules:\n  norway-problem: enable\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg("--fix")
+        .arg(test_file.to_str().unwrap());
+    cmd.assert().success();
+
+    let fixed_content = fs::read_to_string(&test_file).unwrap();
+    assert_eq!(
+        fixed_content,
+        "---\ncountry: \"NO\"\nduration: \"1:30\"\nlegacy: 1:30  # yamllint disable-line rule:norway-problem\n"
+    );
+}
+
+/// Test that the non-ascii fix runs before trailing-spaces, so an invisible
+/// space at a line end is removed entirely
+#[test]
+fn test_fix_non_ascii_invisible_characters() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    let config_file = temp_dir.path().join("config.yaml");
+
+    fs::write(
+        &test_file,
+        "---\nkey: value\u{00A0}\ntoken: ab\u{200B}c\nname: café\n",
+    )
+    .unwrap();
+    fs::write(
+        &config_file,
+        "extends: default\nrules:\n  non-ascii:\n    invisible-only: true\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg("--fix")
+        .arg(test_file.to_str().unwrap());
+    cmd.assert().success();
+
+    let fixed_content = fs::read_to_string(&test_file).unwrap();
+    assert_eq!(fixed_content, "---\nkey: value\ntoken: abc\nname: café\n");
+}
+
+/// Test that the sequence-ordering fix only reorders sequences under the
+/// configured paths
+#[test]
+fn test_fix_sequence_ordering_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    let config_file = temp_dir.path().join("config.yaml");
+
+    fs::write(
+        &test_file,
+        "---\ndependencies:\n  - requests\n  - flask\nauthors:\n  - Zoe\n  - Adam\n",
+    )
+    .unwrap();
+    fs::write(
+        &config_file,
+        "extends: default\nrules:\n  sequence-ordering:\n    paths: [dependencies]\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg("--fix")
+        .arg(test_file.to_str().unwrap());
+    cmd.assert().success();
+
+    let fixed_content = fs::read_to_string(&test_file).unwrap();
+    assert_eq!(
+        fixed_content,
+        "---\ndependencies:\n  - flask\n  - requests\nauthors:\n  - Zoe\n  - Adam\n"
+    );
+}
@@ -49,6 +49,7 @@ fn test_gitignore_respect() {
         verbose: false,
         output_format: yamllint_rs::OutputFormat::Standard,
         show_progress: false,
+        ..Default::default()
     };
 
     let processor = FileProcessor::with_default_rules(options);
@@ -105,6 +106,7 @@ fn test_gitignore_nested_patterns() {
         verbose: false,
         output_format: yamllint_rs::OutputFormat::Standard,
         show_progress: false,
+        ..Default::default()
     };
 
     let processor = FileProcessor::with_default_rules(options);
@@ -135,6 +137,7 @@ fn test_no_gitignore_file() {
         verbose: false,
         output_format: yamllint_rs::OutputFormat::Standard,
         show_progress: false,
+        ..Default::default()
     };
 
     let processor = FileProcessor::with_default_rules(options);
@@ -143,3 +146,107 @@ fn test_no_gitignore_file() {
     let result = processor.process_directory(temp_path);
     assert!(result.is_ok(), "Directory processing should succeed");
 }
+
+#[test]
+fn test_no_ignore_lints_ignored_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path();
+
+    // .gitignore files only apply inside a git repository
+    std::process::Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(temp_path)
+        .status()
+        .unwrap();
+    fs::write(temp_path.join(".gitignore"), "generated/\n").unwrap();
+    fs::write(
+        temp_path.join(".yamllint"),
+        "extends: default\nignore: |\n  vendor.yaml\n",
+    )
+    .unwrap();
+    fs::create_dir(temp_path.join("generated")).unwrap();
+    fs::write(temp_path.join("generated/out.yaml"), "---\nkey: value   \n").unwrap();
+    fs::write(temp_path.join("vendor.yaml"), "---\nkey: value   \n").unwrap();
+
+    let list_files = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(temp_path)
+            .args(["--list-files"])
+            .args(args)
+            .arg(".");
+        let output = cmd.assert().success().get_output().stdout.clone();
+        String::from_utf8(output).unwrap()
+    };
+    assert!(list_files(&[]).trim().is_empty());
+    let listed = list_files(&["--no-ignore"]);
+    assert!(listed.contains("out.yaml"));
+    assert!(listed.contains("vendor.yaml"));
+
+    // Explicitly named files are skipped when the config ignores them, unless --no-ignore
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_path)
+        .args(["--format", "standard", "vendor.yaml"])
+        .assert()
+        .success();
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_path)
+        .args(["--format", "standard", "--no-ignore", "vendor.yaml"])
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("trailing spaces"));
+}
+
+#[test]
+fn test_no_gitignore_and_hidden_flags() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path();
+
+    std::process::Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(temp_path)
+        .status()
+        .unwrap();
+    fs::write(temp_path.join(".gitignore"), "generated/\n").unwrap();
+    fs::write(
+        temp_path.join(".yamllint"),
+        "extends: default\nignore: |\n  vendor.yaml\n",
+    )
+    .unwrap();
+    fs::create_dir(temp_path.join("generated")).unwrap();
+    fs::write(temp_path.join("generated/out.yaml"), "---\nkey: value\n").unwrap();
+    fs::write(temp_path.join("vendor.yaml"), "---\nkey: value\n").unwrap();
+    fs::create_dir_all(temp_path.join(".github/workflows")).unwrap();
+    fs::write(
+        temp_path.join(".github/workflows/ci.yml"),
+        "---\non: push\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_path.join(".pre-commit-config.yaml"),
+        "---\nrepos: []\n",
+    )
+    .unwrap();
+
+    let list_files = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(temp_path)
+            .args(["--list-files"])
+            .args(args)
+            .arg(".");
+        let output = cmd.assert().success().get_output().stdout.clone();
+        String::from_utf8(output).unwrap()
+    };
+    assert!(list_files(&[]).trim().is_empty());
+
+    // --no-gitignore keeps config `ignore` patterns
+    let listed = list_files(&["--no-gitignore"]);
+    assert!(listed.contains("out.yaml"));
+    assert!(!listed.contains("vendor.yaml"));
+    assert!(!listed.contains("ci.yml"));
+
+    let listed = list_files(&["--hidden"]);
+    assert!(listed.contains("ci.yml"));
+    assert!(listed.contains(".pre-commit-config.yaml"));
+    assert!(!listed.contains("out.yaml"));
+    assert!(!listed.contains(".git/"));
+}
@@ -45,7 +45,7 @@ rules:
     enabled: true
     severity: Error
     settings:
-      max_length: 100
+      max_length: 60
 "#;
     fs::write(&config_file, config_content).unwrap();
 
@@ -56,7 +56,7 @@ rules:
 
     cmd.assert()
         .code(1)
-        .stdout(predicate::str::contains("error"));
+        .stdout(predicate::str::contains("(86 > 60 characters)"));
 }
 
 #[test]
@@ -76,7 +76,7 @@ rules:
     enabled: true
     severity: Error
     settings:
-      max_length: 100
+      max_length: 60
 "#;
     fs::write(&config_file, config_content).unwrap();
 
@@ -87,7 +87,7 @@ rules:
 
     cmd.assert()
         .code(1)
-        .stdout(predicate::str::contains("error"));
+        .stdout(predicate::str::contains("(86 > 60 characters)"));
 }
 
 #[test]
@@ -265,7 +265,10 @@ rules:
     fs::write(&config_file, config_content).unwrap();
 
     let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
-    cmd.current_dir(temp_dir.path()).arg("-r").arg(".");
+    cmd.current_dir(temp_dir.path())
+        .arg("--strict")
+        .arg("-r")
+        .arg(".");
 
     let output = cmd.assert().code(1);
     let stdout = String::from_utf8_lossy(&output.get_output().stdout);
@@ -302,7 +305,10 @@ rules:
     fs::write(&config_file, config_content).unwrap();
 
     let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
-    cmd.current_dir(temp_dir.path()).arg("-r").arg(".");
+    cmd.current_dir(temp_dir.path())
+        .arg("--strict")
+        .arg("-r")
+        .arg(".");
 
     let output = cmd.assert().code(1);
     let stdout = String::from_utf8_lossy(&output.get_output().stdout);
@@ -316,3 +322,1062 @@ rules:
         "Files not in ignore list should still be processed"
     );
 }
+
+#[test]
+fn test_ignore_pattern_flag_merges_with_config() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("build/out")).unwrap();
+    let content = "---\nkey: value   \n";
+    for file in [
+        "config_ignored.yaml",
+        "app.generated.yaml",
+        "build/out/artifact.yaml",
+        "normal.yaml",
+    ] {
+        fs::write(temp_dir.path().join(file), content).unwrap();
+    }
+    fs::write(
+        temp_dir.path().join(".yamllint"),
+        "extends: default\nignore: |\n  config_ignored.yaml\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--list-files", "--ignore-pattern", "*.generated.yaml"])
+        .args(["--ignore-pattern", "bu*/", "."]);
+    let output = cmd.assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+
+    assert_eq!(stdout.trim(), "./normal.yaml");
+}
+
+#[test]
+fn test_multiple_reporters() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    let sarif_file = temp_dir.path().join("out.sarif");
+
+    fs::write(&test_file, "---\nkey: value   \n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .arg("--reporter")
+        .arg("standard")
+        .arg("--reporter")
+        .arg(format!("sarif:{}", sarif_file.display()))
+        .arg("test.yaml");
+
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("trailing-spaces"));
+
+    let sarif: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&sarif_file).unwrap()).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    assert_eq!(sarif["runs"][0]["results"][0]["ruleId"], "trailing-spaces");
+}
+
+#[test]
+fn test_unknown_reporter_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, "---\nkey: value\n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("--reporter")
+        .arg("xml")
+        .arg(test_file.to_str().unwrap());
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown reporter"));
+}
+
+#[test]
+fn test_per_root_configs_merge_into_one_report() {
+    let temp_dir = TempDir::new().unwrap();
+    let strict = temp_dir.path().join("strict");
+    let relaxed = temp_dir.path().join("relaxed");
+    fs::create_dir(&strict).unwrap();
+    fs::create_dir(&relaxed).unwrap();
+
+    fs::write(strict.join("a.yaml"), "---\nkey: value   \n").unwrap();
+    fs::write(relaxed.join("b.yaml"), "---\nkey: value   \n").unwrap();
+    fs::write(
+        relaxed.join(".yamllint"),
+        "rules:\n  trailing-spaces: disable\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .arg("--format")
+        .arg("standard")
+        .arg("strict")
+        .arg("relaxed");
+
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("a.yaml"))
+        .stdout(predicate::str::contains("b.yaml").not());
+
+    // The relaxed root alone is clean under its own config
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path()).arg("relaxed");
+    cmd.assert().success();
+}
+
+#[test]
+fn test_lint_stdin() {
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("--format")
+        .arg("standard")
+        .arg("-")
+        .write_stdin("---\nkey: value   \n");
+
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("stdin"))
+        .stdout(predicate::str::contains("trailing spaces"));
+}
+
+#[test]
+fn test_lint_stdin_with_filename_uses_its_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    fs::create_dir(&project).unwrap();
+    fs::write(
+        project.join(".yamllint"),
+        "rules:\n  trailing-spaces: disable\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .arg("--format")
+        .arg("standard")
+        .arg("--stdin-filename")
+        .arg("project/unsaved.yaml")
+        .arg("-")
+        .write_stdin("---\nkey: value   \nother:  value\n");
+
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("project/unsaved.yaml"))
+        .stdout(predicate::str::contains("colons"))
+        .stdout(predicate::str::contains("trailing spaces").not());
+}
+
+#[test]
+fn test_lint_stdin_rejects_fix() {
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("--fix").arg("-").write_stdin("key: value\n");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--fix cannot be used"));
+}
+
+#[test]
+fn test_list_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let sub_dir = temp_dir.path().join("sub");
+    fs::create_dir(&sub_dir).unwrap();
+    fs::write(temp_dir.path().join("a.yaml"), "key: value   \n").unwrap();
+    fs::write(sub_dir.join("b.yml"), "key: value\n").unwrap();
+    fs::write(sub_dir.join("skipped.yaml"), "key: value\n").unwrap();
+    fs::write(temp_dir.path().join("notes.txt"), "text\n").unwrap();
+    fs::write(
+        temp_dir.path().join(".yamllint"),
+        "extends: default\nignore: |\n  skipped.yaml\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .arg("--list-files")
+        .arg(".");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("a.yaml"))
+        .stdout(predicate::str::contains("b.yml"))
+        .stdout(predicate::str::contains("skipped.yaml").not())
+        .stdout(predicate::str::contains("notes.txt").not())
+        .stdout(predicate::str::contains("trailing").not());
+}
+
+#[test]
+fn test_conflicting_config_flags_are_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, "---\nkey: value\n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg("a.yaml")
+        .arg("-C")
+        .arg("b.yaml")
+        .arg(test_file.to_str().unwrap());
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used multiple times"));
+}
+
+#[test]
+fn test_invalid_flag_combinations_are_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, "---\nkey: value\n").unwrap();
+    let test_file = test_file.to_str().unwrap();
+
+    let cases: [(&[&str], &str); 3] = [
+        (&["--format", "sarif"], "use --reporter sarif"),
+        (&["--list-files", "--fix"], "cannot be used with"),
+        (&["--stdin-filename", "a.yaml"], "requires `-`"),
+    ];
+
+    for (args, expected) in cases {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.args(args).arg(test_file);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains(expected));
+    }
+}
+
+#[test]
+fn test_exit_codes_follow_yamllint() {
+    let temp_dir = TempDir::new().unwrap();
+    let warning_file = temp_dir.path().join("warning.yaml");
+    let error_file = temp_dir.path().join("error.yaml");
+
+    // document-start is a warning, trailing-spaces an error
+    fs::write(&warning_file, "key: value\n").unwrap();
+    fs::write(&error_file, "---\nkey: value   \n").unwrap();
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.args(args).assert()
+    };
+    let warning = warning_file.to_str().unwrap();
+    let error = error_file.to_str().unwrap();
+
+    run(&[warning]).code(0);
+    run(&["--strict", warning]).code(2);
+    run(&[error]).code(1);
+    run(&["--strict", warning, error]).code(1);
+    run(&["--config", "missing-config.yaml", warning]).code(255);
+}
+
+#[test]
+fn test_unreadable_file_is_reported_and_the_rest_linted() {
+    let temp_dir = TempDir::new().unwrap();
+    let error_file = temp_dir.path().join("error.yaml");
+    let missing_file = temp_dir.path().join("missing.yaml");
+    fs::write(&error_file, "---\nkey: value   \n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--format", "standard", "--fail-on", "never"])
+        .arg(&missing_file)
+        .arg(&error_file)
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains("cannot read file"))
+        .stdout(predicate::str::contains("(file-error)"))
+        .stdout(predicate::str::contains("trailing spaces"))
+        .stderr(predicate::str::contains("1 file(s) could not be read"));
+}
+
+#[test]
+fn test_fail_on_sets_exit_code_threshold() {
+    let temp_dir = TempDir::new().unwrap();
+    let warning_file = temp_dir.path().join("warning.yaml");
+    let error_file = temp_dir.path().join("error.yaml");
+    let info_file = temp_dir.path().join("info.yaml");
+
+    fs::write(&warning_file, "key: value\n").unwrap();
+    fs::write(&error_file, "---\nkey: value   \n").unwrap();
+    fs::write(&info_file, "---\nkey: value   \n").unwrap();
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.args(args).assert()
+    };
+    let warning = warning_file.to_str().unwrap();
+    let error = error_file.to_str().unwrap();
+    let info = info_file.to_str().unwrap();
+    let as_info = "trailing-spaces.level=info";
+
+    run(&["--fail-on", "error", warning]).code(0);
+    run(&["--fail-on", "warning", warning]).code(2);
+    run(&["--fail-on", "warning", "--set", as_info, info]).code(0);
+    run(&["--fail-on", "info", "--set", as_info, info]).code(2);
+    run(&["--fail-on", "info", warning, error]).code(1);
+
+    // Issues are still printed when they do not affect the exit code
+    run(&["--fail-on", "never", "--format", "standard", warning, error])
+        .code(0)
+        .stdout(predicate::str::contains("trailing spaces"));
+
+    run(&["--fail-on", "warning", "--strict", warning])
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_no_warnings_hides_warnings_and_ignores_them_for_exit_code() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+
+    // document-start is a warning, trailing-spaces an error
+    fs::write(&test_file, "key: value   \n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("--format")
+        .arg("standard")
+        .arg("--no-warnings")
+        .arg(test_file.to_str().unwrap());
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("trailing-spaces"))
+        .stdout(predicate::str::contains("document-start").not());
+
+    fs::write(&test_file, "key: value\n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("--strict")
+        .arg("--no-warnings")
+        .arg(test_file.to_str().unwrap());
+    cmd.assert().success().stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn test_cli_rule_selection() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, "key:  value   \n").unwrap();
+    let test_file = test_file.to_str().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args([
+        "--format",
+        "standard",
+        "--disable",
+        "trailing-spaces,colons",
+    ])
+    .arg(test_file);
+    cmd.assert()
+        .stdout(predicate::str::contains("document-start"))
+        .stdout(predicate::str::contains("trailing-spaces").not())
+        .stdout(predicate::str::contains("(colons)").not());
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--format", "standard", "--enable-only", "colons"])
+        .arg(test_file);
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("(colons)"))
+        .stdout(predicate::str::contains("document-start").not())
+        .stdout(predicate::str::contains("trailing-spaces").not());
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--disable", "line-lenght"]).arg(test_file);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown rule: line-lenght"));
+}
+
+#[test]
+fn test_json_reports_block_scalar_start_line() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(
+        &test_file,
+        "---\nscript: |\n  echo one\n  echo two   \nkey: value   \n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--reporter", "json"]).arg(&test_file);
+    let output = cmd.assert().code(1);
+
+    let report: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    let in_scalar = issues.iter().find(|i| i["line"] == 4).unwrap();
+    assert_eq!(in_scalar["scalar_start_line"], 2);
+    let outside = issues.iter().find(|i| i["line"] == 5).unwrap();
+    assert!(outside.get("scalar_start_line").is_none());
+}
+
+#[test]
+fn test_inline_config_data() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, format!("---\nkey: {}\n", "x".repeat(100))).unwrap();
+    // A discovered config must not be used when -d is given
+    fs::write(
+        temp_dir.path().join(".yamllint"),
+        "extends: default\nrules:\n  line-length:\n    max: 20\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--format", "standard", "-d"])
+        .arg("{extends: default, rules: {line-length: {max: 120}}}")
+        .arg("test.yaml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("line-length").not());
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--format", "standard", "-d"])
+        .arg("{extends: default, rules: {line-length: {max: 80}}}")
+        .arg("test.yaml");
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("line-length"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["-d", "{extends: default}", "-c", "other.yaml"])
+        .arg(&test_file);
+    cmd.assert().failure();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["-d", "{extends: [unclosed"]).arg(&test_file);
+    cmd.assert()
+        .code(255)
+        .stderr(predicate::str::contains("Invalid inline config (-d)"));
+}
+
+#[test]
+fn test_set_rule_option() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, format!("---\nkey: {}\n", "x".repeat(100))).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--format", "standard", "--set", "line-length.max=120"])
+        .arg(&test_file);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("line-length").not());
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--set", "line-length.level=warning", "--strict"])
+        .arg(&test_file);
+    cmd.assert().code(2);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--set", "line-length=120"]).arg(&test_file);
+    cmd.assert()
+        .code(255)
+        .stderr(predicate::str::contains("expected rule.option=value"));
+}
+
+#[test]
+fn test_config_profiles() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("test.yaml"),
+        format!("---\nkey: {}\n", "x".repeat(100)),
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join(".yamllint"),
+        r#"
+extends: default
+rules:
+  line-length:
+    max: 120
+profiles:
+  ci:
+    rules:
+      line-length:
+        max: 80
+  local:
+    rules:
+      line-length: disable
+"#,
+    )
+    .unwrap();
+
+    let run = |args: &[&str], env: Option<&str>| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .env_remove("YAMLLINT_RS_PROFILE")
+            .args(["--format", "standard"])
+            .args(args)
+            .arg("test.yaml");
+        if let Some(profile) = env {
+            cmd.env("YAMLLINT_RS_PROFILE", profile);
+        }
+        cmd.assert()
+    };
+
+    run(&[], None)
+        .success()
+        .stdout(predicate::str::contains("line-length").not());
+    run(&["--profile", "ci"], None)
+        .code(1)
+        .stdout(predicate::str::contains("line-length"));
+    run(&[], Some("ci"))
+        .code(1)
+        .stdout(predicate::str::contains("line-length"));
+    run(&["--profile", "local"], Some("ci"))
+        .success()
+        .stdout(predicate::str::contains("line-length").not());
+    run(&["--profile", "nightly"], None)
+        .code(255)
+        .stderr(predicate::str::contains(
+            "Unknown profile 'nightly' (available: ci, local)",
+        ));
+}
+
+#[test]
+fn test_jobs_limits_parallelism() {
+    let temp_dir = TempDir::new().unwrap();
+    for i in 0..6 {
+        fs::write(
+            temp_dir.path().join(format!("file{}.yaml", i)),
+            "---\nkey: value   \n",
+        )
+        .unwrap();
+    }
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--format", "standard", "--jobs", "1"])
+        .arg(temp_dir.path());
+    let output = cmd.assert().code(1);
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert_eq!(stdout.matches("trailing-spaces").count(), 6);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--jobs", "0"]).arg(temp_dir.path());
+    cmd.assert().code(2);
+}
+
+#[test]
+fn test_invalid_encoding_is_reported_as_finding() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("good.yaml"), "---\nkey: value\n").unwrap();
+    fs::write(temp_dir.path().join("latin1.yaml"), b"---\nkey: caf\xe9\n").unwrap();
+    let utf16: Vec<u8> = [0xFF, 0xFE]
+        .into_iter()
+        .chain("---\nkey: value\n".bytes().flat_map(|b| [b, 0]))
+        .collect();
+    fs::write(temp_dir.path().join("utf16.yaml"), utf16).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path()).args([
+        "--reporter",
+        "json",
+        "good.yaml",
+        "latin1.yaml",
+        "utf16.yaml",
+    ]);
+    let output = cmd
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("2 file(s) could not be linted"));
+
+    let report: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 2);
+    assert!(issues.iter().all(|i| i["code"] == "invalid-encoding"));
+    let utf16_issue = issues.iter().find(|i| i["file"] == "utf16.yaml").unwrap();
+    assert!(utf16_issue["message"]
+        .as_str()
+        .unwrap()
+        .contains("UTF-16LE"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--format", "standard", "-"])
+        .write_stdin(b"key: \0\n".to_vec());
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("looks binary"));
+}
+
+#[test]
+fn test_rules_subcommand_lists_registry() {
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("rules");
+    let output = cmd.assert().success();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+
+    let registry = yamllint_rs::rules::registry::RuleRegistry::new();
+    let rule_ids = registry.get_rule_ids();
+    assert_eq!(stdout.lines().count(), rule_ids.len() + 1);
+    let trailing = stdout
+        .lines()
+        .find(|line| line.starts_with("trailing-spaces "))
+        .unwrap();
+    assert!(trailing.contains("error"));
+    assert!(trailing.contains("yes"));
+    assert!(trailing.contains("Ensures that lines do not have trailing whitespace"));
+}
+
+#[test]
+fn test_schema_subcommand_prints_report_schema() {
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["schema", "report"]);
+    let output = cmd.assert().success();
+    let schema: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, "---\nkey: value   \n").unwrap();
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--reporter", "json"]).arg(&test_file);
+    let output = cmd.assert().code(1);
+    let report: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+
+    assert_eq!(report["$schema"], schema["$id"]);
+    assert_eq!(report["version"], schema["properties"]["version"]["const"]);
+    assert_eq!(report["issues"][0]["rule"], "trailing-spaces");
+}
+
+#[test]
+fn test_modeline_overrides_rule_options() {
+    let temp_dir = TempDir::new().unwrap();
+    let long_line = format!("key: {}\n", "x".repeat(100));
+    fs::write(
+        temp_dir.path().join("wide.yaml"),
+        format!("# yamllint-rs: line-length.max=120\n---\n{}", long_line),
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("normal.yaml"),
+        format!("---\n{}", long_line),
+    )
+    .unwrap();
+
+    let lint = |config: &str| {
+        fs::write(temp_dir.path().join(".yamllint"), config).unwrap();
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .args(["--format", "standard", "wide.yaml", "normal.yaml"]);
+        let output = cmd.assert().code(1);
+        String::from_utf8(output.get_output().stdout.clone()).unwrap()
+    };
+
+    // Modelines are opt-in
+    let stdout = lint("extends: default\n");
+    assert_eq!(stdout.matches("(line-length)").count(), 2, "{}", stdout);
+
+    let stdout = lint("extends: default\nmodelines: true\n");
+    assert_eq!(stdout.matches("(line-length)").count(), 1, "{}", stdout);
+    assert!(stdout.contains("normal.yaml\n  2:81"), "{}", stdout);
+}
+
+#[test]
+fn test_rule_ignore_from_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let long_line = format!("---\nkey: {}\n", "word ".repeat(20).trim_end());
+    fs::create_dir(temp_dir.path().join("generated")).unwrap();
+    fs::write(temp_dir.path().join("generated/a.yaml"), &long_line).unwrap();
+    fs::write(temp_dir.path().join("b.yaml"), &long_line).unwrap();
+    fs::write(temp_dir.path().join("c.yaml"), &long_line).unwrap();
+    fs::write(
+        temp_dir.path().join(".line-length-ignore"),
+        "# generated code\ngenerated/\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join(".more-ignore"), "c.yaml\n").unwrap();
+    fs::write(
+        temp_dir.path().join(".yamllint"),
+        "extends: default\nrules:\n  line-length:\n    ignore-from-file: [.line-length-ignore, .more-ignore]\n  trailing-spaces:\n    ignore-from-file: .missing\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--no-progress", "-f", "standard", "-r", "."]);
+    let output = cmd.assert().failure();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("b.yaml"), "{}", stdout);
+    assert!(!stdout.contains("a.yaml"), "{}", stdout);
+    assert!(!stdout.contains("c.yaml"), "{}", stdout);
+}
+
+#[test]
+fn test_config_dump_prints_resolved_config() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".yamllint"),
+        "extends: default\nrules:\n  line-length:\n    max: 100\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["config", "dump", "--set", "line-length.level=warning"])
+        .args(["--disable", "trailing-spaces"])
+        .arg(temp_dir.path());
+    let output = cmd.assert().success();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(stdout.starts_with("# Loaded from "));
+
+    let dump: serde_yaml::Value = serde_yaml::from_str(&stdout).unwrap();
+    let rules = &dump["rules"];
+    assert_eq!(rules["line-length"]["max"], 100);
+    assert_eq!(rules["line-length"]["severity"], "warning");
+    assert_eq!(rules["line-length"]["enabled"], true);
+    assert_eq!(rules["trailing-spaces"]["enabled"], false);
+    let registry = yamllint_rs::rules::registry::RuleRegistry::new();
+    assert_eq!(
+        rules.as_mapping().unwrap().len(),
+        registry.get_rule_ids().len()
+    );
+}
+
+#[test]
+fn test_config_init_writes_starter_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join(".yamllint");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["config", "init", "--preset", "relaxed"])
+        .arg(&config_path);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("(relaxed preset)"));
+    let written = fs::read_to_string(&config_path).unwrap();
+    assert!(written.contains("  truthy: disable\n"), "{}", written);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["config", "init"]).arg(&config_path);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("use --force to replace it"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["config", "init", "--force", "--preset", "strict"])
+        .arg(&config_path);
+    cmd.assert().success();
+
+    // The starter config loads, and lints clean under its own rules
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--no-progress", "--config"])
+        .arg(&config_path)
+        .arg(&config_path);
+    cmd.assert().success().stdout(predicate::str::is_empty());
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["config", "init", "-"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("extends: default\n"));
+}
+
+#[test]
+fn test_config_infer_matches_existing_style() {
+    let temp_dir = TempDir::new().unwrap();
+    for name in ["a.yaml", "b.yaml", "nested/c.yml"] {
+        let path = temp_dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            path,
+            "server:\n    name: 'web'\n    ports:\n    - 80\n    - 443\n",
+        )
+        .unwrap();
+    }
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["config", "infer"]).arg(temp_dir.path());
+    let output = cmd.assert().success();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("inferred from 3 file(s)"), "{}", stdout);
+
+    let config: serde_yaml::Value = serde_yaml::from_str(&stdout).unwrap();
+    let rules = &config["rules"];
+    assert_eq!(rules["indentation"]["spaces"], 4);
+    assert_eq!(rules["indentation"]["indent-sequences"], false);
+    assert_eq!(rules["document-start"]["present"], false);
+    assert_eq!(rules["quoted-strings"]["quote-type"], "single");
+
+    // The surveyed files lint clean under the inferred config
+    let config_path = temp_dir.path().join("inferred.yamllint");
+    fs::write(&config_path, &stdout).unwrap();
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--no-progress", "-r", "--config"])
+        .arg(&config_path)
+        .arg(temp_dir.path());
+    cmd.assert().success().stdout(predicate::str::is_empty());
+
+    let empty_dir = TempDir::new().unwrap();
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["config", "infer"]).arg(empty_dir.path());
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("No YAML files found"));
+}
+
+#[test]
+fn test_rules_subcommand_json_includes_default_options() {
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["rules", "--format", "json"]);
+    let output = cmd.assert().success();
+    let rules: Vec<serde_json::Value> =
+        serde_json::from_slice(&output.get_output().stdout).unwrap();
+
+    let registry = yamllint_rs::rules::registry::RuleRegistry::new();
+    assert_eq!(rules.len(), registry.get_rule_ids().len());
+    assert!(rules.iter().all(|rule| rule["options"].is_object()));
+
+    let find = |id: &str| rules.iter().find(|rule| rule["id"] == id).unwrap();
+    let line_length = find("line-length");
+    assert_eq!(line_length["default_severity"], "error");
+    assert_eq!(line_length["options"]["max"], 80);
+    assert_eq!(find("braces")["options"]["forbid"], false);
+    assert_eq!(find("new-lines")["options"]["type"], "unix");
+}
+
+/// Test that the fix support `rules` shows from the registry agrees with the
+/// rules that `--fix` runs
+#[test]
+fn test_registry_can_fix_matches_rules() {
+    let registry = yamllint_rs::rules::registry::RuleRegistry::new();
+    let factory = yamllint_rs::rules::factory::RuleFactory::new();
+    let mut mismatches: Vec<_> = registry
+        .get_rule_ids()
+        .into_iter()
+        .filter(|id| {
+            let rule = factory.create_rule(id).unwrap();
+            registry.get_rule_metadata(id).unwrap().can_fix != rule.can_fix()
+        })
+        .collect();
+    mismatches.sort();
+    assert!(mismatches.is_empty(), "{:?}", mismatches);
+}
+
+#[test]
+fn test_diff_reports_only_changed_lines() {
+    let temp_dir = TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    };
+    git(&["init", "-q"]);
+    fs::write(
+        temp_dir.path().join("changed.yaml"),
+        "---\nold: a   \nb: c\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("same.yaml"), "---\nsame: 1   \n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "initial"]);
+
+    fs::write(
+        temp_dir.path().join("changed.yaml"),
+        "---\nold: a   \nb: c   \n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("new.yaml"), "---\nnew: a   \n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--diff", "--format", "standard", "--no-progress"]);
+    let output = cmd.assert().code(1);
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+
+    assert!(
+        stdout.contains("changed.yaml\n  3:5 ") && !stdout.contains("changed.yaml\n  2:"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("new.yaml\n  2:"), "{}", stdout);
+    assert!(!stdout.contains("same.yaml"), "{}", stdout);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--changed-since", "no-such-ref"]);
+    cmd.assert()
+        .code(255)
+        .stderr(predicate::str::contains("no-such-ref"));
+}
+
+#[test]
+fn test_diff_ignores_the_users_diff_prefix_settings() {
+    let temp_dir = TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    };
+    git(&["init", "-q"]);
+    fs::write(temp_dir.path().join("changed.yaml"), "---\nold: a\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "initial"]);
+    fs::write(temp_dir.path().join("changed.yaml"), "---\nold: a   \n").unwrap();
+
+    for (key, value) in [("diff.noprefix", "true"), ("diff.mnemonicPrefix", "true")] {
+        // One setting at a time
+        git(&["config", "diff.noprefix", "false"]);
+        git(&["config", key, value]);
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .args(["--diff", "--format", "standard", "--no-progress"]);
+        cmd.assert()
+            .code(1)
+            .stdout(predicate::str::contains("changed.yaml\n  2:7 "));
+    }
+}
+
+#[test]
+fn test_cache_reuses_results_for_unchanged_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, "---\nkey: value   \n").unwrap();
+
+    let run = || {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .args(["--cache", "--format", "standard", "test.yaml"]);
+        let output = cmd.assert().code(1);
+        String::from_utf8(output.get_output().stdout.clone()).unwrap()
+    };
+
+    assert!(run().contains("trailing spaces"));
+    let cache_file = temp_dir.path().join(".yamllint-cache/results.json");
+    let cached = fs::read_to_string(&cache_file).unwrap();
+    assert!(cached.contains("trailing spaces"));
+
+    // A hit is served from the cache without linting again
+    fs::write(&cache_file, cached.replace("trailing spaces", "from cache")).unwrap();
+    assert!(run().contains("from cache"));
+
+    fs::write(&test_file, "---\nkey: other   \n").unwrap();
+    assert!(run().contains("trailing spaces"));
+}
+
+#[test]
+fn test_cache_is_invalidated_when_effective_config_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("test.yaml"), "---\nkey: value   \n").unwrap();
+    let config_file = temp_dir.path().join(".yamllint");
+    fs::write(&config_file, "extends: default\n").unwrap();
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .args(["--cache", "--format", "standard"])
+            .args(args)
+            .arg("test.yaml");
+        let output = cmd.assert().get_output().stdout.clone();
+        String::from_utf8(output).unwrap()
+    };
+
+    assert!(run(&[]).contains("trailing spaces"));
+    let cache_file = temp_dir.path().join(".yamllint-cache/results.json");
+    let cached = fs::read_to_string(&cache_file).unwrap();
+    fs::write(&cache_file, cached.replace("trailing spaces", "from cache")).unwrap();
+    assert!(run(&[]).contains("from cache"));
+
+    // Rewriting the config without changing its meaning keeps the entry
+    fs::write(
+        &config_file,
+        "extends: default\nrules:\n  trailing-spaces: enable\n",
+    )
+    .unwrap();
+    assert!(run(&[]).contains("from cache"));
+
+    // Overrides on the command line change the effective config
+    assert!(run(&["--set", "trailing-spaces.level=warning"]).contains("warning  trailing spaces"));
+
+    fs::write(
+        &config_file,
+        "extends: default\nrules:\n  trailing-spaces: disable\n",
+    )
+    .unwrap();
+    assert!(!run(&[]).contains("trailing spaces"));
+}
+
+#[test]
+fn test_profile_rules_prints_timings_to_stderr() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    fs::write(&test_file, "---\nkey: value\n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    let output = cmd
+        .args(["--format", "standard", "--profile-rules"])
+        .arg(&test_file)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .get_output()
+        .stderr
+        .clone();
+    let stderr = String::from_utf8(output).unwrap();
+    assert!(stderr.starts_with("RULE"), "{}", stderr);
+    for rule in ["(analysis)", "line-length", "trailing-spaces"] {
+        assert!(
+            stderr.lines().any(|line| line.starts_with(rule)),
+            "{} missing from {}",
+            rule,
+            stderr
+        );
+    }
+}
+
+#[test]
+fn test_files_from_stdin() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("a b.yaml"), "---\nkey: value   \n").unwrap();
+    fs::write(temp_dir.path().join("c.yaml"), "---\nkey: value\n").unwrap();
+    fs::write(temp_dir.path().join("d.yaml"), "---\nkey: value   \n").unwrap();
+
+    let lint = |args: &[&str], list: &str| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        let output = cmd
+            .current_dir(temp_dir.path())
+            .args(["--format", "standard", "--files-from", "-"])
+            .args(args)
+            .write_stdin(list)
+            .output()
+            .unwrap();
+        (
+            output.status.code(),
+            String::from_utf8(output.stdout).unwrap(),
+        )
+    };
+
+    let (code, stdout) = lint(&["-0"], "a b.yaml\0c.yaml\0");
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("a b.yaml"));
+    assert!(!stdout.contains("d.yaml"));
+
+    let (code, stdout) = lint(&[], "c.yaml\r\n\nd.yaml\n");
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("d.yaml"));
+
+    // Nothing listed, e.g. no changed files: nothing to lint
+    let (code, stdout) = lint(&[], "");
+    assert_eq!(code, Some(0));
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn test_files_from_cannot_share_stdin() {
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.args(["--files-from", "-", "-"])
+        .write_stdin("a.yaml\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--files-from"));
+}
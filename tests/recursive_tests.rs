@@ -82,3 +82,303 @@ fn test_recursive_fix() {
     assert_eq!(content1, "---\nkey1: value1\n");
     assert_eq!(content2, "---\nkey2: value2\n");
 }
+
+/// Test that symlinked directories are only walked with --follow-symlinks,
+/// and that symlink loops are skipped instead of recursing forever
+#[cfg(unix)]
+#[test]
+fn test_follow_symlinks_with_loop_detection() {
+    use std::os::unix::fs::symlink;
+
+    let temp_dir = TempDir::new().unwrap();
+    let shared = temp_dir.path().join("shared");
+    let project = temp_dir.path().join("project");
+    fs::create_dir(&shared).unwrap();
+    fs::create_dir(&project).unwrap();
+    fs::write(shared.join("fragment.yaml"), "---\nkey: value\n").unwrap();
+    symlink(&shared, project.join("shared")).unwrap();
+    symlink(&project, project.join("loop")).unwrap();
+
+    let list_files = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.arg("--list-files").args(args).arg(&project);
+        cmd.assert()
+    };
+
+    list_files(&[])
+        .success()
+        .stdout(predicate::str::contains("fragment.yaml").not());
+    list_files(&["--follow-symlinks"])
+        .success()
+        .stdout(predicate::str::contains("shared/fragment.yaml"))
+        .stderr(predicate::str::contains("loop"));
+}
+
+/// Test that `yaml-files` and --extensions choose which files a directory walk lints
+#[test]
+fn test_yaml_files_and_extensions_select_walked_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("templates")).unwrap();
+    fs::create_dir_all(root.join(".hidden")).unwrap();
+    for file in [
+        "a.yaml",
+        "b.YML",
+        ".clang-format",
+        ".other.yaml",
+        "templates/deploy",
+        "templates/values.tpl",
+        ".hidden/c.yaml",
+    ] {
+        fs::write(root.join(file), "---\nkey: value\n").unwrap();
+    }
+
+    let list_files = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(root)
+            .arg("--list-files")
+            .args(args)
+            .arg(".");
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let mut files: Vec<String> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| line.trim_start_matches("./").to_string())
+            .collect();
+        files.sort();
+        files
+    };
+
+    assert_eq!(list_files(&[]), vec!["a.yaml", "b.YML"]);
+    assert_eq!(
+        list_files(&[
+            "-d",
+            "{extends: default, yaml-files: ['*.yaml', '.clang-format', 'templates/*']}"
+        ]),
+        vec![
+            ".clang-format",
+            "a.yaml",
+            "templates/deploy",
+            "templates/values.tpl"
+        ]
+    );
+    assert_eq!(
+        list_files(&["--extensions", "yaml,tpl"]),
+        vec!["a.yaml", "templates/values.tpl"]
+    );
+}
+
+/// Test that --detect-yaml lints extensionless files whose content looks like YAML
+#[test]
+fn test_detect_yaml_sniffs_extensionless_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    for (file, content) in [
+        ("a.yaml", "---\nkey: value\n"),
+        (
+            "Procfile",
+            "web: bundle exec rails server\nworker: bundle exec sidekiq\n",
+        ),
+        ("deploy", "# deployment\n---\nreplicas: 2\n"),
+        (
+            "playbook",
+            "#!/usr/bin/env ansible-playbook\n- hosts: all\n",
+        ),
+        ("run", "#!/bin/sh\necho: hello\n"),
+        ("README", "Some notes.\nNote: not YAML\n"),
+        ("notes.txt", "---\nkey: value\n"),
+        (".env", "KEY: value\n"),
+    ] {
+        fs::write(root.join(file), content).unwrap();
+    }
+
+    let list_files = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(root)
+            .arg("--list-files")
+            .args(args)
+            .arg(".");
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let mut files: Vec<String> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| line.trim_start_matches("./").to_string())
+            .collect();
+        files.sort();
+        files
+    };
+
+    assert_eq!(list_files(&[]), vec!["a.yaml"]);
+    assert_eq!(
+        list_files(&["--detect-yaml"]),
+        vec!["Procfile", "a.yaml", "deploy", "playbook"]
+    );
+}
+
+#[test]
+fn test_output_is_sorted_by_file_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let names: Vec<String> = (0..12).rev().map(|i| format!("f{:02}.yaml", i)).collect();
+    for name in &names {
+        fs::write(temp_dir.path().join(name), "---\nkey: value   \n").unwrap();
+    }
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .args(["--format", "standard", "--no-progress"])
+            .args(args);
+        let output = cmd.assert().code(1).get_output().stdout.clone();
+        let stdout = String::from_utf8(output).unwrap();
+        stdout
+            .lines()
+            .filter(|line| line.ends_with(".yaml"))
+            .map(|line| line.trim_start_matches("./").to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let mut sorted = names.clone();
+    sorted.sort();
+    // Named files are linted in parallel, and reported in path order anyway
+    let named: Vec<&str> = names.iter().map(String::as_str).collect();
+    assert_eq!(run(&named), sorted);
+    assert_eq!(run(&["-j", "4", "."]), sorted);
+}
+
+#[test]
+fn test_sort_by_severity_orders_issues_within_a_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("test.yaml");
+    fs::write(&file, "key: value   \n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    let output = cmd
+        .args(["--format", "standard", "--sort-by", "severity"])
+        .arg(&file)
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    let levels: Vec<&str> = stdout
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .collect();
+    assert_eq!(levels, vec!["error", "warning"]);
+}
+
+#[test]
+fn test_group_by_rolls_up_counts_per_directory_and_owner() {
+    let temp_dir = TempDir::new().unwrap();
+    for dir in ["services/billing", "services/search", "charts", ".github"] {
+        fs::create_dir_all(temp_dir.path().join(dir)).unwrap();
+    }
+    fs::write(
+        temp_dir.path().join("services/billing/app.yaml"),
+        "---\nkey: value   \n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("services/search/app.yaml"),
+        "---\nkey: value   \n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("charts/values.yaml"),
+        "---\nkey: value\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("root.yaml"), "---\nkey: value   \n").unwrap();
+
+    let run = |group_by: &str| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        let output = cmd
+            .current_dir(temp_dir.path())
+            .args(["--no-progress", "--group-by", group_by, "."])
+            .assert()
+            .code(1)
+            .get_output()
+            .stdout
+            .clone();
+        String::from_utf8(output).unwrap()
+    };
+
+    let rows = |output: String| -> Vec<Vec<String>> {
+        output
+            .lines()
+            .map(|line| line.split_whitespace().map(str::to_string).collect())
+            .collect()
+    };
+    assert_eq!(
+        rows(run("dir")),
+        vec![
+            vec!["GROUP", "FILES", "ERRORS", "WARNINGS", "INFOS"],
+            vec![".", "1", "1", "0", "0"],
+            vec!["services", "2", "2", "0", "0"],
+            vec!["TOTAL", "3", "3", "0", "0"],
+        ]
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--no-progress", "--group-by", "owner", "."])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs a CODEOWNERS file"));
+
+    fs::write(
+        temp_dir.path().join(".github/CODEOWNERS"),
+        "*.yaml @org/platform\n/services/billing/ @org/billing\n",
+    )
+    .unwrap();
+    assert_eq!(
+        rows(run("owner"))[1..3],
+        [
+            vec!["@org/billing", "1", "1", "0", "0"],
+            vec!["@org/platform", "2", "2", "0", "0"],
+        ]
+    );
+}
+
+#[test]
+fn test_max_issues_truncates_output() {
+    let temp_dir = TempDir::new().unwrap();
+    for name in ["a.yaml", "b.yaml", "c.yaml"] {
+        fs::write(
+            temp_dir.path().join(name),
+            "---\na: one   \nb: two   \nc: six   \n",
+        )
+        .unwrap();
+    }
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+        let assert = cmd
+            .current_dir(temp_dir.path())
+            .args(["--format", "standard", "--no-progress"])
+            .args(args)
+            .arg(".")
+            .assert()
+            .code(1);
+        let output = assert.get_output();
+        (
+            String::from_utf8(output.stdout.clone()).unwrap(),
+            String::from_utf8(output.stderr.clone()).unwrap(),
+        )
+    };
+    let issue_count = |stdout: &str| stdout.lines().filter(|line| line.starts_with("  ")).count();
+
+    let (stdout, stderr) = run(&["--max-issues", "2"]);
+    assert_eq!(issue_count(&stdout), 2, "{}", stdout);
+    assert!(stdout.contains("a.yaml") && !stdout.contains("b.yaml"));
+    assert!(stderr.contains("Output truncated: 1 more issue(s) not reported, 2 file(s) not linted"));
+
+    let (stdout, stderr) = run(&["--max-issues-per-file", "1"]);
+    assert_eq!(issue_count(&stdout), 3, "{}", stdout);
+    assert!(stderr.contains("Output truncated: 6 more issue(s) not reported"));
+
+    let (_, stderr) = run(&[]);
+    assert!(!stderr.contains("truncated"));
+}
@@ -212,3 +212,182 @@ severity:
         .code(1)
         .stdout(predicate::str::contains("non-fixable issues"));
 }
+
+/// Test that the opt-in secrets rule reports credentials and honors directives
+#[test]
+fn test_secrets_rule_opt_in() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    let config_file = temp_dir.path().join("config.yaml");
+
+    let content = "---\npassword: Xk9mP2vLq8zR4tW\nallowed_token: Xk9mP2vLq8zR4tW  # yamllint disable-line rule:secrets\n";
+    fs::write(&test_file, content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg(test_file.to_str().unwrap());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("secret").not());
+
+    fs::write(&config_file, "rules:\n  secrets:\n    level: warning\n").unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("--strict")
+        .arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg(test_file.to_str().unwrap());
+    let output = cmd.assert().code(2);
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+
+    assert!(stdout.contains("2:11"));
+    assert!(stdout.contains("probable secret in value of key \"password\""));
+    assert!(!stdout.contains("allowed_token"));
+
+    fs::write(
+        &config_file,
+        "extends: default\nrules:\n  secrets:\n    value-patterns: ['(unclosed']\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg(test_file.to_str().unwrap());
+    cmd.assert().stderr(predicate::str::contains(
+        "invalid option 'value-patterns' for rule 'secrets'",
+    ));
+}
+
+/// Test that forbidden-values reports each pattern with its own message and level
+#[test]
+fn test_forbidden_values_per_pattern_messages_and_levels() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    let config_file = temp_dir.path().join("config.yaml");
+
+    let content = "---\nimage: nginx:latest\npassword: hunter2\nhost: db.example.com\n";
+    fs::write(&test_file, content).unwrap();
+
+    let config_content = r#"
+rules:
+  forbidden-values:
+    level: warning
+    patterns:
+      - pattern: ':latest$'
+        message: pin image tags instead of using latest
+      - pattern: '.'
+        key: '^password$'
+        level: error
+"#;
+    fs::write(&config_file, config_content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg(test_file.to_str().unwrap());
+    let output = cmd.assert().code(1);
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+
+    assert!(stdout.contains("2:8       warning  pin image tags instead of using latest"));
+    assert!(stdout.contains("3:11      error    forbidden value 'hunter2' for key \"password\""));
+    assert!(!stdout.contains("db.example.com"));
+}
+
+/// Test that key-naming checks only the configured paths and reports bad presets
+#[test]
+fn test_key_naming_paths_and_invalid_convention() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    let config_file = temp_dir.path().join("config.yaml");
+
+    let content = "---\napi_version: v1\nspec:\n  maxReplicas: 2\n  min_replicas: 1\n";
+    fs::write(&test_file, content).unwrap();
+
+    let config_content =
+        "extends: default\nrules:\n  key-naming:\n    convention: camelCase\n    paths: [spec]\n";
+    fs::write(&config_file, config_content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg(test_file.to_str().unwrap());
+    let output = cmd.assert().code(1);
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+
+    assert!(stdout.contains("5:3       error    key \"min_replicas\" does not match camelCase"));
+    assert!(!stdout.contains("api_version"));
+    assert!(!stdout.contains("maxReplicas"));
+
+    let config_content = "extends: default\nrules:\n  key-naming:\n    convention: camel\n";
+    fs::write(&config_file, config_content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg(test_file.to_str().unwrap());
+    cmd.assert().stderr(predicate::str::contains(
+        "invalid option 'convention' for rule 'key-naming'",
+    ));
+}
+
+/// Test that custom-tags allows the CloudFormation preset and reports bad presets
+#[test]
+fn test_custom_tags_cloudformation_preset() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    let config_file = temp_dir.path().join("config.yaml");
+
+    let content =
+        "---\nbucket: !Ref Bucket\narn: !GetAtt Bucket.Arn\nhook: !!python/object:os.system {}\n";
+    fs::write(&test_file, content).unwrap();
+
+    let config_content =
+        "extends: default\nrules:\n  custom-tags:\n    presets: [cloudformation]\n";
+    fs::write(&config_file, config_content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg(test_file.to_str().unwrap());
+    let output = cmd.assert().code(1);
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+
+    assert!(stdout.contains("4:7       error    tag \"!!python/object:os.system\" is not allowed"));
+    assert!(!stdout.contains("!Ref"));
+    assert!(!stdout.contains("!GetAtt"));
+
+    let config_content = "extends: default\nrules:\n  custom-tags:\n    presets: [aws]\n";
+    fs::write(&config_file, config_content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg(test_file.to_str().unwrap());
+    cmd.assert().stderr(predicate::str::contains(
+        "invalid option 'presets' for rule 'custom-tags'",
+    ));
+}
+
+/// Test that no-aliases reports aliases outside merge keys when they are allowed
+#[test]
+fn test_no_aliases_allow_merge_keys() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("test.yaml");
+    let config_file = temp_dir.path().join("config.yaml");
+
+    let content = "---\nbase: &base\n  image: nginx\nweb:\n  <<: *base\ncopy: *base\n";
+    fs::write(&test_file, content).unwrap();
+
+    let config_content = "extends: default\nrules:\n  no-aliases:\n    allow-merge-keys: true\n";
+    fs::write(&config_file, config_content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.arg("-c")
+        .arg(config_file.to_str().unwrap())
+        .arg(test_file.to_str().unwrap());
+    let output = cmd.assert().code(1);
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+
+    assert!(stdout.contains("6:7       error    found alias \"*base\"  (no-aliases)"));
+    assert!(!stdout.contains("5:"));
+}
@@ -1,6 +1,7 @@
 //! Tests for severity configuration functionality.
 
 use std::fs;
+use std::str::FromStr;
 use tempfile::TempDir;
 use yamllint_rs::config::Config;
 use yamllint_rs::{load_config, FileProcessor, ProcessingOptions, Severity};
@@ -24,6 +25,23 @@ fn test_severity_enum_conversion() {
     assert_eq!(Severity::Info.to_string(), "info");
 }
 
+#[test]
+fn test_severity_ordering() {
+    assert!(Severity::Info < Severity::Warning);
+    assert!(Severity::Warning < Severity::Error);
+    assert_eq!(
+        [Severity::Error, Severity::Info, Severity::Warning]
+            .into_iter()
+            .max(),
+        Some(Severity::Error)
+    );
+
+    assert!(Severity::Error.is_at_least(Severity::Warning));
+    assert!(Severity::Warning.is_at_least(Severity::Warning));
+    assert!(!Severity::Info.is_at_least(Severity::Warning));
+    assert_eq!("warning".parse::<Severity>().unwrap(), Severity::Warning);
+}
+
 /// Test that rules can have their severity overridden
 #[test]
 fn test_rule_severity_override() {
@@ -153,7 +171,8 @@ rules:
         .arg(config_file.to_str().unwrap())
         .arg(test_file.to_str().unwrap());
 
-    let output = cmd.assert().code(1);
+    // Warnings and infos alone do not fail the run
+    let output = cmd.assert().code(0);
 
     // Check that the output contains the severity levels
     let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
@@ -201,7 +220,7 @@ rules:
     enabled: true
     severity: Error
     settings:
-      max_length: 100
+      max_length: 60
 "#;
     fs::write(&config_file, config_content).unwrap();
 
@@ -256,9 +275,12 @@ fn test_all_severity_levels() {
     let content = "key: value   \n# This line is way too long and exceeds the maximum line length limit of 80 characters\n";
     fs::write(&test_file, content).unwrap();
 
-    for (severity_name, expected_output) in
-        [("Error", "error"), ("Warning", "warning"), ("Info", "info")]
-    {
+    // Only errors fail the run; warnings and infos exit 0 without --strict
+    for (severity_name, expected_output, expected_code) in [
+        ("Error", "error", 1),
+        ("Warning", "warning", 0),
+        ("Info", "info", 0),
+    ] {
         let config_file = temp_dir
             .path()
             .join(format!("config_{}.yaml", severity_name));
@@ -286,7 +308,7 @@ rules:
             .arg(config_file.to_str().unwrap())
             .arg(test_file.to_str().unwrap());
 
-        let output = cmd.assert().code(1);
+        let output = cmd.assert().code(expected_code);
         let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
         assert!(
             stdout.contains(expected_output),
@@ -297,3 +319,155 @@ rules:
         );
     }
 }
+
+/// Test that severity-by-path overrides severities for matching files only
+#[test]
+fn test_severity_by_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let legacy_dir = temp_dir.path().join("legacy").join("nested");
+    fs::create_dir_all(&legacy_dir).unwrap();
+    fs::write(legacy_dir.join("old.yaml"), "---\nkey: value   \n").unwrap();
+    fs::write(temp_dir.path().join("new.yaml"), "---\nkey: value   \n").unwrap();
+
+    let config_content = r#"
+extends: default
+severity-by-path:
+  - files: "legacy/**"
+    level: warning
+"#;
+    fs::write(temp_dir.path().join(".yamllint"), config_content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .arg("--format")
+        .arg("standard")
+        .arg("legacy/nested/old.yaml");
+    let output = cmd.assert().code(0);
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("warning"), "got: {}", stdout);
+    assert!(!stdout.contains("error"), "got: {}", stdout);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path()).arg("new.yaml");
+    cmd.assert().code(1);
+}
+
+/// Test that severity-by-path globs are relative to the config's directory,
+/// not to where the linter runs
+#[test]
+fn test_severity_by_path_from_another_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("ig");
+    fs::create_dir_all(project.join("legacy")).unwrap();
+    fs::write(project.join("legacy/old.yaml"), "---\nkey: value   \n").unwrap();
+    let config_content = r#"
+extends: default
+severity-by-path:
+  - files: "legacy/**"
+    level: warning
+"#;
+    fs::write(project.join(".yamllint"), config_content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .arg("--no-progress")
+        .arg("-r")
+        .arg("ig");
+    cmd.assert().code(0);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(project.join("legacy"))
+        .arg("--config")
+        .arg("../.yamllint")
+        .arg("old.yaml");
+    cmd.assert().code(0);
+}
+
+/// Test severity-by-path glob matching in the native config format
+#[test]
+fn test_severity_override_for_globs() {
+    let config: Config = serde_yaml::from_str(
+        r#"
+rules: {}
+global:
+  default_severity: Error
+severity-by-path:
+  - files: "legacy/**"
+    level: Warning
+  - files: "*.generated.yaml"
+    level: Info
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.severity_override_for("legacy/a/b.yaml", "trailing-spaces"),
+        Some(Severity::Warning)
+    );
+    assert_eq!(
+        config.severity_override_for("./legacy/b.yaml", "trailing-spaces"),
+        Some(Severity::Warning)
+    );
+    assert_eq!(
+        config.severity_override_for("src/x.generated.yaml", "trailing-spaces"),
+        Some(Severity::Info)
+    );
+    assert_eq!(
+        config.severity_override_for("src/legacy.yaml", "trailing-spaces"),
+        None
+    );
+}
+
+/// Test severity-by-path entries limited to some rules
+#[test]
+fn test_severity_by_path_for_rules() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("vendor/lib")).unwrap();
+    fs::write(
+        temp_dir.path().join("vendor/lib/a.yaml"),
+        "---\nlist:\n- item   \n",
+    )
+    .unwrap();
+
+    let config_content = r#"
+extends: default
+severity-by-path:
+  - files: "vendor/**"
+    level: warning
+    rules: [indentation]
+"#;
+    fs::write(temp_dir.path().join(".yamllint"), config_content).unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("yamllint-rs").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["--format", "standard", "vendor/lib/a.yaml"]);
+    let output = cmd.assert().code(1);
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.contains("warning") && line.contains("(indentation)")),
+        "got: {}",
+        stdout
+    );
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.contains("error") && line.contains("(trailing-spaces)")),
+        "got: {}",
+        stdout
+    );
+
+    let config: Config = serde_yaml::from_str(
+        "rules: {}\nglobal:\n  default_severity: Error\nseverity-by-path:\n  - files: \"vendor/**\"\n    level: Warning\n    rules: [indentaton]\n",
+    )
+    .unwrap();
+    assert_eq!(
+        config.severity_override_for("vendor/a.yaml", "indentation"),
+        None
+    );
+    assert_eq!(
+        config.invalid_settings(),
+        vec!["unknown rule 'indentaton' in severity-by-path (did you mean 'indentation'?)"]
+    );
+}